@@ -0,0 +1,3 @@
+-- 记录最近一次重置密码的时间，早于该时间签发的 refresh token 一律失效
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS password_changed_at TIMESTAMPTZ;
@@ -0,0 +1,5 @@
+-- 近似重复单词建议：按 canonical_key 的三元组相似度比较
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_words_canonical_key_trgm
+    ON words USING GIN (canonical_key gin_trgm_ops);
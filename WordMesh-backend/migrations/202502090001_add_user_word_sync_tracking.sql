@@ -0,0 +1,20 @@
+-- 增量同步：单词及其义项的最后修改时间，以及从网络中移除单词留下的墓碑
+ALTER TABLE user_words ADD COLUMN IF NOT EXISTS updated_at TIMESTAMPTZ;
+UPDATE user_words SET updated_at = created_at WHERE updated_at IS NULL;
+ALTER TABLE user_words
+    ALTER COLUMN updated_at SET DEFAULT NOW(),
+    ALTER COLUMN updated_at SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_user_words_user_id_updated_at
+    ON user_words (user_id, updated_at);
+
+CREATE TABLE IF NOT EXISTS user_word_tombstones (
+    id BIGSERIAL PRIMARY KEY,
+    user_id BIGINT NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    user_word_id BIGINT NOT NULL,
+    word_id BIGINT NOT NULL,
+    deleted_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_word_tombstones_user_id_deleted_at
+    ON user_word_tombstones (user_id, deleted_at);
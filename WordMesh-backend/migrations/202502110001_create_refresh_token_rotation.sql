@@ -0,0 +1,17 @@
+-- Refresh token rotation: each refresh token may be exchanged once; replaying a consumed
+-- one revokes its whole family (every token descended from the same login)
+CREATE TABLE IF NOT EXISTS consumed_refresh_tokens (
+    jti TEXT PRIMARY KEY,
+    family TEXT NOT NULL,
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS revoked_token_families (
+    family TEXT PRIMARY KEY,
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_consumed_refresh_tokens_expires_at
+    ON consumed_refresh_tokens (expires_at);
+CREATE INDEX IF NOT EXISTS idx_revoked_token_families_expires_at
+    ON revoked_token_families (expires_at);
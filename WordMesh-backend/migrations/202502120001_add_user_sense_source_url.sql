@@ -0,0 +1,2 @@
+-- Optional citation for a sense: where the definition was taken from
+ALTER TABLE user_senses ADD COLUMN IF NOT EXISTS source_url TEXT;
@@ -0,0 +1,12 @@
+-- 全文检索：单词与义项各自维护 tsvector 生成列，连字符按空格切词
+ALTER TABLE words ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (to_tsvector('english', replace(canonical_key, '-', ' '))) STORED;
+
+ALTER TABLE user_senses ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (to_tsvector('english', text)) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_words_search_vector
+    ON words USING GIN (search_vector);
+
+CREATE INDEX IF NOT EXISTS idx_user_senses_search_vector
+    ON user_senses USING GIN (search_vector);
@@ -0,0 +1,3 @@
+-- 中文义项切不出 english 词元，义项检索回落到 ILIKE 包含匹配，用三元组索引支撑
+CREATE INDEX IF NOT EXISTS idx_user_senses_text_trgm
+    ON user_senses USING GIN (text gin_trgm_ops);
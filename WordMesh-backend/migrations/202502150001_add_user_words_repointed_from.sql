@@ -0,0 +1,3 @@
+-- 改挂单词后图库尚未跟进时记下原 word_id，重试改写时据此补做图库改挂；
+-- 不加外键：原 words 行可能已被其他清理删除，图中的节点仍按 word_id 查找
+ALTER TABLE user_words ADD COLUMN IF NOT EXISTS repointed_from_word_id BIGINT;
@@ -0,0 +1,164 @@
+use std::fmt;
+use std::time::Duration;
+
+use sqlx::Connection;
+use tokio::time::timeout;
+
+use crate::config::Settings;
+use crate::config::settings::DatabaseSettings;
+use crate::repository::Neo4jGraphRepository;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckOutcome {
+    Passed,
+    Skipped(String),
+    Failed(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckItem {
+    pub name: &'static str,
+    pub outcome: CheckOutcome,
+}
+
+/// `--check-config` 的逐项结果；任一项失败时进程以非零状态退出
+#[derive(Debug, Default)]
+pub struct ConfigCheckReport {
+    items: Vec<CheckItem>,
+}
+
+impl ConfigCheckReport {
+    pub fn items(&self) -> &[CheckItem] {
+        &self.items
+    }
+
+    pub fn passed(&self) -> bool {
+        self.items
+            .iter()
+            .all(|item| !matches!(item.outcome, CheckOutcome::Failed(_)))
+    }
+
+    fn record(&mut self, name: &'static str, result: Result<(), String>) {
+        let outcome = match result {
+            Ok(()) => CheckOutcome::Passed,
+            Err(message) => CheckOutcome::Failed(message),
+        };
+        self.items.push(CheckItem { name, outcome });
+    }
+
+    fn skip(&mut self, name: &'static str, reason: impl Into<String>) {
+        self.items.push(CheckItem {
+            name,
+            outcome: CheckOutcome::Skipped(reason.into()),
+        });
+    }
+}
+
+impl fmt::Display for ConfigCheckReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for item in &self.items {
+            match &item.outcome {
+                CheckOutcome::Passed => writeln!(f, "[ok]   {}", item.name)?,
+                CheckOutcome::Skipped(reason) => writeln!(f, "[skip] {}: {reason}", item.name)?,
+                CheckOutcome::Failed(message) => writeln!(f, "[fail] {}: {message}", item.name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 记录配置加载与校验的结果；失败时返回 None，后续连通性检查随之跳过
+pub fn check_settings(
+    loaded: Result<Settings, config::ConfigError>,
+    report: &mut ConfigCheckReport,
+) -> Option<Settings> {
+    match loaded {
+        Ok(settings) => {
+            report.record("config", Ok(()));
+            Some(settings)
+        }
+        Err(err) => {
+            report.record("config", Err(err.to_string()));
+            report.skip("postgres", "configuration invalid");
+            report.skip("neo4j", "configuration invalid");
+            None
+        }
+    }
+}
+
+/// 加载、校验配置并依次探测 Postgres 与 Neo4j，不启动 HTTP 服务
+pub async fn run(loaded: Result<Settings, config::ConfigError>) -> ConfigCheckReport {
+    let mut report = ConfigCheckReport::default();
+    let Some(settings) = check_settings(loaded, &mut report) else {
+        return report;
+    };
+
+    report.record("postgres", ping_postgres(&settings.database).await);
+    if settings.features.graph_enabled {
+        report.record("neo4j", ping_neo4j(&settings).await);
+    } else {
+        report.skip("neo4j", "features.graph_enabled = false");
+    }
+    report
+}
+
+async fn ping_postgres(database: &DatabaseSettings) -> Result<(), String> {
+    let probe = async {
+        let mut conn = sqlx::PgConnection::connect_with(&database.connect_options()).await?;
+        sqlx::query_scalar::<_, i32>("SELECT 1")
+            .fetch_one(&mut conn)
+            .await?;
+        conn.close().await
+    };
+    match timeout(Duration::from_secs(database.connect_timeout_seconds), probe).await {
+        Ok(result) => result.map_err(|err| err.to_string()),
+        Err(_) => Err("timed out".into()),
+    }
+}
+
+async fn ping_neo4j(settings: &Settings) -> Result<(), String> {
+    let graph = Neo4jGraphRepository::from_settings(&settings.neo4j)
+        .await
+        .map_err(|err| err.to_string())?;
+    graph.ping().await.map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validated(settings: Settings) -> Result<Settings, config::ConfigError> {
+        settings.validate().map(|()| settings)
+    }
+
+    #[tokio::test]
+    async fn reports_invalid_auth_settings_without_connecting() {
+        let mut settings = Settings::default();
+        settings.auth.jwt.secret = None;
+
+        let report = run(validated(settings)).await;
+        assert!(!report.passed());
+        let items = report.items();
+        assert_eq!(items[0].name, "config");
+        assert!(matches!(
+            &items[0].outcome,
+            CheckOutcome::Failed(message) if message.contains("auth.jwt.secret")
+        ));
+        assert!(
+            items[1..]
+                .iter()
+                .all(|item| matches!(item.outcome, CheckOutcome::Skipped(_)))
+        );
+        assert!(report.to_string().contains("[fail] config"));
+    }
+
+    #[test]
+    fn valid_settings_pass_the_config_step() {
+        let mut settings = Settings::default();
+        settings.auth.jwt.secret = Some("test-secret".into());
+
+        let mut report = ConfigCheckReport::default();
+        assert!(check_settings(validated(settings), &mut report).is_some());
+        assert!(report.passed());
+    }
+}
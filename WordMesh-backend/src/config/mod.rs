@@ -1,3 +1,4 @@
+pub mod check;
 pub mod settings;
 
 pub use settings::Settings;
@@ -1,5 +1,16 @@
 use serde::Deserialize;
-use sqlx::postgres::PgConnectOptions;
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions};
+use std::time::Duration;
+
+use crate::middleware::DEFAULT_REQUEST_ID_HEADER;
+use crate::repository::graph::{SenseWordLinkKind, WordLinkKind};
+use crate::repository::word::EmptyQueryBehavior;
+use crate::util::canonical::CanonicalMode;
+use crate::util::validation::{
+    MAX_NOTE_BYTES, MAX_NOTE_LENGTH, MAX_SENSE_NOTE_LENGTH, MAX_SENSE_TEXT_LENGTH, MAX_SENSES,
+    MAX_TAG_LENGTH, MAX_TEXT_BYTES, TagCharset, TagRules, TextLimits,
+};
+use actix_web::http::header::HeaderName;
 use std::env;
 
 #[allow(dead_code)]
@@ -11,6 +22,16 @@ pub struct Settings {
     pub jwt: JwtSettings,
     pub auth: AuthSettings,
     pub logging: LoggingSettings,
+    #[serde(default)]
+    pub graph: GraphSettings,
+    #[serde(default)]
+    pub features: FeatureSettings,
+    #[serde(default)]
+    pub words: WordSettings,
+    #[serde(default)]
+    pub search: SearchSettings,
+    #[serde(default)]
+    pub cors: CorsSettings,
 }
 
 #[allow(dead_code)]
@@ -25,6 +46,15 @@ pub struct DatabaseSettings {
     pub pool_size: u32,
     #[serde(default = "DatabaseSettings::default_connect_timeout_seconds")]
     pub connect_timeout_seconds: u64,
+    /// 空闲超过该时长的连接会被回收，应小于数据库或代理的空闲断开时间
+    #[serde(default = "DatabaseSettings::default_idle_timeout_seconds")]
+    pub idle_timeout_seconds: u64,
+    /// 连接的最长存活时间，到期后在归还时关闭并重建
+    #[serde(default = "DatabaseSettings::default_max_lifetime_seconds")]
+    pub max_lifetime_seconds: u64,
+    /// 单条语句在服务端的最长执行时间，超时由 Postgres 取消
+    #[serde(default = "DatabaseSettings::default_statement_timeout_seconds")]
+    pub statement_timeout_seconds: u64,
 }
 
 impl DatabaseSettings {
@@ -43,6 +73,17 @@ impl DatabaseSettings {
             .password(&self.password)
             .port(self.port)
             .database(&self.database_name)
+            .options([(
+                "statement_timeout",
+                format!("{}s", self.statement_timeout_seconds),
+            )])
+    }
+
+    pub fn pool_options(&self) -> PgPoolOptions {
+        PgPoolOptions::new()
+            .max_connections(self.pool_size)
+            .idle_timeout(Duration::from_secs(self.idle_timeout_seconds))
+            .max_lifetime(Duration::from_secs(self.max_lifetime_seconds))
     }
 
     fn default_pool_size() -> u32 {
@@ -53,6 +94,18 @@ impl DatabaseSettings {
         5
     }
 
+    fn default_idle_timeout_seconds() -> u64 {
+        300
+    }
+
+    fn default_max_lifetime_seconds() -> u64 {
+        1800
+    }
+
+    fn default_statement_timeout_seconds() -> u64 {
+        30
+    }
+
     #[allow(dead_code)]
     pub fn validate(&self) -> Result<(), config::ConfigError> {
         if self.pool_size == 0 {
@@ -67,6 +120,25 @@ impl DatabaseSettings {
             ));
         }
 
+        if self.idle_timeout_seconds == 0 {
+            return Err(config::ConfigError::Message(
+                "database.idle_timeout_seconds must be greater than 0".into(),
+            ));
+        }
+
+        if self.max_lifetime_seconds == 0 {
+            return Err(config::ConfigError::Message(
+                "database.max_lifetime_seconds must be greater than 0".into(),
+            ));
+        }
+
+        // 0 在 Postgres 中表示不限时，这里不允许
+        if self.statement_timeout_seconds == 0 {
+            return Err(config::ConfigError::Message(
+                "database.statement_timeout_seconds must be greater than 0".into(),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -110,11 +182,481 @@ impl Neo4jSettings {
     }
 }
 
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct GraphSettings {
+    #[serde(default = "GraphSettings::default_max_depth")]
+    pub max_depth: u32,
+    #[serde(default = "GraphSettings::default_max_results")]
+    pub max_results: i64,
+    /// 停用的关联类型（如 "root_affix"），不会出现在 link-kinds 列表中
+    #[serde(default)]
+    pub disabled_link_kinds: Vec<String>,
+    /// 创建时必须填写非空备注的关联类型；默认为空，即备注均可选
+    #[serde(default)]
+    pub note_required_link_kinds: Vec<String>,
+    /// 创建 synonym 义项关联时，是否同时从目标单词的主义项建立指回源单词的关联
+    #[serde(default)]
+    pub reverse_synonym_links: bool,
+    /// 单个请求内同时进行的图库调用上限，超出的调用排队等待
+    #[serde(default = "GraphSettings::default_max_concurrent_queries")]
+    pub max_concurrent_queries: usize,
+    /// 同一对单词之间最多可存在的关联类型数；默认允许全部类型
+    #[serde(default = "GraphSettings::default_max_link_kinds_per_pair")]
+    pub max_link_kinds_per_pair: usize,
+    /// 创建单词关联时请求省略 `kind` 所用的类型，必须是启用的单词关联类型
+    #[serde(default = "GraphSettings::default_word_link_kind")]
+    pub default_word_link_kind: String,
+    /// 创建义项关联时请求省略 `kind` 所用的类型，必须是启用的义项关联类型
+    #[serde(default = "GraphSettings::default_sense_link_kind")]
+    pub default_sense_link_kind: String,
+}
+
+impl GraphSettings {
+    fn default_max_depth() -> u32 {
+        3
+    }
+
+    fn default_max_results() -> i64 {
+        200
+    }
+
+    fn default_max_concurrent_queries() -> usize {
+        4
+    }
+
+    fn default_max_link_kinds_per_pair() -> usize {
+        WordLinkKind::ALL.len()
+    }
+
+    fn default_word_link_kind() -> String {
+        WordLinkKind::SimilarForm.as_str().to_string()
+    }
+
+    fn default_sense_link_kind() -> String {
+        SenseWordLinkKind::Related.as_str().to_string()
+    }
+
+    #[allow(dead_code)]
+    pub fn validate(&self) -> Result<(), config::ConfigError> {
+        if self.max_depth == 0 {
+            return Err(config::ConfigError::Message(
+                "graph.max_depth must be greater than 0".into(),
+            ));
+        }
+
+        if self.max_results <= 0 {
+            return Err(config::ConfigError::Message(
+                "graph.max_results must be greater than 0".into(),
+            ));
+        }
+
+        if self.max_concurrent_queries == 0 {
+            return Err(config::ConfigError::Message(
+                "graph.max_concurrent_queries must be greater than 0".into(),
+            ));
+        }
+
+        if self.max_link_kinds_per_pair == 0 {
+            return Err(config::ConfigError::Message(
+                "graph.max_link_kinds_per_pair must be greater than 0".into(),
+            ));
+        }
+
+        for (key, kinds) in [
+            ("disabled_link_kinds", &self.disabled_link_kinds),
+            ("note_required_link_kinds", &self.note_required_link_kinds),
+        ] {
+            for kind in kinds {
+                if WordLinkKind::try_from_str(kind).is_none()
+                    && SenseWordLinkKind::try_from_str(kind).is_none()
+                {
+                    return Err(config::ConfigError::Message(format!(
+                        "graph.{key} contains unknown kind '{kind}'"
+                    )));
+                }
+            }
+        }
+
+        for (key, kind, known) in [
+            (
+                "default_word_link_kind",
+                &self.default_word_link_kind,
+                WordLinkKind::try_from_str(&self.default_word_link_kind).is_some(),
+            ),
+            (
+                "default_sense_link_kind",
+                &self.default_sense_link_kind,
+                SenseWordLinkKind::try_from_str(&self.default_sense_link_kind).is_some(),
+            ),
+        ] {
+            if !known {
+                return Err(config::ConfigError::Message(format!(
+                    "graph.{key} '{kind}' is not a kind of that link type"
+                )));
+            }
+            if self.disabled_link_kinds.contains(kind) {
+                return Err(config::ConfigError::Message(format!(
+                    "graph.{key} '{kind}' is listed in graph.disabled_link_kinds"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for GraphSettings {
+    fn default() -> Self {
+        Self {
+            max_depth: GraphSettings::default_max_depth(),
+            max_results: GraphSettings::default_max_results(),
+            disabled_link_kinds: Vec::new(),
+            note_required_link_kinds: Vec::new(),
+            reverse_synonym_links: false,
+            max_concurrent_queries: GraphSettings::default_max_concurrent_queries(),
+            max_link_kinds_per_pair: GraphSettings::default_max_link_kinds_per_pair(),
+            default_word_link_kind: GraphSettings::default_word_link_kind(),
+            default_sense_link_kind: GraphSettings::default_sense_link_kind(),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct WordSettings {
+    /// 列表/搜索结果中每个单词内嵌的义项上限，超出部分需通过单词详情获取
+    #[serde(default = "WordSettings::default_max_senses_per_word")]
+    pub max_senses_per_word: i64,
+    /// 每个单词最多保存的义项数，超出时拒绝继续添加
+    #[serde(default = "WordSettings::default_max_senses")]
+    pub max_senses: usize,
+    /// 按 canonical_key 查询单词的 LRU 缓存容量，仅在 `features.word_cache_enabled` 时生效
+    #[serde(default = "WordSettings::default_cache_capacity")]
+    pub cache_capacity: usize,
+    /// 义项释义文本的最大字符数
+    #[serde(default = "WordSettings::default_max_sense_text_length")]
+    pub max_sense_text_length: usize,
+    /// 义项备注的最大字符数
+    #[serde(default = "WordSettings::default_max_sense_note_length")]
+    pub max_sense_note_length: usize,
+    /// 单词备注的最大字符数
+    #[serde(default = "WordSettings::default_max_note_length")]
+    pub max_note_length: usize,
+    /// 义项释义文本 UTF-8 编码后的最大字节数，与字符数上限同时生效
+    #[serde(default = "WordSettings::default_max_text_bytes")]
+    pub max_text_bytes: usize,
+    /// 单词与义项备注 UTF-8 编码后的最大字节数
+    #[serde(default = "WordSettings::default_max_note_bytes")]
+    pub max_note_bytes: usize,
+    /// 标签字符集：`strict` 仅允许 ASCII 字母数字与 `_`、`-`；`unicode` 允许任意语言的字母数字
+    #[serde(default)]
+    pub tag_charset: TagCharset,
+    /// 自定义标签正则（整体匹配），设置后取代 `tag_charset`
+    #[serde(default)]
+    pub tag_pattern: Option<String>,
+    /// 单个标签的最大字符数
+    #[serde(default = "WordSettings::default_max_tag_length")]
+    pub max_tag_length: usize,
+    /// `collapse`：空格与连字符等价（`co op` 与 `co-op` 视为同一单词）；
+    /// `preserve_hyphens`：连字符与空格区分
+    #[serde(default)]
+    pub canonical_mode: CanonicalMode,
+    /// 每个用户在 `creation_rate_window_secs` 内最多添加的单词数；0 表示不限
+    #[serde(default = "WordSettings::default_creation_rate_limit")]
+    pub creation_rate_limit: u32,
+    #[serde(default = "WordSettings::default_creation_rate_window_secs")]
+    pub creation_rate_window_secs: u64,
+    /// 分页 limit 越界时返回参数校验错误；关闭时静默截断到允许范围
+    #[serde(default)]
+    pub strict_limits: bool,
+    /// 仅用于调试：单词/义项属于其他用户时返回 4206 而不是与不存在相同的 4202。
+    /// 生产环境保持关闭，避免他人的 id 被枚举
+    #[serde(default)]
+    pub reveal_ownership: bool,
+}
+
+impl WordSettings {
+    fn default_max_senses_per_word() -> i64 {
+        50
+    }
+
+    fn default_max_senses() -> usize {
+        MAX_SENSES
+    }
+
+    fn default_cache_capacity() -> usize {
+        1024
+    }
+
+    fn default_max_sense_text_length() -> usize {
+        MAX_SENSE_TEXT_LENGTH
+    }
+
+    fn default_max_sense_note_length() -> usize {
+        MAX_SENSE_NOTE_LENGTH
+    }
+
+    fn default_max_note_length() -> usize {
+        MAX_NOTE_LENGTH
+    }
+
+    fn default_max_text_bytes() -> usize {
+        MAX_TEXT_BYTES
+    }
+
+    fn default_max_note_bytes() -> usize {
+        MAX_NOTE_BYTES
+    }
+
+    fn default_max_tag_length() -> usize {
+        MAX_TAG_LENGTH
+    }
+
+    fn default_creation_rate_limit() -> u32 {
+        120
+    }
+
+    fn default_creation_rate_window_secs() -> u64 {
+        60
+    }
+
+    pub fn text_limits(&self) -> TextLimits {
+        TextLimits {
+            sense_text: self.max_sense_text_length,
+            sense_note: self.max_sense_note_length,
+            word_note: self.max_note_length,
+            text_bytes: self.max_text_bytes,
+            note_bytes: self.max_note_bytes,
+        }
+    }
+
+    pub fn tag_rules(&self) -> Result<TagRules, config::ConfigError> {
+        TagRules::new(
+            self.tag_charset,
+            self.tag_pattern.as_deref(),
+            self.max_tag_length,
+        )
+        .map_err(|err| config::ConfigError::Message(format!("words.tag_pattern is invalid: {err}")))
+    }
+
+    #[allow(dead_code)]
+    pub fn validate(&self) -> Result<(), config::ConfigError> {
+        if self.max_senses_per_word <= 0 {
+            return Err(config::ConfigError::Message(
+                "words.max_senses_per_word must be greater than 0".into(),
+            ));
+        }
+
+        if self.cache_capacity == 0 {
+            return Err(config::ConfigError::Message(
+                "words.cache_capacity must be greater than 0".into(),
+            ));
+        }
+
+        for (key, value) in [
+            ("max_senses", self.max_senses),
+            ("max_sense_text_length", self.max_sense_text_length),
+            ("max_sense_note_length", self.max_sense_note_length),
+            ("max_note_length", self.max_note_length),
+            ("max_text_bytes", self.max_text_bytes),
+            ("max_note_bytes", self.max_note_bytes),
+            ("max_tag_length", self.max_tag_length),
+        ] {
+            if value == 0 {
+                return Err(config::ConfigError::Message(format!(
+                    "words.{key} must be greater than 0"
+                )));
+            }
+        }
+
+        if self.creation_rate_limit > 0 && self.creation_rate_window_secs == 0 {
+            return Err(config::ConfigError::Message(
+                "words.creation_rate_window_secs must be greater than 0".into(),
+            ));
+        }
+
+        self.tag_rules()?;
+        Ok(())
+    }
+}
+
+impl Default for WordSettings {
+    fn default() -> Self {
+        Self {
+            max_senses_per_word: WordSettings::default_max_senses_per_word(),
+            max_senses: WordSettings::default_max_senses(),
+            cache_capacity: WordSettings::default_cache_capacity(),
+            max_sense_text_length: WordSettings::default_max_sense_text_length(),
+            max_sense_note_length: WordSettings::default_max_sense_note_length(),
+            max_note_length: WordSettings::default_max_note_length(),
+            max_text_bytes: WordSettings::default_max_text_bytes(),
+            max_note_bytes: WordSettings::default_max_note_bytes(),
+            tag_charset: TagCharset::default(),
+            tag_pattern: None,
+            max_tag_length: WordSettings::default_max_tag_length(),
+            canonical_mode: CanonicalMode::default(),
+            creation_rate_limit: WordSettings::default_creation_rate_limit(),
+            creation_rate_window_secs: WordSettings::default_creation_rate_window_secs(),
+            strict_limits: false,
+            reveal_ownership: false,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize, Clone)]
+pub struct SearchSettings {
+    /// `list_all`：空关键字返回全部单词；`empty`：空关键字返回空结果
+    #[serde(default)]
+    pub empty_query_behavior: EmptyQueryBehavior,
+}
+
+#[derive(Debug, Default, Deserialize, Clone)]
+pub struct CorsSettings {
+    /// `/auth` 之外的接口
+    #[serde(default)]
+    pub general: CorsPolicy,
+    /// 登录、刷新等 `/auth` 接口单独的策略，通常来源更少、允许携带凭证；省略时沿用 `general`
+    #[serde(default)]
+    pub auth: Option<CorsPolicy>,
+}
+
+impl CorsSettings {
+    pub fn auth_policy(&self) -> &CorsPolicy {
+        self.auth.as_ref().unwrap_or(&self.general)
+    }
+
+    pub fn validate(&self) -> Result<(), config::ConfigError> {
+        self.general.validate("cors.general")?;
+        if let Some(auth) = &self.auth {
+            auth.validate("cors.auth")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CorsPolicy {
+    /// 允许跨域访问的来源，如 `https://app.example.com`；`*` 表示任意来源，为空时不处理跨域请求
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    /// 允许携带 Cookie 等凭证，不能与 `*` 同时使用
+    #[serde(default)]
+    pub allow_credentials: bool,
+    #[serde(default = "CorsPolicy::default_max_age_secs")]
+    pub max_age_secs: usize,
+}
+
+impl CorsPolicy {
+    fn default_max_age_secs() -> usize {
+        3600
+    }
+
+    fn validate(&self, section: &str) -> Result<(), config::ConfigError> {
+        for origin in &self.allowed_origins {
+            if origin == "*" {
+                if self.allow_credentials {
+                    return Err(config::ConfigError::Message(format!(
+                        "{section}.allowed_origins cannot contain '*' when allow_credentials is enabled"
+                    )));
+                }
+                continue;
+            }
+            // 来源只能是 scheme://host[:port]，不带路径
+            let valid = origin.parse::<actix_web::http::Uri>().is_ok_and(|uri| {
+                matches!(uri.scheme_str(), Some("http" | "https"))
+                    && uri.authority().is_some()
+                    && uri.path_and_query().is_none_or(|path| path.as_str() == "/")
+            }) && !origin.ends_with('/');
+            if !valid {
+                return Err(config::ConfigError::Message(format!(
+                    "{section}.allowed_origins contains invalid origin '{origin}'"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for CorsPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_origins: Vec::new(),
+            allow_credentials: false,
+            max_age_secs: CorsPolicy::default_max_age_secs(),
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, Clone)]
+pub struct FeatureSettings {
+    /// 关闭后不连接 Neo4j：单词/义项 CRUD 照常可用，图相关接口返回功能未启用
+    #[serde(default = "FeatureSettings::default_graph_enabled")]
+    pub graph_enabled: bool,
+    /// 开启后按 canonical_key 查询单词走进程内 LRU 缓存
+    #[serde(default)]
+    pub word_cache_enabled: bool,
+    /// 开启后注册 `/metrics`，以 Prometheus 文本格式输出请求与依赖耗时指标
+    #[serde(default)]
+    pub metrics_enabled: bool,
+}
+
+impl FeatureSettings {
+    fn default_graph_enabled() -> bool {
+        true
+    }
+}
+
+impl Default for FeatureSettings {
+    fn default() -> Self {
+        Self {
+            graph_enabled: FeatureSettings::default_graph_enabled(),
+            word_cache_enabled: false,
+            metrics_enabled: false,
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Deserialize, Clone)]
 pub struct ApplicationSettings {
     pub host: String,
     pub port: u16,
+    /// 容忍路径末尾的 `/`，例如 `/auth/login/` 与 `/auth/login` 命中同一路由
+    #[serde(default = "ApplicationSettings::default_trim_trailing_slash")]
+    pub trim_trailing_slash: bool,
+    /// 读取与回写 Request-Id 使用的请求头，如 `X-Correlation-Id`
+    #[serde(default = "ApplicationSettings::default_request_id_header")]
+    pub request_id_header: String,
+    /// 以缩进格式输出 JSON 响应，便于本地调试；生产保持紧凑
+    #[serde(default)]
+    pub pretty_json: bool,
+}
+
+impl ApplicationSettings {
+    fn default_trim_trailing_slash() -> bool {
+        true
+    }
+
+    fn default_request_id_header() -> String {
+        DEFAULT_REQUEST_ID_HEADER.to_string()
+    }
+
+    #[allow(dead_code)]
+    pub fn request_id_header_name(&self) -> Result<HeaderName, config::ConfigError> {
+        HeaderName::from_bytes(self.request_id_header.as_bytes()).map_err(|_| {
+            config::ConfigError::Message(format!(
+                "application.request_id_header '{}' is not a valid header name",
+                self.request_id_header
+            ))
+        })
+    }
+
+    #[allow(dead_code)]
+    pub fn validate(&self) -> Result<(), config::ConfigError> {
+        self.request_id_header_name().map(|_| ())
+    }
 }
 
 #[allow(dead_code)]
@@ -135,6 +677,12 @@ pub struct AuthSettings {
     pub enabled: bool,
     pub jwt: AuthJwtSettings,
     pub password: AuthPasswordSettings,
+    /// 邮箱验证令牌的有效期
+    #[serde(default = "AuthSettings::default_email_verification_ttl_secs")]
+    pub email_verification_ttl_secs: u64,
+    /// 重置密码令牌的有效期，应远短于邮箱验证
+    #[serde(default = "AuthSettings::default_password_reset_ttl_secs")]
+    pub password_reset_ttl_secs: u64,
 }
 
 #[allow(dead_code)]
@@ -161,6 +709,13 @@ pub struct AuthPasswordSettings {
     pub min_length: u8,
     #[serde(default = "AuthPasswordSettings::default_require_complexity")]
     pub require_complexity: bool,
+    /// 密码熵估计的下限（bit）；未配置时不检查
+    #[serde(default)]
+    pub min_entropy_bits: Option<u32>,
+    #[serde(default = "AuthPasswordSettings::default_hash_cost")]
+    pub hash_cost: u32,
+    #[serde(default = "AuthPasswordSettings::default_rehash_on_login")]
+    pub rehash_on_login: bool,
 }
 
 #[allow(dead_code)]
@@ -207,8 +762,12 @@ impl Settings {
     #[allow(dead_code)]
     pub fn validate(&self) -> Result<(), config::ConfigError> {
         self.database.validate()?;
+        self.application.validate()?;
         self.neo4j.validate()?;
         self.auth.validate()?;
+        self.graph.validate()?;
+        self.words.validate()?;
+        self.cors.validate()?;
         Ok(())
     }
 }
@@ -224,6 +783,9 @@ impl Default for Settings {
                 database_name: "wordmesh_dev".to_string(),
                 pool_size: DatabaseSettings::default_pool_size(),
                 connect_timeout_seconds: DatabaseSettings::default_connect_timeout_seconds(),
+                idle_timeout_seconds: DatabaseSettings::default_idle_timeout_seconds(),
+                max_lifetime_seconds: DatabaseSettings::default_max_lifetime_seconds(),
+                statement_timeout_seconds: DatabaseSettings::default_statement_timeout_seconds(),
             },
             neo4j: Neo4jSettings {
                 uri: "bolt://localhost:7687".to_string(),
@@ -235,6 +797,9 @@ impl Default for Settings {
             application: ApplicationSettings {
                 host: "127.0.0.1".to_string(),
                 port: 8080,
+                trim_trailing_slash: ApplicationSettings::default_trim_trailing_slash(),
+                request_id_header: ApplicationSettings::default_request_id_header(),
+                pretty_json: false,
             },
             jwt: JwtSettings {
                 secret: "your-secret-key".to_string(),
@@ -246,6 +811,11 @@ impl Default for Settings {
             logging: LoggingSettings {
                 level: "info".to_string(),
             },
+            graph: GraphSettings::default(),
+            features: FeatureSettings::default(),
+            words: WordSettings::default(),
+            search: SearchSettings::default(),
+            cors: CorsSettings::default(),
         }
     }
 }
@@ -266,6 +836,8 @@ impl Default for AuthSettings {
             enabled: AuthSettings::default_enabled(),
             jwt: AuthJwtSettings::default(),
             password: AuthPasswordSettings::default(),
+            email_verification_ttl_secs: AuthSettings::default_email_verification_ttl_secs(),
+            password_reset_ttl_secs: AuthSettings::default_password_reset_ttl_secs(),
         }
     }
 }
@@ -275,10 +847,28 @@ impl AuthSettings {
         true
     }
 
+    fn default_email_verification_ttl_secs() -> u64 {
+        86400
+    }
+
+    fn default_password_reset_ttl_secs() -> u64 {
+        1800
+    }
+
     #[allow(dead_code)]
     pub fn validate(&self) -> Result<(), config::ConfigError> {
         self.jwt.validate()?;
         self.password.validate()?;
+        if self.email_verification_ttl_secs == 0 {
+            return Err(config::ConfigError::Message(
+                "auth.email_verification_ttl_secs must be greater than 0".into(),
+            ));
+        }
+        if self.password_reset_ttl_secs == 0 {
+            return Err(config::ConfigError::Message(
+                "auth.password_reset_ttl_secs must be greater than 0".into(),
+            ));
+        }
         Ok(())
     }
 }
@@ -314,25 +904,23 @@ impl AuthJwtSettings {
         let algorithm = self.algorithm.to_uppercase();
         match algorithm.as_str() {
             "HS256" => {
-                if self.secret.as_ref().map_or(true, |s| s.trim().is_empty()) {
+                if self.secret.as_ref().is_none_or(|s| s.trim().is_empty()) {
                     return Err(config::ConfigError::Message(
                         "auth.jwt.secret is required when using HS256".into(),
                     ));
                 }
             }
-            "RS256" => {
+            "RS256" | "ES256" | "EDDSA" => {
                 if self
                     .private_key
                     .as_ref()
-                    .map_or(true, |s| s.trim().is_empty())
-                    || self
-                        .public_key
-                        .as_ref()
-                        .map_or(true, |s| s.trim().is_empty())
+                    .is_none_or(|s| s.trim().is_empty())
+                    || self.public_key.as_ref().is_none_or(|s| s.trim().is_empty())
                 {
-                    return Err(config::ConfigError::Message(
-                        "auth.jwt.private_key and auth.jwt.public_key are required when using RS256".into(),
-                    ));
+                    return Err(config::ConfigError::Message(format!(
+                        "auth.jwt.private_key and auth.jwt.public_key are required when using {}",
+                        self.algorithm
+                    )));
                 }
             }
             other => {
@@ -369,6 +957,14 @@ impl AuthPasswordSettings {
         false
     }
 
+    fn default_hash_cost() -> u32 {
+        bcrypt::DEFAULT_COST
+    }
+
+    fn default_rehash_on_login() -> bool {
+        true
+    }
+
     #[allow(dead_code)]
     pub fn validate(&self) -> Result<(), config::ConfigError> {
         if self.min_length < 8 {
@@ -376,6 +972,16 @@ impl AuthPasswordSettings {
                 "auth.password.min_length must be at least 8".into(),
             ));
         }
+        if self.min_entropy_bits == Some(0) {
+            return Err(config::ConfigError::Message(
+                "auth.password.min_entropy_bits must be greater than 0 when set".into(),
+            ));
+        }
+        if !(4..=31).contains(&self.hash_cost) {
+            return Err(config::ConfigError::Message(
+                "auth.password.hash_cost must be between 4 and 31".into(),
+            ));
+        }
         Ok(())
     }
 }
@@ -385,6 +991,176 @@ impl Default for AuthPasswordSettings {
         Self {
             min_length: AuthPasswordSettings::default_min_length(),
             require_complexity: AuthPasswordSettings::default_require_complexity(),
+            min_entropy_bits: None,
+            hash_cost: AuthPasswordSettings::default_hash_cost(),
+            rehash_on_login: AuthPasswordSettings::default_rehash_on_login(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn asymmetric_jwt_algorithms_require_a_key_pair() {
+        for algorithm in ["ES256", "EdDSA"] {
+            let mut jwt = Settings::default().auth.jwt;
+            jwt.algorithm = algorithm.into();
+            jwt.private_key = Some("private".into());
+            jwt.public_key = None;
+            assert!(jwt.validate().is_err(), "{algorithm} without public key");
+
+            jwt.public_key = Some("public".into());
+            assert!(jwt.validate().is_ok(), "{algorithm} with key pair");
+        }
+
+        let mut jwt = Settings::default().auth.jwt;
+        jwt.algorithm = "ES512".into();
+        assert!(jwt.validate().is_err());
+    }
+
+    #[test]
+    fn pool_options_reflect_configured_timeouts() {
+        let mut database = Settings::default().database;
+        database.pool_size = 7;
+        database.idle_timeout_seconds = 45;
+        database.max_lifetime_seconds = 900;
+
+        let options = database.pool_options();
+
+        assert_eq!(options.get_max_connections(), 7);
+        assert_eq!(options.get_idle_timeout(), Some(Duration::from_secs(45)));
+        assert_eq!(options.get_max_lifetime(), Some(Duration::from_secs(900)));
+    }
+
+    #[test]
+    fn connect_options_carry_statement_timeout() {
+        let mut database = Settings::default().database;
+        database.statement_timeout_seconds = 12;
+
+        let options = database.connect_options();
+
+        assert_eq!(options.get_options(), Some("-c statement_timeout=12s"));
+
+        database.statement_timeout_seconds = 0;
+        assert!(database.validate().is_err());
+    }
+
+    #[test]
+    fn zero_idle_timeout_is_rejected() {
+        let mut database = Settings::default().database;
+        database.idle_timeout_seconds = 0;
+
+        assert!(database.validate().is_err());
+    }
+
+    #[test]
+    fn text_limits_are_configured_independently() {
+        let mut words = Settings::default().words;
+        words.max_sense_text_length = 2048;
+        words.max_sense_note_length = 256;
+        words.max_note_length = 128;
+        words.max_note_bytes = 300;
+
+        assert_eq!(
+            words.text_limits(),
+            TextLimits {
+                sense_text: 2048,
+                sense_note: 256,
+                word_note: 128,
+                text_bytes: MAX_TEXT_BYTES,
+                note_bytes: 300,
+            }
+        );
+
+        words.max_sense_note_length = 0;
+        assert!(words.validate().is_err());
+    }
+
+    #[test]
+    fn invalid_tag_pattern_is_rejected() {
+        let mut words = Settings::default().words;
+        assert!(words.validate().is_ok());
+        words.tag_pattern = Some("[unclosed".into());
+        let err = words.validate().unwrap_err().to_string();
+        assert!(err.contains("words.tag_pattern"), "{err}");
+    }
+
+    #[test]
+    fn unknown_note_required_link_kind_is_rejected() {
+        let graph = GraphSettings {
+            note_required_link_kinds: vec!["root_affix".into(), "cousin".into()],
+            ..GraphSettings::default()
+        };
+        let err = graph.validate().unwrap_err().to_string();
+        assert!(err.contains("note_required_link_kinds"), "{err}");
+    }
+
+    #[test]
+    fn default_link_kinds_must_be_known_and_enabled() {
+        assert!(GraphSettings::default().validate().is_ok());
+
+        let graph = GraphSettings {
+            default_word_link_kind: "synonym".into(),
+            ..GraphSettings::default()
+        };
+        let err = graph.validate().unwrap_err().to_string();
+        assert!(err.contains("default_word_link_kind"), "{err}");
+
+        let graph = GraphSettings {
+            disabled_link_kinds: vec!["related".into()],
+            ..GraphSettings::default()
+        };
+        let err = graph.validate().unwrap_err().to_string();
+        assert!(err.contains("default_sense_link_kind"), "{err}");
+    }
+
+    #[test]
+    fn cors_origins_are_validated() {
+        let mut cors = CorsSettings::default();
+        assert!(cors.validate().is_ok());
+        cors.general.allowed_origins = vec!["https://app.example.com".into(), "*".into()];
+        assert!(cors.validate().is_ok());
+
+        cors.general.allow_credentials = true;
+        let err = cors.validate().unwrap_err().to_string();
+        assert!(err.contains("cors.general"), "{err}");
+
+        for origin in [
+            "app.example.com",
+            "https://app.example.com/login",
+            "ftp://host",
+        ] {
+            cors.auth = Some(CorsPolicy {
+                allowed_origins: vec![origin.into()],
+                ..CorsPolicy::default()
+            });
+            cors.general.allow_credentials = false;
+            let err = cors.validate().unwrap_err().to_string();
+            assert!(err.contains("cors.auth"), "{origin}: {err}");
+        }
+    }
+
+    #[test]
+    fn auth_cors_policy_falls_back_to_general() {
+        let mut cors = CorsSettings::default();
+        cors.general.allowed_origins = vec!["https://app.example.com".into()];
+        assert_eq!(
+            cors.auth_policy().allowed_origins,
+            vec!["https://app.example.com".to_string()]
+        );
+
+        // 自带的配置文件都不单独配置 [cors.auth]，回退才会生效
+        for env in ["development", "production", "testing"] {
+            let cors: CorsSettings = config::Config::builder()
+                .add_source(config::File::with_name("config/default"))
+                .add_source(config::File::with_name(&format!("config/{env}")))
+                .build()
+                .unwrap()
+                .get("cors")
+                .unwrap();
+            assert!(cors.auth.is_none(), "{env}");
         }
     }
 }
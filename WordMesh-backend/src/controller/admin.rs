@@ -0,0 +1,369 @@
+use actix_web::{HttpResponse, web};
+use std::sync::Arc;
+
+use crate::dto::admin::{AdminUserQuery, RepairPrimariesResponse};
+use crate::middleware::{AuthGuard, RequireScope};
+use crate::repository::user::UserRepository;
+use crate::service::admin::{AdminService, UserLookup};
+use crate::service::word::validation_error;
+use crate::util::token::TokenConfig;
+use crate::util::{AppError, ResponseBuilder};
+
+const ADMIN_SCOPE: &str = "admin";
+
+pub struct AdminController<R>
+where
+    R: UserRepository + Send + Sync + 'static,
+{
+    service: Arc<AdminService<R>>,
+    token_config: Arc<TokenConfig>,
+}
+
+impl<R> Clone for AdminController<R>
+where
+    R: UserRepository + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            service: self.service.clone(),
+            token_config: self.token_config.clone(),
+        }
+    }
+}
+
+impl<R> AdminController<R>
+where
+    R: UserRepository + Send + Sync + 'static,
+{
+    pub fn new(service: AdminService<R>, token_config: Arc<TokenConfig>) -> Self {
+        Self {
+            service: Arc::new(service),
+            token_config,
+        }
+    }
+
+    pub fn configure(cfg: &mut web::ServiceConfig, controller: web::Data<AdminController<R>>) {
+        // AuthGuard 最后注册、最先执行，RequireScope 才能读到身份
+        cfg.service(
+            web::scope("/admin/users")
+                .app_data(controller.clone())
+                .wrap(RequireScope::new(ADMIN_SCOPE))
+                .wrap(AuthGuard::new(controller.token_config.clone()))
+                .route("", web::get().to(Self::find_by_username))
+                .route("/{id}", web::get().to(Self::find_by_id))
+                .route(
+                    "/{id}/repair-primaries",
+                    web::post().to(Self::repair_primaries),
+                ),
+        );
+    }
+
+    async fn find_by_username(
+        controller: web::Data<AdminController<R>>,
+        query: web::Query<AdminUserQuery>,
+    ) -> Result<HttpResponse, AppError> {
+        let username = query
+            .into_inner()
+            .username
+            .map(|username| username.trim().to_string())
+            .filter(|username| !username.is_empty())
+            .ok_or_else(|| validation_error("username", "username is required"))?;
+        let user = controller
+            .service
+            .find_user(UserLookup::Username(username))
+            .await?;
+        ResponseBuilder::ok(user)
+    }
+
+    async fn find_by_id(
+        controller: web::Data<AdminController<R>>,
+        user_id: web::Path<i64>,
+    ) -> Result<HttpResponse, AppError> {
+        let user = controller
+            .service
+            .find_user(UserLookup::Id(user_id.into_inner()))
+            .await?;
+        ResponseBuilder::ok(user)
+    }
+
+    async fn repair_primaries(
+        controller: web::Data<AdminController<R>>,
+        user_id: web::Path<i64>,
+    ) -> Result<HttpResponse, AppError> {
+        let user_id = user_id.into_inner();
+        let repaired_words = controller.service.repair_primaries(user_id).await?;
+        ResponseBuilder::ok(RepairPrimariesResponse {
+            user_id,
+            repaired_words,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_support::token_config;
+    use actix_web::{App, test};
+    use async_trait::async_trait;
+    use chrono::Utc;
+
+    use crate::domain::word::CanonicalKey;
+    use crate::domain::{HashedPassword, User};
+    use crate::repository::graph::{GraphRepository, SenseWordLinkKind, WordLinkKind};
+    use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+    use crate::repository::user::{
+        NewOneTimeToken, NewUser, RepositoryError, TokenPurpose, UserStats,
+    };
+    use crate::repository::word::{NewUserSense, SenseOrder, UpsertUserWord, WordRepository};
+    use crate::util::token::generate_access_token;
+
+    struct SingleUserRepository {
+        user: User,
+    }
+
+    impl SingleUserRepository {
+        fn new() -> Self {
+            let password_hash = HashedPassword::new("$2b$04$secret-hash".into()).unwrap();
+            Self {
+                user: User::new(5, "support_me".into(), password_hash, Utc::now()).unwrap(),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl UserRepository for SingleUserRepository {
+        async fn create_user(&self, _new_user: NewUser) -> Result<User, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn find_by_username(&self, username: &str) -> Result<Option<User>, RepositoryError> {
+            Ok(Some(self.user.clone()).filter(|user| user.username == username))
+        }
+
+        async fn find_by_id(&self, user_id: i64) -> Result<Option<User>, RepositoryError> {
+            Ok(Some(self.user.clone()).filter(|user| user.id == user_id))
+        }
+
+        async fn find_by_email(&self, _email: &str) -> Result<Option<User>, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn update_password(
+            &self,
+            _user_id: i64,
+            _password_hash: HashedPassword,
+        ) -> Result<(), RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn reset_password(
+            &self,
+            _user_id: i64,
+            _password_hash: HashedPassword,
+        ) -> Result<(), RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn find_stats(&self, _user_id: i64) -> Result<UserStats, RepositoryError> {
+            Ok(UserStats {
+                word_count: 12,
+                sense_count: 30,
+            })
+        }
+
+        async fn store_one_time_token(
+            &self,
+            _token: NewOneTimeToken,
+        ) -> Result<(), RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn consume_one_time_token(
+            &self,
+            _purpose: TokenPurpose,
+            _token_hash: &str,
+        ) -> Result<Option<i64>, RepositoryError> {
+            unimplemented!()
+        }
+
+        async fn mark_email_verified(&self, _user_id: i64) -> Result<(), RepositoryError> {
+            unimplemented!()
+        }
+    }
+
+    async fn call(
+        request: test::TestRequest,
+        words: InMemoryWordRepository,
+        scope: Option<&str>,
+    ) -> serde_json::Value {
+        call_with_graph(request, words, InMemoryGraphRepository::default(), scope).await
+    }
+
+    async fn call_with_graph(
+        request: test::TestRequest,
+        words: InMemoryWordRepository,
+        graph: InMemoryGraphRepository,
+        scope: Option<&str>,
+    ) -> serde_json::Value {
+        let config = token_config();
+        let controller = web::Data::new(AdminController::new(
+            AdminService::new(
+                SingleUserRepository::new(),
+                Arc::new(words),
+                Arc::new(graph),
+            ),
+            config.clone(),
+        ));
+        let app = test::init_service(
+            App::new().configure(|cfg| AdminController::configure(cfg, controller.clone())),
+        )
+        .await;
+
+        let token = generate_access_token(&config, "1", scope.map(str::to_string), None).unwrap();
+        let req = request
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        test::call_and_read_body_json(&app, req).await
+    }
+
+    async fn get(uri: &str, scope: Option<&str>) -> serde_json::Value {
+        call(
+            test::TestRequest::get().uri(uri),
+            InMemoryWordRepository::default(),
+            scope,
+        )
+        .await
+    }
+
+    #[actix_rt::test]
+    async fn non_admin_token_is_rejected() {
+        let json = get("/admin/users/5", None).await;
+
+        assert_eq!(json["code"], 4031);
+        assert!(json["data"].is_null());
+    }
+
+    #[actix_rt::test]
+    async fn admin_token_can_look_up_by_id_and_username() {
+        for uri in ["/admin/users/5", "/admin/users?username=support_me"] {
+            let json = get(uri, Some("admin")).await;
+
+            assert_eq!(json["code"], 2000, "{uri}");
+            assert_eq!(json["data"]["profile"]["username"], "support_me");
+            assert_eq!(json["data"]["stats"]["word_count"], 12);
+            assert!(!json.to_string().contains("secret-hash"));
+        }
+    }
+
+    #[actix_rt::test]
+    async fn stats_include_link_counts_for_the_user_only() {
+        let graph = InMemoryGraphRepository::default();
+        for target in [2, 3] {
+            graph
+                .create_word_link(5, 1, target, WordLinkKind::SimilarForm, None)
+                .await
+                .unwrap();
+        }
+        graph
+            .create_word_link(6, 1, 2, WordLinkKind::SimilarForm, None)
+            .await
+            .unwrap();
+        graph
+            .create_sense_word_link(5, 10, 1, 2, SenseWordLinkKind::Synonym, None)
+            .await
+            .unwrap();
+
+        let json = call_with_graph(
+            test::TestRequest::get().uri("/admin/users/5"),
+            InMemoryWordRepository::default(),
+            graph,
+            Some("admin"),
+        )
+        .await;
+
+        let stats = &json["data"]["stats"];
+        assert_eq!(stats["links_available"], true);
+        assert_eq!(stats["word_link_count"], 2);
+        assert_eq!(stats["sense_word_link_count"], 1);
+    }
+
+    #[actix_rt::test]
+    async fn stats_report_links_unavailable_when_graph_fails() {
+        let json = call_with_graph(
+            test::TestRequest::get().uri("/admin/users/5"),
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::failing(),
+            Some("admin"),
+        )
+        .await;
+
+        assert_eq!(json["code"], 2000);
+        let stats = &json["data"]["stats"];
+        assert_eq!(stats["word_count"], 12);
+        assert_eq!(stats["links_available"], false);
+        assert!(stats["word_link_count"].is_null());
+        assert!(stats["sense_word_link_count"].is_null());
+    }
+
+    #[actix_rt::test]
+    async fn lookup_requires_username_or_id() {
+        let json = get("/admin/users", Some("admin")).await;
+
+        assert_eq!(json["code"], 4001);
+    }
+
+    #[actix_rt::test]
+    async fn repair_primaries_is_admin_only_and_reports_repaired_words() {
+        let words = InMemoryWordRepository::default();
+        let user_word_id = words
+            .upsert_user_word(UpsertUserWord {
+                user_id: 5,
+                word_text: "drift".into(),
+                canonical_key: CanonicalKey::new("drift").unwrap(),
+                tags: vec![],
+                note: None,
+            })
+            .await
+            .unwrap()
+            .aggregate
+            .user_word
+            .id
+            .unwrap();
+        words
+            .add_user_sense(NewUserSense {
+                user_word_id,
+                text: "slow movement".into(),
+                is_primary: false,
+                sort_order: 0,
+                note: None,
+                source_url: None,
+            })
+            .await
+            .unwrap();
+        let repair = || test::TestRequest::post().uri("/admin/users/5/repair-primaries");
+
+        let json = call(repair(), words.clone(), None).await;
+        assert_eq!(json["code"], 4031);
+
+        let json = call(repair(), words.clone(), Some("admin")).await;
+        assert_eq!(json["code"], 2000);
+        assert_eq!(json["data"]["repaired_words"], 1);
+        let repaired = words
+            .find_user_word(5, user_word_id, SenseOrder::default())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(repaired.user_word.senses()[0].is_primary);
+
+        let json = call(repair(), words.clone(), Some("admin")).await;
+        assert_eq!(json["data"]["repaired_words"], 0);
+
+        let json = call(
+            test::TestRequest::post().uri("/admin/users/99/repair-primaries"),
+            words,
+            Some("admin"),
+        )
+        .await;
+        assert_eq!(json["code"], 4000);
+    }
+}
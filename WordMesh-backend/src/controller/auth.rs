@@ -1,9 +1,15 @@
 use actix_web::{HttpResponse, web};
 use std::sync::Arc;
 
-use crate::dto::auth::{LoginRequest, RefreshRequest, RegisterRequest};
-use crate::middleware::{AuthGuard, AuthenticatedUser};
+use crate::config::settings::CorsPolicy;
+use crate::dto::auth::{
+    ChangePasswordRequest, LoginRequest, LogoutRequest, PasswordResetConfirmRequest,
+    PasswordResetRequest, RefreshRequest, RegisterRequest, VerifyEmailRequest,
+};
+use crate::middleware::{AuthGuard, AuthenticatedUser, cors};
 use crate::service::auth::AuthService;
+use crate::util::metrics::metrics;
+use crate::util::token::TokenConfig;
 use crate::util::{AppError, ResponseBuilder};
 
 #[derive(Clone)]
@@ -12,6 +18,7 @@ where
     R: crate::repository::user::UserRepository + Send + Sync + 'static,
 {
     service: Arc<AuthService<R>>,
+    cors: CorsPolicy,
 }
 
 impl<R> AuthController<R>
@@ -21,21 +28,48 @@ where
     pub fn new(service: AuthService<R>) -> Self {
         Self {
             service: Arc::new(service),
+            cors: CorsPolicy::default(),
         }
     }
 
+    /// `/auth` 作用域自带的 CORS 策略，见 `cors.auth`；外层不应再套用通用策略
+    pub fn with_cors(mut self, policy: CorsPolicy) -> Self {
+        self.cors = policy;
+        self
+    }
+
     pub fn configure(cfg: &mut web::ServiceConfig, controller: web::Data<AuthController<R>>) {
         let guard = controller.auth_guard();
         cfg.service(
             web::scope("/auth")
+                .wrap(cors(&controller.cors))
                 .app_data(controller.clone())
                 .route("/register", web::post().to(Self::register))
                 .route("/login", web::post().to(Self::login))
                 .route("/refresh", web::post().to(Self::refresh))
+                .route("/verify-email", web::post().to(Self::verify_email))
+                .route(
+                    "/password-reset",
+                    web::post().to(Self::request_password_reset),
+                )
+                .route(
+                    "/password-reset/confirm",
+                    web::post().to(Self::confirm_password_reset),
+                )
                 .service(
                     web::resource("/profile")
-                        .wrap(guard)
+                        .wrap(guard.clone())
                         .route(web::get().to(Self::profile)),
+                )
+                .service(
+                    web::resource("/password")
+                        .wrap(guard.clone())
+                        .route(web::put().to(Self::change_password)),
+                )
+                .service(
+                    web::resource("/logout")
+                        .wrap(guard)
+                        .route(web::post().to(Self::logout)),
                 ),
         );
     }
@@ -52,16 +86,51 @@ where
         controller: web::Data<AuthController<R>>,
         payload: web::Json<LoginRequest>,
     ) -> Result<HttpResponse, AppError> {
-        let tokens = controller.service.login(payload.into_inner()).await?;
-        ResponseBuilder::ok(tokens)
+        let result = controller.service.login(payload.into_inner()).await;
+        metrics().record_auth("login", result.is_ok());
+        ResponseBuilder::ok(result?)
     }
 
     async fn refresh(
         controller: web::Data<AuthController<R>>,
         payload: web::Json<RefreshRequest>,
     ) -> Result<HttpResponse, AppError> {
-        let tokens = controller.service.refresh(payload.into_inner()).await?;
-        ResponseBuilder::ok(tokens)
+        let result = controller.service.refresh(payload.into_inner()).await;
+        metrics().record_auth("refresh", result.is_ok());
+        ResponseBuilder::ok(result?)
+    }
+
+    async fn verify_email(
+        controller: web::Data<AuthController<R>>,
+        payload: web::Json<VerifyEmailRequest>,
+    ) -> Result<HttpResponse, AppError> {
+        let profile = controller
+            .service
+            .verify_email(payload.into_inner())
+            .await?;
+        ResponseBuilder::ok(profile)
+    }
+
+    async fn request_password_reset(
+        controller: web::Data<AuthController<R>>,
+        payload: web::Json<PasswordResetRequest>,
+    ) -> Result<HttpResponse, AppError> {
+        controller
+            .service
+            .request_password_reset(&payload.email)
+            .await?;
+        ResponseBuilder::ok(())
+    }
+
+    async fn confirm_password_reset(
+        controller: web::Data<AuthController<R>>,
+        payload: web::Json<PasswordResetConfirmRequest>,
+    ) -> Result<HttpResponse, AppError> {
+        controller
+            .service
+            .reset_password(&payload.token, &payload.new_password)
+            .await?;
+        ResponseBuilder::ok(())
     }
 
     async fn profile(
@@ -72,14 +141,47 @@ where
         ResponseBuilder::ok(profile)
     }
 
+    async fn change_password(
+        controller: web::Data<AuthController<R>>,
+        identity: AuthenticatedUser,
+        payload: web::Json<ChangePasswordRequest>,
+    ) -> Result<HttpResponse, AppError> {
+        controller
+            .service
+            .change_password(identity.user_id, payload.into_inner())
+            .await?;
+        ResponseBuilder::ok(())
+    }
+
+    /// 请求体可省略；仅在需要同时吊销 refresh token 时提交
+    async fn logout(
+        controller: web::Data<AuthController<R>>,
+        identity: AuthenticatedUser,
+        payload: Option<web::Json<LogoutRequest>>,
+    ) -> Result<HttpResponse, AppError> {
+        controller
+            .service
+            .logout(
+                &identity.claims,
+                payload.map(web::Json::into_inner).unwrap_or_default(),
+            )
+            .await?;
+        ResponseBuilder::ok(())
+    }
+
+    pub fn token_config(&self) -> Arc<TokenConfig> {
+        self.service.token_config()
+    }
+
     fn auth_guard(&self) -> AuthGuard {
-        AuthGuard::new(self.service.token_config())
+        AuthGuard::new(self.token_config())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::repository::token_store::InMemoryRefreshTokenStore;
     use actix_web::{App, test};
     use async_trait::async_trait;
     use chrono::Utc;
@@ -88,14 +190,20 @@ mod tests {
     use tokio::sync::RwLock;
 
     use crate::config::settings::{AuthJwtSettings, AuthPasswordSettings, AuthSettings};
-    use crate::domain::User;
-    use crate::repository::user::{NewUser, RepositoryError, UserRepository};
+    use crate::domain::{HashedPassword, User};
+    use crate::repository::user::{
+        NewOneTimeToken, NewUser, RepositoryError, TokenPurpose, UserRepository,
+    };
     use crate::service::auth::AuthService;
 
+    /// (user_id, purpose, token_hash, expires_at, consumed)
+    type StoredToken = (i64, TokenPurpose, String, chrono::DateTime<Utc>, bool);
+
     #[derive(Default, Clone)]
     struct InMemoryUserRepository {
         users: Arc<RwLock<HashMap<i64, User>>>,
         username_index: Arc<RwLock<HashMap<String, i64>>>,
+        tokens: Arc<RwLock<Vec<StoredToken>>>,
     }
 
     #[async_trait]
@@ -117,7 +225,8 @@ mod tests {
                 new_user.password_hash,
                 Utc::now(),
             )
-            .unwrap();
+            .unwrap()
+            .with_email(new_user.email, false);
             username_idx.insert(user.username.clone(), user.id);
             users.insert(id, user.clone());
             Ok(user)
@@ -136,6 +245,89 @@ mod tests {
             let users = self.users.read().await;
             Ok(users.get(&user_id).cloned())
         }
+
+        async fn find_by_email(&self, email: &str) -> Result<Option<User>, RepositoryError> {
+            let users = self.users.read().await;
+            Ok(users
+                .values()
+                .find(|user| {
+                    user.email
+                        .as_deref()
+                        .is_some_and(|stored| stored.eq_ignore_ascii_case(email))
+                })
+                .cloned())
+        }
+
+        async fn update_password(
+            &self,
+            user_id: i64,
+            password_hash: HashedPassword,
+        ) -> Result<(), RepositoryError> {
+            let mut users = self.users.write().await;
+            if let Some(user) = users.get_mut(&user_id) {
+                user.password_hash = password_hash;
+            }
+            Ok(())
+        }
+
+        async fn reset_password(
+            &self,
+            user_id: i64,
+            password_hash: HashedPassword,
+        ) -> Result<(), RepositoryError> {
+            let mut users = self.users.write().await;
+            if let Some(user) = users.get_mut(&user_id) {
+                user.password_hash = password_hash;
+                user.password_changed_at = Some(Utc::now());
+            }
+            Ok(())
+        }
+
+        async fn find_stats(
+            &self,
+            _user_id: i64,
+        ) -> Result<crate::repository::user::UserStats, RepositoryError> {
+            Ok(Default::default())
+        }
+
+        async fn store_one_time_token(
+            &self,
+            token: NewOneTimeToken,
+        ) -> Result<(), RepositoryError> {
+            self.tokens.write().await.push((
+                token.user_id,
+                token.purpose,
+                token.token_hash,
+                token.expires_at,
+                false,
+            ));
+            Ok(())
+        }
+
+        async fn consume_one_time_token(
+            &self,
+            purpose: TokenPurpose,
+            token_hash: &str,
+        ) -> Result<Option<i64>, RepositoryError> {
+            let mut tokens = self.tokens.write().await;
+            let now = Utc::now();
+            Ok(tokens
+                .iter_mut()
+                .find(|(_, p, hash, expires_at, consumed)| {
+                    *p == purpose && hash == token_hash && !*consumed && *expires_at > now
+                })
+                .map(|entry| {
+                    entry.4 = true;
+                    entry.0
+                }))
+        }
+
+        async fn mark_email_verified(&self, user_id: i64) -> Result<(), RepositoryError> {
+            if let Some(user) = self.users.write().await.get_mut(&user_id) {
+                user.email_verified = true;
+            }
+            Ok(())
+        }
     }
 
     fn default_settings() -> AuthSettings {
@@ -152,13 +344,24 @@ mod tests {
             password: AuthPasswordSettings {
                 min_length: 8,
                 require_complexity: false,
+                min_entropy_bits: None,
+                hash_cost: 4,
+                rehash_on_login: true,
             },
+            email_verification_ttl_secs: 3600,
+            password_reset_ttl_secs: 600,
         }
     }
 
     fn service() -> AuthService<InMemoryUserRepository> {
         let settings = default_settings();
-        AuthService::new(InMemoryUserRepository::default(), &settings, &settings.jwt).unwrap()
+        AuthService::new(
+            InMemoryUserRepository::default(),
+            Arc::new(InMemoryRefreshTokenStore::default()),
+            &settings,
+            &settings.jwt,
+        )
+        .unwrap()
     }
 
     #[actix_rt::test]
@@ -171,7 +374,7 @@ mod tests {
 
         let req = test::TestRequest::post()
             .uri("/auth/register")
-            .set_json(&json!({ "username": "user_register", "password": "password123" }))
+            .set_json(json!({ "username": "user_register", "password": "password123" }))
             .to_request();
 
         let resp = test::call_service(&app, req).await;
@@ -191,19 +394,284 @@ mod tests {
         // register first
         let register = test::TestRequest::post()
             .uri("/auth/register")
-            .set_json(&json!({ "username": "user_login", "password": "password123" }))
+            .set_json(json!({ "username": "user_login", "password": "password123" }))
             .to_request();
         let _ = test::call_service(&app, register).await;
 
         let req = test::TestRequest::post()
             .uri("/auth/login")
-            .set_json(&json!({ "username": "user_login", "password": "password123" }))
+            .set_json(json!({ "username": "user_login", "password": "password123" }))
             .to_request();
 
         let resp = test::call_service(&app, req).await;
         assert!(resp.status().is_success());
         let body: serde_json::Value = test::read_body_json(resp).await;
         assert!(body["data"]["access_token"].as_str().unwrap().len() > 10);
+        assert_eq!(body["data"]["refresh_enabled"], true);
+    }
+
+    #[actix_rt::test]
+    async fn logout_revokes_the_presented_tokens() {
+        let controller = web::Data::new(AuthController::new(service()));
+        let app = test::init_service(
+            App::new().configure(|cfg| AuthController::configure(cfg, controller.clone())),
+        )
+        .await;
+
+        let register = test::TestRequest::post()
+            .uri("/auth/register")
+            .set_json(json!({ "username": "user_logout", "password": "password123" }))
+            .to_request();
+        let _ = test::call_service(&app, register).await;
+        let login = test::TestRequest::post()
+            .uri("/auth/login")
+            .set_json(json!({ "username": "user_logout", "password": "password123" }))
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, login).await;
+        let access = body["data"]["access_token"].as_str().unwrap().to_string();
+        let refresh = body["data"]["refresh_token"].as_str().unwrap().to_string();
+
+        let profile = || {
+            test::TestRequest::get()
+                .uri("/auth/profile")
+                .insert_header(("Authorization", format!("Bearer {access}")))
+                .to_request()
+        };
+        let body: serde_json::Value = test::call_and_read_body_json(&app, profile()).await;
+        assert_eq!(body["code"], 2000);
+
+        let logout = test::TestRequest::post()
+            .uri("/auth/logout")
+            .insert_header(("Authorization", format!("Bearer {access}")))
+            .set_json(json!({ "refresh_token": refresh }))
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, logout).await;
+        assert_eq!(body["code"], 2000);
+
+        let body: serde_json::Value = test::call_and_read_body_json(&app, profile()).await;
+        assert_eq!(body["code"], 4013);
+        let refresh = test::TestRequest::post()
+            .uri("/auth/refresh")
+            .set_json(json!({ "refresh_token": refresh }))
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, refresh).await;
+        assert_eq!(body["code"], 4013);
+    }
+
+    #[actix_rt::test]
+    async fn auth_disabled_rejects_auth_flows_and_protected_routes() {
+        use crate::controller::word::WordController;
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+        use crate::service::WordService;
+
+        let mut settings = default_settings();
+        settings.enabled = false;
+        let service = AuthService::new(
+            InMemoryUserRepository::default(),
+            Arc::new(InMemoryRefreshTokenStore::default()),
+            &settings,
+            &settings.jwt,
+        )
+        .unwrap();
+        let config = service.token_config();
+        let auth = web::Data::new(AuthController::new(service));
+        let words = web::Data::new(WordController::new(
+            WordService::new(
+                InMemoryWordRepository::default(),
+                InMemoryGraphRepository::default(),
+            ),
+            config.clone(),
+        ));
+        let app = test::init_service(
+            App::new()
+                .configure(|cfg| AuthController::configure(cfg, auth.clone()))
+                .configure(|cfg| WordController::configure(cfg, words.clone())),
+        )
+        .await;
+
+        let login = test::TestRequest::post()
+            .uri("/auth/login")
+            .set_json(json!({ "username": "someone", "password": "password123" }))
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, login).await;
+        assert_eq!(body["code"], 4015);
+
+        // 即便持有签名正确的令牌，受保护接口也给出同样明确的错误
+        let token = crate::util::token::generate_access_token(&config, "1", None, None).unwrap();
+        let sync = test::TestRequest::get()
+            .uri("/sync")
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, sync).await;
+        assert_eq!(body["code"], 4015);
+    }
+
+    #[actix_rt::test]
+    async fn auth_scope_uses_its_own_cors_policy() {
+        use crate::config::settings::CorsPolicy;
+        use crate::controller::word::WordController;
+        use crate::middleware::cors;
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+        use crate::service::WordService;
+        use actix_web::http::header::{
+            ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_ORIGIN,
+            ACCESS_CONTROL_REQUEST_METHOD, ORIGIN,
+        };
+        use actix_web::http::{Method, StatusCode};
+
+        let service = service();
+        let config = service.token_config();
+        let auth = web::Data::new(AuthController::new(service).with_cors(CorsPolicy {
+            allowed_origins: vec!["https://app.example.com".into()],
+            allow_credentials: true,
+            ..CorsPolicy::default()
+        }));
+        let words = web::Data::new(WordController::new(
+            WordService::new(
+                InMemoryWordRepository::default(),
+                InMemoryGraphRepository::default(),
+            ),
+            config,
+        ));
+        let general = CorsPolicy {
+            allowed_origins: vec![
+                "https://app.example.com".into(),
+                "https://partner.example.com".into(),
+            ],
+            ..CorsPolicy::default()
+        };
+        // 与 main 中的注册方式一致
+        let app = test::init_service(
+            App::new().service(
+                web::scope("/api/v1")
+                    .configure(|cfg| AuthController::configure(cfg, auth.clone()))
+                    .service(
+                        web::scope("")
+                            .wrap(cors(&general))
+                            .configure(|cfg| WordController::configure(cfg, words.clone())),
+                    ),
+            ),
+        )
+        .await;
+
+        let login = |origin: &'static str| {
+            test::TestRequest::post()
+                .uri("/api/v1/auth/login")
+                .insert_header((ORIGIN, origin))
+                .set_json(json!({ "username": "someone", "password": "password123" }))
+                .to_request()
+        };
+        let res = test::call_service(&app, login("https://app.example.com")).await;
+        let headers = res.headers();
+        assert_eq!(
+            headers.get(ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(),
+            "https://app.example.com"
+        );
+        assert_eq!(
+            headers.get(ACCESS_CONTROL_ALLOW_CREDENTIALS).unwrap(),
+            "true"
+        );
+        let res = test::call_service(&app, login("https://partner.example.com")).await;
+        assert!(res.headers().get(ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+
+        let words = test::TestRequest::get()
+            .uri("/api/v1/words")
+            .insert_header((ORIGIN, "https://partner.example.com"))
+            .to_request();
+        let res = test::call_service(&app, words).await;
+        let headers = res.headers();
+        assert_eq!(
+            headers.get(ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(),
+            "https://partner.example.com"
+        );
+        assert!(headers.get(ACCESS_CONTROL_ALLOW_CREDENTIALS).is_none());
+
+        // 预检同样按各自的策略判断
+        for (uri, expected) in [
+            ("/api/v1/auth/login", StatusCode::BAD_REQUEST),
+            ("/api/v1/words", StatusCode::OK),
+        ] {
+            let preflight = test::TestRequest::default()
+                .method(Method::OPTIONS)
+                .uri(uri)
+                .insert_header((ORIGIN, "https://partner.example.com"))
+                .insert_header((ACCESS_CONTROL_REQUEST_METHOD, "POST"))
+                .to_request();
+            let res = test::call_service(&app, preflight).await;
+            assert_eq!(res.status(), expected, "{uri}");
+        }
+    }
+
+    #[actix_rt::test]
+    async fn refresh_disabled_is_signalled_on_login_and_refresh() {
+        let mut settings = default_settings();
+        settings.jwt.refresh_ttl_secs = 0;
+        let service = AuthService::new(
+            InMemoryUserRepository::default(),
+            Arc::new(InMemoryRefreshTokenStore::default()),
+            &settings,
+            &settings.jwt,
+        )
+        .unwrap();
+        let controller = web::Data::new(AuthController::new(service));
+        let app = test::init_service(
+            App::new().configure(|cfg| AuthController::configure(cfg, controller.clone())),
+        )
+        .await;
+
+        let register = test::TestRequest::post()
+            .uri("/auth/register")
+            .set_json(json!({ "username": "no_refresh", "password": "password123" }))
+            .to_request();
+        let _ = test::call_service(&app, register).await;
+
+        let login = test::TestRequest::post()
+            .uri("/auth/login")
+            .set_json(json!({ "username": "no_refresh", "password": "password123" }))
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, login).await;
+        assert!(body["data"]["access_token"].is_string());
+        assert!(body["data"]["refresh_token"].is_null());
+        assert_eq!(body["data"]["refresh_enabled"], false);
+
+        let refresh = test::TestRequest::post()
+            .uri("/auth/refresh")
+            .set_json(json!({ "refresh_token": "0000000000" }))
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, refresh).await;
+        assert_eq!(body["code"], 4014);
+    }
+
+    #[actix_rt::test]
+    async fn login_endpoint_tolerates_trailing_slash() {
+        use crate::config::settings::Settings;
+        use crate::middleware::trailing_slash;
+
+        let controller = web::Data::new(AuthController::new(service()));
+        let app = test::init_service(
+            App::new()
+                .wrap(trailing_slash(&Settings::default().application))
+                .service(
+                    web::scope("/api/v1")
+                        .configure(|cfg| AuthController::configure(cfg, controller.clone())),
+                ),
+        )
+        .await;
+
+        let register = test::TestRequest::post()
+            .uri("/api/v1/auth/register/")
+            .set_json(json!({ "username": "user_slash", "password": "password123" }))
+            .to_request();
+        let _ = test::call_service(&app, register).await;
+
+        for uri in ["/api/v1/auth/login", "/api/v1/auth/login/"] {
+            let req = test::TestRequest::post()
+                .uri(uri)
+                .set_json(json!({ "username": "user_slash", "password": "password123" }))
+                .to_request();
+            let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+            assert!(body["data"]["access_token"].is_string(), "{uri}");
+        }
     }
 
     #[actix_rt::test]
@@ -216,7 +684,7 @@ mod tests {
 
         let register = test::TestRequest::post()
             .uri("/auth/register")
-            .set_json(&json!({ "username": "user_profile", "password": "password123" }))
+            .set_json(json!({ "username": "user_profile", "password": "password123" }))
             .to_request();
         let resp = test::call_service(&app, register).await;
         assert!(resp.status().is_success());
@@ -225,7 +693,7 @@ mod tests {
 
         let login = test::TestRequest::post()
             .uri("/auth/login")
-            .set_json(&json!({ "username": "user_profile", "password": "password123" }))
+            .set_json(json!({ "username": "user_profile", "password": "password123" }))
             .to_request();
         let login_resp = test::call_service(&app, login).await;
         assert!(login_resp.status().is_success());
@@ -241,4 +709,36 @@ mod tests {
         let body: serde_json::Value = test::read_body_json(resp).await;
         assert_eq!(body["data"]["id"].as_i64().unwrap(), user_id);
     }
+
+    #[actix_rt::test]
+    async fn verify_email_rejects_unknown_token() {
+        let controller = web::Data::new(AuthController::new(service()));
+        let app = test::init_service(
+            App::new().configure(|cfg| AuthController::configure(cfg, controller.clone())),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/auth/verify-email")
+            .set_json(json!({ "token": "0123456789abcdef" }))
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["code"], 4013);
+    }
+
+    #[actix_rt::test]
+    async fn password_reset_request_does_not_reveal_unknown_email() {
+        let controller = web::Data::new(AuthController::new(service()));
+        let app = test::init_service(
+            App::new().configure(|cfg| AuthController::configure(cfg, controller.clone())),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/auth/password-reset")
+            .set_json(json!({ "email": "nobody@example.com" }))
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["code"], 2000);
+    }
 }
@@ -0,0 +1,67 @@
+use actix_web::{HttpResponse, web};
+
+use crate::dto::health::{HealthResponse, PoolStatus};
+use crate::util::{AppError, ResponseBuilder};
+
+#[derive(Clone)]
+pub struct HealthController {
+    pool: sqlx::PgPool,
+}
+
+impl HealthController {
+    pub fn new(pool: sqlx::PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub fn configure(cfg: &mut web::ServiceConfig, controller: web::Data<HealthController>) {
+        cfg.service(
+            web::resource("/health")
+                .app_data(controller)
+                .route(web::get().to(Self::health)),
+        );
+    }
+
+    async fn health(controller: web::Data<HealthController>) -> Result<HttpResponse, AppError> {
+        ResponseBuilder::ok(HealthResponse {
+            status: "healthy",
+            service: "WordMesh Backend",
+            version: env!("CARGO_PKG_VERSION"),
+            database: PoolStatus::of(&controller.pool),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Settings;
+    use actix_web::{App, test};
+
+    #[actix_rt::test]
+    async fn reports_pool_metrics() {
+        let database = Settings::default().database;
+        let pool = database
+            .pool_options()
+            .connect_lazy_with(database.connect_options());
+        let controller = web::Data::new(HealthController::new(pool));
+        let app = test::init_service(
+            App::new().configure(|cfg| HealthController::configure(cfg, controller.clone())),
+        )
+        .await;
+
+        for _ in 0..2 {
+            let req = test::TestRequest::get().uri("/health").to_request();
+            let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+
+            assert_eq!(json["data"]["status"], "healthy");
+            let database = &json["data"]["database"];
+            for field in ["size", "idle", "in_use", "max_connections"] {
+                assert!(database[field].as_u64().is_some(), "{field}");
+            }
+            assert_eq!(
+                database["max_connections"],
+                Settings::default().database.pool_size
+            );
+        }
+    }
+}
@@ -0,0 +1,966 @@
+use actix_web::{HttpResponse, web};
+use std::sync::Arc;
+
+use crate::dto::link::{
+    AddWordLinkRequest, CreateSenseLinkRequest, CreateWordLinkRequest, DeleteWordLinkQuery,
+    LinkKindStatsResponse, LinkKindsResponse, LinkListQuery, NeighborhoodResponse, NeighborsQuery,
+    SenseLinkListResponse, SenseLinkResponse, UpdateWordLinkRequest, WordLinkDetailsResponse,
+    WordLinkListResponse, WordLinkResponse,
+};
+use crate::middleware::{AuthGuard, AuthenticatedUser};
+use crate::repository::graph::{GraphRepository, SenseWordLinkKind, WordLinkKind};
+use crate::repository::word::WordRepository;
+use crate::service::assoc::AssocService;
+use crate::util::error::{BusinessError, LinkError};
+use crate::util::token::TokenConfig;
+use crate::util::{AppError, ResponseBuilder};
+
+const DEFAULT_LINK_PAGE_SIZE: i64 = 20;
+
+pub struct LinkController<W, G>
+where
+    W: WordRepository + Send + Sync + 'static,
+    G: GraphRepository + Send + Sync + 'static,
+{
+    service: Arc<AssocService<W, G>>,
+    token_config: Arc<TokenConfig>,
+}
+
+impl<W, G> Clone for LinkController<W, G>
+where
+    W: WordRepository + Send + Sync + 'static,
+    G: GraphRepository + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            service: self.service.clone(),
+            token_config: self.token_config.clone(),
+        }
+    }
+}
+
+impl<W, G> LinkController<W, G>
+where
+    W: WordRepository + Send + Sync + 'static,
+    G: GraphRepository + Send + Sync + 'static,
+{
+    pub fn new(service: AssocService<W, G>, token_config: Arc<TokenConfig>) -> Self {
+        Self {
+            service: Arc::new(service),
+            token_config,
+        }
+    }
+
+    pub fn configure(cfg: &mut web::ServiceConfig, controller: web::Data<LinkController<W, G>>) {
+        cfg.service(
+            web::resource("/link-kinds")
+                .app_data(controller.clone())
+                .route(web::get().to(Self::link_kinds)),
+        );
+        cfg.service(
+            web::resource("/graph/stats")
+                .app_data(controller.clone())
+                .wrap(AuthGuard::new(controller.token_config.clone()))
+                .route(web::get().to(Self::link_stats_by_kind)),
+        );
+        cfg.service(
+            web::resource("/links/words")
+                .app_data(controller.clone())
+                .wrap(AuthGuard::new(controller.token_config.clone()))
+                .route(web::post().to(Self::create_word_link)),
+        );
+        cfg.service(
+            web::resource("/links/words/{word_a_id}/{word_b_id}")
+                .app_data(controller.clone())
+                .wrap(AuthGuard::new(controller.token_config.clone()))
+                .route(web::get().to(Self::word_link_details))
+                .route(web::patch().to(Self::update_word_link)),
+        );
+        cfg.service(
+            web::resource("/links/senses")
+                .app_data(controller.clone())
+                .wrap(AuthGuard::new(controller.token_config.clone()))
+                .route(web::post().to(Self::create_sense_link)),
+        );
+        cfg.service(
+            web::resource("/words/{word_id}/links")
+                .app_data(controller.clone())
+                .wrap(AuthGuard::new(controller.token_config.clone()))
+                .route(web::get().to(Self::list_word_links))
+                .route(web::post().to(Self::add_word_link)),
+        );
+        cfg.service(
+            web::resource("/words/{word_id}/links/{target_word_id}")
+                .app_data(controller.clone())
+                .wrap(AuthGuard::new(controller.token_config.clone()))
+                .route(web::delete().to(Self::delete_word_link)),
+        );
+        cfg.service(
+            web::resource("/words/{word_id}/neighbors")
+                .app_data(controller.clone())
+                .wrap(AuthGuard::new(controller.token_config.clone()))
+                .route(web::get().to(Self::neighbors)),
+        );
+        cfg.service(
+            web::resource("/senses/{sense_id}/links")
+                .app_data(controller.clone())
+                .wrap(AuthGuard::new(controller.token_config.clone()))
+                .route(web::get().to(Self::list_sense_links)),
+        );
+    }
+
+    async fn link_kinds(
+        controller: web::Data<LinkController<W, G>>,
+    ) -> Result<HttpResponse, AppError> {
+        let service = &controller.service;
+        ResponseBuilder::ok(LinkKindsResponse::new(
+            service.word_link_kinds(),
+            service.sense_link_kinds(),
+        ))
+    }
+
+    async fn link_stats_by_kind(
+        controller: web::Data<LinkController<W, G>>,
+        identity: AuthenticatedUser,
+    ) -> Result<HttpResponse, AppError> {
+        let stats = controller
+            .service
+            .link_stats_by_kind(identity.user_id)
+            .await;
+        ResponseBuilder::ok(LinkKindStatsResponse::from(stats))
+    }
+
+    async fn create_word_link(
+        controller: web::Data<LinkController<W, G>>,
+        identity: AuthenticatedUser,
+        payload: web::Json<CreateWordLinkRequest>,
+    ) -> Result<HttpResponse, AppError> {
+        let request = payload.into_inner();
+        let kind = controller.word_link_kind(request.kind.as_deref())?;
+        let record = controller
+            .service
+            .create_word_link(
+                identity.user_id,
+                request.word_a_id,
+                request.word_b_id,
+                kind,
+                request.note,
+            )
+            .await?;
+        ResponseBuilder::ok(WordLinkResponse::from(record))
+    }
+
+    async fn word_link_details(
+        controller: web::Data<LinkController<W, G>>,
+        identity: AuthenticatedUser,
+        path: web::Path<(i64, i64)>,
+    ) -> Result<HttpResponse, AppError> {
+        let (word_a_id, word_b_id) = path.into_inner();
+        let links = controller
+            .service
+            .get_word_links(identity.user_id, word_a_id, word_b_id)
+            .await?;
+        ResponseBuilder::ok(WordLinkDetailsResponse::new(word_a_id, word_b_id, links))
+    }
+
+    async fn update_word_link(
+        controller: web::Data<LinkController<W, G>>,
+        identity: AuthenticatedUser,
+        path: web::Path<(i64, i64)>,
+        payload: web::Json<UpdateWordLinkRequest>,
+    ) -> Result<HttpResponse, AppError> {
+        let (word_a_id, word_b_id) = path.into_inner();
+        let request = payload.into_inner();
+        let kind = controller.word_link_kind(Some(&request.kind))?;
+        let new_kind = request
+            .new_kind
+            .as_deref()
+            .map(|value| controller.word_link_kind(Some(value)))
+            .transpose()?;
+        let record = controller
+            .service
+            .update_word_link(
+                identity.user_id,
+                word_a_id,
+                word_b_id,
+                kind,
+                new_kind,
+                request.note,
+            )
+            .await?;
+        ResponseBuilder::ok(WordLinkResponse::from(record))
+    }
+
+    async fn list_word_links(
+        controller: web::Data<LinkController<W, G>>,
+        identity: AuthenticatedUser,
+        word_id: web::Path<i64>,
+        query: web::Query<LinkListQuery>,
+    ) -> Result<HttpResponse, AppError> {
+        let LinkListQuery {
+            kind,
+            limit,
+            offset,
+        } = query.into_inner();
+        let kind = kind
+            .map(|value| WordLinkKind::try_from_str(&value).ok_or_else(type_invalid))
+            .transpose()?;
+        let word_id = word_id.into_inner();
+        let links = controller
+            .service
+            .list_word_links(
+                identity.user_id,
+                word_id,
+                kind,
+                limit.unwrap_or(DEFAULT_LINK_PAGE_SIZE),
+                offset.unwrap_or(0),
+            )
+            .await?;
+        ResponseBuilder::ok(WordLinkListResponse::new(word_id, links))
+    }
+
+    /// 与 `POST /links/words` 相同，路径中的单词作为 `word_a_id`
+    async fn add_word_link(
+        controller: web::Data<LinkController<W, G>>,
+        identity: AuthenticatedUser,
+        word_id: web::Path<i64>,
+        payload: web::Json<AddWordLinkRequest>,
+    ) -> Result<HttpResponse, AppError> {
+        let request = payload.into_inner();
+        let request = CreateWordLinkRequest {
+            word_a_id: word_id.into_inner(),
+            word_b_id: request.target_word_id,
+            kind: request.kind,
+            note: request.note,
+        };
+        Self::create_word_link(controller, identity, web::Json(request)).await
+    }
+
+    async fn delete_word_link(
+        controller: web::Data<LinkController<W, G>>,
+        identity: AuthenticatedUser,
+        path: web::Path<(i64, i64)>,
+        query: web::Query<DeleteWordLinkQuery>,
+    ) -> Result<HttpResponse, AppError> {
+        let (word_id, target_word_id) = path.into_inner();
+        let kind = WordLinkKind::try_from_str(&query.kind).ok_or_else(type_invalid)?;
+        controller
+            .service
+            .delete_word_link(identity.user_id, word_id, target_word_id, kind)
+            .await?;
+        ResponseBuilder::ok(())
+    }
+
+    async fn neighbors(
+        controller: web::Data<LinkController<W, G>>,
+        identity: AuthenticatedUser,
+        word_id: web::Path<i64>,
+        query: web::Query<NeighborsQuery>,
+    ) -> Result<HttpResponse, AppError> {
+        let NeighborsQuery { depth, kind } = query.into_inner();
+        let kind = kind
+            .map(|value| WordLinkKind::try_from_str(&value).ok_or_else(type_invalid))
+            .transpose()?;
+        let word_id = word_id.into_inner();
+        let neighborhood = controller
+            .service
+            .neighbors(identity.user_id, word_id, depth.unwrap_or(1), kind)
+            .await?;
+        ResponseBuilder::ok(NeighborhoodResponse::new(word_id, neighborhood))
+    }
+
+    async fn list_sense_links(
+        controller: web::Data<LinkController<W, G>>,
+        identity: AuthenticatedUser,
+        sense_id: web::Path<i64>,
+        query: web::Query<LinkListQuery>,
+    ) -> Result<HttpResponse, AppError> {
+        let LinkListQuery {
+            kind,
+            limit,
+            offset,
+        } = query.into_inner();
+        let kind = kind
+            .map(|value| SenseWordLinkKind::try_from_str(&value).ok_or_else(type_invalid))
+            .transpose()?;
+        let sense_id = sense_id.into_inner();
+        let links = controller
+            .service
+            .list_sense_links(
+                identity.user_id,
+                sense_id,
+                kind,
+                limit.unwrap_or(DEFAULT_LINK_PAGE_SIZE),
+                offset.unwrap_or(0),
+            )
+            .await?;
+        ResponseBuilder::ok(SenseLinkListResponse::new(sense_id, links))
+    }
+
+    async fn create_sense_link(
+        controller: web::Data<LinkController<W, G>>,
+        identity: AuthenticatedUser,
+        payload: web::Json<CreateSenseLinkRequest>,
+    ) -> Result<HttpResponse, AppError> {
+        let request = payload.into_inner();
+        let kind = controller.sense_link_kind(request.kind.as_deref())?;
+        let record = controller
+            .service
+            .create_sense_link(
+                identity.user_id,
+                request.sense_id,
+                request.source_word_id,
+                request.target_word_id,
+                kind,
+                request.note,
+            )
+            .await?;
+        ResponseBuilder::ok(SenseLinkResponse::from(record))
+    }
+}
+
+impl<W, G> LinkController<W, G>
+where
+    W: WordRepository + Send + Sync + 'static,
+    G: GraphRepository + Send + Sync + 'static,
+{
+    fn word_link_kind(&self, value: Option<&str>) -> Result<WordLinkKind, AppError> {
+        resolve_kind(
+            value,
+            WordLinkKind::try_from_str,
+            self.service.word_link_kinds(),
+            self.service.default_word_link_kind(),
+        )
+    }
+
+    fn sense_link_kind(&self, value: Option<&str>) -> Result<SenseWordLinkKind, AppError> {
+        resolve_kind(
+            value,
+            SenseWordLinkKind::try_from_str,
+            self.service.sense_link_kinds(),
+            self.service.default_sense_link_kind(),
+        )
+    }
+}
+
+/// 创建或修改关联时的类型：省略时取配置的默认类型，未知或已停用的类型在访问图库前拒绝
+fn resolve_kind<K: Copy + PartialEq>(
+    value: Option<&str>,
+    parse: fn(&str) -> Option<K>,
+    enabled: &[K],
+    default: K,
+) -> Result<K, AppError> {
+    let Some(value) = value else {
+        return Ok(default);
+    };
+    parse(value)
+        .filter(|kind| enabled.contains(kind))
+        .ok_or_else(type_invalid)
+}
+
+fn type_invalid() -> AppError {
+    AppError::from(BusinessError::Link(LinkError::TypeInvalid))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_support::token_config;
+    use actix_web::{App, test};
+
+    use crate::config::settings::GraphSettings;
+    use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+    async fn fetch_kinds(settings: GraphSettings) -> serde_json::Value {
+        let service = AssocService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+            &settings,
+        );
+        let controller = web::Data::new(LinkController::new(service, token_config()));
+        let app = test::init_service(
+            App::new().configure(|cfg| LinkController::configure(cfg, controller.clone())),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/link-kinds").to_request();
+        test::call_and_read_body_json(&app, req).await
+    }
+
+    fn values(json: &serde_json::Value, key: &str) -> Vec<String> {
+        json["data"][key]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|kind| kind["value"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[actix_rt::test]
+    async fn lists_all_kinds_with_stable_values() {
+        let json = fetch_kinds(GraphSettings::default()).await;
+
+        assert_eq!(json["code"], 2000);
+        assert_eq!(
+            values(&json, "word_link_kinds"),
+            vec!["similar_form", "root_affix"]
+        );
+        assert_eq!(
+            values(&json, "sense_word_link_kinds"),
+            vec!["synonym", "antonym", "related"]
+        );
+        assert!(json["data"]["word_link_kinds"][0]["description"].is_string());
+    }
+
+    async fn post_link(uri: &str, body: serde_json::Value) -> serde_json::Value {
+        post_link_with(&GraphSettings::default(), uri, body).await
+    }
+
+    async fn post_link_with(
+        settings: &GraphSettings,
+        uri: &str,
+        body: serde_json::Value,
+    ) -> serde_json::Value {
+        let config = token_config();
+        let service = AssocService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+            settings,
+        );
+        let controller = web::Data::new(LinkController::new(service, config.clone()));
+        let app = test::init_service(
+            App::new().configure(|cfg| LinkController::configure(cfg, controller.clone())),
+        )
+        .await;
+
+        let token = crate::util::token::generate_access_token(&config, "7", None, None).unwrap();
+        let req = test::TestRequest::post()
+            .uri(uri)
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .set_json(body)
+            .to_request();
+        test::call_and_read_body_json(&app, req).await
+    }
+
+    #[actix_rt::test]
+    async fn self_word_link_is_forbidden() {
+        let json = post_link(
+            "/links/words",
+            serde_json::json!({ "word_a_id": 3, "word_b_id": 3, "kind": "similar_form" }),
+        )
+        .await;
+
+        assert_eq!(json["code"], 4302);
+    }
+
+    #[actix_rt::test]
+    async fn sense_link_to_its_own_word_is_forbidden() {
+        let json = post_link(
+            "/links/senses",
+            serde_json::json!({
+                "sense_id": 11,
+                "source_word_id": 3,
+                "target_word_id": 3,
+                "kind": "synonym"
+            }),
+        )
+        .await;
+
+        assert_eq!(json["code"], 4302);
+    }
+
+    #[actix_rt::test]
+    async fn word_link_is_created() {
+        let json = post_link(
+            "/links/words",
+            serde_json::json!({ "word_a_id": 9, "word_b_id": 3, "kind": "root_affix" }),
+        )
+        .await;
+
+        assert_eq!(json["code"], 2000);
+        assert_eq!(json["data"]["word_a_id"], 3);
+        assert_eq!(json["data"]["kind"], "root_affix");
+    }
+
+    #[actix_rt::test]
+    async fn omitted_link_kind_uses_the_configured_default() {
+        let json = post_link(
+            "/links/words",
+            serde_json::json!({ "word_a_id": 9, "word_b_id": 3 }),
+        )
+        .await;
+        assert_eq!(json["code"], 2000);
+        assert_eq!(json["data"]["kind"], "similar_form");
+
+        let settings = GraphSettings {
+            default_word_link_kind: "root_affix".into(),
+            ..GraphSettings::default()
+        };
+        let json = post_link_with(
+            &settings,
+            "/links/words",
+            serde_json::json!({ "word_a_id": 9, "word_b_id": 3 }),
+        )
+        .await;
+        assert_eq!(json["data"]["kind"], "root_affix");
+
+        // 显式给出的类型优先于默认类型
+        let json = post_link_with(
+            &settings,
+            "/links/words",
+            serde_json::json!({ "word_a_id": 9, "word_b_id": 3, "kind": "similar_form" }),
+        )
+        .await;
+        assert_eq!(json["code"], 2000);
+        assert_eq!(json["data"]["kind"], "similar_form");
+    }
+
+    #[actix_rt::test]
+    async fn disabled_or_unknown_link_kinds_are_rejected() {
+        let settings = GraphSettings {
+            disabled_link_kinds: vec!["root_affix".into(), "antonym".into()],
+            ..GraphSettings::default()
+        };
+        for kind in ["root_affix", "cousin"] {
+            let json = post_link_with(
+                &settings,
+                "/links/words",
+                serde_json::json!({ "word_a_id": 9, "word_b_id": 3, "kind": kind }),
+            )
+            .await;
+            assert_eq!(json["code"], 4304, "{kind}");
+        }
+        let json = post_link_with(
+            &settings,
+            "/words/9/links",
+            serde_json::json!({ "target_word_id": 3, "kind": "root_affix" }),
+        )
+        .await;
+        assert_eq!(json["code"], 4304);
+        let json = post_link_with(
+            &settings,
+            "/links/senses",
+            serde_json::json!({
+                "sense_id": 11,
+                "source_word_id": 3,
+                "target_word_id": 9,
+                "kind": "antonym"
+            }),
+        )
+        .await;
+        assert_eq!(json["code"], 4304);
+    }
+
+    async fn get_details(graph: InMemoryGraphRepository, uri: &str) -> serde_json::Value {
+        let config = token_config();
+        let service = AssocService::new(
+            InMemoryWordRepository::default(),
+            graph,
+            &GraphSettings::default(),
+        );
+        let controller = web::Data::new(LinkController::new(service, config.clone()));
+        let app = test::init_service(
+            App::new().configure(|cfg| LinkController::configure(cfg, controller.clone())),
+        )
+        .await;
+
+        let token = crate::util::token::generate_access_token(&config, "7", None, None).unwrap();
+        let req = test::TestRequest::get()
+            .uri(uri)
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        test::call_and_read_body_json(&app, req).await
+    }
+
+    #[actix_rt::test]
+    async fn word_link_details_include_note_and_rfc3339_timestamp() {
+        let graph = InMemoryGraphRepository::default();
+        let record = graph
+            .create_word_link(7, 3, 9, WordLinkKind::RootAffix, Some("共享 -ject".into()))
+            .await
+            .unwrap();
+
+        let json = get_details(graph, "/links/words/9/3").await;
+
+        assert_eq!(json["code"], 2000);
+        assert_eq!(json["data"]["word_a_id"], 9);
+        let link = &json["data"]["links"][0];
+        assert_eq!(link["link_id"], record.link_id.as_str());
+        assert_eq!(link["kind"], "root_affix");
+        assert_eq!(link["note"], "共享 -ject");
+        let created_at =
+            chrono::DateTime::parse_from_rfc3339(link["created_at"].as_str().unwrap()).unwrap();
+        assert_eq!(created_at, record.created_at);
+    }
+
+    #[actix_rt::test]
+    async fn word_link_details_hide_other_users_links() {
+        let graph = InMemoryGraphRepository::default();
+        graph
+            .create_word_link(8, 3, 9, WordLinkKind::SimilarForm, Some("note".into()))
+            .await
+            .unwrap();
+
+        let json = get_details(graph, "/links/words/3/9").await;
+
+        assert_eq!(json["code"], 4303);
+    }
+
+    #[actix_rt::test]
+    async fn word_links_are_listed_from_either_end_and_filtered_by_kind() {
+        let graph = InMemoryGraphRepository::default();
+        graph
+            .create_word_link(7, 3, 9, WordLinkKind::SimilarForm, None)
+            .await
+            .unwrap();
+        graph
+            .create_word_link(7, 1, 9, WordLinkKind::RootAffix, None)
+            .await
+            .unwrap();
+        graph
+            .create_word_link(8, 2, 9, WordLinkKind::RootAffix, None)
+            .await
+            .unwrap();
+
+        let json = get_details(graph.clone(), "/words/9/links").await;
+        assert_eq!(json["code"], 2000);
+        assert_eq!(json["data"]["word_id"], 9);
+        assert_eq!(json["data"]["links"].as_array().unwrap().len(), 2);
+
+        let json = get_details(graph.clone(), "/words/9/links?kind=root_affix").await;
+        let links = json["data"]["links"].as_array().unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0]["word_a_id"], 1);
+        assert_eq!(links[0]["kind"], "root_affix");
+
+        let json = get_details(graph, "/words/9/links?limit=1&offset=1").await;
+        assert_eq!(json["data"]["links"].as_array().unwrap().len(), 1);
+    }
+
+    #[actix_rt::test]
+    async fn link_listing_rejects_unknown_kind() {
+        let graph = InMemoryGraphRepository::default();
+
+        let json = get_details(graph.clone(), "/words/9/links?kind=synonym").await;
+        assert_eq!(json["code"], 4304);
+        let json = get_details(graph, "/senses/11/links?kind=similar_form").await;
+        assert_eq!(json["code"], 4304);
+    }
+
+    #[actix_rt::test]
+    async fn sense_links_are_listed_by_kind() {
+        let graph = InMemoryGraphRepository::default();
+        for (target, kind) in [
+            (4, SenseWordLinkKind::Synonym),
+            (5, SenseWordLinkKind::Antonym),
+        ] {
+            graph
+                .create_sense_word_link(7, 11, 3, target, kind, None)
+                .await
+                .unwrap();
+        }
+
+        let json = get_details(graph, "/senses/11/links?kind=antonym").await;
+
+        assert_eq!(json["code"], 2000);
+        assert_eq!(json["data"]["sense_id"], 11);
+        let links = json["data"]["links"].as_array().unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0]["target_word_id"], 5);
+        assert_eq!(links[0]["kind"], "antonym");
+    }
+
+    async fn patch_link(
+        graph: InMemoryGraphRepository,
+        uri: &str,
+        body: serde_json::Value,
+    ) -> serde_json::Value {
+        patch_link_with(graph, &GraphSettings::default(), uri, body).await
+    }
+
+    async fn patch_link_with(
+        graph: InMemoryGraphRepository,
+        settings: &GraphSettings,
+        uri: &str,
+        body: serde_json::Value,
+    ) -> serde_json::Value {
+        let config = token_config();
+        let service = AssocService::new(InMemoryWordRepository::default(), graph, settings);
+        let controller = web::Data::new(LinkController::new(service, config.clone()));
+        let app = test::init_service(
+            App::new().configure(|cfg| LinkController::configure(cfg, controller.clone())),
+        )
+        .await;
+
+        let token = crate::util::token::generate_access_token(&config, "7", None, None).unwrap();
+        let req = test::TestRequest::patch()
+            .uri(uri)
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .set_json(body)
+            .to_request();
+        test::call_and_read_body_json(&app, req).await
+    }
+
+    #[actix_rt::test]
+    async fn patch_word_link_changes_kind_and_note_together() {
+        let graph = InMemoryGraphRepository::default();
+        let original = graph
+            .create_word_link(7, 3, 9, WordLinkKind::SimilarForm, Some("形近".into()))
+            .await
+            .unwrap();
+
+        let json = patch_link(
+            graph.clone(),
+            "/links/words/9/3",
+            serde_json::json!({ "kind": "similar_form", "new_kind": "root_affix", "note": "共享词根" }),
+        )
+        .await;
+
+        assert_eq!(json["code"], 2000);
+        assert_eq!(json["data"]["link_id"], original.link_id.as_str());
+        assert_eq!(json["data"]["kind"], "root_affix");
+        assert_eq!(json["data"]["note"], "共享词根");
+        let links = graph.word_links();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].created_at, original.created_at);
+
+        // 新类型与已有关联重复时整体拒绝，备注也保持不变
+        graph
+            .create_word_link(7, 3, 9, WordLinkKind::SimilarForm, None)
+            .await
+            .unwrap();
+        let json = patch_link(
+            graph.clone(),
+            "/links/words/3/9",
+            serde_json::json!({ "kind": "similar_form", "new_kind": "root_affix", "note": "x" }),
+        )
+        .await;
+        assert_eq!(json["code"], 4301);
+        let similar = graph
+            .word_links()
+            .into_iter()
+            .find(|link| link.kind == WordLinkKind::SimilarForm)
+            .unwrap();
+        assert_eq!(similar.note, None);
+
+        // 显式 null 清除备注，省略 new_kind 时类型不变
+        let json = patch_link(
+            graph.clone(),
+            "/links/words/3/9",
+            serde_json::json!({ "kind": "root_affix", "note": null }),
+        )
+        .await;
+        assert_eq!(json["code"], 2000);
+        assert_eq!(json["data"]["kind"], "root_affix");
+        assert!(json["data"]["note"].is_null());
+    }
+
+    #[actix_rt::test]
+    async fn patch_word_link_rejects_unknown_kinds() {
+        let graph = InMemoryGraphRepository::default();
+        graph
+            .create_word_link(7, 3, 9, WordLinkKind::SimilarForm, None)
+            .await
+            .unwrap();
+
+        for body in [
+            serde_json::json!({ "kind": "cousin", "new_kind": "root_affix" }),
+            serde_json::json!({ "kind": "similar_form", "new_kind": "cousin" }),
+        ] {
+            let json = patch_link(graph.clone(), "/links/words/3/9", body.clone()).await;
+            assert_eq!(json["code"], 4304, "{body}");
+        }
+        assert_eq!(graph.word_links()[0].kind, WordLinkKind::SimilarForm);
+    }
+
+    #[actix_rt::test]
+    async fn patch_word_link_rejects_a_disabled_new_kind() {
+        let graph = InMemoryGraphRepository::default();
+        graph
+            .create_word_link(7, 3, 9, WordLinkKind::SimilarForm, None)
+            .await
+            .unwrap();
+        let settings = GraphSettings {
+            disabled_link_kinds: vec!["root_affix".into()],
+            ..GraphSettings::default()
+        };
+
+        let json = patch_link_with(
+            graph.clone(),
+            &settings,
+            "/links/words/3/9",
+            serde_json::json!({ "kind": "similar_form", "new_kind": "root_affix" }),
+        )
+        .await;
+
+        assert_eq!(json["code"], 4304);
+        let links = graph.word_links();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].kind, WordLinkKind::SimilarForm);
+    }
+
+    #[actix_rt::test]
+    async fn omits_disabled_kinds() {
+        let json = fetch_kinds(GraphSettings {
+            disabled_link_kinds: vec!["root_affix".into(), "antonym".into()],
+            ..GraphSettings::default()
+        })
+        .await;
+
+        assert_eq!(values(&json, "word_link_kinds"), vec!["similar_form"]);
+        assert_eq!(
+            values(&json, "sense_word_link_kinds"),
+            vec!["synonym", "related"]
+        );
+    }
+
+    async fn send(graph: InMemoryGraphRepository, request: test::TestRequest) -> serde_json::Value {
+        let config = token_config();
+        let service = AssocService::new(
+            InMemoryWordRepository::default(),
+            graph,
+            &GraphSettings::default(),
+        );
+        let controller = web::Data::new(LinkController::new(service, config.clone()));
+        let app = test::init_service(
+            App::new().configure(|cfg| LinkController::configure(cfg, controller.clone())),
+        )
+        .await;
+
+        let token = crate::util::token::generate_access_token(&config, "7", None, None).unwrap();
+        let req = request
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        test::call_and_read_body_json(&app, req).await
+    }
+
+    fn add_link(word_id: i64, body: serde_json::Value) -> test::TestRequest {
+        test::TestRequest::post()
+            .uri(&format!("/words/{word_id}/links"))
+            .set_json(body)
+    }
+
+    #[actix_rt::test]
+    async fn word_link_is_added_from_the_path_word() {
+        let graph = InMemoryGraphRepository::default();
+
+        let json = send(
+            graph.clone(),
+            add_link(
+                9,
+                serde_json::json!({ "target_word_id": 3, "kind": "similar_form", "note": "形近" }),
+            ),
+        )
+        .await;
+
+        assert_eq!(json["code"], 2000);
+        assert_eq!(json["data"]["word_a_id"], 3);
+        assert_eq!(json["data"]["word_b_id"], 9);
+        assert_eq!(json["data"]["note"], "形近");
+        let stored = graph.get_word_links_between(7, 3, 9).await.unwrap();
+        assert_eq!(stored.len(), 1);
+    }
+
+    #[actix_rt::test]
+    async fn adding_word_link_surfaces_specific_errors() {
+        let json = send(
+            InMemoryGraphRepository::default(),
+            add_link(
+                3,
+                serde_json::json!({ "target_word_id": 3, "kind": "root_affix" }),
+            ),
+        )
+        .await;
+        assert_eq!(json["code"], 4302);
+
+        let json = send(
+            InMemoryGraphRepository::default(),
+            add_link(
+                3,
+                serde_json::json!({ "target_word_id": 9, "kind": "synonym" }),
+            ),
+        )
+        .await;
+        assert_eq!(json["code"], 4304);
+
+        // 图库故障不再被误报为关联不存在
+        let json = send(
+            InMemoryGraphRepository::failing(),
+            add_link(
+                3,
+                serde_json::json!({ "target_word_id": 9, "kind": "root_affix" }),
+            ),
+        )
+        .await;
+        assert_eq!(json["code"], 5000);
+    }
+
+    #[actix_rt::test]
+    async fn word_link_is_deleted_by_kind() {
+        let graph = InMemoryGraphRepository::default();
+        for kind in [WordLinkKind::SimilarForm, WordLinkKind::RootAffix] {
+            graph.create_word_link(7, 3, 9, kind, None).await.unwrap();
+        }
+        let delete = || test::TestRequest::delete().uri("/words/9/links/3?kind=root_affix");
+
+        let json = send(graph.clone(), delete()).await;
+        assert_eq!(json["code"], 2000);
+        let remaining = graph.get_word_links_between(7, 3, 9).await.unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].kind, WordLinkKind::SimilarForm);
+
+        let json = send(graph, delete()).await;
+        assert_eq!(json["code"], 4303);
+    }
+
+    #[actix_rt::test]
+    async fn other_users_word_link_is_not_deleted() {
+        let graph = InMemoryGraphRepository::default();
+        graph
+            .create_word_link(8, 3, 9, WordLinkKind::SimilarForm, None)
+            .await
+            .unwrap();
+
+        let json = send(
+            graph.clone(),
+            test::TestRequest::delete().uri("/words/3/links/9?kind=similar_form"),
+        )
+        .await;
+
+        assert_eq!(json["code"], 4303);
+        assert_eq!(
+            graph.get_word_links_between(8, 3, 9).await.unwrap().len(),
+            1
+        );
+    }
+
+    #[actix_rt::test]
+    async fn neighbors_follow_only_the_requested_kind() {
+        let graph = InMemoryGraphRepository::default();
+        graph
+            .create_word_link(7, 1, 2, WordLinkKind::SimilarForm, None)
+            .await
+            .unwrap();
+        graph
+            .create_word_link(7, 2, 3, WordLinkKind::RootAffix, None)
+            .await
+            .unwrap();
+
+        let json = get_details(graph.clone(), "/words/1/neighbors?depth=2").await;
+        assert_eq!(json["code"], 2000);
+        assert_eq!(json["data"]["word_id"], 1);
+        assert_eq!(json["data"]["word_ids"], serde_json::json!([2, 3]));
+        assert_eq!(json["data"]["links"].as_array().unwrap().len(), 2);
+        assert_eq!(json["data"]["truncated"], false);
+
+        let json = get_details(
+            graph.clone(),
+            "/words/1/neighbors?depth=2&kind=similar_form",
+        )
+        .await;
+        assert_eq!(json["data"]["word_ids"], serde_json::json!([2]));
+
+        let json = get_details(graph, "/words/1/neighbors?kind=antonym").await;
+        assert_eq!(json["code"], 4304);
+    }
+}
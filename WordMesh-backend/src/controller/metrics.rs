@@ -0,0 +1,57 @@
+use actix_web::{HttpResponse, web};
+
+use crate::util::metrics::metrics;
+
+/// Prometheus 抓取入口；不走统一响应结构，直接输出文本格式。是否注册由 `features.metrics_enabled` 决定
+pub struct MetricsController;
+
+impl MetricsController {
+    pub fn configure(cfg: &mut web::ServiceConfig) {
+        cfg.service(web::resource("/metrics").route(web::get().to(Self::metrics)));
+    }
+
+    async fn metrics() -> HttpResponse {
+        HttpResponse::Ok()
+            .content_type("text/plain; version=0.0.4; charset=utf-8")
+            .body(metrics().render())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::RequestMetrics;
+    use crate::util::metrics::HTTP_REQUESTS_TOTAL;
+    use actix_web::{App, test};
+
+    #[actix_rt::test]
+    async fn exposes_the_request_counter() {
+        let app = test::init_service(
+            App::new()
+                .wrap(RequestMetrics)
+                .configure(MetricsController::configure),
+        )
+        .await;
+        // 第一次抓取本身也会被计数
+        test::call_service(&app, test::TestRequest::get().uri("/metrics").to_request()).await;
+
+        let res =
+            test::call_service(&app, test::TestRequest::get().uri("/metrics").to_request()).await;
+        assert!(
+            res.headers()
+                .get("content-type")
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .starts_with("text/plain; version=0.0.4")
+        );
+        let body = String::from_utf8(test::read_body(res).await.to_vec()).unwrap();
+        assert!(body.contains(&format!("# TYPE {HTTP_REQUESTS_TOTAL} counter")));
+        assert!(
+            body.contains(&format!(
+                "{HTTP_REQUESTS_TOTAL}{{method=\"GET\",path=\"/metrics\",status=\"200\"}}"
+            )),
+            "{body}"
+        );
+    }
+}
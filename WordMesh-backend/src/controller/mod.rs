@@ -1 +1,8 @@
+pub mod admin;
 pub mod auth;
+pub mod health;
+pub mod link;
+pub mod metrics;
+pub mod preferences;
+pub mod sense;
+pub mod word;
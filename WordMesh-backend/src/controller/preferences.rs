@@ -0,0 +1,138 @@
+use actix_web::{HttpResponse, web};
+use std::sync::Arc;
+
+use crate::dto::preferences::{PreferencesResponse, UpdatePreferencesRequest};
+use crate::middleware::{AuthGuard, AuthenticatedUser};
+use crate::repository::preferences::PreferencesRepository;
+use crate::service::preferences::PreferencesService;
+use crate::service::word::FieldErrors;
+use crate::util::token::TokenConfig;
+use crate::util::{AppError, ResponseBuilder};
+
+pub struct PreferencesController<P>
+where
+    P: PreferencesRepository + Send + Sync + 'static,
+{
+    service: Arc<PreferencesService<P>>,
+    token_config: Arc<TokenConfig>,
+}
+
+impl<P> Clone for PreferencesController<P>
+where
+    P: PreferencesRepository + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            service: self.service.clone(),
+            token_config: self.token_config.clone(),
+        }
+    }
+}
+
+impl<P> PreferencesController<P>
+where
+    P: PreferencesRepository + Send + Sync + 'static,
+{
+    pub fn new(service: PreferencesService<P>, token_config: Arc<TokenConfig>) -> Self {
+        Self {
+            service: Arc::new(service),
+            token_config,
+        }
+    }
+
+    pub fn configure(
+        cfg: &mut web::ServiceConfig,
+        controller: web::Data<PreferencesController<P>>,
+    ) {
+        let guard = AuthGuard::new(controller.token_config.clone());
+        cfg.service(
+            web::resource("/preferences")
+                .app_data(controller.clone())
+                .wrap(guard)
+                .route(web::get().to(Self::get))
+                .route(web::put().to(Self::update)),
+        );
+    }
+
+    async fn get(
+        controller: web::Data<PreferencesController<P>>,
+        identity: AuthenticatedUser,
+    ) -> Result<HttpResponse, AppError> {
+        let preferences = controller.service.get(identity.user_id).await?;
+        ResponseBuilder::ok(PreferencesResponse::from(preferences))
+    }
+
+    async fn update(
+        controller: web::Data<PreferencesController<P>>,
+        identity: AuthenticatedUser,
+        payload: web::Json<UpdatePreferencesRequest>,
+    ) -> Result<HttpResponse, AppError> {
+        let request = payload.into_inner();
+        if !request.unknown.is_empty() {
+            let mut errors = FieldErrors::default();
+            for key in request.unknown.keys() {
+                errors.push(key, "未知的偏好项");
+            }
+            return Err(errors.into_error());
+        }
+        let preferences = controller
+            .service
+            .update(identity.user_id, request.into())
+            .await?;
+        ResponseBuilder::ok(PreferencesResponse::from(preferences))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_support::token_config;
+    use actix_web::{App, test};
+
+    use crate::repository::memory::InMemoryPreferencesRepository;
+
+    #[actix_rt::test]
+    async fn update_rejects_unknown_keys_and_keeps_stored_preferences() {
+        let config = token_config();
+        let controller = web::Data::new(PreferencesController::new(
+            PreferencesService::new(InMemoryPreferencesRepository::default()),
+            config.clone(),
+        ));
+        let app = test::init_service(
+            App::new().configure(|cfg| PreferencesController::configure(cfg, controller.clone())),
+        )
+        .await;
+        let token = crate::util::token::generate_access_token(&config, "5", None, None).unwrap();
+        let put = |body: serde_json::Value| {
+            test::TestRequest::put()
+                .uri("/preferences")
+                .insert_header(("Authorization", format!("Bearer {token}")))
+                .set_json(body)
+                .to_request()
+        };
+
+        let json: serde_json::Value = test::call_and_read_body_json(
+            &app,
+            put(serde_json::json!({ "default_scope": "word" })),
+        )
+        .await;
+        assert_eq!(json["code"], 2000);
+        assert_eq!(json["data"]["default_scope"], "word");
+
+        let json: serde_json::Value = test::call_and_read_body_json(
+            &app,
+            put(serde_json::json!({ "default_sort": "recent", "theme": "dark" })),
+        )
+        .await;
+        assert_eq!(json["code"], 4001);
+        assert_eq!(json["data"][0]["field"], "theme");
+
+        let req = test::TestRequest::get()
+            .uri("/preferences")
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .to_request();
+        let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(json["data"]["default_scope"], "word");
+        assert!(json["data"]["default_sort"].is_null());
+    }
+}
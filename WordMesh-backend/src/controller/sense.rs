@@ -0,0 +1,234 @@
+use actix_web::{HttpResponse, web};
+use std::sync::Arc;
+
+use crate::dto::word::{ReorderSensesRequest, SenseListResponse};
+use crate::middleware::{AuthGuard, AuthenticatedUser};
+use crate::repository::graph::GraphRepository;
+use crate::repository::word::WordRepository;
+use crate::service::SenseService;
+use crate::util::token::TokenConfig;
+use crate::util::{AppError, ResponseBuilder};
+
+pub struct SenseController<W, G>
+where
+    W: WordRepository + Send + Sync + 'static,
+    G: GraphRepository + Send + Sync + 'static,
+{
+    service: Arc<SenseService<W, G>>,
+    token_config: Arc<TokenConfig>,
+}
+
+impl<W, G> Clone for SenseController<W, G>
+where
+    W: WordRepository + Send + Sync + 'static,
+    G: GraphRepository + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            service: self.service.clone(),
+            token_config: self.token_config.clone(),
+        }
+    }
+}
+
+impl<W, G> SenseController<W, G>
+where
+    W: WordRepository + Send + Sync + 'static,
+    G: GraphRepository + Send + Sync + 'static,
+{
+    pub fn new(service: SenseService<W, G>, token_config: Arc<TokenConfig>) -> Self {
+        Self {
+            service: Arc::new(service),
+            token_config,
+        }
+    }
+
+    pub fn configure(cfg: &mut web::ServiceConfig, controller: web::Data<SenseController<W, G>>) {
+        cfg.service(
+            web::resource("/words/{id}/senses/order")
+                .app_data(controller.clone())
+                .wrap(AuthGuard::new(controller.token_config.clone()))
+                .route(web::put().to(Self::reorder_senses)),
+        );
+        cfg.service(
+            web::resource("/senses/{sense_id}/primary")
+                .app_data(controller.clone())
+                .wrap(AuthGuard::new(controller.token_config.clone()))
+                .route(web::put().to(Self::set_primary)),
+        );
+    }
+
+    async fn reorder_senses(
+        controller: web::Data<SenseController<W, G>>,
+        identity: AuthenticatedUser,
+        path: web::Path<i64>,
+        payload: web::Json<ReorderSensesRequest>,
+    ) -> Result<HttpResponse, AppError> {
+        // 与 `/words/{id}` 一致，路径中的 id 是 user_word_id
+        let user_word_id = path.into_inner();
+        let senses = controller
+            .service
+            .reorder_senses(
+                identity.user_id,
+                user_word_id,
+                payload.into_inner().sense_ids,
+            )
+            .await?;
+        ResponseBuilder::ok(SenseListResponse::new(user_word_id, &senses))
+    }
+
+    async fn set_primary(
+        controller: web::Data<SenseController<W, G>>,
+        identity: AuthenticatedUser,
+        sense_id: web::Path<i64>,
+    ) -> Result<HttpResponse, AppError> {
+        let user_word = controller
+            .service
+            .set_primary(identity.user_id, sense_id.into_inner())
+            .await?;
+        ResponseBuilder::ok(SenseListResponse::new(
+            user_word.id.unwrap_or_default(),
+            user_word.senses(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_support::token_config;
+    use actix_web::{App, test};
+
+    use crate::domain::word::CanonicalKey;
+    use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+    use crate::repository::word::{NewUserSense, UpsertUserWord};
+
+    #[actix_rt::test]
+    async fn put_sense_order_returns_the_new_order() {
+        let repository = InMemoryWordRepository::default();
+        let user_word_id = repository
+            .upsert_user_word(UpsertUserWord {
+                user_id: 7,
+                word_text: "light".into(),
+                canonical_key: CanonicalKey::new("light").unwrap(),
+                tags: vec![],
+                note: None,
+            })
+            .await
+            .unwrap()
+            .aggregate
+            .user_word
+            .id
+            .unwrap();
+        let mut ids = Vec::new();
+        for (sort_order, text) in ["not heavy", "brightness"].iter().enumerate() {
+            let sense = repository
+                .add_user_sense(NewUserSense {
+                    user_word_id,
+                    text: text.to_string(),
+                    is_primary: false,
+                    sort_order: sort_order as i32,
+                    note: None,
+                    source_url: None,
+                })
+                .await
+                .unwrap();
+            ids.push(sense.id().unwrap());
+        }
+
+        let config = token_config();
+        let service = SenseService::new(repository, InMemoryGraphRepository::default());
+        let controller = web::Data::new(SenseController::new(service, config.clone()));
+        let app = test::init_service(
+            App::new().configure(|cfg| SenseController::configure(cfg, controller.clone())),
+        )
+        .await;
+        let token = crate::util::token::generate_access_token(&config, "7", None, None).unwrap();
+        let put = |sense_ids: Vec<i64>| {
+            test::TestRequest::put()
+                .uri(&format!("/words/{user_word_id}/senses/order"))
+                .insert_header(("Authorization", format!("Bearer {token}")))
+                .set_json(serde_json::json!({ "sense_ids": sense_ids }))
+                .to_request()
+        };
+
+        let json: serde_json::Value =
+            test::call_and_read_body_json(&app, put(vec![ids[1], ids[0]])).await;
+        assert_eq!(json["code"], 2000);
+        assert_eq!(json["data"]["user_word_id"], user_word_id);
+        assert_eq!(json["data"]["senses"][0]["text"], "brightness");
+        assert_eq!(json["data"]["senses"][0]["sort_order"], 0);
+        assert_eq!(json["data"]["senses"][1]["text"], "not heavy");
+
+        let json: serde_json::Value = test::call_and_read_body_json(&app, put(vec![ids[1]])).await;
+        assert_eq!(json["code"], 4001);
+        assert_eq!(json["data"][0]["field"], "sense_ids");
+    }
+
+    #[actix_rt::test]
+    async fn put_primary_leaves_exactly_one_primary_sense() {
+        let repository = InMemoryWordRepository::default();
+        let user_word_id = repository
+            .upsert_user_word(UpsertUserWord {
+                user_id: 7,
+                word_text: "spring".into(),
+                canonical_key: CanonicalKey::new("spring").unwrap(),
+                tags: vec![],
+                note: None,
+            })
+            .await
+            .unwrap()
+            .aggregate
+            .user_word
+            .id
+            .unwrap();
+        let mut ids = Vec::new();
+        for (sort_order, text) in ["season", "coil"].iter().enumerate() {
+            let sense = repository
+                .add_user_sense(NewUserSense {
+                    user_word_id,
+                    text: text.to_string(),
+                    is_primary: sort_order == 0,
+                    sort_order: sort_order as i32,
+                    note: None,
+                    source_url: None,
+                })
+                .await
+                .unwrap();
+            ids.push(sense.id().unwrap());
+        }
+
+        let config = token_config();
+        let service = SenseService::new(repository, InMemoryGraphRepository::default());
+        let controller = web::Data::new(SenseController::new(service, config.clone()));
+        let app = test::init_service(
+            App::new().configure(|cfg| SenseController::configure(cfg, controller.clone())),
+        )
+        .await;
+        let token = crate::util::token::generate_access_token(&config, "7", None, None).unwrap();
+        let put = |sense_id: i64| {
+            test::TestRequest::put()
+                .uri(&format!("/senses/{sense_id}/primary"))
+                .insert_header(("Authorization", format!("Bearer {token}")))
+                .to_request()
+        };
+
+        // 重复设置同一个义项结果不变
+        for _ in 0..2 {
+            let json: serde_json::Value = test::call_and_read_body_json(&app, put(ids[1])).await;
+            assert_eq!(json["code"], 2000);
+            assert_eq!(json["data"]["user_word_id"], user_word_id);
+            let primaries: Vec<&str> = json["data"]["senses"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .filter(|sense| sense["is_primary"] == true)
+                .map(|sense| sense["text"].as_str().unwrap())
+                .collect();
+            assert_eq!(primaries, ["coil"]);
+        }
+
+        let json: serde_json::Value = test::call_and_read_body_json(&app, put(ids[1] + 100)).await;
+        assert_eq!(json["code"], 4202);
+    }
+}
@@ -0,0 +1,1290 @@
+use actix_web::{HttpResponse, web};
+use std::sync::Arc;
+
+use crate::dto::word::{
+    AddWordRequest, AddWordResponse, BatchAddWordsRequest, BatchAddWordsResponse,
+    BatchLinkItemResponse, BatchWordRequest, BulkTagRequest, BulkTagResponse,
+    DuplicatePairResponse, DuplicatesQuery, DuplicatesResponse, ExistsBatchRequest,
+    ExistsBatchResponse, NetworkExportResponse, RewriteWordTextRequest, SearchItemResponse,
+    SearchMatchResponse, SearchQuery, SetTagsRequest, SyncDeltaQuery, SyncDeltaResponse, SyncQuery,
+    SyncResponse, TagWordsQuery, UpdateWordRequest, WordDetailResponse, WordExistenceResponse,
+    WordExportResponse, WordResponse, WordSensesQuery, WordSensesResponse, WordsQuery,
+};
+use crate::middleware::{AuthGuard, AuthenticatedUser};
+use crate::repository::graph::GraphRepository;
+use crate::repository::word::WordRepository;
+use crate::service::assoc::{AssocService, ImportedIds, validate_link_import};
+use crate::service::word::{BatchAddItem, SearchOptions, WordService, validation_error};
+use crate::util::response::{PagedData, Pagination};
+use crate::util::token::TokenConfig;
+use crate::util::{AppError, ResponseBuilder, normalize_nfc};
+
+const DEFAULT_PAGE_SIZE: u32 = 20;
+
+pub struct WordController<W, G>
+where
+    W: WordRepository + Send + Sync + 'static,
+    G: GraphRepository + Send + Sync + 'static,
+{
+    service: Arc<WordService<W, G>>,
+    token_config: Arc<TokenConfig>,
+    links: Option<Arc<AssocService<W, G>>>,
+}
+
+impl<W, G> Clone for WordController<W, G>
+where
+    W: WordRepository + Send + Sync + 'static,
+    G: GraphRepository + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            service: self.service.clone(),
+            token_config: self.token_config.clone(),
+            links: self.links.clone(),
+        }
+    }
+}
+
+impl<W, G> WordController<W, G>
+where
+    W: WordRepository + Send + Sync + 'static,
+    G: GraphRepository + Send + Sync + 'static,
+{
+    pub fn new(service: WordService<W, G>, token_config: Arc<TokenConfig>) -> Self {
+        Self {
+            service: Arc::new(service),
+            token_config,
+            links: None,
+        }
+    }
+
+    /// 批量加词时一并导入关联所用的服务；未设置时请求中带关联会被拒绝
+    pub fn with_link_service(mut self, links: AssocService<W, G>) -> Self {
+        self.links = Some(Arc::new(links));
+        self
+    }
+
+    pub fn configure(cfg: &mut web::ServiceConfig, controller: web::Data<WordController<W, G>>) {
+        let guard = controller.auth_guard();
+        cfg.service(
+            web::resource("/tags/{tag}/words")
+                .app_data(controller.clone())
+                .wrap(guard)
+                .route(web::get().to(Self::words_by_tag)),
+        );
+        cfg.service(
+            web::resource("/words")
+                .app_data(controller.clone())
+                .wrap(controller.auth_guard())
+                .route(web::get().to(Self::list_words))
+                .route(web::post().to(Self::add_word)),
+        );
+        cfg.service(
+            web::resource("/words/search")
+                .app_data(controller.clone())
+                .wrap(controller.auth_guard())
+                .route(web::get().to(Self::search)),
+        );
+        cfg.service(
+            web::resource("/words/tags")
+                .app_data(controller.clone())
+                .wrap(controller.auth_guard())
+                .route(web::post().to(Self::bulk_tag)),
+        );
+        cfg.service(
+            web::resource("/words/batch")
+                .app_data(controller.clone())
+                .wrap(controller.auth_guard())
+                .route(web::post().to(Self::add_words)),
+        );
+        cfg.service(
+            web::resource("/words/exists-batch")
+                .app_data(controller.clone())
+                .wrap(controller.auth_guard())
+                .route(web::post().to(Self::exists_batch)),
+        );
+        cfg.service(
+            web::resource("/words/duplicates")
+                .app_data(controller.clone())
+                .wrap(controller.auth_guard())
+                .route(web::get().to(Self::duplicates)),
+        );
+        cfg.service(
+            web::resource("/words/{id}/senses")
+                .app_data(controller.clone())
+                .wrap(controller.auth_guard())
+                .route(web::get().to(Self::senses_with_links)),
+        );
+        cfg.service(
+            web::resource("/words/{id}/export")
+                .app_data(controller.clone())
+                .wrap(controller.auth_guard())
+                .route(web::get().to(Self::export_word)),
+        );
+        cfg.service(
+            web::resource("/words/{id}/tags")
+                .app_data(controller.clone())
+                .wrap(controller.auth_guard())
+                .route(web::put().to(Self::set_tags)),
+        );
+        cfg.service(
+            web::resource("/words/{id}/text")
+                .app_data(controller.clone())
+                .wrap(controller.auth_guard())
+                .route(web::put().to(Self::rewrite_text)),
+        );
+        // 放在 `/words/tags` 等静态路径之后注册，避免被 `{id}` 先行匹配
+        cfg.service(
+            web::resource("/words/{id}")
+                .app_data(controller.clone())
+                .wrap(controller.auth_guard())
+                .route(web::get().to(Self::get_word))
+                .route(web::patch().to(Self::update_word))
+                .route(web::delete().to(Self::remove_word)),
+        );
+        cfg.service(
+            web::resource("/export")
+                .app_data(controller.clone())
+                .wrap(controller.auth_guard())
+                .route(web::get().to(Self::export_network)),
+        );
+        cfg.service(
+            web::resource("/sync")
+                .app_data(controller.clone())
+                .wrap(controller.auth_guard())
+                .route(web::get().to(Self::sync)),
+        );
+        cfg.service(
+            web::resource("/sync/delta")
+                .app_data(controller.clone())
+                .wrap(controller.auth_guard())
+                .route(web::get().to(Self::sync_delta)),
+        );
+    }
+
+    async fn words_by_tag(
+        controller: web::Data<WordController<W, G>>,
+        identity: AuthenticatedUser,
+        tag: web::Path<String>,
+        query: web::Query<TagWordsQuery>,
+    ) -> Result<HttpResponse, AppError> {
+        let query = query.into_inner();
+        let page = controller
+            .service
+            .list_words_by_tag(
+                identity.user_id,
+                tag.into_inner(),
+                query.page.unwrap_or(1),
+                query.page_size.unwrap_or(DEFAULT_PAGE_SIZE),
+                query.sort,
+                query.after,
+            )
+            .await?;
+
+        ResponseBuilder::ok(PagedData {
+            items: page.items.into_iter().map(WordResponse::from).collect(),
+            pagination: Pagination {
+                page: page.page,
+                page_size: page.page_size,
+                total: page.total,
+                next_cursor: page.next_cursor,
+            },
+        })
+    }
+
+    async fn add_word(
+        controller: web::Data<WordController<W, G>>,
+        identity: AuthenticatedUser,
+        payload: web::Json<AddWordRequest>,
+    ) -> Result<HttpResponse, AppError> {
+        let added = controller
+            .service
+            .add_to_my_network(identity.user_id, payload.into_inner().into())
+            .await?;
+        ResponseBuilder::ok(AddWordResponse::from(added))
+    }
+
+    async fn add_words(
+        controller: web::Data<WordController<W, G>>,
+        identity: AuthenticatedUser,
+        payload: web::Json<BatchAddWordsRequest>,
+    ) -> Result<HttpResponse, AppError> {
+        let BatchAddWordsRequest {
+            words,
+            word_links,
+            sense_links,
+        } = payload.into_inner();
+        let has_links = !word_links.is_empty() || !sense_links.is_empty();
+        // 关联无法导入时在写入单词之前拒绝
+        let links = match &controller.links {
+            Some(links) if has_links => {
+                validate_link_import(word_links.len(), sense_links.len())?;
+                Some(links)
+            }
+            None if has_links => return Err(validation_error("word_links", "不支持导入关联")),
+            _ => None,
+        };
+        let references: Vec<ExportReference> = words.iter().map(ExportReference::new).collect();
+        let inputs = words.into_iter().map(Into::into).collect();
+        let items = controller
+            .service
+            .add_many_to_network(identity.user_id, inputs)
+            .await?;
+
+        let (mut word_link_items, mut sense_link_items) = (Vec::new(), Vec::new());
+        if let Some(links) = links {
+            let ids = imported_ids(&references, &items);
+            let imported = links
+                .import_links(
+                    identity.user_id,
+                    &ids,
+                    word_links.into_iter().map(Into::into).collect(),
+                    sense_links.into_iter().map(Into::into).collect(),
+                )
+                .await?;
+            word_link_items = imported
+                .word_links
+                .into_iter()
+                .enumerate()
+                .map(|(index, result)| {
+                    BatchLinkItemResponse::new(index, result.map(|record| record.link_id))
+                })
+                .collect();
+            sense_link_items = imported
+                .sense_links
+                .into_iter()
+                .enumerate()
+                .map(|(index, result)| {
+                    BatchLinkItemResponse::new(index, result.map(|record| record.link_id))
+                })
+                .collect();
+        }
+        ResponseBuilder::ok(BatchAddWordsResponse {
+            items: items.into_iter().map(Into::into).collect(),
+            word_links: word_link_items,
+            sense_links: sense_link_items,
+        })
+    }
+
+    async fn export_word(
+        controller: web::Data<WordController<W, G>>,
+        identity: AuthenticatedUser,
+        path: web::Path<i64>,
+    ) -> Result<HttpResponse, AppError> {
+        let export = controller
+            .service
+            .export_word(identity.user_id, path.into_inner())
+            .await?;
+        ResponseBuilder::ok(WordExportResponse::from(export))
+    }
+
+    async fn get_word(
+        controller: web::Data<WordController<W, G>>,
+        identity: AuthenticatedUser,
+        path: web::Path<i64>,
+    ) -> Result<HttpResponse, AppError> {
+        let aggregate = controller
+            .service
+            .get_word(identity.user_id, path.into_inner())
+            .await?;
+        ResponseBuilder::ok(WordDetailResponse::from(aggregate))
+    }
+
+    async fn update_word(
+        controller: web::Data<WordController<W, G>>,
+        identity: AuthenticatedUser,
+        path: web::Path<i64>,
+        payload: web::Json<UpdateWordRequest>,
+    ) -> Result<HttpResponse, AppError> {
+        let aggregate = controller
+            .service
+            .update_word_metadata(
+                identity.user_id,
+                path.into_inner(),
+                payload.into_inner().into(),
+            )
+            .await?;
+        ResponseBuilder::ok(WordResponse::from(aggregate))
+    }
+
+    async fn set_tags(
+        controller: web::Data<WordController<W, G>>,
+        identity: AuthenticatedUser,
+        path: web::Path<i64>,
+        payload: web::Json<SetTagsRequest>,
+    ) -> Result<HttpResponse, AppError> {
+        let aggregate = controller
+            .service
+            .set_tags(
+                identity.user_id,
+                path.into_inner(),
+                payload.into_inner().tags,
+            )
+            .await?;
+        ResponseBuilder::ok(WordResponse::from(aggregate))
+    }
+
+    async fn rewrite_text(
+        controller: web::Data<WordController<W, G>>,
+        identity: AuthenticatedUser,
+        path: web::Path<i64>,
+        payload: web::Json<RewriteWordTextRequest>,
+    ) -> Result<HttpResponse, AppError> {
+        let aggregate = controller
+            .service
+            .rewrite_word_text(
+                identity.user_id,
+                path.into_inner(),
+                payload.into_inner().text,
+            )
+            .await?;
+        ResponseBuilder::ok(WordResponse::from(aggregate))
+    }
+
+    async fn remove_word(
+        controller: web::Data<WordController<W, G>>,
+        identity: AuthenticatedUser,
+        path: web::Path<i64>,
+    ) -> Result<HttpResponse, AppError> {
+        controller
+            .service
+            .remove_from_my_network(identity.user_id, path.into_inner())
+            .await?;
+        ResponseBuilder::ok(())
+    }
+
+    async fn search(
+        controller: web::Data<WordController<W, G>>,
+        identity: AuthenticatedUser,
+        query: web::Query<SearchQuery>,
+        // 标签以重复的 `tag=` 传入，SearchQuery 无法表达重复的键，另行收集
+        pairs: web::Query<Vec<(String, String)>>,
+    ) -> Result<HttpResponse, AppError> {
+        let tags = pairs
+            .into_inner()
+            .into_iter()
+            .filter(|(key, _)| key == "tag")
+            .map(|(_, value)| value)
+            .collect();
+        let SearchQuery {
+            query,
+            scope,
+            sort,
+            limit,
+            offset,
+        } = query.into_inner();
+        let defaults = SearchOptions::default();
+        let page = controller
+            .service
+            .search_page(
+                identity.user_id,
+                SearchOptions {
+                    query,
+                    scope,
+                    sort,
+                    tags,
+                    limit: limit.unwrap_or(defaults.limit),
+                    offset: offset.unwrap_or(defaults.offset),
+                },
+            )
+            .await?;
+
+        let mut matches = page.matches;
+        let items = page
+            .items
+            .into_iter()
+            .map(|aggregate| SearchItemResponse {
+                search_match: aggregate
+                    .user_word
+                    .id
+                    .and_then(|id| matches.remove(&id))
+                    .map(SearchMatchResponse::from),
+                word: WordResponse::from(aggregate),
+            })
+            .collect();
+        ResponseBuilder::ok(PagedData {
+            items,
+            pagination: Pagination {
+                page: page.page,
+                page_size: page.page_size,
+                total: page.total,
+                next_cursor: page.next_cursor,
+            },
+        })
+    }
+
+    async fn list_words(
+        controller: web::Data<WordController<W, G>>,
+        identity: AuthenticatedUser,
+        query: web::Query<WordsQuery>,
+    ) -> Result<HttpResponse, AppError> {
+        let query = query.into_inner();
+        let page = controller
+            .service
+            .list_words_in_range(
+                identity.user_id,
+                query.created_from.as_deref(),
+                query.created_to.as_deref(),
+                query.has_links,
+                query.page.unwrap_or(1),
+                query.page_size.unwrap_or(DEFAULT_PAGE_SIZE),
+            )
+            .await?;
+
+        ResponseBuilder::ok(PagedData {
+            items: page.items.into_iter().map(WordResponse::from).collect(),
+            pagination: Pagination {
+                page: page.page,
+                page_size: page.page_size,
+                total: page.total,
+                next_cursor: page.next_cursor,
+            },
+        })
+    }
+
+    async fn bulk_tag(
+        controller: web::Data<WordController<W, G>>,
+        identity: AuthenticatedUser,
+        payload: web::Json<BulkTagRequest>,
+    ) -> Result<HttpResponse, AppError> {
+        let BulkTagRequest {
+            action,
+            tag,
+            user_word_ids,
+        } = payload.into_inner();
+        let changed = controller
+            .service
+            .update_tag_for_words(identity.user_id, user_word_ids, tag, action)
+            .await?;
+        ResponseBuilder::ok(BulkTagResponse { changed })
+    }
+
+    async fn exists_batch(
+        controller: web::Data<WordController<W, G>>,
+        identity: AuthenticatedUser,
+        payload: web::Json<ExistsBatchRequest>,
+    ) -> Result<HttpResponse, AppError> {
+        let items = controller
+            .service
+            .check_existing_words(identity.user_id, payload.into_inner().texts)
+            .await?;
+        ResponseBuilder::ok(ExistsBatchResponse {
+            items: items.into_iter().map(WordExistenceResponse::from).collect(),
+        })
+    }
+
+    async fn duplicates(
+        controller: web::Data<WordController<W, G>>,
+        identity: AuthenticatedUser,
+        query: web::Query<DuplicatesQuery>,
+    ) -> Result<HttpResponse, AppError> {
+        let DuplicatesQuery { threshold, limit } = query.into_inner();
+        let pairs = controller
+            .service
+            .suggest_duplicate_words(identity.user_id, threshold, limit)
+            .await?;
+        ResponseBuilder::ok(DuplicatesResponse {
+            items: pairs.into_iter().map(DuplicatePairResponse::from).collect(),
+        })
+    }
+
+    async fn senses_with_links(
+        controller: web::Data<WordController<W, G>>,
+        identity: AuthenticatedUser,
+        user_word_id: web::Path<i64>,
+        query: web::Query<WordSensesQuery>,
+    ) -> Result<HttpResponse, AppError> {
+        let senses = controller
+            .service
+            .get_senses_with_links(
+                identity.user_id,
+                user_word_id.into_inner(),
+                query.into_inner().sense_order.unwrap_or_default(),
+            )
+            .await?;
+        ResponseBuilder::ok(WordSensesResponse::from(senses))
+    }
+
+    async fn export_network(
+        controller: web::Data<WordController<W, G>>,
+        identity: AuthenticatedUser,
+    ) -> Result<HttpResponse, AppError> {
+        let export = controller.service.export_network(identity.user_id).await?;
+        ResponseBuilder::ok(NetworkExportResponse::from(export))
+    }
+
+    async fn sync(
+        controller: web::Data<WordController<W, G>>,
+        identity: AuthenticatedUser,
+        query: web::Query<SyncQuery>,
+    ) -> Result<HttpResponse, AppError> {
+        let SyncQuery { cursor, limit } = query.into_inner();
+        let page = controller
+            .service
+            .sync_page(identity.user_id, cursor, limit)
+            .await?;
+        ResponseBuilder::ok(SyncResponse::from(page))
+    }
+
+    async fn sync_delta(
+        controller: web::Data<WordController<W, G>>,
+        identity: AuthenticatedUser,
+        query: web::Query<SyncDeltaQuery>,
+    ) -> Result<HttpResponse, AppError> {
+        let delta = controller
+            .service
+            .sync_delta(identity.user_id, &query.since)
+            .await?;
+        ResponseBuilder::ok(SyncDeltaResponse::from(delta))
+    }
+
+    fn auth_guard(&self) -> AuthGuard {
+        AuthGuard::new(self.token_config.clone())
+    }
+}
+
+/// 批量加词一项在导出文档中的 id：单词的 word_id 与各义项的 (id, 文本)
+struct ExportReference {
+    word_id: Option<i64>,
+    senses: Vec<(i64, String)>,
+}
+
+impl ExportReference {
+    fn new(request: &BatchWordRequest) -> Self {
+        Self {
+            word_id: request.word_id,
+            senses: request
+                .senses
+                .iter()
+                // 与义项写入前的校验一致地规整文本
+                .filter_map(|sense| {
+                    let text = normalize_nfc(&sense.sense.text).trim().to_string();
+                    Some((sense.id?, text))
+                })
+                .collect(),
+        }
+    }
+}
+
+/// 导入成功的项按导出文档中的 id 建立映射；义项按文本在导入后的单词中找回，
+/// 与已有义项重复而被跳过的同样映射到已有义项
+fn imported_ids(references: &[ExportReference], items: &[BatchAddItem]) -> ImportedIds {
+    let mut ids = ImportedIds::default();
+    for item in items {
+        let (Some(reference), Ok(added)) = (references.get(item.index), &item.result) else {
+            continue;
+        };
+        if let Some(word_id) = reference.word_id {
+            ids.words.insert(word_id, added.aggregate.word.id);
+        }
+        for (sense_id, text) in &reference.senses {
+            if let Some(imported) = added
+                .aggregate
+                .user_word
+                .senses()
+                .iter()
+                .find(|sense| sense.text() == text)
+                .and_then(|sense| sense.id())
+            {
+                ids.senses.insert(*sense_id, imported);
+            }
+        }
+    }
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_support::token_config;
+    use actix_web::{App, test};
+
+    use crate::repository::graph::WordLinkKind;
+    use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+    use crate::service::word::{AddWordInput, SenseInput};
+
+    #[actix_rt::test]
+    async fn sync_returns_words_links_and_server_time() {
+        let graph = InMemoryGraphRepository::default();
+        let service = WordService::new(InMemoryWordRepository::default(), graph.clone());
+        let mut word_ids = Vec::new();
+        for text in ["inject", "eject"] {
+            let added = service
+                .add_to_my_network(
+                    3,
+                    AddWordInput {
+                        text: text.into(),
+                        tags: vec![],
+                        note: None,
+                        first_sense: Some(SenseInput {
+                            text: format!("{text} sense"),
+                            is_primary: true,
+                            sort_order: 0,
+                            note: None,
+                            source_url: None,
+                        }),
+                    },
+                )
+                .await
+                .unwrap();
+            word_ids.push(added.aggregate.word.id);
+        }
+        graph
+            .create_word_link(3, word_ids[0], word_ids[1], WordLinkKind::RootAffix, None)
+            .await
+            .unwrap();
+
+        let config = token_config();
+        let controller = web::Data::new(WordController::new(service, config.clone()));
+        let app = test::init_service(
+            App::new().configure(|cfg| WordController::configure(cfg, controller.clone())),
+        )
+        .await;
+        let token = crate::util::token::generate_access_token(&config, "3", None, None).unwrap();
+        let req = test::TestRequest::get()
+            .uri("/sync")
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .to_request();
+        let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+
+        assert_eq!(json["code"], 2000);
+        let data = &json["data"];
+        assert!(
+            data["server_time"]
+                .as_str()
+                .is_some_and(|time| chrono::DateTime::parse_from_rfc3339(time).is_ok())
+        );
+        assert_eq!(data["words"].as_array().unwrap().len(), 2);
+        assert_eq!(data["words"][0]["senses"][0]["text"], "inject sense");
+        assert_eq!(data["word_links"][0]["kind"], "root_affix");
+        assert_eq!(data["sense_links"], serde_json::json!([]));
+        assert_eq!(data["links_available"], true);
+        assert!(data["next_cursor"].is_null());
+    }
+
+    #[actix_rt::test]
+    async fn network_export_round_trips_through_batch_import() {
+        use crate::config::settings::GraphSettings;
+        use crate::repository::graph::SenseWordLinkKind;
+        use crate::repository::word::{NewUserSense, WordRepository};
+
+        let controller = |words: InMemoryWordRepository, graph: InMemoryGraphRepository| {
+            web::Data::new(
+                WordController::new(
+                    WordService::new(words.clone(), graph.clone()),
+                    token_config(),
+                )
+                .with_link_service(AssocService::new(
+                    words,
+                    graph,
+                    &GraphSettings::default(),
+                )),
+            )
+        };
+        let words = InMemoryWordRepository::default();
+        let graph = InMemoryGraphRepository::default();
+        let service = WordService::new(words.clone(), graph.clone());
+        let mut added = Vec::new();
+        for (text, tag) in [("inject", "verb"), ("eject", "verb")] {
+            let word = service
+                .add_to_my_network(
+                    3,
+                    AddWordInput {
+                        text: text.into(),
+                        tags: vec![tag.into()],
+                        note: Some(format!("{text} note")),
+                        first_sense: Some(SenseInput {
+                            text: format!("{text} sense"),
+                            is_primary: true,
+                            sort_order: 0,
+                            note: None,
+                            source_url: None,
+                        }),
+                    },
+                )
+                .await
+                .unwrap();
+            added.push(word.aggregate);
+        }
+        let (inject, eject) = (&added[0], &added[1]);
+        words
+            .add_user_sense(NewUserSense {
+                user_word_id: inject.user_word.id.unwrap(),
+                text: "to introduce".into(),
+                is_primary: false,
+                sort_order: 1,
+                note: Some("figurative".into()),
+                source_url: None,
+            })
+            .await
+            .unwrap();
+        graph
+            .create_word_link(
+                3,
+                inject.word.id,
+                eject.word.id,
+                WordLinkKind::RootAffix,
+                Some("in / e".into()),
+            )
+            .await
+            .unwrap();
+        let sense_id = inject.user_word.senses()[0].id().unwrap();
+        graph
+            .create_sense_word_link(
+                3,
+                sense_id,
+                inject.word.id,
+                eject.word.id,
+                SenseWordLinkKind::Antonym,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let config = token_config();
+        let token = crate::util::token::generate_access_token(&config, "3", None, None).unwrap();
+        let auth = ("Authorization", format!("Bearer {token}"));
+        let call = |controller: web::Data<WordController<_, _>>, req: test::TestRequest| {
+            let auth = auth.clone();
+            async move {
+                let app = test::init_service(
+                    App::new().configure(|cfg| WordController::configure(cfg, controller.clone())),
+                )
+                .await;
+                let json: serde_json::Value =
+                    test::call_and_read_body_json(&app, req.insert_header(auth).to_request()).await;
+                assert_eq!(json["code"], 2000, "{json}");
+                json["data"].clone()
+            }
+        };
+
+        let data = call(
+            controller(words, graph),
+            test::TestRequest::get().uri("/export"),
+        )
+        .await;
+        assert_eq!(data["links_available"], true);
+        assert_eq!(data["links_truncated"], false);
+        assert_eq!(data["word_links"].as_array().unwrap().len(), 1);
+        assert_eq!(data["sense_links"][0]["sense_id"], sense_id);
+
+        // 导出文档原样提交给批量导入；目标库里已有其他数据，id 与导出时不同
+        let fresh_words = InMemoryWordRepository::default();
+        let fresh_graph = InMemoryGraphRepository::default();
+        let fresh_service = WordService::new(fresh_words.clone(), fresh_graph.clone());
+        for text in ["offset", "padding"] {
+            fresh_service
+                .add_to_my_network(
+                    99,
+                    AddWordInput {
+                        text: text.into(),
+                        tags: vec![],
+                        note: None,
+                        first_sense: Some(SenseInput {
+                            text: format!("{text} sense"),
+                            is_primary: true,
+                            sort_order: 0,
+                            note: None,
+                            source_url: None,
+                        }),
+                    },
+                )
+                .await
+                .unwrap();
+        }
+        let fresh = controller(fresh_words, fresh_graph);
+        let imported = call(
+            fresh.clone(),
+            test::TestRequest::post()
+                .uri("/words/batch")
+                .set_json(&data),
+        )
+        .await;
+        for key in ["items", "word_links", "sense_links"] {
+            let results = imported[key].as_array().unwrap();
+            assert!(!results.is_empty(), "{key}");
+            assert!(
+                results.iter().all(|item| item["error"].is_null()),
+                "{key}: {imported}"
+            );
+        }
+
+        let reimported = call(fresh, test::TestRequest::get().uri("/export")).await;
+        assert_ne!(
+            reimported["words"][0]["word_id"],
+            data["words"][0]["word_id"]
+        );
+        assert_eq!(network_summary(&reimported), network_summary(&data));
+    }
+
+    /// 与 id 无关的导出内容：单词及其全部义项，关联按两端的文本表示
+    fn network_summary(export: &serde_json::Value) -> serde_json::Value {
+        let words = export["words"].as_array().unwrap();
+        let word_text = |word_id: &serde_json::Value| {
+            words
+                .iter()
+                .find(|word| &word["word_id"] == word_id)
+                .map(|word| word["text"].clone())
+                .unwrap()
+        };
+        let sense_text = |sense_id: &serde_json::Value| {
+            words
+                .iter()
+                .flat_map(|word| word["senses"].as_array().unwrap())
+                .find(|sense| &sense["id"] == sense_id)
+                .map(|sense| sense["text"].clone())
+                .unwrap()
+        };
+        serde_json::json!({
+            "words": words
+                .iter()
+                .map(|word| {
+                    let senses: Vec<serde_json::Value> = word["senses"]
+                        .as_array()
+                        .unwrap()
+                        .iter()
+                        .map(|sense| {
+                            serde_json::json!([
+                                sense["text"],
+                                sense["is_primary"],
+                                sense["sort_order"],
+                                sense["note"],
+                                sense["source_url"],
+                            ])
+                        })
+                        .collect();
+                    serde_json::json!([
+                        word["text"],
+                        word["tags"],
+                        word["note"],
+                        word["first_sense"]["text"],
+                        senses,
+                    ])
+                })
+                .collect::<Vec<_>>(),
+            "word_links": export["word_links"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|link| {
+                    serde_json::json!([
+                        link["kind"],
+                        link["note"],
+                        word_text(&link["word_a_id"]),
+                        word_text(&link["word_b_id"]),
+                    ])
+                })
+                .collect::<Vec<_>>(),
+            "sense_links": export["sense_links"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|link| {
+                    serde_json::json!([
+                        link["kind"],
+                        link["note"],
+                        sense_text(&link["sense_id"]),
+                        word_text(&link["source_word_id"]),
+                        word_text(&link["target_word_id"]),
+                    ])
+                })
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    #[actix_rt::test]
+    async fn add_and_remove_words_over_http() {
+        let config = token_config();
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+        let controller = web::Data::new(WordController::new(service, config.clone()));
+        let app = test::init_service(
+            App::new().configure(|cfg| WordController::configure(cfg, controller.clone())),
+        )
+        .await;
+        let token = crate::util::token::generate_access_token(&config, "5", None, None).unwrap();
+        let auth = ("Authorization", format!("Bearer {token}"));
+
+        let req = test::TestRequest::post()
+            .uri("/words")
+            .insert_header(auth.clone())
+            .set_json(serde_json::json!({
+                "text": "graph database",
+                "tags": ["tech"],
+                "first_sense": { "text": "a database built on nodes and edges", "is_primary": true }
+            }))
+            .to_request();
+        let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(json["code"], 2000, "{json}");
+        assert_eq!(json["data"]["text"], "graph database");
+        assert_eq!(json["data"]["tags"], serde_json::json!(["tech"]));
+        assert_eq!(json["data"]["senses"][0]["is_primary"], true);
+        assert_eq!(json["data"]["created"], true);
+        assert!(json["data"]["matched_existing_text"].is_null());
+        let user_word_id = json["data"]["user_word_id"].as_i64().unwrap();
+
+        let req = test::TestRequest::post()
+            .uri("/words")
+            .insert_header(auth.clone())
+            .set_json(serde_json::json!({ "text": "Graph Database" }))
+            .to_request();
+        let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(json["data"]["created"], false);
+        assert_eq!(json["data"]["user_word_id"], user_word_id);
+        assert_eq!(json["data"]["matched_existing_text"], "graph database");
+
+        let req = test::TestRequest::post()
+            .uri("/words")
+            .insert_header(auth.clone())
+            .set_json(serde_json::json!({ "text": "   " }))
+            .to_request();
+        let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(json["code"], 4001);
+
+        // 静态路径不被 `/words/{id}` 抢先匹配
+        let req = test::TestRequest::post()
+            .uri("/words/exists-batch")
+            .insert_header(auth.clone())
+            .set_json(serde_json::json!({ "texts": ["graph database"] }))
+            .to_request();
+        let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(json["code"], 2000, "{json}");
+
+        for expected in [2000, 4202] {
+            let req = test::TestRequest::delete()
+                .uri(&format!("/words/{user_word_id}"))
+                .insert_header(auth.clone())
+                .to_request();
+            let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+            assert_eq!(json["code"], expected, "{json}");
+        }
+
+        let req = test::TestRequest::delete()
+            .uri(&format!("/words/{user_word_id}"))
+            .to_request();
+        let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_ne!(json["code"], 2000);
+    }
+
+    #[actix_rt::test]
+    async fn get_word_returns_full_detail_only_to_its_owner() {
+        let config = token_config();
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+        let added = service
+            .add_to_my_network(
+                5,
+                AddWordInput {
+                    text: "harbor".into(),
+                    tags: vec!["sea".into()],
+                    note: Some("kept".into()),
+                    first_sense: Some(SenseInput {
+                        text: "a sheltered port".into(),
+                        is_primary: true,
+                        sort_order: 0,
+                        note: Some("nautical".into()),
+                        source_url: None,
+                    }),
+                },
+            )
+            .await
+            .unwrap();
+        let id = added.aggregate.user_word.id.unwrap();
+        let controller = web::Data::new(WordController::new(service, config.clone()));
+        let app = test::init_service(
+            App::new().configure(|cfg| WordController::configure(cfg, controller.clone())),
+        )
+        .await;
+        let token = crate::util::token::generate_access_token(&config, "5", None, None).unwrap();
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/words/{id}"))
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .to_request();
+        let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(json["code"], 2000, "{json}");
+        let data = &json["data"];
+        assert_eq!(data["user_word_id"], id);
+        assert_eq!(data["text"], "harbor");
+        assert_eq!(data["tags"], serde_json::json!(["sea"]));
+        assert_eq!(data["note"], "kept");
+        let sense = &data["senses"][0];
+        assert_eq!(sense["text"], "a sheltered port");
+        assert_eq!(sense["is_primary"], true);
+        assert_eq!(sense["sort_order"], 0);
+        assert_eq!(sense["note"], "nautical");
+        assert!(sense["id"].is_i64());
+
+        // 不存在的单词与他人的单词同样按不在网络中处理
+        let other = crate::util::token::generate_access_token(&config, "6", None, None).unwrap();
+        for (token, uri) in [
+            (&token, format!("/words/{}", id + 100)),
+            (&other, format!("/words/{id}")),
+        ] {
+            let req = test::TestRequest::get()
+                .uri(&uri)
+                .insert_header(("Authorization", format!("Bearer {token}")))
+                .to_request();
+            let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+            assert_eq!(json["code"], 4202, "{uri}: {json}");
+        }
+    }
+
+    #[actix_rt::test]
+    async fn patch_word_distinguishes_omitted_and_null_note() {
+        let config = token_config();
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+        let added = service
+            .add_to_my_network(
+                5,
+                AddWordInput {
+                    text: "harbor".into(),
+                    tags: vec!["sea".into()],
+                    note: Some("kept".into()),
+                    first_sense: None,
+                },
+            )
+            .await
+            .unwrap();
+        let id = added.aggregate.user_word.id.unwrap();
+        let controller = web::Data::new(WordController::new(service, config.clone()));
+        let app = test::init_service(
+            App::new().configure(|cfg| WordController::configure(cfg, controller.clone())),
+        )
+        .await;
+        let token = crate::util::token::generate_access_token(&config, "5", None, None).unwrap();
+        let auth = ("Authorization", format!("Bearer {token}"));
+
+        let mut responses = Vec::new();
+        for body in [
+            serde_json::json!({ "tags": ["port"] }),
+            serde_json::json!({ "note": null }),
+            serde_json::json!({ "tags": ["bad tag!"] }),
+        ] {
+            let req = test::TestRequest::patch()
+                .uri(&format!("/words/{id}"))
+                .insert_header(auth.clone())
+                .set_json(body)
+                .to_request();
+            let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+            responses.push(json);
+        }
+
+        assert_eq!(responses[0]["code"], 2000, "{}", responses[0]);
+        assert_eq!(responses[0]["data"]["tags"], serde_json::json!(["port"]));
+        assert_eq!(responses[0]["data"]["note"], "kept");
+        assert_eq!(responses[1]["data"]["tags"], serde_json::json!(["port"]));
+        assert!(responses[1]["data"]["note"].is_null());
+        assert_eq!(responses[2]["code"], 4001);
+        assert_eq!(responses[2]["data"][0]["field"], "tags");
+    }
+
+    #[actix_rt::test]
+    async fn batch_add_returns_a_result_per_item() {
+        let config = token_config();
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+        let controller = web::Data::new(WordController::new(service, config.clone()));
+        let app = test::init_service(
+            App::new().configure(|cfg| WordController::configure(cfg, controller.clone())),
+        )
+        .await;
+        let token = crate::util::token::generate_access_token(&config, "5", None, None).unwrap();
+
+        let req = test::TestRequest::post()
+            .uri("/words/batch")
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .set_json(serde_json::json!({
+                "words": [
+                    { "text": "   " },
+                    { "text": "harbor", "first_sense": { "text": "a sheltered port" } }
+                ]
+            }))
+            .to_request();
+        let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+
+        assert_eq!(json["code"], 2000, "{json}");
+        let items = &json["data"]["items"];
+        assert_eq!(items[0]["index"], 0);
+        assert!(items[0]["word"].is_null());
+        assert_eq!(items[0]["error"]["code"], 4001);
+        assert_eq!(items[0]["error"]["fields"][0]["field"], "text");
+        assert_eq!(items[1]["index"], 1);
+        assert!(items[1]["error"].is_null());
+        assert_eq!(items[1]["word"]["text"], "harbor");
+        assert_eq!(items[1]["word"]["created"], true);
+        assert_eq!(items[1]["word"]["senses"][0]["text"], "a sheltered port");
+    }
+
+    #[actix_rt::test]
+    async fn search_pages_results_with_total_for_each_scope() {
+        let config = token_config();
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+        for (text, sense) in [
+            ("river", "a natural stream of water"),
+            ("stream", "a small narrow river"),
+            ("bank", "land alongside a river"),
+            ("apple", "a round fruit"),
+        ] {
+            service
+                .add_to_my_network(
+                    5,
+                    AddWordInput {
+                        text: text.into(),
+                        tags: vec![],
+                        note: None,
+                        first_sense: Some(SenseInput {
+                            text: sense.into(),
+                            is_primary: true,
+                            sort_order: 0,
+                            note: None,
+                            source_url: None,
+                        }),
+                    },
+                )
+                .await
+                .unwrap();
+        }
+        let controller = web::Data::new(WordController::new(service, config.clone()));
+        let app = test::init_service(
+            App::new().configure(|cfg| WordController::configure(cfg, controller.clone())),
+        )
+        .await;
+        let token = crate::util::token::generate_access_token(&config, "5", None, None).unwrap();
+
+        let cases = [
+            // 空查询返回全部单词，按字母序分页
+            ("limit=2", 4, vec!["apple", "bank"], 1),
+            ("limit=2&offset=2", 4, vec!["river", "stream"], 2),
+            ("query=river&scope=word", 1, vec!["river"], 1),
+            ("query=river&scope=sense", 2, vec!["bank", "stream"], 1),
+            (
+                "query=river&scope=both",
+                3,
+                vec!["bank", "river", "stream"],
+                1,
+            ),
+            ("query=river", 3, vec!["bank", "river", "stream"], 1),
+        ];
+        for (params, total, texts, page) in cases {
+            let req = test::TestRequest::get()
+                .uri(&format!("/words/search?{params}"))
+                .insert_header(("Authorization", format!("Bearer {token}")))
+                .to_request();
+            let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+            assert_eq!(json["code"], 2000, "{params}: {json}");
+            let data = &json["data"];
+            assert_eq!(data["pagination"]["total"], total, "{params}");
+            assert_eq!(data["pagination"]["page"], page, "{params}");
+            let found: Vec<&str> = data["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|item| item["text"].as_str().unwrap())
+                .collect();
+            assert_eq!(found, texts, "{params}");
+        }
+
+        let side_of = |json: &serde_json::Value, text: &str| {
+            json["data"]["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .find(|item| item["text"] == text)
+                .map(|item| item["match"].clone())
+                .unwrap()
+        };
+        let req = test::TestRequest::get()
+            .uri("/words/search?query=river")
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .to_request();
+        let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(side_of(&json, "river")["side"], "word");
+        assert_eq!(side_of(&json, "bank")["side"], "sense");
+        assert_eq!(side_of(&json, "bank")["snippet"], "land alongside a river");
+        let req = test::TestRequest::get()
+            .uri("/words/search")
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .to_request();
+        let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert!(side_of(&json, "river").is_null());
+    }
+
+    #[actix_rt::test]
+    async fn search_filters_by_repeated_tag_params() {
+        let config = token_config();
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        )
+        .with_empty_query_behavior(crate::repository::word::EmptyQueryBehavior::Empty);
+        for (text, tags) in [
+            ("apple", vec!["fruit", "red"]),
+            ("banana", vec!["fruit"]),
+            ("brick", vec!["red"]),
+        ] {
+            service
+                .add_to_my_network(
+                    5,
+                    AddWordInput {
+                        text: text.into(),
+                        tags: tags.into_iter().map(String::from).collect(),
+                        note: None,
+                        first_sense: None,
+                    },
+                )
+                .await
+                .unwrap();
+        }
+        let controller = web::Data::new(WordController::new(service, config.clone()));
+        let app = test::init_service(
+            App::new().configure(|cfg| WordController::configure(cfg, controller.clone())),
+        )
+        .await;
+        let token = crate::util::token::generate_access_token(&config, "5", None, None).unwrap();
+
+        let cases = [
+            // 只按标签过滤时，空关键字不会被当作空查询
+            ("tag=fruit", vec!["apple", "banana"]),
+            ("tag=fruit&tag=red", vec!["apple"]),
+            ("tag=red&query=bri", vec!["brick"]),
+            ("tag=fruit&query=bri", vec![]),
+            ("", vec![]),
+        ];
+        for (params, texts) in cases {
+            let req = test::TestRequest::get()
+                .uri(&format!("/words/search?{params}"))
+                .insert_header(("Authorization", format!("Bearer {token}")))
+                .to_request();
+            let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+            assert_eq!(json["code"], 2000, "{params}: {json}");
+            let found: Vec<&str> = json["data"]["items"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|item| item["text"].as_str().unwrap())
+                .collect();
+            assert_eq!(found, texts, "{params}");
+        }
+
+        let req = test::TestRequest::get()
+            .uri("/words/search?tag=%20%20")
+            .insert_header(("Authorization", format!("Bearer {token}")))
+            .to_request();
+        let json: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(json["code"], 4001);
+        assert_eq!(json["data"][0]["field"], "tag");
+    }
+}
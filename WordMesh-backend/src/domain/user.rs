@@ -17,6 +17,10 @@ pub struct User {
     pub username: String,
     pub password_hash: HashedPassword,
     pub created_at: DateTime<Utc>,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    /// 最近一次重置密码的时间；早于它签发的会话视为已吊销
+    pub password_changed_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone)]
@@ -65,9 +69,18 @@ impl User {
             username,
             password_hash,
             created_at,
+            email: None,
+            email_verified: false,
+            password_changed_at: None,
         })
     }
 
+    pub fn with_email(mut self, email: Option<String>, email_verified: bool) -> Self {
+        self.email = email;
+        self.email_verified = email_verified;
+        self
+    }
+
     #[allow(dead_code)]
     pub fn from_registration(
         username: String,
@@ -79,6 +92,9 @@ impl User {
             username,
             password_hash,
             created_at: Utc::now(),
+            email: None,
+            email_verified: false,
+            password_changed_at: None,
         })
     }
 }
@@ -2,10 +2,10 @@ use chrono::{DateTime, Utc};
 use std::fmt::{Display, Formatter};
 use thiserror::Error;
 
-use crate::util::canonical::{CanonicalError, canonicalize};
+use crate::util::canonical::{CanonicalError, CanonicalMode, canonicalize, canonicalize_with};
 use crate::util::validation::{
-    MAX_NOTE_LENGTH, MAX_SENSE_NOTE_LENGTH, MAX_SENSE_TEXT_LENGTH, MAX_TAGS, ValidationError,
-    normalize_tags, validate_non_empty_text, validate_note,
+    MAX_SENSES, MAX_TAGS, TagRules, TextLimits, ValidationError, normalize_tags,
+    validate_non_empty_text, validate_note, validate_source_url,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -21,13 +21,25 @@ pub enum CanonicalKeyError {
 
 impl CanonicalKey {
     pub fn new(text: impl AsRef<str>) -> Result<Self, CanonicalKeyError> {
-        let normalized = canonicalize(text.as_ref())?;
+        Self::new_with(text, CanonicalMode::Collapse)
+    }
+
+    pub fn new_with(text: impl AsRef<str>, mode: CanonicalMode) -> Result<Self, CanonicalKeyError> {
+        let normalized = canonicalize_with(text.as_ref(), mode)?;
         if normalized.is_empty() {
             return Err(CanonicalKeyError::Empty);
         }
         Ok(Self(normalized))
     }
 
+    /// 从存储还原已规范化的键；不再重新规范化，以免不同模式写入的键被改写
+    pub fn from_stored(key: String) -> Result<Self, CanonicalKeyError> {
+        if key.is_empty() {
+            return Err(CanonicalKeyError::Empty);
+        }
+        Ok(Self(key))
+    }
+
     pub fn as_str(&self) -> &str {
         &self.0
     }
@@ -61,6 +73,7 @@ pub struct UserSense {
     pub is_primary: bool,
     pub sort_order: i32,
     note: Option<String>,
+    source_url: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -72,14 +85,20 @@ pub enum UserWordError {
     InvalidTag(String),
     #[error("note cannot be blank")]
     InvalidNote,
-    #[error("note too long: {0} characters (max {MAX_NOTE_LENGTH})")]
-    NoteTooLong(usize),
+    #[error("note too long: {0} characters (max {1})")]
+    NoteTooLong(usize, usize),
+    #[error("note too large: {0} bytes (max {1})")]
+    NoteTooManyBytes(usize, usize),
+    #[error("note contains disallowed control characters")]
+    NoteControlCharacter,
     #[error("duplicate sense text detected: {0}")]
     DuplicateSenseText(String),
     #[error("sense with id {0} not found")]
     SenseNotFound(i64),
     #[error("sense index {0} out of bounds")]
     SenseIndexOutOfBounds(usize),
+    #[error("sense limit exceeded (max {0})")]
+    SenseLimitExceeded(usize),
     #[error(transparent)]
     Sense(#[from] UserSenseError),
 }
@@ -90,8 +109,14 @@ impl From<ValidationError> for UserWordError {
             ValidationError::TagLimitExceeded(count) => UserWordError::TagLimitExceeded(count),
             ValidationError::InvalidTag(tag) => UserWordError::InvalidTag(tag),
             ValidationError::Blank => UserWordError::InvalidNote,
-            ValidationError::NoteTooLong(len) => UserWordError::NoteTooLong(len),
-            ValidationError::TextTooLong(len) => UserWordError::NoteTooLong(len),
+            ValidationError::NoteTooLong(len, max) => UserWordError::NoteTooLong(len, max),
+            ValidationError::TextTooLong(len, max) => UserWordError::NoteTooLong(len, max),
+            ValidationError::NoteTooManyBytes(len, max)
+            | ValidationError::TextTooManyBytes(len, max) => {
+                UserWordError::NoteTooManyBytes(len, max)
+            }
+            ValidationError::ControlCharacter => UserWordError::NoteControlCharacter,
+            ValidationError::InvalidUrl(_) => UserWordError::InvalidNote,
         }
     }
 }
@@ -100,23 +125,39 @@ impl From<ValidationError> for UserWordError {
 pub enum UserSenseError {
     #[error("sense text cannot be empty")]
     EmptyText,
-    #[error("sense text too long: {0} characters (max {MAX_SENSE_TEXT_LENGTH})")]
-    TextTooLong(usize),
+    #[error("sense text too long: {0} characters (max {1})")]
+    TextTooLong(usize, usize),
+    #[error("sense text too large: {0} bytes (max {1})")]
+    TextTooManyBytes(usize, usize),
     #[error("sense note cannot be blank")]
     InvalidNote,
-    #[error("sense note too long: {0} characters (max {MAX_SENSE_NOTE_LENGTH})")]
-    NoteTooLong(usize),
+    #[error("sense note too long: {0} characters (max {1})")]
+    NoteTooLong(usize, usize),
+    #[error("sense note too large: {0} bytes (max {1})")]
+    NoteTooManyBytes(usize, usize),
+    #[error("sense contains disallowed control characters")]
+    ControlCharacter,
+    #[error("sense source url is not a valid http(s) url")]
+    InvalidSourceUrl,
 }
 
 impl From<ValidationError> for UserSenseError {
     fn from(err: ValidationError) -> Self {
         match err {
             ValidationError::Blank => UserSenseError::InvalidNote,
-            ValidationError::TextTooLong(len) => UserSenseError::TextTooLong(len),
-            ValidationError::NoteTooLong(len) => UserSenseError::NoteTooLong(len),
+            ValidationError::TextTooLong(len, max) => UserSenseError::TextTooLong(len, max),
+            ValidationError::NoteTooLong(len, max) => UserSenseError::NoteTooLong(len, max),
+            ValidationError::TextTooManyBytes(len, max) => {
+                UserSenseError::TextTooManyBytes(len, max)
+            }
+            ValidationError::NoteTooManyBytes(len, max) => {
+                UserSenseError::NoteTooManyBytes(len, max)
+            }
+            ValidationError::ControlCharacter => UserSenseError::ControlCharacter,
             ValidationError::InvalidTag(_) | ValidationError::TagLimitExceeded(_) => {
                 UserSenseError::InvalidNote
             }
+            ValidationError::InvalidUrl(_) => UserSenseError::InvalidSourceUrl,
         }
     }
 }
@@ -127,9 +168,12 @@ impl UserWord {
         word_id: i64,
         tags: Vec<String>,
         note: Option<String>,
+        limits: &TextLimits,
+        tag_rules: &TagRules,
     ) -> Result<Self, UserWordError> {
-        let tags = normalize_tags(tags).map_err(UserWordError::from)?;
-        let note = validate_note(note).map_err(UserWordError::from)?;
+        let tags = normalize_tags(tags, tag_rules).map_err(UserWordError::from)?;
+        let note = validate_note(note, limits.word_note, limits.note_bytes)
+            .map_err(UserWordError::from)?;
         Ok(Self {
             id: None,
             user_id,
@@ -141,6 +185,7 @@ impl UserWord {
         })
     }
 
+    /// 从存储还原；长度上限与标签字符规则不再检查，见 [`TextLimits::unbounded`]
     pub fn from_parts(
         id: Option<i64>,
         user_id: i64,
@@ -150,8 +195,8 @@ impl UserWord {
         senses: Vec<UserSense>,
         created_at: DateTime<Utc>,
     ) -> Result<Self, UserWordError> {
-        let tags = normalize_tags(tags).map_err(UserWordError::from)?;
-        let note = validate_note(note).map_err(UserWordError::from)?;
+        let tags = normalize_tags(tags, &TagRules::permissive()).map_err(UserWordError::from)?;
+        let note = validate_note(note, usize::MAX, usize::MAX).map_err(UserWordError::from)?;
         let mut word = Self {
             id,
             user_id,
@@ -162,7 +207,7 @@ impl UserWord {
             created_at,
         };
         for sense in senses {
-            word.add_sense(sense)?;
+            word.add_sense_with_limit(sense, usize::MAX)?;
         }
         Ok(word)
     }
@@ -179,21 +224,48 @@ impl UserWord {
         &self.senses
     }
 
+    /// 稳定排序；仅调整顺序，不改动 sort_order
+    pub fn sort_senses_by_key<K: Ord>(&mut self, key: impl FnMut(&UserSense) -> K) {
+        self.senses.sort_by_key(key);
+    }
+
     pub fn senses_mut_for_test(&mut self) -> &mut Vec<UserSense> {
         &mut self.senses
     }
 
-    pub fn update_tags(&mut self, tags: Vec<String>) -> Result<(), UserWordError> {
-        self.tags = normalize_tags(tags).map_err(UserWordError::from)?;
+    pub fn update_tags(
+        &mut self,
+        tags: Vec<String>,
+        tag_rules: &TagRules,
+    ) -> Result<(), UserWordError> {
+        self.tags = normalize_tags(tags, tag_rules).map_err(UserWordError::from)?;
         Ok(())
     }
 
-    pub fn update_note(&mut self, note: Option<String>) -> Result<(), UserWordError> {
-        self.note = validate_note(note).map_err(UserWordError::from)?;
+    pub fn update_note(
+        &mut self,
+        note: Option<String>,
+        limits: &TextLimits,
+    ) -> Result<(), UserWordError> {
+        self.note = validate_note(note, limits.word_note, limits.note_bytes)
+            .map_err(UserWordError::from)?;
         Ok(())
     }
 
-    pub fn add_sense(&mut self, mut sense: UserSense) -> Result<(), UserWordError> {
+    pub fn add_sense(&mut self, sense: UserSense) -> Result<(), UserWordError> {
+        self.add_sense_with_limit(sense, MAX_SENSES)
+    }
+
+    /// `limit` 为义项总数上限；从存储还原时传 `usize::MAX`，上限事后调低也不影响读取旧数据
+    pub fn add_sense_with_limit(
+        &mut self,
+        mut sense: UserSense,
+        limit: usize,
+    ) -> Result<(), UserWordError> {
+        if self.senses.len() >= limit {
+            return Err(UserWordError::SenseLimitExceeded(limit));
+        }
+
         if self
             .senses
             .iter()
@@ -266,22 +338,24 @@ impl UserSense {
         is_primary: bool,
         sort_order: i32,
         note: Option<String>,
+        limits: &TextLimits,
     ) -> Result<Self, UserSenseError> {
-        let text = validate_non_empty_text(text.into()).map_err(UserSenseError::from)?;
-        if text.chars().count() > MAX_SENSE_TEXT_LENGTH {
-            return Err(UserSenseError::TextTooLong(text.chars().count()));
-        }
-        let note = validate_note(note).map_err(UserSenseError::from)?;
+        let text = validate_non_empty_text(text.into(), limits.sense_text, limits.text_bytes)
+            .map_err(UserSenseError::from)?;
+        let note = validate_note(note, limits.sense_note, limits.note_bytes)
+            .map_err(UserSenseError::from)?;
         Ok(Self {
             id: None,
             text,
             is_primary,
             sort_order,
             note,
+            source_url: None,
             created_at: Utc::now(),
         })
     }
 
+    /// 从存储还原；长度上限不再检查，见 [`TextLimits::unbounded`]
     pub fn from_parts(
         id: Option<i64>,
         text: String,
@@ -290,21 +364,28 @@ impl UserSense {
         note: Option<String>,
         created_at: DateTime<Utc>,
     ) -> Result<Self, UserSenseError> {
-        let text = validate_non_empty_text(text).map_err(UserSenseError::from)?;
-        if text.chars().count() > MAX_SENSE_TEXT_LENGTH {
-            return Err(UserSenseError::TextTooLong(text.chars().count()));
-        }
-        let note = validate_note(note).map_err(UserSenseError::from)?;
+        let limits = TextLimits::unbounded();
+        let text = validate_non_empty_text(text, limits.sense_text, limits.text_bytes)
+            .map_err(UserSenseError::from)?;
+        let note = validate_note(note, limits.sense_note, limits.note_bytes)
+            .map_err(UserSenseError::from)?;
         Ok(Self {
             id,
             text,
             is_primary,
             sort_order,
             note,
+            source_url: None,
             created_at,
         })
     }
 
+    /// 构造后设置来源链接，`new` 与 `from_parts` 均默认不带链接
+    pub fn with_source_url(mut self, source_url: Option<String>) -> Result<Self, UserSenseError> {
+        self.set_source_url(source_url)?;
+        Ok(self)
+    }
+
     pub fn id(&self) -> Option<i64> {
         self.id
     }
@@ -317,17 +398,36 @@ impl UserSense {
         self.note.as_deref()
     }
 
-    pub fn set_text(&mut self, text: impl Into<String>) -> Result<(), UserSenseError> {
-        let value = validate_non_empty_text(text.into()).map_err(UserSenseError::from)?;
-        if value.chars().count() > MAX_SENSE_TEXT_LENGTH {
-            return Err(UserSenseError::TextTooLong(value.chars().count()));
-        }
-        self.text = value;
+    pub fn source_url(&self) -> Option<&str> {
+        self.source_url.as_deref()
+    }
+
+    pub fn set_text(
+        &mut self,
+        text: impl Into<String>,
+        limits: &TextLimits,
+    ) -> Result<(), UserSenseError> {
+        self.text = validate_non_empty_text(text.into(), limits.sense_text, limits.text_bytes)
+            .map_err(UserSenseError::from)?;
+        Ok(())
+    }
+
+    pub fn set_note(
+        &mut self,
+        note: Option<String>,
+        limits: &TextLimits,
+    ) -> Result<(), UserSenseError> {
+        self.note = validate_note(note, limits.sense_note, limits.note_bytes)
+            .map_err(UserSenseError::from)?;
         Ok(())
     }
 
-    pub fn set_note(&mut self, note: Option<String>) -> Result<(), UserSenseError> {
-        self.note = validate_note(note).map_err(UserSenseError::from)?;
+    /// 空白链接视为格式错误，清除链接请传 `None`
+    pub fn set_source_url(&mut self, source_url: Option<String>) -> Result<(), UserSenseError> {
+        self.source_url = validate_source_url(source_url).map_err(|err| match err {
+            ValidationError::ControlCharacter => UserSenseError::ControlCharacter,
+            _ => UserSenseError::InvalidSourceUrl,
+        })?;
         Ok(())
     }
 
@@ -369,6 +469,8 @@ mod tests {
             10,
             vec!["tag-one".into(), "Tag-One".into(), "tag_two".into()],
             Some(" personal note ".into()),
+            &TextLimits::default(),
+            &TagRules::default(),
         )
         .unwrap();
         assert_eq!(word.tags.len(), 2);
@@ -380,21 +482,43 @@ mod tests {
     #[test]
     fn user_word_rejects_too_many_tags() {
         let tags = (0..25).map(|i| format!("tag{i}")).collect::<Vec<_>>();
-        let result = UserWord::create(1, 1, tags, None);
+        let result = UserWord::create(
+            1,
+            1,
+            tags,
+            None,
+            &TextLimits::default(),
+            &TagRules::default(),
+        );
         assert!(matches!(result, Err(UserWordError::TagLimitExceeded(_))));
     }
 
     #[test]
     fn user_word_rejects_invalid_tag() {
-        let result = UserWord::create(1, 1, vec!["bad tag".into()], None);
+        let result = UserWord::create(
+            1,
+            1,
+            vec!["bad tag".into()],
+            None,
+            &TextLimits::default(),
+            &TagRules::default(),
+        );
         assert!(matches!(result, Err(UserWordError::InvalidTag(_))));
     }
 
     #[test]
     fn user_sense_creation_and_addition_preserves_primary_uniqueness() {
-        let mut word = UserWord::create(1, 1, vec![], None).unwrap();
-        let primary = UserSense::new("meaning", true, 0, None).unwrap();
-        let secondary = UserSense::new("second", false, 1, None).unwrap();
+        let mut word = UserWord::create(
+            1,
+            1,
+            vec![],
+            None,
+            &TextLimits::default(),
+            &TagRules::default(),
+        )
+        .unwrap();
+        let primary = UserSense::new("meaning", true, 0, None, &TextLimits::default()).unwrap();
+        let secondary = UserSense::new("second", false, 1, None, &TextLimits::default()).unwrap();
 
         word.add_sense(primary).unwrap();
         word.add_sense(secondary).unwrap();
@@ -406,9 +530,17 @@ mod tests {
 
     #[test]
     fn user_word_set_primary_by_index_updates_all_senses() {
-        let mut word = UserWord::create(1, 1, vec![], None).unwrap();
-        let first = UserSense::new("first", true, 0, None).unwrap();
-        let mut second = UserSense::new("second", false, 1, None).unwrap();
+        let mut word = UserWord::create(
+            1,
+            1,
+            vec![],
+            None,
+            &TextLimits::default(),
+            &TagRules::default(),
+        )
+        .unwrap();
+        let first = UserSense::new("first", true, 0, None, &TextLimits::default()).unwrap();
+        let mut second = UserSense::new("second", false, 1, None, &TextLimits::default()).unwrap();
         second.id = Some(2);
 
         word.add_sense(first).unwrap();
@@ -425,19 +557,211 @@ mod tests {
 
     #[test]
     fn user_word_detects_duplicate_sense_text() {
-        let mut word = UserWord::create(1, 1, vec![], None).unwrap();
-        word.add_sense(UserSense::new("duplicate", false, 0, None).unwrap())
-            .unwrap();
-        let result = word.add_sense(UserSense::new("duplicate", false, 1, None).unwrap());
+        let mut word = UserWord::create(
+            1,
+            1,
+            vec![],
+            None,
+            &TextLimits::default(),
+            &TagRules::default(),
+        )
+        .unwrap();
+        word.add_sense(
+            UserSense::new("duplicate", false, 0, None, &TextLimits::default()).unwrap(),
+        )
+        .unwrap();
+        let result = word.add_sense(
+            UserSense::new("duplicate", false, 1, None, &TextLimits::default()).unwrap(),
+        );
         assert!(matches!(result, Err(UserWordError::DuplicateSenseText(_))));
     }
 
+    #[test]
+    fn user_word_rejects_senses_beyond_the_limit() {
+        let mut word = UserWord::create(
+            1,
+            1,
+            vec![],
+            None,
+            &TextLimits::default(),
+            &TagRules::default(),
+        )
+        .unwrap();
+        let sense = |index: usize| {
+            UserSense::new(
+                format!("meaning {index}"),
+                false,
+                index as i32,
+                None,
+                &TextLimits::default(),
+            )
+            .unwrap()
+        };
+        for index in 0..MAX_SENSES {
+            word.add_sense(sense(index)).unwrap();
+        }
+        let result = word.add_sense(sense(MAX_SENSES));
+        assert!(matches!(
+            result,
+            Err(UserWordError::SenseLimitExceeded(MAX_SENSES))
+        ));
+        assert_eq!(word.senses().len(), MAX_SENSES);
+    }
+
     #[test]
     fn user_sense_validates_note_and_text() {
-        let result = UserSense::new("   ", false, 0, None);
+        let result = UserSense::new("   ", false, 0, None, &TextLimits::default());
         assert!(matches!(result, Err(UserSenseError::InvalidNote)));
 
-        let result = UserSense::new("meaning", false, 0, Some("   ".into()));
+        let result = UserSense::new(
+            "meaning",
+            false,
+            0,
+            Some("   ".into()),
+            &TextLimits::default(),
+        );
         assert!(matches!(result, Err(UserSenseError::InvalidNote)));
     }
+
+    #[test]
+    fn control_characters_are_rejected_in_notes_and_sense_text() {
+        let result = UserWord::create(
+            1,
+            1,
+            vec![],
+            Some("nul\0note".into()),
+            &TextLimits::default(),
+            &TagRules::default(),
+        );
+        assert!(matches!(result, Err(UserWordError::NoteControlCharacter)));
+
+        let result = UserSense::new("mean\u{1b}ing", false, 0, None, &TextLimits::default());
+        assert!(matches!(result, Err(UserSenseError::ControlCharacter)));
+
+        let word = UserWord::create(
+            1,
+            1,
+            vec![],
+            Some("first\nsecond".into()),
+            &TextLimits::default(),
+            &TagRules::default(),
+        )
+        .unwrap();
+        assert_eq!(word.note(), Some("first\nsecond"));
+    }
+
+    #[test]
+    fn byte_limits_reject_short_but_wide_text() {
+        let limits = TextLimits {
+            text_bytes: 8,
+            note_bytes: 8,
+            ..TextLimits::default()
+        };
+        let wide = "\u{1F600}\u{1F600}\u{1F600}";
+
+        assert!(matches!(
+            UserSense::new(wide, false, 0, None, &limits),
+            Err(UserSenseError::TextTooManyBytes(12, 8))
+        ));
+        assert!(matches!(
+            UserSense::new("meaning", false, 0, Some(wide.into()), &limits),
+            Err(UserSenseError::NoteTooManyBytes(12, 8))
+        ));
+        assert!(matches!(
+            UserWord::create(
+                1,
+                1,
+                vec![],
+                Some(wide.into()),
+                &limits,
+                &TagRules::default()
+            ),
+            Err(UserWordError::NoteTooManyBytes(12, 8))
+        ));
+    }
+
+    #[test]
+    fn text_limits_are_enforced_independently() {
+        let limits = TextLimits {
+            sense_text: 8,
+            sense_note: 4,
+            word_note: 6,
+            ..TextLimits::default()
+        };
+
+        // 释义可以比义项备注长
+        assert!(UserSense::new("12345678", false, 0, Some("1234".into()), &limits).is_ok());
+        assert!(matches!(
+            UserSense::new("123456789", false, 0, None, &limits),
+            Err(UserSenseError::TextTooLong(9, 8))
+        ));
+        assert!(matches!(
+            UserSense::new("meaning", false, 0, Some("12345".into()), &limits),
+            Err(UserSenseError::NoteTooLong(5, 4))
+        ));
+
+        assert!(
+            UserWord::create(
+                1,
+                1,
+                vec![],
+                Some("123456".into()),
+                &limits,
+                &TagRules::default()
+            )
+            .is_ok()
+        );
+        assert!(matches!(
+            UserWord::create(
+                1,
+                1,
+                vec![],
+                Some("1234567".into()),
+                &limits,
+                &TagRules::default()
+            ),
+            Err(UserWordError::NoteTooLong(7, 6))
+        ));
+
+        let mut sense = UserSense::new("meaning", false, 0, None, &limits).unwrap();
+        assert!(matches!(
+            sense.set_text("a much longer text", &limits),
+            Err(UserSenseError::TextTooLong(18, 8))
+        ));
+        assert!(matches!(
+            sense.set_note(Some("12345".into()), &limits),
+            Err(UserSenseError::NoteTooLong(5, 4))
+        ));
+    }
+
+    #[test]
+    fn from_parts_accepts_text_longer_than_default_limits() {
+        let long = "x".repeat(MAX_TAGS * 100);
+        let sense = UserSense::from_parts(Some(1), long.clone(), true, 0, None, Utc::now());
+        assert_eq!(sense.unwrap().text(), long);
+    }
+
+    #[test]
+    fn sense_source_url_defaults_to_none_and_is_validated() {
+        let limits = TextLimits::default();
+        let sense = UserSense::new("meaning", false, 0, None, &limits).unwrap();
+        assert_eq!(sense.source_url(), None);
+
+        let mut sense = sense
+            .with_source_url(Some(" https://example.com/entry ".into()))
+            .unwrap();
+        assert_eq!(sense.source_url(), Some("https://example.com/entry"));
+
+        for invalid in ["not a url", "mailto:someone@example.com", " "] {
+            assert!(matches!(
+                sense.set_source_url(Some(invalid.into())),
+                Err(UserSenseError::InvalidSourceUrl)
+            ));
+        }
+        // 校验失败时保留原链接
+        assert_eq!(sense.source_url(), Some("https://example.com/entry"));
+
+        sense.set_source_url(None).unwrap();
+        assert_eq!(sense.source_url(), None);
+    }
 }
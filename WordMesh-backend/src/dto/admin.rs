@@ -0,0 +1,72 @@
+use serde::{Deserialize, Serialize};
+
+use crate::dto::auth::ProfileResponse;
+use crate::repository::graph::LinkCounts;
+use crate::repository::user::UserStats;
+
+#[derive(Debug, Deserialize)]
+pub struct AdminUserQuery {
+    pub username: Option<String>,
+}
+
+/// 图库不可用时 `links_available = false`，各关联计数为 null
+#[derive(Debug, Serialize)]
+pub struct UserStatsResponse {
+    pub word_count: i64,
+    pub sense_count: i64,
+    pub links_available: bool,
+    pub word_link_count: Option<i64>,
+    pub sense_word_link_count: Option<i64>,
+}
+
+impl UserStatsResponse {
+    pub fn new(stats: UserStats, links: Option<LinkCounts>) -> Self {
+        Self {
+            word_count: stats.word_count,
+            sense_count: stats.sense_count,
+            links_available: links.is_some(),
+            word_link_count: links.map(|links| links.word_links),
+            sense_word_link_count: links.map(|links| links.sense_links),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminUserResponse {
+    pub profile: ProfileResponse,
+    pub stats: UserStatsResponse,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RepairPrimariesResponse {
+    pub user_id: i64,
+    /// 主义项被调整的单词数，数据正常时为 0
+    pub repaired_words: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{HashedPassword, User};
+    use chrono::Utc;
+
+    #[test]
+    fn serialized_user_never_contains_password_hash() {
+        let hash = "$2b$04$abcdefghijklmnopqrstuv";
+        let user = User::new(
+            3,
+            "support_target".into(),
+            HashedPassword::new(hash.into()).unwrap(),
+            Utc::now(),
+        )
+        .unwrap();
+        let response = AdminUserResponse {
+            profile: ProfileResponse::from(user),
+            stats: UserStatsResponse::new(UserStats::default(), None),
+        };
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(!json.contains(hash));
+        assert!(!json.contains("password"));
+    }
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+use crate::domain::User;
+
 #[derive(Debug, Deserialize, Validate)]
 pub struct RegisterRequest {
     #[validate(
@@ -10,6 +12,12 @@ pub struct RegisterRequest {
     pub username: String,
     #[validate(length(min = 8, message = "密码长度至少 8 位"))]
     pub password: String,
+    /// 可选；提供时注册后会发送验证邮件
+    #[validate(
+        email(message = "邮箱格式不正确"),
+        length(max = 255, message = "邮箱长度不能超过 255")
+    )]
+    pub email: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Validate)]
@@ -29,10 +37,42 @@ pub struct RefreshRequest {
     pub refresh_token: String,
 }
 
+/// 登出时可一并提交 refresh token，使其同时失效
+#[derive(Debug, Default, Deserialize)]
+pub struct LogoutRequest {
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct VerifyEmailRequest {
+    #[validate(length(min = 10, message = "token 长度不合法"))]
+    pub token: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PasswordResetRequest {
+    pub email: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PasswordResetConfirmRequest {
+    pub token: String,
+    pub new_password: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
 #[derive(Debug, Serialize)]
 pub struct AuthTokens {
     pub access_token: String,
     pub refresh_token: Option<String>,
+    /// 服务端关闭了刷新（`refresh_ttl_secs = 0`）时为 false，客户端不应再调用 /auth/refresh
+    pub refresh_enabled: bool,
 }
 
 #[derive(Debug, Serialize)]
@@ -40,6 +80,21 @@ pub struct ProfileResponse {
     pub id: i64,
     pub username: String,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    pub email: Option<String>,
+    pub email_verified: bool,
+}
+
+/// 只复制公开字段；密码哈希不得出现在任何响应中
+impl From<User> for ProfileResponse {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            username: user.username,
+            created_at: user.created_at,
+            email: user.email,
+            email_verified: user.email_verified,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -51,6 +106,7 @@ mod tests {
         let req = RegisterRequest {
             username: "valid_user".into(),
             password: "password123".into(),
+            email: None,
         };
         assert!(req.validate().is_ok());
     }
@@ -60,6 +116,17 @@ mod tests {
         let req = RegisterRequest {
             username: "ab".into(),
             password: "password123".into(),
+            email: None,
+        };
+        assert!(req.validate().is_err());
+    }
+
+    #[test]
+    fn register_request_rejects_malformed_email() {
+        let req = RegisterRequest {
+            username: "valid_user".into(),
+            password: "password123".into(),
+            email: Some("not-an-email".into()),
         };
         assert!(req.validate().is_err());
     }
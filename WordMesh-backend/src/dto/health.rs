@@ -0,0 +1,31 @@
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+pub struct HealthResponse {
+    pub status: &'static str,
+    pub service: &'static str,
+    pub version: &'static str,
+    pub database: PoolStatus,
+}
+
+/// 连接池快照，直接读取 sqlx 的计数，不访问数据库
+#[derive(Debug, Serialize)]
+pub struct PoolStatus {
+    pub size: u32,
+    pub idle: u32,
+    pub in_use: u32,
+    pub max_connections: u32,
+}
+
+impl PoolStatus {
+    pub fn of(pool: &sqlx::PgPool) -> Self {
+        let size = pool.size();
+        let idle = u32::try_from(pool.num_idle()).unwrap_or(u32::MAX).min(size);
+        Self {
+            size,
+            idle,
+            in_use: size - idle,
+            max_connections: pool.options().get_max_connections(),
+        }
+    }
+}
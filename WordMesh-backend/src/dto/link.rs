@@ -0,0 +1,301 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::repository::graph::{
+    LinkKindStats, Neighborhood, SenseWordLinkKind, SenseWordLinkRecord, WordLinkKind,
+    WordLinkRecord,
+};
+
+/// 关联类型的展示说明；新增枚举值时需同步补充
+const KIND_DESCRIPTIONS: &[(&str, &str)] = &[
+    ("similar_form", "形近词：拼写或读音相近的单词"),
+    ("root_affix", "词根词缀：共享词根或词缀的单词"),
+    ("synonym", "同义：义项与目标单词意思相同或相近"),
+    ("antonym", "反义：义项与目标单词意思相反"),
+    ("related", "相关：义项与目标单词存在其他语义联系"),
+];
+
+fn describe(value: &str) -> &'static str {
+    KIND_DESCRIPTIONS
+        .iter()
+        .find(|(kind, _)| *kind == value)
+        .map(|(_, description)| *description)
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Serialize)]
+pub struct LinkKindResponse {
+    pub value: &'static str,
+    pub description: &'static str,
+}
+
+impl LinkKindResponse {
+    fn new(value: &'static str) -> Self {
+        Self {
+            value,
+            description: describe(value),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct LinkKindsResponse {
+    pub word_link_kinds: Vec<LinkKindResponse>,
+    pub sense_word_link_kinds: Vec<LinkKindResponse>,
+}
+
+impl LinkKindsResponse {
+    pub fn new(word_kinds: &[WordLinkKind], sense_kinds: &[SenseWordLinkKind]) -> Self {
+        Self {
+            word_link_kinds: word_kinds
+                .iter()
+                .map(|kind| LinkKindResponse::new(kind.as_str()))
+                .collect(),
+            sense_word_link_kinds: sense_kinds
+                .iter()
+                .map(|kind| LinkKindResponse::new(kind.as_str()))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct LinkKindCount {
+    pub kind: &'static str,
+    pub count: Option<i64>,
+}
+
+/// 每种类型都会出现；图库不可用时 `available = false`，各计数为 null
+#[derive(Debug, Serialize)]
+pub struct LinkKindStatsResponse {
+    pub available: bool,
+    pub word_links: Vec<LinkKindCount>,
+    pub sense_word_links: Vec<LinkKindCount>,
+}
+
+impl From<Option<LinkKindStats>> for LinkKindStatsResponse {
+    fn from(stats: Option<LinkKindStats>) -> Self {
+        let available = stats.is_some();
+        let stats = stats.unwrap_or_default();
+        Self {
+            available,
+            word_links: stats
+                .word_links
+                .into_iter()
+                .map(|(kind, count)| LinkKindCount {
+                    kind: kind.as_str(),
+                    count: available.then_some(count),
+                })
+                .collect(),
+            sense_word_links: stats
+                .sense_links
+                .into_iter()
+                .map(|(kind, count)| LinkKindCount {
+                    kind: kind.as_str(),
+                    count: available.then_some(count),
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateWordLinkRequest {
+    pub word_a_id: i64,
+    pub word_b_id: i64,
+    /// 省略时使用 `graph.default_word_link_kind`
+    pub kind: Option<String>,
+    pub note: Option<String>,
+}
+
+/// `POST /words/{word_id}/links`：路径中的单词为关联的一端
+#[derive(Debug, Deserialize)]
+pub struct AddWordLinkRequest {
+    pub target_word_id: i64,
+    /// 省略时使用 `graph.default_word_link_kind`
+    pub kind: Option<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DeleteWordLinkQuery {
+    pub kind: String,
+}
+
+/// 省略 `note` 表示不修改，显式传 null 表示清除
+#[derive(Debug, Deserialize)]
+pub struct UpdateWordLinkRequest {
+    /// 要修改的关联当前的类型
+    pub kind: String,
+    pub new_kind: Option<String>,
+    #[serde(default, deserialize_with = "present_or_null")]
+    pub note: Option<Option<String>>,
+}
+
+/// 区分字段缺失（外层 None，由 `serde(default)` 处理）与显式的 null（`Some(None)`）
+pub(crate) fn present_or_null<'de, D>(deserializer: D) -> Result<Option<Option<String>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer).map(Some)
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateSenseLinkRequest {
+    pub sense_id: i64,
+    pub source_word_id: i64,
+    pub target_word_id: i64,
+    /// 省略时使用 `graph.default_sense_link_kind`
+    pub kind: Option<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WordLinkResponse {
+    pub link_id: String,
+    pub kind: &'static str,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub word_a_id: i64,
+    pub word_b_id: i64,
+}
+
+impl From<WordLinkRecord> for WordLinkResponse {
+    fn from(record: WordLinkRecord) -> Self {
+        Self {
+            link_id: record.link_id,
+            kind: record.kind.as_str(),
+            note: record.note,
+            created_at: record.created_at,
+            word_a_id: record.word_a_id,
+            word_b_id: record.word_b_id,
+        }
+    }
+}
+
+/// 两个单词之间的关联详情，`word_a_id`/`word_b_id` 按请求顺序回显
+#[derive(Debug, Serialize)]
+pub struct WordLinkDetailsResponse {
+    pub word_a_id: i64,
+    pub word_b_id: i64,
+    pub links: Vec<WordLinkResponse>,
+}
+
+impl WordLinkDetailsResponse {
+    pub fn new(word_a_id: i64, word_b_id: i64, links: Vec<WordLinkRecord>) -> Self {
+        Self {
+            word_a_id,
+            word_b_id,
+            links: links.into_iter().map(WordLinkResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LinkListQuery {
+    /// 关联类型的字符串形式，如 `similar_form`、`synonym`；缺省时不按类型过滤
+    pub kind: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WordLinkListResponse {
+    pub word_id: i64,
+    pub links: Vec<WordLinkResponse>,
+}
+
+impl WordLinkListResponse {
+    pub fn new(word_id: i64, links: Vec<WordLinkRecord>) -> Self {
+        Self {
+            word_id,
+            links: links.into_iter().map(WordLinkResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SenseLinkListResponse {
+    pub sense_id: i64,
+    pub links: Vec<SenseLinkResponse>,
+}
+
+impl SenseLinkListResponse {
+    pub fn new(sense_id: i64, links: Vec<SenseWordLinkRecord>) -> Self {
+        Self {
+            sense_id,
+            links: links.into_iter().map(SenseLinkResponse::from).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NeighborsQuery {
+    /// 1 到 3 之间，超出范围时钳制；默认 1
+    pub depth: Option<u32>,
+    pub kind: Option<String>,
+}
+
+/// `word_ids` 不含起点单词；`links` 为这些单词（含起点）之间的全部关联
+#[derive(Debug, Serialize)]
+pub struct NeighborhoodResponse {
+    pub word_id: i64,
+    pub word_ids: Vec<i64>,
+    pub links: Vec<WordLinkResponse>,
+    pub truncated: bool,
+}
+
+impl NeighborhoodResponse {
+    pub fn new(word_id: i64, neighborhood: Neighborhood) -> Self {
+        Self {
+            word_id,
+            word_ids: neighborhood.word_ids,
+            links: neighborhood
+                .links
+                .into_iter()
+                .map(WordLinkResponse::from)
+                .collect(),
+            truncated: neighborhood.truncated,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SenseLinkResponse {
+    pub link_id: String,
+    pub kind: &'static str,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub sense_id: i64,
+    pub source_word_id: i64,
+    pub target_word_id: i64,
+}
+
+impl From<SenseWordLinkRecord> for SenseLinkResponse {
+    fn from(record: SenseWordLinkRecord) -> Self {
+        Self {
+            link_id: record.link_id,
+            kind: record.kind.as_str(),
+            note: record.note,
+            created_at: record.created_at,
+            sense_id: record.sense_id,
+            source_word_id: record.source_word_id,
+            target_word_id: record.target_word_id,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_kind_has_a_description() {
+        for kind in WordLinkKind::ALL {
+            assert!(!describe(kind.as_str()).is_empty(), "{kind:?}");
+        }
+        for kind in SenseWordLinkKind::ALL {
+            assert!(!describe(kind.as_str()).is_empty(), "{kind:?}");
+        }
+    }
+}
@@ -1 +1,6 @@
+pub mod admin;
 pub mod auth;
+pub mod health;
+pub mod link;
+pub mod preferences;
+pub mod word;
@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::BTreeMap;
+
+use crate::repository::preferences::UserPreferences;
+use crate::repository::word::{SearchScope, SearchSort};
+
+#[derive(Debug, Deserialize)]
+pub struct UpdatePreferencesRequest {
+    pub default_scope: Option<SearchScope>,
+    pub default_sort: Option<SearchSort>,
+    /// 不认识的键，由控制器逐项报校验错误而不是静默丢弃
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, JsonValue>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PreferencesResponse {
+    pub default_scope: Option<SearchScope>,
+    pub default_sort: Option<SearchSort>,
+}
+
+impl From<UpdatePreferencesRequest> for UserPreferences {
+    fn from(request: UpdatePreferencesRequest) -> Self {
+        Self {
+            default_scope: request.default_scope,
+            default_sort: request.default_sort,
+        }
+    }
+}
+
+impl From<UserPreferences> for PreferencesResponse {
+    fn from(preferences: UserPreferences) -> Self {
+        Self {
+            default_scope: preferences.default_scope,
+            default_sort: preferences.default_sort,
+        }
+    }
+}
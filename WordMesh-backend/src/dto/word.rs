@@ -0,0 +1,872 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::domain::word::UserSense;
+use crate::dto::link::{SenseLinkResponse, WordLinkResponse, present_or_null};
+use crate::repository::word::{
+    DuplicateCandidate, MatchSide, NearDuplicatePair, SearchMatch, SearchScope, SearchSort,
+    SenseOrder, TagAction, UserWordAggregate, WordTombstone,
+};
+use crate::service::assoc::{ImportSenseLink, ImportWordLink};
+use crate::service::word::{
+    AddWordInput, AddedWord, BatchAddItem, BatchWordInput, LinkedWord, NetworkExport, SenseInput,
+    SenseLinkTarget, SenseWithLinks, SyncDelta, SyncPage, WordExistence, WordExport,
+    WordMetadataUpdate, WordSenses,
+};
+use crate::util::AppError;
+use crate::util::error::ErrorResponse;
+
+#[derive(Debug, Deserialize)]
+pub struct TagWordsQuery {
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+    #[serde(default)]
+    pub sort: SearchSort,
+    /// 上一页返回的 `next_cursor`；提供时忽略 page
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddWordRequest {
+    pub text: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub note: Option<String>,
+    pub first_sense: Option<SenseRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SenseRequest {
+    pub text: String,
+    #[serde(default)]
+    pub is_primary: bool,
+    #[serde(default)]
+    pub sort_order: i32,
+    pub note: Option<String>,
+    /// 释义出处，必须是 http/https 链接
+    pub source_url: Option<String>,
+}
+
+impl From<AddWordRequest> for AddWordInput {
+    fn from(request: AddWordRequest) -> Self {
+        Self {
+            text: request.text,
+            tags: request.tags,
+            note: request.note,
+            first_sense: request.first_sense.map(SenseInput::from),
+        }
+    }
+}
+
+impl From<SenseRequest> for SenseInput {
+    fn from(request: SenseRequest) -> Self {
+        Self {
+            text: request.text,
+            is_primary: request.is_primary,
+            sort_order: request.sort_order,
+            note: request.note,
+            source_url: request.source_url,
+        }
+    }
+}
+
+/// 新增单词的结果：单词本身的字段，加上是否新建与归并信息
+#[derive(Debug, Serialize)]
+pub struct AddWordResponse {
+    #[serde(flatten)]
+    pub word: WordResponse,
+    pub created: bool,
+    /// 按 canonical_key 归并到已有单词时，已有单词原先的显示文本
+    pub matched_existing_text: Option<String>,
+}
+
+impl From<AddedWord> for AddWordResponse {
+    fn from(added: AddedWord) -> Self {
+        Self {
+            word: WordResponse::from(added.aggregate),
+            created: added.created,
+            matched_existing_text: added.canonical_match.map(|found| found.existing_text),
+        }
+    }
+}
+
+/// 批量加词请求；GET /export 的导出文档可以原样提交，`word_links` 与 `sense_links`
+/// 中的 id 按各项的 `word_id` 与 `senses[].id` 换算为导入后的 id
+#[derive(Debug, Deserialize)]
+pub struct BatchAddWordsRequest {
+    pub words: Vec<BatchWordRequest>,
+    #[serde(default)]
+    pub word_links: Vec<ImportWordLinkRequest>,
+    #[serde(default)]
+    pub sense_links: Vec<ImportSenseLinkRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BatchWordRequest {
+    #[serde(flatten)]
+    pub word: AddWordRequest,
+    /// 导出文档中的 word_id，供关联引用
+    pub word_id: Option<i64>,
+    /// `first_sense` 之外一并写入的义项，与已有义项文本相同的跳过
+    #[serde(default)]
+    pub senses: Vec<BatchSenseRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BatchSenseRequest {
+    #[serde(flatten)]
+    pub sense: SenseRequest,
+    /// 导出文档中的义项 id，供义项关联引用
+    pub id: Option<i64>,
+}
+
+impl From<BatchWordRequest> for BatchWordInput {
+    fn from(request: BatchWordRequest) -> Self {
+        Self {
+            word: request.word.into(),
+            senses: request
+                .senses
+                .into_iter()
+                .map(|sense| sense.sense.into())
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportWordLinkRequest {
+    pub word_a_id: i64,
+    pub word_b_id: i64,
+    pub kind: String,
+    pub note: Option<String>,
+}
+
+impl From<ImportWordLinkRequest> for ImportWordLink {
+    fn from(request: ImportWordLinkRequest) -> Self {
+        Self {
+            word_a_id: request.word_a_id,
+            word_b_id: request.word_b_id,
+            kind: request.kind,
+            note: request.note,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportSenseLinkRequest {
+    pub sense_id: i64,
+    pub source_word_id: i64,
+    pub target_word_id: i64,
+    pub kind: String,
+    pub note: Option<String>,
+}
+
+impl From<ImportSenseLinkRequest> for ImportSenseLink {
+    fn from(request: ImportSenseLinkRequest) -> Self {
+        Self {
+            sense_id: request.sense_id,
+            source_word_id: request.source_word_id,
+            target_word_id: request.target_word_id,
+            kind: request.kind,
+            note: request.note,
+        }
+    }
+}
+
+/// 批量加词中的一项：成功时 `word` 有值，失败时 `error` 为该项单独提交时会得到的错误
+#[derive(Debug, Serialize)]
+pub struct BatchAddItemResponse {
+    pub index: usize,
+    pub word: Option<AddWordResponse>,
+    pub error: Option<ErrorResponse>,
+    /// 因单词已有同文本义项而未新建的义项数
+    pub deduplicated_senses: u32,
+}
+
+impl From<BatchAddItem> for BatchAddItemResponse {
+    fn from(item: BatchAddItem) -> Self {
+        let (word, error) = match item.result {
+            Ok(added) => (Some(AddWordResponse::from(added)), None),
+            Err(err) => (None, Some(ErrorResponse::from(&err))),
+        };
+        Self {
+            index: item.index,
+            word,
+            error,
+            deduplicated_senses: item.deduplicated_senses,
+        }
+    }
+}
+
+/// 导入的一条关联：成功时 `link_id` 有值，失败时 `error` 为对应创建接口会返回的错误
+#[derive(Debug, Serialize)]
+pub struct BatchLinkItemResponse {
+    pub index: usize,
+    pub link_id: Option<String>,
+    pub error: Option<ErrorResponse>,
+}
+
+impl BatchLinkItemResponse {
+    pub fn new(index: usize, result: Result<String, AppError>) -> Self {
+        let (link_id, error) = match result {
+            Ok(link_id) => (Some(link_id), None),
+            Err(err) => (None, Some(ErrorResponse::from(&err))),
+        };
+        Self {
+            index,
+            link_id,
+            error,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BatchAddWordsResponse {
+    /// 按请求中的下标排列，每项对应 words 中的一个元素
+    pub items: Vec<BatchAddItemResponse>,
+    /// 同上，分别对应请求中的 word_links 与 sense_links
+    pub word_links: Vec<BatchLinkItemResponse>,
+    pub sense_links: Vec<BatchLinkItemResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    /// 为空时返回全部单词
+    #[serde(default)]
+    pub query: String,
+    /// 缺省时使用用户偏好，再回退到 `both`
+    pub scope: Option<SearchScope>,
+    pub sort: Option<SearchSort>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WordsQuery {
+    /// 加入网络时间的下界（RFC 3339，含）
+    pub created_from: Option<String>,
+    /// 加入网络时间的上界（RFC 3339，含）
+    pub created_to: Option<String>,
+    /// true 只列出有关联的单词，false 只列出尚未关联的单词；需要图库开启
+    pub has_links: Option<bool>,
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BulkTagRequest {
+    pub action: TagAction,
+    pub tag: String,
+    pub user_word_ids: Vec<i64>,
+}
+
+/// PUT /words/{id}/tags：以该列表整体替换标签，空列表清空
+#[derive(Debug, Deserialize)]
+pub struct SetTagsRequest {
+    pub tags: Vec<String>,
+}
+
+/// PUT /words/{id}/text：纠正单词文本，义项与关联随之迁移
+#[derive(Debug, Deserialize)]
+pub struct RewriteWordTextRequest {
+    pub text: String,
+}
+
+/// PATCH /words/{id}：省略的字段保持不变，`"note": null` 清空备注
+#[derive(Debug, Deserialize)]
+pub struct UpdateWordRequest {
+    pub tags: Option<Vec<String>>,
+    #[serde(default, deserialize_with = "present_or_null")]
+    pub note: Option<Option<String>>,
+}
+
+impl From<UpdateWordRequest> for WordMetadataUpdate {
+    fn from(request: UpdateWordRequest) -> Self {
+        Self {
+            tags: request.tags,
+            note: request.note,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BulkTagResponse {
+    /// 标签实际发生变化的单词数
+    pub changed: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExistsBatchRequest {
+    pub texts: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WordExistenceResponse {
+    pub text: String,
+    pub canonical_key: String,
+    pub exists: bool,
+}
+
+impl From<WordExistence> for WordExistenceResponse {
+    fn from(existence: WordExistence) -> Self {
+        Self {
+            text: existence.text,
+            canonical_key: existence.canonical_key.as_str().to_string(),
+            exists: existence.exists,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExistsBatchResponse {
+    /// 与请求中的 texts 一一对应
+    pub items: Vec<WordExistenceResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DuplicatesQuery {
+    /// 0.2 到 1.0 之间，默认 0.4
+    pub threshold: Option<f32>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateWordResponse {
+    pub user_word_id: i64,
+    pub word_id: i64,
+    pub text: String,
+    pub canonical_key: String,
+}
+
+impl From<DuplicateCandidate> for DuplicateWordResponse {
+    fn from(candidate: DuplicateCandidate) -> Self {
+        Self {
+            user_word_id: candidate.user_word_id,
+            word_id: candidate.word_id,
+            text: candidate.text,
+            canonical_key: candidate.canonical_key.as_str().to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicatePairResponse {
+    pub first: DuplicateWordResponse,
+    pub second: DuplicateWordResponse,
+    pub similarity: f32,
+}
+
+impl From<NearDuplicatePair> for DuplicatePairResponse {
+    fn from(pair: NearDuplicatePair) -> Self {
+        Self {
+            first: pair.first.into(),
+            second: pair.second.into(),
+            similarity: pair.similarity,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicatesResponse {
+    pub items: Vec<DuplicatePairResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SenseResponse {
+    pub id: Option<i64>,
+    pub text: String,
+    pub is_primary: bool,
+    pub sort_order: i32,
+    pub note: Option<String>,
+    pub source_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WordResponse {
+    pub user_word_id: Option<i64>,
+    pub word_id: i64,
+    pub text: String,
+    pub canonical_key: String,
+    pub tags: Vec<String>,
+    pub note: Option<String>,
+    pub senses: Vec<SenseResponse>,
+    /// 义项超过列表内嵌上限时为 true，完整义项需查询单词详情
+    pub has_more_senses: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<&UserSense> for SenseResponse {
+    fn from(sense: &UserSense) -> Self {
+        Self {
+            id: sense.id(),
+            text: sense.text().to_string(),
+            is_primary: sense.is_primary,
+            sort_order: sense.sort_order,
+            note: sense.note().map(str::to_string),
+            source_url: sense.source_url().map(str::to_string),
+            created_at: sense.created_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SenseLinkTargetResponse {
+    pub link_id: String,
+    pub kind: &'static str,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub target_word_id: i64,
+    pub target_text: String,
+}
+
+impl From<SenseLinkTarget> for SenseLinkTargetResponse {
+    fn from(item: SenseLinkTarget) -> Self {
+        Self {
+            link_id: item.link.link_id,
+            kind: item.link.kind.as_str(),
+            note: item.link.note,
+            created_at: item.link.created_at,
+            target_word_id: item.target.id,
+            target_text: item.target.text,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SenseWithLinksResponse {
+    #[serde(flatten)]
+    pub sense: SenseResponse,
+    pub links: Vec<SenseLinkTargetResponse>,
+}
+
+impl From<SenseWithLinks> for SenseWithLinksResponse {
+    fn from(item: SenseWithLinks) -> Self {
+        Self {
+            sense: SenseResponse::from(&item.sense),
+            links: item
+                .links
+                .into_iter()
+                .map(SenseLinkTargetResponse::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WordSensesQuery {
+    /// 默认按 sort_order；`primary_first` 时主义项置顶
+    pub sense_order: Option<SenseOrder>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WordSensesResponse {
+    pub user_word_id: i64,
+    pub word_id: i64,
+    pub text: String,
+    pub senses: Vec<SenseWithLinksResponse>,
+    /// 图库不可用时为 false，各义项的 links 为空
+    pub links_available: bool,
+}
+
+impl From<WordSenses> for WordSensesResponse {
+    fn from(item: WordSenses) -> Self {
+        Self {
+            user_word_id: item.user_word_id,
+            word_id: item.word.id,
+            text: item.word.text,
+            senses: item
+                .senses
+                .into_iter()
+                .map(SenseWithLinksResponse::from)
+                .collect(),
+            links_available: item.links_available,
+        }
+    }
+}
+
+/// `PUT /words/{word_id}/senses/order`：单词全部义项的 id，按期望顺序排列
+#[derive(Debug, Deserialize)]
+pub struct ReorderSensesRequest {
+    pub sense_ids: Vec<i64>,
+}
+
+/// 调整义项顺序或主义项后，单词全部义项的最新状态
+#[derive(Debug, Serialize)]
+pub struct SenseListResponse {
+    pub user_word_id: i64,
+    pub senses: Vec<SenseResponse>,
+}
+
+impl SenseListResponse {
+    pub fn new(user_word_id: i64, senses: &[UserSense]) -> Self {
+        Self {
+            user_word_id,
+            senses: senses.iter().map(SenseResponse::from).collect(),
+        }
+    }
+}
+
+impl From<UserWordAggregate> for WordResponse {
+    fn from(aggregate: UserWordAggregate) -> Self {
+        let UserWordAggregate {
+            word,
+            user_word,
+            has_more_senses,
+        } = aggregate;
+        Self {
+            user_word_id: user_word.id,
+            word_id: word.id,
+            text: word.text,
+            canonical_key: word.canonical_key.as_str().to_string(),
+            tags: user_word.tags().to_vec(),
+            note: user_word.note().map(str::to_string),
+            senses: user_word.senses().iter().map(SenseResponse::from).collect(),
+            has_more_senses,
+            created_at: user_word.created_at,
+        }
+    }
+}
+
+/// 单词详情：始终包含全部义项，因此不带 `has_more_senses`
+#[derive(Debug, Serialize)]
+pub struct WordDetailResponse {
+    pub user_word_id: Option<i64>,
+    pub word_id: i64,
+    pub text: String,
+    pub canonical_key: String,
+    pub tags: Vec<String>,
+    pub note: Option<String>,
+    pub senses: Vec<SenseResponse>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<UserWordAggregate> for WordDetailResponse {
+    fn from(aggregate: UserWordAggregate) -> Self {
+        let UserWordAggregate {
+            word, user_word, ..
+        } = aggregate;
+        Self {
+            user_word_id: user_word.id,
+            word_id: word.id,
+            text: word.text,
+            canonical_key: word.canonical_key.as_str().to_string(),
+            tags: user_word.tags().to_vec(),
+            note: user_word.note().map(str::to_string),
+            senses: user_word.senses().iter().map(SenseResponse::from).collect(),
+            created_at: user_word.created_at,
+        }
+    }
+}
+
+/// 搜索结果：单词本身的字段加上关键字的命中信息
+#[derive(Debug, Serialize)]
+pub struct SearchItemResponse {
+    #[serde(flatten)]
+    pub word: WordResponse,
+    /// 关键字为空时省略
+    #[serde(rename = "match", skip_serializing_if = "Option::is_none")]
+    pub search_match: Option<SearchMatchResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchMatchResponse {
+    pub side: MatchSide,
+    pub snippet: String,
+}
+
+impl From<SearchMatch> for SearchMatchResponse {
+    fn from(search_match: SearchMatch) -> Self {
+        Self {
+            side: search_match.side,
+            snippet: search_match.snippet,
+        }
+    }
+}
+
+/// 关联单词：单词本身的字段加上连接它的关联
+#[derive(Debug, Serialize)]
+pub struct LinkedWordResponse {
+    #[serde(flatten)]
+    pub word: WordResponse,
+    pub link_id: String,
+    pub link_kind: &'static str,
+    pub link_note: Option<String>,
+    pub linked_at: DateTime<Utc>,
+}
+
+impl From<LinkedWord> for LinkedWordResponse {
+    fn from(item: LinkedWord) -> Self {
+        Self {
+            word: WordResponse::from(item.word),
+            link_id: item.link.link_id,
+            link_kind: item.link.kind.as_str(),
+            link_note: item.link.note,
+            linked_at: item.link.created_at,
+        }
+    }
+}
+
+/// 关联另一端的单词，仅含展示所需字段
+#[derive(Debug, Serialize)]
+pub struct RelatedWordResponse {
+    pub word_id: i64,
+    pub text: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WordExportResponse {
+    pub exported_at: DateTime<Utc>,
+    pub word: WordResponse,
+    pub word_links: Vec<WordLinkResponse>,
+    pub sense_links: Vec<SenseLinkResponse>,
+    pub related_words: Vec<RelatedWordResponse>,
+    /// 图库不可用时为 false，两类关联为空
+    pub links_available: bool,
+    pub links_truncated: bool,
+}
+
+impl From<WordExport> for WordExportResponse {
+    fn from(export: WordExport) -> Self {
+        Self {
+            exported_at: export.exported_at,
+            word: WordResponse::from(export.word),
+            word_links: export
+                .word_links
+                .into_iter()
+                .map(WordLinkResponse::from)
+                .collect(),
+            sense_links: export
+                .sense_links
+                .into_iter()
+                .map(SenseLinkResponse::from)
+                .collect(),
+            related_words: export
+                .related_words
+                .into_iter()
+                .map(|word| RelatedWordResponse {
+                    word_id: word.id,
+                    text: word.text,
+                })
+                .collect(),
+            links_available: export.links_available,
+            links_truncated: export.links_truncated,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SyncQuery {
+    /// 上一页返回的 `next_cursor`，首页不传
+    pub cursor: Option<String>,
+    pub limit: Option<u32>,
+}
+
+/// GET /export：整个网络的导出文档，关联按 word_id 引用单词。原样提交给 POST /words/batch
+/// 即可导回单词、全部义项与两类关联；`links_truncated` 为 true 时只能导回已导出的部分
+#[derive(Debug, Serialize)]
+pub struct NetworkExportResponse {
+    pub exported_at: DateTime<Utc>,
+    pub words: Vec<ExportedWordResponse>,
+    pub word_links: Vec<WordLinkResponse>,
+    pub sense_links: Vec<SenseLinkResponse>,
+    /// 图库不可用时为 false，两类关联为空
+    pub links_available: bool,
+    pub links_truncated: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ExportedWordResponse {
+    #[serde(flatten)]
+    pub word: WordResponse,
+    /// 对应批量导入请求项的 `first_sense`：主义项，没有主义项时取排序最前的义项
+    pub first_sense: Option<SenseResponse>,
+}
+
+impl From<UserWordAggregate> for ExportedWordResponse {
+    fn from(aggregate: UserWordAggregate) -> Self {
+        let senses = aggregate.user_word.senses();
+        let first_sense = senses
+            .iter()
+            .find(|sense| sense.is_primary)
+            .or_else(|| senses.first())
+            .map(SenseResponse::from);
+        Self {
+            word: WordResponse::from(aggregate),
+            first_sense,
+        }
+    }
+}
+
+impl From<NetworkExport> for NetworkExportResponse {
+    fn from(export: NetworkExport) -> Self {
+        Self {
+            exported_at: export.exported_at,
+            words: export
+                .words
+                .into_iter()
+                .map(ExportedWordResponse::from)
+                .collect(),
+            word_links: export
+                .word_links
+                .into_iter()
+                .map(WordLinkResponse::from)
+                .collect(),
+            sense_links: export
+                .sense_links
+                .into_iter()
+                .map(SenseLinkResponse::from)
+                .collect(),
+            links_available: export.links_available,
+            links_truncated: export.links_truncated,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncResponse {
+    pub server_time: DateTime<Utc>,
+    pub words: Vec<WordResponse>,
+    pub word_links: Vec<WordLinkResponse>,
+    pub sense_links: Vec<SenseLinkResponse>,
+    /// 图库不可用时为 false，两类关联为空
+    pub links_available: bool,
+    pub links_truncated: bool,
+    /// 为 null 表示已同步到最后一页
+    pub next_cursor: Option<String>,
+}
+
+impl From<SyncPage> for SyncResponse {
+    fn from(page: SyncPage) -> Self {
+        Self {
+            server_time: page.server_time,
+            words: page.words.into_iter().map(WordResponse::from).collect(),
+            word_links: page
+                .word_links
+                .into_iter()
+                .map(WordLinkResponse::from)
+                .collect(),
+            sense_links: page
+                .sense_links
+                .into_iter()
+                .map(SenseLinkResponse::from)
+                .collect(),
+            links_available: page.links_available,
+            links_truncated: page.links_truncated,
+            next_cursor: page.next_cursor,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SyncDeltaQuery {
+    /// 上一次同步返回的 `server_time`
+    pub since: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct WordTombstoneResponse {
+    pub user_word_id: i64,
+    pub word_id: i64,
+    pub deleted_at: DateTime<Utc>,
+}
+
+impl From<WordTombstone> for WordTombstoneResponse {
+    fn from(tombstone: WordTombstone) -> Self {
+        Self {
+            user_word_id: tombstone.user_word_id,
+            word_id: tombstone.word_id,
+            deleted_at: tombstone.deleted_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncDeltaResponse {
+    pub server_time: DateTime<Utc>,
+    /// 新增或修改过的单词，内嵌全部义项，应整体替换本地副本
+    pub words: Vec<WordResponse>,
+    pub deleted: Vec<WordTombstoneResponse>,
+    /// 为 true 时变化过多未全部返回，应改用 /sync 全量同步
+    pub truncated: bool,
+}
+
+impl From<SyncDelta> for SyncDeltaResponse {
+    fn from(delta: SyncDelta) -> Self {
+        Self {
+            server_time: delta.server_time,
+            words: delta
+                .changes
+                .updated
+                .into_iter()
+                .map(WordResponse::from)
+                .collect(),
+            deleted: delta
+                .changes
+                .deleted
+                .into_iter()
+                .map(WordTombstoneResponse::from)
+                .collect(),
+            truncated: delta.truncated,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::word::{CanonicalKey, UserWord};
+    use crate::repository::word::WordRecord;
+
+    #[test]
+    fn word_detail_exposes_tags_note_and_senses() {
+        let created_at = Utc::now();
+        let senses = vec![
+            UserSense::from_parts(Some(11), "a fruit".into(), true, 0, None, created_at).unwrap(),
+            UserSense::from_parts(
+                Some(12),
+                "a tech company".into(),
+                false,
+                1,
+                Some("proper noun".into()),
+                created_at,
+            )
+            .unwrap(),
+        ];
+        let aggregate = UserWordAggregate {
+            word: WordRecord {
+                id: 3,
+                text: "Apple".into(),
+                canonical_key: CanonicalKey::new("Apple").unwrap(),
+                created_at,
+            },
+            user_word: UserWord::from_parts(
+                Some(7),
+                1,
+                3,
+                vec!["fruit".into()],
+                Some("remember".into()),
+                senses,
+                created_at,
+            )
+            .unwrap(),
+            has_more_senses: false,
+        };
+
+        let json = serde_json::to_value(WordDetailResponse::from(aggregate)).unwrap();
+        assert_eq!(json["user_word_id"], 7);
+        assert_eq!(json["word_id"], 3);
+        assert_eq!(json["canonical_key"], "apple");
+        assert_eq!(json["tags"], serde_json::json!(["fruit"]));
+        assert_eq!(json["note"], "remember");
+        assert!(json.get("has_more_senses").is_none());
+        let senses = json["senses"].as_array().unwrap();
+        assert_eq!(senses.len(), 2);
+        assert_eq!(senses[0]["id"], 11);
+        assert_eq!(senses[0]["is_primary"], true);
+        assert!(senses[0]["note"].is_null());
+        assert_eq!(senses[1]["sort_order"], 1);
+        assert_eq!(senses[1]["note"], "proper noun");
+        assert!(senses[1]["created_at"].is_string());
+    }
+}
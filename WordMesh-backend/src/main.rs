@@ -1,24 +1,34 @@
-use actix_web::{App, HttpServer, middleware::Logger, web};
+use actix_web::middleware::{Condition, Logger};
+use actix_web::{App, HttpServer, web};
 use std::sync::Arc;
+use std::time::Duration;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-mod application;
-mod config;
-mod controller;
-mod domain;
-mod dto;
-mod event;
-mod middleware;
-mod repository;
-mod service;
-mod util;
-
-use config::Settings;
-use controller::auth::AuthController;
-use middleware::RequestId;
-use repository::PgUserRepository;
-use service::auth::AuthService;
-use util::{AppError, ResponseBuilder};
+use wordmesh_backend::config::Settings;
+use wordmesh_backend::config::check;
+use wordmesh_backend::config::settings::DatabaseSettings;
+use wordmesh_backend::controller::admin::AdminController;
+use wordmesh_backend::controller::auth::AuthController;
+use wordmesh_backend::controller::health::HealthController;
+use wordmesh_backend::controller::link::LinkController;
+use wordmesh_backend::controller::metrics::MetricsController;
+use wordmesh_backend::controller::preferences::PreferencesController;
+use wordmesh_backend::controller::sense::SenseController;
+use wordmesh_backend::controller::word::WordController;
+use wordmesh_backend::middleware::{
+    DependencyTiming, GraphConcurrencyLimit, JsonFormat, RequestId, RequestMetrics, cors,
+    trailing_slash,
+};
+use wordmesh_backend::repository::{
+    CachedWordRepository, GraphRepository, InMemoryRateLimitStore, Neo4jGraphRepository,
+    NoopGraphRepository, PgPreferencesRepository, PgRefreshTokenStore, PgTokenStore,
+    PgUserRepository, PgWordRepository,
+};
+use wordmesh_backend::service::auth::AuthService;
+use wordmesh_backend::service::{
+    AdminService, AssocService, PreferencesService, SenseService, WordService,
+};
+use wordmesh_backend::util::AppError;
 
 #[actix_web::main]
 async fn main() -> Result<(), AppError> {
@@ -31,6 +41,13 @@ async fn main() -> Result<(), AppError> {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
+    // `--check-config`：只校验配置与依赖连通性，不启动服务
+    if std::env::args().skip(1).any(|arg| arg == "--check-config") {
+        let report = check::run(Settings::load()).await;
+        print!("{report}");
+        std::process::exit(if report.passed() { 0 } else { 1 });
+    }
+
     // Load configuration
     let settings = Arc::new(Settings::load().unwrap_or_else(|_| Settings::default()));
 
@@ -40,23 +57,150 @@ async fn main() -> Result<(), AppError> {
         settings.application.port
     );
 
-    // Start HTTP server
+    let pool = build_pg_pool(&settings.database);
+    if settings.features.graph_enabled {
+        let graph_repository = Neo4jGraphRepository::from_settings(&settings.neo4j)
+            .await
+            .expect("failed to initialize neo4j client");
+        match graph_repository.backfill_link_ids().await {
+            Ok(0) => {}
+            Ok(updated) => tracing::info!(updated, "backfilled missing link ids"),
+            Err(err) => tracing::warn!(error = %err, "failed to backfill link ids"),
+        }
+        serve(settings, pool, graph_repository).await
+    } else {
+        tracing::warn!("Graph subsystem disabled; Neo4j will not be contacted");
+        serve(settings, pool, NoopGraphRepository).await
+    }
+}
+
+async fn serve<G>(
+    settings: Arc<Settings>,
+    pool: sqlx::PgPool,
+    graph_repository: G,
+) -> Result<(), AppError>
+where
+    G: GraphRepository + Clone + Send + Sync + 'static,
+{
     let address = format!(
         "{}:{}",
         settings.application.host, settings.application.port
     );
+    let health_controller = web::Data::new(HealthController::new(pool.clone()));
+    let auth_controller = web::Data::new(build_auth_controller(&settings, pool.clone()));
+    let preferences_repository = PgPreferencesRepository::new(pool.clone());
+    let preferences_controller = web::Data::new(PreferencesController::new(
+        PreferencesService::new(preferences_repository.clone()),
+        auth_controller.token_config(),
+    ));
+    let word_repository = CachedWordRepository::new(
+        PgWordRepository::new(pool.clone())
+            .with_max_senses_per_word(settings.words.max_senses_per_word)
+            .with_max_senses(settings.words.max_senses),
+        settings
+            .features
+            .word_cache_enabled
+            .then_some(settings.words.cache_capacity),
+    );
+    let admin_controller = web::Data::new(AdminController::new(
+        AdminService::new(
+            PgUserRepository::new(pool),
+            Arc::new(word_repository.clone()),
+            Arc::new(graph_repository.clone()),
+        ),
+        auth_controller.token_config(),
+    ));
+    let word_controller = web::Data::new(
+        WordController::new(
+            WordService::new(word_repository.clone(), graph_repository.clone())
+                .with_preferences(Arc::new(preferences_repository))
+                .with_creation_rate_limit(
+                    Arc::new(InMemoryRateLimitStore::default()),
+                    settings.words.creation_rate_limit,
+                    Duration::from_secs(settings.words.creation_rate_window_secs),
+                )
+                .with_text_limits(settings.words.text_limits())
+                // 配置已在加载时校验，这里不会失败
+                .with_tag_rules(settings.words.tag_rules().expect("invalid tag rules"))
+                .with_canonical_mode(settings.words.canonical_mode)
+                .with_strict_limits(settings.words.strict_limits)
+                .with_reveal_ownership(settings.words.reveal_ownership)
+                .with_empty_query_behavior(settings.search.empty_query_behavior),
+            auth_controller.token_config(),
+        )
+        .with_link_service(AssocService::new(
+            word_repository.clone(),
+            graph_repository.clone(),
+            &settings.graph,
+        )),
+    );
+    let sense_controller = web::Data::new(SenseController::new(
+        SenseService::new(word_repository.clone(), graph_repository.clone())
+            .with_text_limits(settings.words.text_limits())
+            .with_max_senses(settings.words.max_senses)
+            .with_reveal_ownership(settings.words.reveal_ownership),
+        auth_controller.token_config(),
+    ));
+    let link_controller = web::Data::new(LinkController::new(
+        AssocService::new(word_repository, graph_repository, &settings.graph),
+        auth_controller.token_config(),
+    ));
+
+    // 配置已在加载时校验，这里不会失败
+    let request_id_header = settings
+        .application
+        .request_id_header_name()
+        .expect("invalid request id header");
+
+    // Start HTTP server
     let shared_settings = settings.clone();
+    let max_graph_queries = settings.graph.max_concurrent_queries;
+    let pretty_json = settings.application.pretty_json;
+    let metrics_enabled = settings.features.metrics_enabled;
     HttpServer::new(move || {
-        let auth_controller = web::Data::new(build_auth_controller(shared_settings.clone()));
         App::new()
+            .wrap(GraphConcurrencyLimit::new(max_graph_queries))
             .wrap(Logger::default())
-            .wrap(RequestId)
+            .wrap(DependencyTiming)
+            .wrap(Condition::new(metrics_enabled, RequestMetrics))
+            .wrap(JsonFormat::new(pretty_json))
+            .wrap(RequestId::new(request_id_header.clone()))
+            .wrap(trailing_slash(&shared_settings.application))
             .app_data(web::Data::new(shared_settings.clone()))
+            .configure(|cfg| {
+                if metrics_enabled {
+                    MetricsController::configure(cfg);
+                }
+            })
             .service(
                 web::scope("/api/v1")
-                    // Health check endpoint
-                    .route("/health", web::get().to(health_check))
-                    .configure(|cfg| AuthController::configure(cfg, auth_controller.clone())),
+                    // `/auth` 作用域带有自己的 CORS 策略，必须在通用策略的作用域之前注册
+                    .configure(|cfg| AuthController::configure(cfg, auth_controller.clone()))
+                    .service(
+                        web::scope("")
+                            .wrap(cors(&shared_settings.cors.general))
+                            .configure(|cfg| {
+                                HealthController::configure(cfg, health_controller.clone())
+                            })
+                            .configure(|cfg| {
+                                AdminController::configure(cfg, admin_controller.clone())
+                            })
+                            .configure(|cfg| {
+                                WordController::configure(cfg, word_controller.clone())
+                            })
+                            .configure(|cfg| {
+                                SenseController::configure(cfg, sense_controller.clone())
+                            })
+                            .configure(|cfg| {
+                                LinkController::configure(cfg, link_controller.clone())
+                            })
+                            .configure(|cfg| {
+                                PreferencesController::configure(
+                                    cfg,
+                                    preferences_controller.clone(),
+                                )
+                            }),
+                    ),
             )
     })
     .bind(address)
@@ -66,31 +210,26 @@ async fn main() -> Result<(), AppError> {
     .map_err(AppError::from)
 }
 
-fn build_auth_controller(settings: Arc<Settings>) -> AuthController<PgUserRepository> {
-    let db_settings = &settings.database;
-    let pool = sqlx::postgres::PgPoolOptions::new()
-        .max_connections(db_settings.pool_size)
-        .connect_lazy_with(db_settings.connect_options());
-    let repository = PgUserRepository::new(pool);
-    let auth_settings = &settings.auth;
-    let auth_service = AuthService::new(repository, auth_settings, &auth_settings.jwt)
-        .expect("failed to initialize auth service");
-    AuthController::new(auth_service)
+fn build_pg_pool(db_settings: &DatabaseSettings) -> sqlx::PgPool {
+    db_settings
+        .pool_options()
+        .connect_lazy_with(db_settings.connect_options())
 }
 
-async fn health_check() -> Result<actix_web::HttpResponse, AppError> {
-    #[derive(serde::Serialize)]
-    struct HealthStatus {
-        status: String,
-        service: String,
-        version: String,
-    }
-
-    let health_data = HealthStatus {
-        status: "healthy".to_string(),
-        service: "WordMesh Backend".to_string(),
-        version: env!("CARGO_PKG_VERSION").to_string(),
-    };
-
-    Ok(ResponseBuilder::ok(health_data)?)
+fn build_auth_controller(
+    settings: &Settings,
+    pool: sqlx::PgPool,
+) -> AuthController<PgUserRepository> {
+    let repository = PgUserRepository::new(pool.clone());
+    let refresh_tokens = Arc::new(PgRefreshTokenStore::new(pool.clone()));
+    let auth_settings = &settings.auth;
+    let auth_service = AuthService::new(
+        repository,
+        refresh_tokens,
+        auth_settings,
+        &auth_settings.jwt,
+    )
+    .expect("failed to initialize auth service")
+    .with_token_store(Arc::new(PgTokenStore::new(pool)));
+    AuthController::new(auth_service).with_cors(settings.cors.auth_policy().clone())
 }
@@ -1,7 +1,7 @@
 use std::future::{Future, Ready, ready};
 use std::sync::Arc;
 
-use crate::util::error::{AppError, AuthFlowError, BusinessError};
+use crate::util::error::{AppError, AuthFlowError, BusinessError, InternalError};
 use crate::util::token::{self, Claims, TokenConfig, TokenError};
 use actix_web::body::{BoxBody, EitherBody, MessageBody};
 use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
@@ -69,37 +69,66 @@ where
         self.service.borrow_mut().poll_ready(cx)
     }
 
-    fn call(&self, mut req: ServiceRequest) -> Self::Future {
+    fn call(&self, req: ServiceRequest) -> Self::Future {
         let token_config = self.token_config.clone();
 
-        match authenticate_request(req.request(), token_config.as_ref()) {
-            Ok((user, claims)) => {
-                req.extensions_mut().insert(AuthenticatedUser {
-                    user_id: user,
-                    scope: claims.scope.clone(),
-                    request_id: claims.request_id.clone(),
-                    claims,
-                });
-            }
+        let (user, claims) = match authenticate_request(req.request(), token_config.as_ref()) {
+            Ok(authenticated) => authenticated,
             Err(err) => {
                 let response = err.error_response().map_into_boxed_body();
                 let service_response = req.into_response(response).map_into_right_body();
                 return Box::pin(async move { Ok(service_response) });
             }
-        }
+        };
 
-        let fut = self.service.borrow_mut().call(req);
+        let service = self.service.clone();
         Box::pin(async move {
+            if let Err(err) = ensure_not_revoked(token_config.as_ref(), &claims).await {
+                let response = err.error_response().map_into_boxed_body();
+                return Ok(req.into_response(response).map_into_right_body());
+            }
+            req.extensions_mut().insert(AuthenticatedUser {
+                user_id: user,
+                scope: claims.scope.clone(),
+                request_id: claims.request_id.clone(),
+                claims,
+            });
+
+            let fut = service.borrow_mut().call(req);
             let response = fut.await?;
             Ok(response.map_into_left_body())
         })
     }
 }
 
+/// 黑名单不可用时拒绝请求，而不是放行可能已登出的令牌
+async fn ensure_not_revoked(token_config: &TokenConfig, claims: &Claims) -> Result<(), Error> {
+    let (Some(store), Some(jti)) = (&token_config.revocations, &claims.jti) else {
+        return Ok(());
+    };
+    match store.is_revoked(jti).await {
+        Ok(false) => Ok(()),
+        Ok(true) => Err(actix_web::Error::from(app_error(
+            AuthFlowError::TokenInvalid,
+        ))),
+        Err(err) => {
+            tracing::error!(error = %err, "token revocation check failed");
+            Err(actix_web::Error::from(AppError::from(
+                InternalError::Unknown,
+            )))
+        }
+    }
+}
+
 fn authenticate_request(
     req: &HttpRequest,
     token_config: &TokenConfig,
 ) -> Result<(i64, Claims), Error> {
+    if !token_config.auth_enabled {
+        return Err(actix_web::Error::from(app_error(
+            AuthFlowError::AuthDisabled,
+        )));
+    }
     let bearer = extract_bearer_token(req)?;
     let claims = validate_access_token(token_config, bearer)?;
     let user_id = claims
@@ -119,8 +148,9 @@ fn extract_bearer_token(req: &HttpRequest) -> Result<&str, Error> {
         .to_str()
         .map_err(|_| actix_web::Error::from(app_error(AuthFlowError::InvalidCredentials)))?;
 
+    // 容忍令牌首尾的空白；令牌内部的空白仍会在解码时失败
     if let Some(token) = header_str.strip_prefix("Bearer ") {
-        Ok(token)
+        Ok(token.trim())
     } else {
         Err(actix_web::Error::from(app_error(
             AuthFlowError::InvalidCredentials,
@@ -130,6 +160,10 @@ fn extract_bearer_token(req: &HttpRequest) -> Result<&str, Error> {
 
 fn validate_access_token(config: &TokenConfig, token: &str) -> Result<Claims, Error> {
     match token::validate_token(config, token) {
+        // refresh token 只能用于 /auth/refresh：有效期长，且家族吊销不会作用到这里
+        Ok(claims) if claims.family.is_some() => Err(actix_web::Error::from(app_error(
+            AuthFlowError::TokenInvalid,
+        ))),
         Ok(claims) => Ok(claims),
         Err(TokenError::Decode(err)) => {
             let flow_error = if matches!(
@@ -193,6 +227,8 @@ mod tests {
             encoding_key: jsonwebtoken::EncodingKey::from_secret(secret),
             decoding_key: jsonwebtoken::DecodingKey::from_secret(secret),
             issuer: Some("wordmesh".into()),
+            auth_enabled: true,
+            revocations: None,
         })
     }
 
@@ -220,6 +256,53 @@ mod tests {
         assert!(resp.status().is_success());
     }
 
+    #[actix_rt::test]
+    async fn guard_trims_whitespace_around_the_token_only() {
+        let config = test_config();
+        let token = token::generate_access_token(&config, "42", None, None).unwrap();
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthGuard::new(config.clone()))
+                .default_service(web::to(|| async { HttpResponse::Ok().finish() })),
+        )
+        .await;
+
+        let call = |header_value: String| {
+            test::TestRequest::get()
+                .uri("/protected")
+                .insert_header((header::AUTHORIZATION, header_value))
+                .to_request()
+        };
+        let resp = test::call_service(&app, call(format!("Bearer   {token}  "))).await;
+        assert_eq!(resp.status(), actix_web::http::StatusCode::OK);
+        let body = test::read_body(resp).await;
+        assert!(body.is_empty());
+
+        let (head, tail) = token.split_at(10);
+        let body: serde_json::Value =
+            test::call_and_read_body_json(&app, call(format!("Bearer {head} {tail}"))).await;
+        assert_eq!(body["code"], 4013);
+    }
+
+    #[actix_rt::test]
+    async fn guard_rejects_refresh_token_as_bearer() {
+        let config = test_config();
+        let token = token::generate_refresh_token(&config, "42", None, None).unwrap();
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthGuard::new(config.clone()))
+                .default_service(web::to(|| async { HttpResponse::Ok().finish() })),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/protected")
+            .insert_header((header::AUTHORIZATION, format!("Bearer {token}")))
+            .to_request();
+        let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body["code"], 4013);
+    }
+
     #[actix_rt::test]
     async fn guard_rejects_missing_token() {
         let config = test_config();
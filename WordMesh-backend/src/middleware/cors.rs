@@ -0,0 +1,103 @@
+use actix_cors::Cors;
+use actix_web::middleware::Condition;
+
+use crate::config::settings::CorsPolicy;
+
+/// 按策略构造 CORS 中间件；未配置任何来源时不启用。来源不匹配的普通请求照常处理，
+/// 只是不带 CORS 响应头，由浏览器拦截，非浏览器客户端不受影响
+pub fn cors(policy: &CorsPolicy) -> Condition<Cors> {
+    let mut cors = Cors::default()
+        .allowed_methods(["GET", "POST", "PUT", "PATCH", "DELETE"])
+        .allow_any_header()
+        .max_age(policy.max_age_secs)
+        .block_on_origin_mismatch(false);
+    // `allowed_origin` 会覆盖 `allow_any_origin`，含 `*` 时不再逐个添加
+    if policy.allowed_origins.iter().any(|origin| origin == "*") {
+        cors = cors.allow_any_origin();
+    } else {
+        for origin in &policy.allowed_origins {
+            cors = cors.allowed_origin(origin);
+        }
+    }
+    if policy.allow_credentials {
+        cors = cors.supports_credentials();
+    }
+    Condition::new(!policy.allowed_origins.is_empty(), cors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::http::header::{
+        ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_ORIGIN,
+        ACCESS_CONTROL_REQUEST_METHOD, ORIGIN,
+    };
+    use actix_web::{App, HttpResponse, http::StatusCode, test, web};
+
+    fn policy(origins: &[&str], allow_credentials: bool) -> CorsPolicy {
+        CorsPolicy {
+            allowed_origins: origins.iter().map(|origin| origin.to_string()).collect(),
+            allow_credentials,
+            ..CorsPolicy::default()
+        }
+    }
+
+    #[actix_rt::test]
+    async fn cors_headers_follow_the_policy() {
+        let app = test::init_service(
+            App::new()
+                .wrap(cors(&policy(&["https://app.example.com"], true)))
+                .route("/", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/")
+            .insert_header((ORIGIN, "https://app.example.com"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(
+            res.headers().get(ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(),
+            "https://app.example.com"
+        );
+        assert_eq!(
+            res.headers().get(ACCESS_CONTROL_ALLOW_CREDENTIALS).unwrap(),
+            "true"
+        );
+
+        // 来源不匹配时请求照常处理，但不带 CORS 头；预检直接拒绝
+        let req = test::TestRequest::get()
+            .uri("/")
+            .insert_header((ORIGIN, "https://evil.example.com"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(res.headers().get(ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+
+        let req = test::TestRequest::default()
+            .method(actix_web::http::Method::OPTIONS)
+            .uri("/")
+            .insert_header((ORIGIN, "https://evil.example.com"))
+            .insert_header((ACCESS_CONTROL_REQUEST_METHOD, "GET"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_rt::test]
+    async fn empty_policy_leaves_responses_untouched() {
+        let app = test::init_service(
+            App::new()
+                .wrap(cors(&CorsPolicy::default()))
+                .route("/", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+        let req = test::TestRequest::get()
+            .uri("/")
+            .insert_header((ORIGIN, "https://app.example.com"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(res.headers().get(ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+    }
+}
@@ -0,0 +1,136 @@
+use std::future::{Ready, ready};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Instant;
+
+use actix_web::Error;
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready};
+
+use crate::util::response::REQUEST_ID;
+use crate::util::timing::{DEPENDENCY_TIMINGS, Dependency, DependencyTimings};
+
+/// 在请求结束时输出 Postgres / Neo4j 的累计耗时与总耗时，便于定位慢请求的瓶颈。
+/// 需注册在 `RequestId` 之内，日志才能带上 Request-Id
+#[derive(Clone, Copy, Default)]
+pub struct DependencyTiming;
+
+impl<S, B> Transform<S, ServiceRequest> for DependencyTiming
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = DependencyTimingMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(DependencyTimingMiddleware { service }))
+    }
+}
+
+pub struct DependencyTimingMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for DependencyTimingMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future =
+        Pin<Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + 'static>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let method = req.method().clone();
+        let path = req.path().to_string();
+        let timings = Arc::new(DependencyTimings::default());
+        let started = Instant::now();
+        let fut = DEPENDENCY_TIMINGS.scope(timings.clone(), self.service.call(req));
+
+        Box::pin(async move {
+            let result = fut.await;
+            let request_id = REQUEST_ID.try_with(|id| id.clone()).unwrap_or_default();
+            tracing::info!(
+                request_id = %request_id,
+                method = %method,
+                path = %path,
+                pg_ms = timings.total(Dependency::Postgres).as_millis() as u64,
+                neo4j_ms = timings.total(Dependency::Neo4j).as_millis() as u64,
+                total_ms = started.elapsed().as_millis() as u64,
+                "request timings"
+            );
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::RequestId;
+    use crate::util::test_support::capture_logs;
+    use crate::util::timing::timed;
+    use actix_web::{App, HttpResponse, test, web};
+    use std::time::Duration;
+
+    #[actix_rt::test]
+    async fn logs_time_spent_in_each_store() {
+        let logs = capture_logs();
+
+        let app = test::init_service(
+            App::new()
+                .wrap(DependencyTiming)
+                .wrap(RequestId::default())
+                .route(
+                    "/both",
+                    web::get().to(|| async {
+                        timed(
+                            Dependency::Postgres,
+                            tokio::time::sleep(Duration::from_millis(20)),
+                        )
+                        .await;
+                        timed(
+                            Dependency::Neo4j,
+                            tokio::time::sleep(Duration::from_millis(30)),
+                        )
+                        .await;
+                        HttpResponse::Ok().finish()
+                    }),
+                ),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/both")
+            .insert_header(("X-Request-Id", "timing-1"))
+            .to_request();
+        test::call_service(&app, req).await;
+
+        let output = logs.output();
+        let line = output
+            .lines()
+            .find(|line| line.contains("request timings"))
+            .expect("timing line logged");
+        assert!(line.contains("request_id=timing-1"), "{line}");
+        let field = |name: &str| -> u64 {
+            let start = line.find(&format!("{name}=")).expect(name) + name.len() + 1;
+            line[start..]
+                .split(|c: char| !c.is_ascii_digit())
+                .next()
+                .unwrap()
+                .parse()
+                .unwrap()
+        };
+        assert!(field("pg_ms") >= 20, "{line}");
+        assert!(field("neo4j_ms") >= 30, "{line}");
+        assert!(field("total_ms") >= 50, "{line}");
+    }
+}
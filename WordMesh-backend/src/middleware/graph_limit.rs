@@ -0,0 +1,111 @@
+use std::future::{Ready, ready};
+use std::pin::Pin;
+use std::sync::Arc;
+
+use actix_web::Error;
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready};
+use tokio::sync::Semaphore;
+
+use crate::util::graph_limit::GRAPH_PERMITS;
+
+/// 为每个请求创建独立的图库并发许可，限制单个请求内同时进行的图库调用数
+#[derive(Clone, Copy)]
+pub struct GraphConcurrencyLimit {
+    max_concurrent: usize,
+}
+
+impl GraphConcurrencyLimit {
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            max_concurrent: max_concurrent.max(1),
+        }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for GraphConcurrencyLimit
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = GraphConcurrencyLimitMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(GraphConcurrencyLimitMiddleware {
+            service,
+            max_concurrent: self.max_concurrent,
+        }))
+    }
+}
+
+pub struct GraphConcurrencyLimitMiddleware<S> {
+    service: S,
+    max_concurrent: usize,
+}
+
+impl<S, B> Service<ServiceRequest> for GraphConcurrencyLimitMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future =
+        Pin<Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + 'static>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let permits = Arc::new(Semaphore::new(self.max_concurrent));
+        Box::pin(GRAPH_PERMITS.scope(permits, self.service.call(req)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::graph_limit::limited;
+    use actix_web::{App, HttpResponse, test, web};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[actix_rt::test]
+    async fn handler_fan_out_is_bounded_per_request() {
+        let peak = Arc::new(AtomicUsize::new(0));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let query = {
+            let (peak, in_flight) = (peak.clone(), in_flight.clone());
+            move || {
+                let (peak, in_flight) = (peak.clone(), in_flight.clone());
+                limited(async move {
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                })
+            }
+        };
+        let app = test::init_service(App::new().wrap(GraphConcurrencyLimit::new(2)).route(
+            "/fan-out",
+            web::get().to(move || {
+                let query = query.clone();
+                async move {
+                    tokio::join!(query(), query(), query(), query());
+                    HttpResponse::Ok().finish()
+                }
+            }),
+        ))
+        .await;
+
+        let resp =
+            test::call_service(&app, test::TestRequest::get().uri("/fan-out").to_request()).await;
+
+        assert!(resp.status().is_success());
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,95 @@
+use std::future::{Ready, ready};
+use std::pin::Pin;
+
+use actix_web::Error;
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready};
+
+use crate::util::response::PRETTY_JSON;
+
+/// 按 `application.pretty_json` 决定本请求的响应体是否缩进输出；
+/// 只影响经 `ResponseBuilder` 与 `AppError` 构建的统一结构响应
+#[derive(Clone, Copy)]
+pub struct JsonFormat {
+    pretty: bool,
+}
+
+impl JsonFormat {
+    pub fn new(pretty: bool) -> Self {
+        Self { pretty }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for JsonFormat
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = JsonFormatMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(JsonFormatMiddleware {
+            service,
+            pretty: self.pretty,
+        }))
+    }
+}
+
+pub struct JsonFormatMiddleware<S> {
+    service: S,
+    pretty: bool,
+}
+
+impl<S, B> Service<ServiceRequest> for JsonFormatMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future =
+        Pin<Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + 'static>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        Box::pin(PRETTY_JSON.scope(self.pretty, self.service.call(req)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::ResponseBuilder;
+    use actix_web::{App, test, web};
+
+    async fn body_for(pretty: bool) -> String {
+        let app = test::init_service(App::new().wrap(JsonFormat::new(pretty)).route(
+            "/ok",
+            web::get().to(|| async { ResponseBuilder::ok(serde_json::json!({ "word": "mesh" })) }),
+        ))
+        .await;
+        let resp = test::call_service(&app, test::TestRequest::get().uri("/ok").to_request()).await;
+        assert_eq!(
+            resp.headers().get("content-type").unwrap(),
+            "application/json"
+        );
+        String::from_utf8(test::read_body(resp).await.to_vec()).unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn pretty_flag_indents_success_responses() {
+        let pretty = body_for(true).await;
+        assert!(pretty.contains("\n  \"code\": 2000"), "{pretty}");
+        assert!(pretty.contains("\n    \"word\": \"mesh\""), "{pretty}");
+
+        let compact = body_for(false).await;
+        assert!(!compact.contains('\n'));
+        assert!(compact.contains("\"code\":2000"));
+    }
+}
@@ -1,5 +1,19 @@
 pub mod auth_guard;
+pub mod cors;
+pub mod dependency_timing;
+pub mod graph_limit;
+pub mod json_format;
 pub mod request_id;
+pub mod request_metrics;
+pub mod require_scope;
+pub mod trailing_slash;
 
 pub use auth_guard::{AuthGuard, AuthenticatedUser};
-pub use request_id::RequestId;
+pub use cors::cors;
+pub use dependency_timing::DependencyTiming;
+pub use graph_limit::GraphConcurrencyLimit;
+pub use json_format::JsonFormat;
+pub use request_id::{DEFAULT_REQUEST_ID_HEADER, RequestId};
+pub use request_metrics::RequestMetrics;
+pub use require_scope::RequireScope;
+pub use trailing_slash::trailing_slash;
@@ -6,12 +6,29 @@ use actix_web::http::header::{HeaderName, HeaderValue};
 use actix_web::{Error, HttpMessage};
 use uuid::Uuid;
 
-/// 确保每个请求拥有 `X-Request-Id` 的中间件：
-/// - 若请求头包含 `X-Request-Id` 则复用，否则生成 UUID v4
+pub const DEFAULT_REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// 确保每个请求拥有 Request-Id 的中间件（头名默认 `X-Request-Id`，可通过配置替换）：
+/// - 若请求头包含该头则复用，否则生成 UUID v4
 /// - 将 Request-Id 写入请求扩展（extensions）便于下游读取
-/// - 将 `X-Request-Id` 写入响应头，便于前后端与日志关联
+/// - 将同名头写入响应头，便于前后端与日志关联
 /// - 通过 task-local 传递 Request-Id，便于响应构建等任意位置读取
-pub struct RequestId;
+#[derive(Clone)]
+pub struct RequestId {
+    header_name: HeaderName,
+}
+
+impl RequestId {
+    pub fn new(header_name: HeaderName) -> Self {
+        Self { header_name }
+    }
+}
+
+impl Default for RequestId {
+    fn default() -> Self {
+        Self::new(HeaderName::from_static(DEFAULT_REQUEST_ID_HEADER))
+    }
+}
 
 impl<S, B> Transform<S, ServiceRequest> for RequestId
 where
@@ -26,12 +43,16 @@ where
     type Future = Ready<Result<Self::Transform, Self::InitError>>;
 
     fn new_transform(&self, service: S) -> Self::Future {
-        ready(Ok(RequestIdMiddleware { service }))
+        ready(Ok(RequestIdMiddleware {
+            service,
+            header_name: self.header_name.clone(),
+        }))
     }
 }
 
 pub struct RequestIdMiddleware<S> {
     service: S,
+    header_name: HeaderName,
 }
 
 impl<S, B> Service<ServiceRequest> for RequestIdMiddleware<S>
@@ -48,7 +69,7 @@ where
     forward_ready!(service);
 
     fn call(&self, req: ServiceRequest) -> Self::Future {
-        let header_name = HeaderName::from_static("x-request-id");
+        let header_name = self.header_name.clone();
         let incoming = req
             .headers()
             .get(&header_name)
@@ -64,12 +85,12 @@ where
         // 在带有 Request-Id 的 task-local 作用域下执行下游服务
         Box::pin(async move {
             let result = crate::util::response::REQUEST_ID
-                .scope(incoming.clone(), async move { fut.await })
+                .scope(incoming.clone(), fut)
                 .await;
 
             match result {
                 Ok(mut res) => {
-                    // 确保在响应头中设置 `X-Request-Id`
+                    // 确保在响应头中回写 Request-Id
                     if let Ok(val) = HeaderValue::from_str(&incoming) {
                         res.headers_mut().insert(header_name, val);
                     }
@@ -80,3 +101,35 @@ where
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, HttpResponse, test, web};
+
+    #[actix_rt::test]
+    async fn custom_header_name_is_read_and_echoed() {
+        let app = test::init_service(
+            App::new()
+                .wrap(RequestId::new(HeaderName::from_static("x-correlation-id")))
+                .route(
+                    "/ping",
+                    web::get().to(|| async {
+                        let id = crate::util::response::REQUEST_ID.with(|id| id.clone());
+                        HttpResponse::Ok().body(id)
+                    }),
+                ),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/ping")
+            .insert_header(("X-Correlation-Id", "corr-123"))
+            .to_request();
+        let resp = test::call_service(&app, req).await;
+
+        assert_eq!(resp.headers().get("x-correlation-id").unwrap(), "corr-123");
+        assert!(resp.headers().get(DEFAULT_REQUEST_ID_HEADER).is_none());
+        assert_eq!(test::read_body(resp).await, "corr-123");
+    }
+}
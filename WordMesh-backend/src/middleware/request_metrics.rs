@@ -0,0 +1,98 @@
+use std::future::{Ready, ready};
+use std::pin::Pin;
+
+use actix_web::Error;
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready};
+
+use crate::util::metrics::metrics;
+
+/// 按方法、路由模板与 HTTP 状态统计请求数，供 `/metrics` 输出；
+/// 未命中任何路由的请求统一记为 `unmatched`
+#[derive(Clone, Copy, Default)]
+pub struct RequestMetrics;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestMetrics
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestMetricsMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestMetricsMiddleware { service }))
+    }
+}
+
+pub struct RequestMetricsMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestMetricsMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future =
+        Pin<Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>> + 'static>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let method = req.method().to_string();
+        // 路由表在注册时已确定，进入处理链之前即可取到模板
+        let path = req
+            .match_pattern()
+            .unwrap_or_else(|| "unmatched".to_string());
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            let result = fut.await;
+            let status = match &result {
+                Ok(res) => res.status(),
+                Err(err) => err.as_response_error().status_code(),
+            };
+            metrics().record_request(&method, &path, status.as_u16());
+            result
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, HttpResponse, test, web};
+
+    #[actix_rt::test]
+    async fn counts_requests_by_route_template() {
+        let app = test::init_service(
+            App::new()
+                .wrap(RequestMetrics)
+                .route("/metrics-test/{id}", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+        for uri in [
+            "/metrics-test/1",
+            "/metrics-test/2",
+            "/metrics-test-missing",
+        ] {
+            test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+        }
+
+        let text = metrics().render();
+        assert!(
+            text.contains(
+                "wordmesh_http_requests_total{method=\"GET\",path=\"/metrics-test/{id}\",status=\"200\"} 2"
+            ),
+            "{text}"
+        );
+        assert!(text.contains("path=\"unmatched\",status=\"404\""), "{text}");
+    }
+}
@@ -0,0 +1,149 @@
+use std::future::{Future, Ready, ready};
+use std::pin::Pin;
+use std::rc::Rc;
+
+use crate::middleware::AuthenticatedUser;
+use crate::util::error::{AppError, AuthFlowError, BusinessError};
+use actix_web::body::{BoxBody, EitherBody, MessageBody};
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::{Error, HttpMessage, ResponseError};
+
+/// 要求访问令牌携带指定 scope（空格分隔的多个 scope 中任一匹配即可）。
+/// 依赖 [`AuthGuard`](crate::middleware::AuthGuard) 注入的身份，需注册在其内层：
+/// `.wrap(RequireScope::new("admin")).wrap(AuthGuard::new(..))`
+#[derive(Clone)]
+pub struct RequireScope {
+    scope: &'static str,
+}
+
+impl RequireScope {
+    pub fn new(scope: &'static str) -> Self {
+        Self { scope }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequireScope
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B, BoxBody>>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RequireScopeMiddleware<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequireScopeMiddleware {
+            service: Rc::new(service),
+            scope: self.scope,
+        }))
+    }
+}
+
+pub struct RequireScopeMiddleware<S> {
+    service: Rc<S>,
+    scope: &'static str,
+}
+
+impl<S, B> Service<ServiceRequest> for RequireScopeMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B, BoxBody>>;
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+
+    fn poll_ready(
+        &self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let rejection = match req.extensions().get::<AuthenticatedUser>() {
+            None => Some(AuthFlowError::InvalidCredentials),
+            Some(user) if !has_scope(user.scope.as_deref(), self.scope) => {
+                Some(AuthFlowError::Forbidden)
+            }
+            Some(_) => None,
+        };
+        if let Some(flow_error) = rejection {
+            let response = AppError::from(BusinessError::Auth(flow_error))
+                .error_response()
+                .map_into_boxed_body();
+            let service_response = req.into_response(response).map_into_right_body();
+            return Box::pin(async move { Ok(service_response) });
+        }
+
+        let fut = self.service.call(req);
+        Box::pin(async move {
+            let response = fut.await?;
+            Ok(response.map_into_left_body())
+        })
+    }
+}
+
+fn has_scope(granted: Option<&str>, required: &str) -> bool {
+    granted.is_some_and(|granted| granted.split_whitespace().any(|scope| scope == required))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::AuthGuard;
+    use crate::util::token::{self, TokenConfig};
+    use actix_web::http::header;
+    use actix_web::{App, HttpResponse, test, web};
+    use std::sync::Arc;
+
+    fn test_config() -> Arc<TokenConfig> {
+        let secret = b"scope-secret-scope-secret";
+        Arc::new(TokenConfig {
+            algorithm: jsonwebtoken::Algorithm::HS256,
+            access_ttl_secs: 60,
+            refresh_ttl_secs: None,
+            encoding_key: jsonwebtoken::EncodingKey::from_secret(secret),
+            decoding_key: jsonwebtoken::DecodingKey::from_secret(secret),
+            issuer: None,
+            auth_enabled: true,
+            revocations: None,
+        })
+    }
+
+    async fn call_with_scope(scope: Option<&str>) -> serde_json::Value {
+        let config = test_config();
+        let app = test::init_service(
+            App::new().service(
+                web::resource("/admin")
+                    .wrap(RequireScope::new("admin"))
+                    .wrap(AuthGuard::new(config.clone()))
+                    .route(web::get().to(|| async {
+                        HttpResponse::Ok().json(serde_json::json!({ "code": 2000 }))
+                    })),
+            ),
+        )
+        .await;
+
+        let token =
+            token::generate_access_token(&config, "1", scope.map(str::to_string), None).unwrap();
+        let req = test::TestRequest::get()
+            .uri("/admin")
+            .insert_header((header::AUTHORIZATION, format!("Bearer {}", token)))
+            .to_request();
+        test::call_and_read_body_json(&app, req).await
+    }
+
+    #[actix_rt::test]
+    async fn rejects_token_without_required_scope() {
+        assert_eq!(call_with_scope(None).await["code"], 4031);
+        assert_eq!(call_with_scope(Some("user")).await["code"], 4031);
+    }
+
+    #[actix_rt::test]
+    async fn accepts_token_listing_required_scope() {
+        assert_eq!(call_with_scope(Some("user admin")).await["code"], 2000);
+    }
+}
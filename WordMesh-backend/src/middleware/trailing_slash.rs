@@ -0,0 +1,53 @@
+use actix_web::middleware::{Condition, NormalizePath};
+
+use crate::config::settings::ApplicationSettings;
+
+/// 按 `application.trim_trailing_slash` 去掉路径末尾的 `/`；
+/// 需作为 App 最外层中间件注册，确保在路由匹配之前执行
+pub fn trailing_slash(settings: &ApplicationSettings) -> Condition<NormalizePath> {
+    Condition::new(settings.trim_trailing_slash, NormalizePath::trim())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{App, HttpResponse, http::StatusCode, test, web};
+
+    fn settings(trim_trailing_slash: bool) -> ApplicationSettings {
+        ApplicationSettings {
+            host: "127.0.0.1".into(),
+            port: 0,
+            trim_trailing_slash,
+            request_id_header: "x-request-id".into(),
+            pretty_json: false,
+        }
+    }
+
+    async fn status_for(trim_trailing_slash: bool, uri: &str) -> StatusCode {
+        let app = test::init_service(
+            App::new()
+                .wrap(trailing_slash(&settings(trim_trailing_slash)))
+                .service(web::scope("/api/v1").route(
+                    "/health",
+                    web::get().to(|| async { HttpResponse::Ok().finish() }),
+                )),
+        )
+        .await;
+        let req = test::TestRequest::get().uri(uri).to_request();
+        test::call_service(&app, req).await.status()
+    }
+
+    #[actix_rt::test]
+    async fn trailing_slash_is_tolerated_inside_scope() {
+        assert_eq!(status_for(true, "/api/v1/health/").await, StatusCode::OK);
+        assert_eq!(status_for(true, "/api/v1/health").await, StatusCode::OK);
+    }
+
+    #[actix_rt::test]
+    async fn trailing_slash_is_strict_when_disabled() {
+        assert_eq!(
+            status_for(false, "/api/v1/health/").await,
+            StatusCode::NOT_FOUND
+        );
+    }
+}
@@ -0,0 +1,502 @@
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use hashlink::LruCache;
+
+use crate::domain::CanonicalKey;
+use crate::domain::word::UserSense;
+use crate::repository::word::{
+    ImportUserWord, ImportedUserWord, NearDuplicatePair, NewUserSense, OwnedResource, SearchMatch,
+    SearchParams, SenseOrder, SenseReorder, SenseUpdate, TagAction, UpsertUserWord,
+    UpsertedUserWord, UserWordAggregate, WordChanges, WordRecord, WordRepoint, WordRepository,
+    WordRepositoryError,
+};
+
+type CacheKey = (i64, String);
+
+/// 在 `find_user_word_by_canonical` 前加一层有界 LRU 缓存
+///
+/// 任何可能影响缓存条目的写操作完成后（无论成败）都会失效对应条目；无法精确定位时按用户整体失效，
+/// 宁可多查一次数据库也不返回过期数据。克隆实例共享同一份缓存。
+#[derive(Clone)]
+pub struct CachedWordRepository<R> {
+    inner: R,
+    cache: Option<Arc<Mutex<LruCache<CacheKey, UserWordAggregate>>>>,
+}
+
+impl<R> CachedWordRepository<R>
+where
+    R: WordRepository + Send + Sync,
+{
+    /// `capacity` 为 None 时不启用缓存，所有调用直接透传
+    pub fn new(inner: R, capacity: Option<usize>) -> Self {
+        Self {
+            inner,
+            cache: capacity.map(|capacity| Arc::new(Mutex::new(LruCache::new(capacity)))),
+        }
+    }
+
+    fn invalidate_where(&self, predicate: impl Fn(&CacheKey, &UserWordAggregate) -> bool) {
+        let Some(cache) = &self.cache else {
+            return;
+        };
+        let mut cache = cache.lock().unwrap();
+        let stale: Vec<CacheKey> = cache
+            .iter()
+            .filter(|(key, value)| predicate(key, value))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in stale {
+            cache.remove(&key);
+        }
+    }
+
+    fn invalidate_user(&self, user_id: i64) {
+        self.invalidate_where(|(owner, _), _| *owner == user_id);
+    }
+}
+
+#[async_trait]
+impl<R> WordRepository for CachedWordRepository<R>
+where
+    R: WordRepository + Send + Sync,
+{
+    async fn upsert_word(
+        &self,
+        canonical: &CanonicalKey,
+        text: &str,
+    ) -> Result<WordRecord, WordRepositoryError> {
+        // 单词文本对所有用户共享
+        let result = self.inner.upsert_word(canonical, text).await;
+        self.invalidate_where(|(_, key), _| key == canonical.as_str());
+        result
+    }
+
+    async fn upsert_user_word(
+        &self,
+        payload: UpsertUserWord,
+    ) -> Result<UpsertedUserWord, WordRepositoryError> {
+        let user_id = payload.user_id;
+        let canonical = payload.canonical_key.as_str().to_string();
+        let result = self.inner.upsert_user_word(payload).await;
+        self.invalidate_where(|(owner, key), _| *owner == user_id && *key == canonical);
+        result
+    }
+
+    async fn import_user_words(
+        &self,
+        items: Vec<ImportUserWord>,
+    ) -> Result<Vec<Result<ImportedUserWord, WordRepositoryError>>, WordRepositoryError> {
+        // 导入会改写共享的单词文本，与 upsert_word 一样按 canonical_key 失效所有用户的缓存
+        let canonicals: Vec<String> = items
+            .iter()
+            .map(|item| item.word.canonical_key.as_str().to_string())
+            .collect();
+        let result = self.inner.import_user_words(items).await;
+        self.invalidate_where(|(_, key), _| canonicals.contains(key));
+        result
+    }
+
+    async fn find_user_word(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        sense_order: SenseOrder,
+    ) -> Result<Option<UserWordAggregate>, WordRepositoryError> {
+        self.inner
+            .find_user_word(user_id, user_word_id, sense_order)
+            .await
+    }
+
+    async fn find_user_word_by_canonical(
+        &self,
+        user_id: i64,
+        canonical: &CanonicalKey,
+    ) -> Result<Option<UserWordAggregate>, WordRepositoryError> {
+        let Some(cache) = &self.cache else {
+            return self
+                .inner
+                .find_user_word_by_canonical(user_id, canonical)
+                .await;
+        };
+        let key = (user_id, canonical.as_str().to_string());
+        if let Some(hit) = cache.lock().unwrap().get(&key) {
+            return Ok(Some(hit.clone()));
+        }
+
+        let found = self
+            .inner
+            .find_user_word_by_canonical(user_id, canonical)
+            .await?;
+        if let Some(aggregate) = &found {
+            cache.lock().unwrap().insert(key, aggregate.clone());
+        }
+        Ok(found)
+    }
+
+    async fn remove_user_word(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+    ) -> Result<(), WordRepositoryError> {
+        let result = self.inner.remove_user_word(user_id, user_word_id).await;
+        self.invalidate_where(|(owner, _), cached| {
+            *owner == user_id && cached.user_word.id == Some(user_word_id)
+        });
+        result
+    }
+
+    async fn add_user_sense(&self, sense: NewUserSense) -> Result<UserSense, WordRepositoryError> {
+        let user_word_id = sense.user_word_id;
+        let result = self.inner.add_user_sense(sense).await;
+        self.invalidate_where(|_, cached| cached.user_word.id == Some(user_word_id));
+        result
+    }
+
+    async fn update_user_sense(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+        update: SenseUpdate,
+    ) -> Result<UserSense, WordRepositoryError> {
+        // 设为主义项会影响同一单词的其他义项，按用户整体失效
+        let result = self
+            .inner
+            .update_user_sense(user_id, sense_id, update)
+            .await;
+        self.invalidate_user(user_id);
+        result
+    }
+
+    async fn remove_user_sense(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+    ) -> Result<UserSense, WordRepositoryError> {
+        let result = self.inner.remove_user_sense(user_id, sense_id).await;
+        self.invalidate_user(user_id);
+        result
+    }
+
+    async fn set_primary_sense(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+    ) -> Result<UserSense, WordRepositoryError> {
+        let result = self.inner.set_primary_sense(user_id, sense_id).await;
+        self.invalidate_user(user_id);
+        result
+    }
+
+    async fn search(
+        &self,
+        params: SearchParams,
+    ) -> Result<Vec<(UserWordAggregate, Option<SearchMatch>)>, WordRepositoryError> {
+        self.inner.search(params).await
+    }
+
+    async fn count_search(&self, params: &SearchParams) -> Result<i64, WordRepositoryError> {
+        self.inner.count_search(params).await
+    }
+
+    async fn search_word_ids(
+        &self,
+        params: &SearchParams,
+    ) -> Result<Vec<i64>, WordRepositoryError> {
+        self.inner.search_word_ids(params).await
+    }
+
+    async fn find_sense_word_id(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+    ) -> Result<Option<i64>, WordRepositoryError> {
+        self.inner.find_sense_word_id(user_id, sense_id).await
+    }
+
+    async fn find_user_word_by_sense(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+        sense_order: SenseOrder,
+    ) -> Result<Option<UserWordAggregate>, WordRepositoryError> {
+        self.inner
+            .find_user_word_by_sense(user_id, sense_id, sense_order)
+            .await
+    }
+
+    async fn find_owner(
+        &self,
+        resource: OwnedResource,
+    ) -> Result<Option<i64>, WordRepositoryError> {
+        self.inner.find_owner(resource).await
+    }
+
+    async fn count_owned_user_words(
+        &self,
+        user_id: i64,
+        user_word_ids: &[i64],
+    ) -> Result<i64, WordRepositoryError> {
+        self.inner
+            .count_owned_user_words(user_id, user_word_ids)
+            .await
+    }
+
+    async fn existing_canonical_keys(
+        &self,
+        user_id: i64,
+        keys: &[CanonicalKey],
+    ) -> Result<Vec<CanonicalKey>, WordRepositoryError> {
+        self.inner.existing_canonical_keys(user_id, keys).await
+    }
+
+    async fn find_near_duplicate_words(
+        &self,
+        user_id: i64,
+        threshold: f32,
+        limit: i64,
+    ) -> Result<Vec<NearDuplicatePair>, WordRepositoryError> {
+        self.inner
+            .find_near_duplicate_words(user_id, threshold, limit)
+            .await
+    }
+
+    async fn find_words_by_ids(
+        &self,
+        word_ids: &[i64],
+    ) -> Result<Vec<WordRecord>, WordRepositoryError> {
+        self.inner.find_words_by_ids(word_ids).await
+    }
+
+    async fn find_user_words_by_word_ids(
+        &self,
+        user_id: i64,
+        word_ids: &[i64],
+    ) -> Result<Vec<UserWordAggregate>, WordRepositoryError> {
+        self.inner
+            .find_user_words_by_word_ids(user_id, word_ids)
+            .await
+    }
+
+    async fn list_user_words_after(
+        &self,
+        user_id: i64,
+        after: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<UserWordAggregate>, WordRepositoryError> {
+        self.inner
+            .list_user_words_after(user_id, after, limit)
+            .await
+    }
+
+    async fn find_words_in_range(
+        &self,
+        user_id: i64,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<UserWordAggregate>, WordRepositoryError> {
+        self.inner
+            .find_words_in_range(user_id, from, to, limit, offset)
+            .await
+    }
+
+    async fn find_word_ids_in_range(
+        &self,
+        user_id: i64,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<i64>, WordRepositoryError> {
+        self.inner.find_word_ids_in_range(user_id, from, to).await
+    }
+
+    async fn count_words_in_range(
+        &self,
+        user_id: i64,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<i64, WordRepositoryError> {
+        self.inner.count_words_in_range(user_id, from, to).await
+    }
+
+    async fn list_changes_since(
+        &self,
+        user_id: i64,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<WordChanges, WordRepositoryError> {
+        self.inner.list_changes_since(user_id, since, limit).await
+    }
+
+    async fn update_tag(
+        &self,
+        user_id: i64,
+        user_word_ids: &[i64],
+        tag: &str,
+        action: TagAction,
+    ) -> Result<u64, WordRepositoryError> {
+        let result = self
+            .inner
+            .update_tag(user_id, user_word_ids, tag, action)
+            .await;
+        self.invalidate_where(|(owner, _), cached| {
+            *owner == user_id
+                && cached
+                    .user_word
+                    .id
+                    .is_some_and(|id| user_word_ids.contains(&id))
+        });
+        result
+    }
+
+    async fn replace_tags(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        tags: &[String],
+    ) -> Result<bool, WordRepositoryError> {
+        let result = self.inner.replace_tags(user_id, user_word_id, tags).await;
+        self.invalidate_where(|(owner, _), cached| {
+            *owner == user_id && cached.user_word.id == Some(user_word_id)
+        });
+        result
+    }
+
+    async fn update_user_word_metadata(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        tags: &[String],
+        note: Option<&str>,
+    ) -> Result<bool, WordRepositoryError> {
+        let result = self
+            .inner
+            .update_user_word_metadata(user_id, user_word_id, tags, note)
+            .await;
+        self.invalidate_where(|(owner, _), cached| {
+            *owner == user_id && cached.user_word.id == Some(user_word_id)
+        });
+        result
+    }
+
+    async fn repoint_user_word(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        word_id: i64,
+    ) -> Result<WordRepoint, WordRepositoryError> {
+        let result = self
+            .inner
+            .repoint_user_word(user_id, user_word_id, word_id)
+            .await;
+        // 缓存按 canonical_key 组织，改挂后旧键与新键都可能失效
+        self.invalidate_user(user_id);
+        result
+    }
+
+    async fn pending_word_repoint(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+    ) -> Result<Option<i64>, WordRepositoryError> {
+        self.inner.pending_word_repoint(user_id, user_word_id).await
+    }
+
+    async fn complete_word_repoint(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        previous_word_id: i64,
+    ) -> Result<(), WordRepositoryError> {
+        self.inner
+            .complete_word_repoint(user_id, user_word_id, previous_word_id)
+            .await
+    }
+
+    async fn reorder_senses(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        sense_ids: &[i64],
+    ) -> Result<SenseReorder, WordRepositoryError> {
+        let result = self
+            .inner
+            .reorder_senses(user_id, user_word_id, sense_ids)
+            .await;
+        self.invalidate_where(|(owner, _), cached| {
+            *owner == user_id && cached.user_word.id == Some(user_word_id)
+        });
+        result
+    }
+
+    async fn repair_primaries(&self, user_id: i64) -> Result<u64, WordRepositoryError> {
+        let result = self.inner.repair_primaries(user_id).await;
+        self.invalidate_where(|(owner, _), _| *owner == user_id);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::memory::InMemoryWordRepository;
+
+    fn payload(note: Option<&str>) -> UpsertUserWord {
+        UpsertUserWord {
+            user_id: 1,
+            word_text: "apple".into(),
+            canonical_key: CanonicalKey::new("apple").unwrap(),
+            tags: vec![],
+            note: note.map(str::to_string),
+        }
+    }
+
+    async fn cached_note(repo: &CachedWordRepository<InMemoryWordRepository>) -> Option<String> {
+        repo.find_user_word_by_canonical(1, &CanonicalKey::new("apple").unwrap())
+            .await
+            .unwrap()
+            .unwrap()
+            .user_word
+            .note()
+            .map(str::to_string)
+    }
+
+    #[tokio::test]
+    async fn second_lookup_hits_cache_until_write_invalidates() {
+        let inner = InMemoryWordRepository::default();
+        let repo = CachedWordRepository::new(inner.clone(), Some(16));
+        repo.upsert_user_word(payload(Some("first"))).await.unwrap();
+        assert_eq!(cached_note(&repo).await.as_deref(), Some("first"));
+
+        // 绕过缓存直接写底层仓储：命中缓存时仍返回旧值
+        inner
+            .upsert_user_word(payload(Some("bypass")))
+            .await
+            .unwrap();
+        assert_eq!(cached_note(&repo).await.as_deref(), Some("first"));
+
+        repo.upsert_user_word(payload(Some("second")))
+            .await
+            .unwrap();
+        assert_eq!(cached_note(&repo).await.as_deref(), Some("second"));
+    }
+
+    #[tokio::test]
+    async fn remove_invalidates_cached_entry() {
+        let repo = CachedWordRepository::new(InMemoryWordRepository::default(), Some(16));
+        let added = repo.upsert_user_word(payload(None)).await.unwrap();
+        cached_note(&repo).await;
+
+        repo.remove_user_word(1, added.aggregate.user_word.id.unwrap())
+            .await
+            .unwrap();
+
+        let found = repo
+            .find_user_word_by_canonical(1, &CanonicalKey::new("apple").unwrap())
+            .await
+            .unwrap();
+        assert!(found.is_none());
+    }
+}
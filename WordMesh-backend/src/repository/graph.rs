@@ -1,3 +1,4 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -5,9 +6,12 @@ use chrono::{DateTime, Utc};
 use neo4rs::{Graph, Node, Relation, query};
 use thiserror::Error;
 use tokio::time::{Duration, timeout};
+use uuid::Uuid;
 
 use crate::config::settings::Neo4jSettings;
 use crate::util::error::{BusinessError, LinkError};
+use crate::util::graph_limit::limited;
+use crate::util::timing::{Dependency, timed};
 
 pub type GraphResult<T> = Result<T, GraphRepositoryError>;
 
@@ -30,14 +34,16 @@ pub enum WordLinkKind {
 }
 
 impl WordLinkKind {
-    fn as_str(self) -> &'static str {
+    pub const ALL: [WordLinkKind; 2] = [WordLinkKind::SimilarForm, WordLinkKind::RootAffix];
+
+    pub fn as_str(self) -> &'static str {
         match self {
             WordLinkKind::SimilarForm => "similar_form",
             WordLinkKind::RootAffix => "root_affix",
         }
     }
 
-    fn try_from_str(value: &str) -> Option<Self> {
+    pub fn try_from_str(value: &str) -> Option<Self> {
         match value {
             "similar_form" => Some(Self::SimilarForm),
             "root_affix" => Some(Self::RootAffix),
@@ -54,7 +60,13 @@ pub enum SenseWordLinkKind {
 }
 
 impl SenseWordLinkKind {
-    fn as_str(self) -> &'static str {
+    pub const ALL: [SenseWordLinkKind; 3] = [
+        SenseWordLinkKind::Synonym,
+        SenseWordLinkKind::Antonym,
+        SenseWordLinkKind::Related,
+    ];
+
+    pub fn as_str(self) -> &'static str {
         match self {
             SenseWordLinkKind::Synonym => "synonym",
             SenseWordLinkKind::Antonym => "antonym",
@@ -62,7 +74,7 @@ impl SenseWordLinkKind {
         }
     }
 
-    fn try_from_str(value: &str) -> Option<Self> {
+    pub fn try_from_str(value: &str) -> Option<Self> {
         match value {
             "synonym" => Some(Self::Synonym),
             "antonym" => Some(Self::Antonym),
@@ -113,6 +125,62 @@ pub struct SenseLinkFilter {
     pub offset: i64,
 }
 
+#[derive(Debug, Clone)]
+pub struct NeighborQuery {
+    pub user_id: i64,
+    pub word_id: i64,
+    pub depth: u32,
+    /// 只沿该类型的关联展开；None 时不限类型
+    pub kind: Option<WordLinkKind>,
+    pub limit: i64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LinkCounts {
+    pub word_links: i64,
+    pub sense_links: i64,
+}
+
+/// 按类型统计的关联数；每种类型各占一项（没有关联时为 0），顺序同各自的 `ALL`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkKindStats {
+    pub word_links: Vec<(WordLinkKind, i64)>,
+    pub sense_links: Vec<(SenseWordLinkKind, i64)>,
+}
+
+impl Default for LinkKindStats {
+    fn default() -> Self {
+        Self {
+            word_links: WordLinkKind::ALL.iter().map(|kind| (*kind, 0)).collect(),
+            sense_links: SenseWordLinkKind::ALL
+                .iter()
+                .map(|kind| (*kind, 0))
+                .collect(),
+        }
+    }
+}
+
+impl LinkKindStats {
+    pub fn add_word_links(&mut self, kind: WordLinkKind, count: i64) {
+        if let Some(entry) = self.word_links.iter_mut().find(|(k, _)| *k == kind) {
+            entry.1 += count;
+        }
+    }
+
+    pub fn add_sense_links(&mut self, kind: SenseWordLinkKind, count: i64) {
+        if let Some(entry) = self.sense_links.iter_mut().find(|(k, _)| *k == kind) {
+            entry.1 += count;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Neighborhood {
+    pub word_ids: Vec<i64>,
+    pub links: Vec<WordLinkRecord>,
+    pub truncated: bool,
+}
+
 #[async_trait]
 pub trait GraphRepository: Send + Sync {
     async fn create_word_link(
@@ -124,16 +192,48 @@ pub trait GraphRepository: Send + Sync {
         note: Option<String>,
     ) -> GraphResult<WordLinkRecord>;
 
+    /// 返回实际删除的关系数；关系不存在时为 0，重复调用是安全的
     async fn delete_word_link(
         &self,
         user_id: i64,
         word_a_id: i64,
         word_b_id: i64,
         kind: WordLinkKind,
-    ) -> GraphResult<()>;
+    ) -> GraphResult<u64>;
 
+    /// 修改一条已有关联：`new_kind` 为 Some 时改为该类型（保留 link_id 与 created_at），
+    /// `note` 为 Some 时设置或清除备注；两项修改原子生效。关联不存在返回 TargetNotFound，
+    /// 新类型与两词之间的另一条关联重复时返回 Exists 且不做任何修改
+    async fn update_word_link(
+        &self,
+        user_id: i64,
+        word_a_id: i64,
+        word_b_id: i64,
+        old_kind: WordLinkKind,
+        new_kind: Option<WordLinkKind>,
+        note: Option<Option<String>>,
+    ) -> GraphResult<WordLinkRecord>;
+
+    /// `word_id` 作为任一端的关联，按创建时间倒序分页
     async fn list_word_links(&self, filter: WordLinkFilter) -> GraphResult<Vec<WordLinkRecord>>;
 
+    /// 该用户在两个单词之间的全部关联（不分方向），按创建时间升序
+    async fn get_word_links_between(
+        &self,
+        user_id: i64,
+        word_a_id: i64,
+        word_b_id: i64,
+    ) -> GraphResult<Vec<WordLinkRecord>>;
+
+    /// 以 `word_ids` 中的单词为较小 id 一端（word_a）的 WORD_TO_WORD 关联，按 id 对排序，最多 `limit` 条；
+    /// 每条关联只归属于一个单词，分批导出时不会重复
+    async fn list_word_links_from_words(
+        &self,
+        user_id: i64,
+        word_ids: &[i64],
+        limit: i64,
+    ) -> GraphResult<Vec<WordLinkRecord>>;
+
     async fn create_sense_word_link(
         &self,
         user_id: i64,
@@ -157,11 +257,51 @@ pub trait GraphRepository: Send + Sync {
         filter: SenseLinkFilter,
     ) -> GraphResult<Vec<SenseWordLinkRecord>>;
 
+    /// 一次查询取回多个义项的 SENSE_TO_WORD 关联，按义项分组、组内按创建时间倒序，最多 `limit` 条
+    async fn list_links_for_senses(
+        &self,
+        user_id: i64,
+        sense_ids: &[i64],
+        limit: i64,
+    ) -> GraphResult<Vec<SenseWordLinkRecord>>;
+
     async fn remove_links_for_sense(&self, sense_id: i64) -> GraphResult<()>;
 
+    /// Words reachable from `word_id` through the user's WORD_TO_WORD links within
+    /// `depth` hops, plus the links between them. At most `limit` words are returned.
+    async fn neighbors(&self, query: NeighborQuery) -> GraphResult<Neighborhood>;
+
+    /// 用户名下两类关联各自的总数
+    async fn total_link_count(&self, user_id: i64) -> GraphResult<LinkCounts>;
+
+    /// 用户名下按类型分组的关联数
+    async fn link_stats_by_kind(&self, user_id: i64) -> GraphResult<LinkKindStats>;
+
+    /// 每个单词的关联度：用户的 WORD_TO_WORD 边数，加上指向该单词以及从它的义项发出的 SENSE_TO_WORD 边数；
+    /// 没有任何关联的单词可能不出现在结果中
+    async fn word_degrees(&self, user_id: i64, word_ids: &[i64]) -> GraphResult<HashMap<i64, i64>>;
+
+    /// `word_ids` 中关联度（见 `word_degrees`）大于 0 的单词
+    async fn linked_word_ids(&self, user_id: i64, word_ids: &[i64]) -> GraphResult<HashSet<i64>>;
+
+    /// 合并单词时把用户在 `from_word_id` 上的全部关联改挂到 `to_word_id`：
+    /// WORD_TO_WORD 重新排序 id 对，与已有边重复时合并为一条并保留较早的 created_at；
+    /// SENSE_TO_WORD 的目标同样改挂，合并后变成自环的关联直接删除。整个过程在单个事务内完成
+    async fn repoint_word_links(
+        &self,
+        user_id: i64,
+        from_word_id: i64,
+        to_word_id: i64,
+    ) -> GraphResult<()>;
+
     async fn upsert_node_word(&self, word_id: i64) -> GraphResult<()>;
 
     async fn upsert_node_sense(&self, sense_id: i64, user_id: i64) -> GraphResult<()>;
+
+    /// 图子系统关闭时返回 false，调用方应跳过节点维护与清理
+    fn is_enabled(&self) -> bool {
+        true
+    }
 }
 
 #[derive(Clone)]
@@ -191,17 +331,32 @@ impl Neo4jGraphRepository {
     }
 
     async fn run_with_timeout(&self, query: neo4rs::Query) -> GraphResult<Vec<neo4rs::Row>> {
-        match timeout(self.timeout, self.graph.execute(query)).await {
-            Ok(Ok(mut result)) => {
-                let mut rows = Vec::new();
-                while let Ok(Some(row)) = result.next().await {
-                    rows.push(row);
+        self.run_with_row_limit(query, usize::MAX).await
+    }
+
+    /// Like `run_with_timeout`, but stops pulling rows once `max_rows` have been read.
+    async fn run_with_row_limit(
+        &self,
+        query: neo4rs::Query,
+        max_rows: usize,
+    ) -> GraphResult<Vec<neo4rs::Row>> {
+        limited(timed(Dependency::Neo4j, async {
+            match timeout(self.timeout, self.graph.execute(query)).await {
+                Ok(Ok(mut result)) => {
+                    let mut rows = Vec::new();
+                    while rows.len() < max_rows {
+                        match result.next().await {
+                            Ok(Some(row)) => rows.push(row),
+                            _ => break,
+                        }
+                    }
+                    Ok(rows)
                 }
-                Ok(rows)
+                Ok(Err(err)) => Err(GraphRepositoryError::Database(err)),
+                Err(_) => Err(GraphRepositoryError::Timeout),
             }
-            Ok(Err(err)) => Err(GraphRepositoryError::Database(err)),
-            Err(_) => Err(GraphRepositoryError::Timeout),
-        }
+        }))
+        .await
     }
 
     fn parse_word_link(row: neo4rs::Row) -> GraphResult<WordLinkRecord> {
@@ -218,7 +373,7 @@ impl Neo4jGraphRepository {
             GraphRepositoryError::InvalidData("missing kind field on relationship".into())
         })?;
         Ok(WordLinkRecord {
-            link_id: rel.id().to_string(),
+            link_id: Self::parse_link_id(&rel),
             user_id: rel.get("user_id").map_err(|_| {
                 GraphRepositoryError::InvalidData("missing user_id on relationship".into())
             })?,
@@ -250,7 +405,7 @@ impl Neo4jGraphRepository {
             GraphRepositoryError::InvalidData("missing kind on relationship".into())
         })?;
         Ok(SenseWordLinkRecord {
-            link_id: rel.id().to_string(),
+            link_id: Self::parse_link_id(&rel),
             user_id: sense_node.get("user_id").map_err(|_| {
                 GraphRepositoryError::InvalidData("missing user_id on sense".into())
             })?,
@@ -271,6 +426,47 @@ impl Neo4jGraphRepository {
         })
     }
 
+    /// 优先使用应用生成的 `link_id` 属性；Neo4j 内部 id 会在删除后复用且不随备份恢复保持，
+    /// 仅作为尚未回填的旧关系的兜底
+    fn parse_link_id(rel: &Relation) -> String {
+        match rel.get::<String>("link_id") {
+            Ok(link_id) => link_id,
+            Err(_) => {
+                tracing::warn!(
+                    rel_id = rel.id(),
+                    "relationship missing link_id, run backfill"
+                );
+                rel.id().to_string()
+            }
+        }
+    }
+
+    /// 执行 `RETURN 1` 确认能连上并完成一次查询
+    pub async fn ping(&self) -> GraphResult<()> {
+        let rows = self.run_with_timeout(query("RETURN 1 AS ok")).await?;
+        if rows.is_empty() {
+            return Err(GraphRepositoryError::InvalidData(
+                "ping returned no rows".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 为缺少 `link_id` 的历史关系补齐 UUID，返回更新的关系数
+    pub async fn backfill_link_ids(&self) -> GraphResult<i64> {
+        let backfill = query(
+            "MATCH ()-[r:WORD_TO_WORD|SENSE_TO_WORD]->()\nWHERE r.link_id IS NULL\nSET r.link_id = randomUUID()\nRETURN count(r) AS updated",
+        );
+        let rows = self.run_with_timeout(backfill).await?;
+        rows.into_iter()
+            .next()
+            .map(|row| {
+                row.get::<i64>("updated")
+                    .map_err(|_| GraphRepositoryError::InvalidData("missing backfill count".into()))
+            })
+            .unwrap_or(Ok(0))
+    }
+
     fn parse_datetime(rel: &Relation) -> GraphResult<DateTime<Utc>> {
         let dt = rel.get::<DateTime<Utc>>("created_at").map_err(|_| {
             GraphRepositoryError::InvalidData("missing created_at on relationship".into())
@@ -292,6 +488,14 @@ impl Neo4jGraphRepository {
     }
 }
 
+/// 每个 `$ids` 的关联度：WORD_TO_WORD 的任一端、SENSE_TO_WORD 的目标，以及自己的义项发出的 SENSE_TO_WORD；
+/// `word_degrees` 与 `linked_word_ids` 共用，保证“有关联”的口径一致
+const WORD_DEGREES: &str = "UNWIND $ids AS id\nOPTIONAL MATCH (:Word { word_id: id })-[r:WORD_TO_WORD { user_id: $user_id }]-()\nWITH id, count(r) AS word_links\nOPTIONAL MATCH ()-[i:SENSE_TO_WORD { user_id: $user_id }]->(:Word { word_id: id })\nWITH id, word_links, count(i) AS incoming\nOPTIONAL MATCH (:UserSense { user_id: $user_id, word_id: id })-[o:SENSE_TO_WORD { user_id: $user_id }]->()\nWITH id, word_links + incoming + count(o) AS degree";
+
+/// 服务层已按 Postgres 校验归属；图中记录的来源单词不一致说明数据已漂移，
+/// 由 `WITH ... WHERE` 在写关系之前截断，不留下孤立的边
+const CREATE_SENSE_WORD_LINK: &str = "MERGE (sense:UserSense { sense_id: $sense_id, user_id: $user_id })\nSET sense.word_id = coalesce(sense.word_id, $source_word_id)\nWITH sense WHERE sense.word_id = $source_word_id\nMERGE (target:Word { word_id: $target_word_id })\nMERGE (sense)-[rel:SENSE_TO_WORD { user_id: $user_id, kind: $kind }]->(target)\nON CREATE SET rel.created_at = datetime(), rel.note = $note, rel.link_id = $link_id\nON MATCH SET rel.note = CASE WHEN $note IS NULL THEN rel.note ELSE $note END, rel.link_id = coalesce(rel.link_id, $link_id)\nRETURN sense, target AS word, rel";
+
 #[async_trait]
 impl GraphRepository for Neo4jGraphRepository {
     async fn create_word_link(
@@ -303,9 +507,10 @@ impl GraphRepository for Neo4jGraphRepository {
         note: Option<String>,
     ) -> GraphResult<WordLinkRecord> {
         let (min_id, max_id) = Self::sort_word_ids(word_a_id, word_b_id)?;
-        let mut builder = query(
-            "MERGE (a:Word { word_id: $min_id })\nMERGE (b:Word { word_id: $max_id })\nMERGE (a)-[r:WORD_TO_WORD { user_id: $user_id, kind: $kind }]->(b)\nON CREATE SET r.created_at = datetime(), r.note = $note\nON MATCH SET r.note = CASE WHEN $note IS NULL THEN r.note ELSE $note END\nRETURN a AS word_a, b AS word_b, r AS rel",
+        let builder = query(
+            "MERGE (a:Word { word_id: $min_id })\nMERGE (b:Word { word_id: $max_id })\nMERGE (a)-[r:WORD_TO_WORD { user_id: $user_id, kind: $kind }]->(b)\nON CREATE SET r.created_at = datetime(), r.note = $note, r.link_id = $link_id\nON MATCH SET r.note = CASE WHEN $note IS NULL THEN r.note ELSE $note END, r.link_id = coalesce(r.link_id, $link_id)\nRETURN a AS word_a, b AS word_b, r AS rel",
         )
+        .param("link_id", Uuid::new_v4().to_string())
         .param("min_id", min_id)
         .param("max_id", max_id)
         .param("user_id", user_id)
@@ -331,21 +536,70 @@ impl GraphRepository for Neo4jGraphRepository {
         word_a_id: i64,
         word_b_id: i64,
         kind: WordLinkKind,
-    ) -> GraphResult<()> {
+    ) -> GraphResult<u64> {
         let (min_id, max_id) = Self::sort_word_ids(word_a_id, word_b_id)?;
         let query = query(
-            "MATCH (a:Word { word_id: $min_id })-[r:WORD_TO_WORD { user_id: $user_id, kind: $kind }]->(b:Word { word_id: $max_id })\nDELETE r",
+            "MATCH (a:Word { word_id: $min_id })-[r:WORD_TO_WORD { user_id: $user_id, kind: $kind }]->(b:Word { word_id: $max_id })\nDELETE r\nRETURN count(*) AS deleted",
         )
         .param("min_id", min_id)
         .param("max_id", max_id)
         .param("user_id", user_id)
         .param("kind", kind.as_str());
-        self.run_with_timeout(query).await.map(|_| ())
+        let rows = self.run_with_timeout(query).await?;
+        rows.into_iter()
+            .next()
+            .map(|row| {
+                row.get::<i64>("deleted")
+                    .map(|deleted| deleted.max(0) as u64)
+                    .map_err(|_| GraphRepositoryError::InvalidData("missing delete count".into()))
+            })
+            .unwrap_or(Ok(0))
+    }
+
+    async fn update_word_link(
+        &self,
+        user_id: i64,
+        word_a_id: i64,
+        word_b_id: i64,
+        old_kind: WordLinkKind,
+        new_kind: Option<WordLinkKind>,
+        note: Option<Option<String>>,
+    ) -> GraphResult<WordLinkRecord> {
+        let (min_id, max_id) = Self::sort_word_ids(word_a_id, word_b_id)?;
+        // 单条语句在一个事务内完成冲突检查与修改；有冲突时 FOREACH 不执行，原样返回
+        let builder = query(
+            "MATCH (a:Word { word_id: $min_id })-[r:WORD_TO_WORD { user_id: $user_id, kind: $old_kind }]->(b:Word { word_id: $max_id })\nOPTIONAL MATCH (a)-[c:WORD_TO_WORD { user_id: $user_id, kind: $new_kind }]->(b)\nWHERE c <> r\nWITH a, b, r, count(c) AS collisions\nFOREACH (_ IN CASE WHEN collisions = 0 THEN [1] ELSE [] END |\n  SET r.kind = $new_kind, r.note = CASE WHEN $set_note THEN $note ELSE r.note END)\nRETURN a AS word_a, b AS word_b, r AS rel, collisions",
+        )
+        .param("min_id", min_id)
+        .param("max_id", max_id)
+        .param("user_id", user_id)
+        .param("old_kind", old_kind.as_str())
+        .param("new_kind", new_kind.unwrap_or(old_kind).as_str())
+        .param("set_note", note.is_some())
+        .param("note", note.flatten());
+
+        let Some(row) = self.run_with_timeout(builder).await?.pop() else {
+            return Err(GraphRepositoryError::Business(BusinessError::from(
+                LinkError::TargetNotFound,
+            )));
+        };
+        let collisions: i64 = row
+            .get("collisions")
+            .map_err(|_| GraphRepositoryError::InvalidData("missing collisions".into()))?;
+        if collisions > 0 {
+            return Err(GraphRepositoryError::Business(BusinessError::from(
+                LinkError::Exists,
+            )));
+        }
+        let mut record = Self::parse_word_link(row)?;
+        record.word_a_id = word_a_id;
+        record.word_b_id = word_b_id;
+        Ok(record)
     }
 
     async fn list_word_links(&self, filter: WordLinkFilter) -> GraphResult<Vec<WordLinkRecord>> {
         let mut builder = query(
-            "MATCH (word_a:Word { word_id: $word_id })-[rel:WORD_TO_WORD { user_id: $user_id }]->(word_b:Word)\nWHERE rel.kind IN $kinds\nRETURN word_a, word_b, rel\nORDER BY rel.created_at DESC\nSKIP $offset LIMIT $limit",
+            "MATCH (:Word { word_id: $word_id })-[rel:WORD_TO_WORD { user_id: $user_id }]-(:Word)\nWHERE rel.kind IN $kinds\nRETURN startNode(rel) AS word_a, endNode(rel) AS word_b, rel\nORDER BY rel.created_at DESC\nSKIP $offset LIMIT $limit",
         )
         .param("word_id", filter.word_id)
         .param("user_id", filter.user_id)
@@ -366,6 +620,44 @@ impl GraphRepository for Neo4jGraphRepository {
         rows.into_iter().map(Self::parse_word_link).collect()
     }
 
+    async fn get_word_links_between(
+        &self,
+        user_id: i64,
+        word_a_id: i64,
+        word_b_id: i64,
+    ) -> GraphResult<Vec<WordLinkRecord>> {
+        let (min_id, max_id) = Self::sort_word_ids(word_a_id, word_b_id)?;
+        let builder = query(
+            "MATCH (word_a:Word { word_id: $min_id })-[rel:WORD_TO_WORD { user_id: $user_id }]->(word_b:Word { word_id: $max_id })\nRETURN word_a, word_b, rel\nORDER BY rel.created_at ASC",
+        )
+        .param("min_id", min_id)
+        .param("max_id", max_id)
+        .param("user_id", user_id);
+
+        let rows = self.run_with_timeout(builder).await?;
+        rows.into_iter().map(Self::parse_word_link).collect()
+    }
+
+    async fn list_word_links_from_words(
+        &self,
+        user_id: i64,
+        word_ids: &[i64],
+        limit: i64,
+    ) -> GraphResult<Vec<WordLinkRecord>> {
+        if word_ids.is_empty() || limit <= 0 {
+            return Ok(Vec::new());
+        }
+        let builder = query(
+            "UNWIND $ids AS id\nMATCH (word_a:Word { word_id: id })-[rel:WORD_TO_WORD { user_id: $user_id }]->(word_b:Word)\nRETURN word_a, word_b, rel\nORDER BY word_a.word_id, word_b.word_id, rel.created_at\nLIMIT $limit",
+        )
+        .param("ids", word_ids.to_vec())
+        .param("user_id", user_id)
+        .param("limit", limit);
+
+        let rows = self.run_with_row_limit(builder, limit as usize).await?;
+        rows.into_iter().map(Self::parse_word_link).collect()
+    }
+
     async fn create_sense_word_link(
         &self,
         user_id: i64,
@@ -381,29 +673,24 @@ impl GraphRepository for Neo4jGraphRepository {
             )));
         }
 
-        let mut builder = query(
-            "MERGE (sense:UserSense { sense_id: $sense_id, user_id: $user_id })\nMERGE (target:Word { word_id: $target_word_id })\nMERGE (sense)-[rel:SENSE_TO_WORD { user_id: $user_id, kind: $kind }]->(target)\nON CREATE SET rel.created_at = datetime(), rel.note = $note\nON MATCH SET rel.note = CASE WHEN $note IS NULL THEN rel.note ELSE $note END\nRETURN sense, target AS word, rel",
-        )
-        .param("sense_id", sense_id)
-        .param("user_id", user_id)
-        .param("target_word_id", target_word_id)
-        .param("kind", kind.as_str())
-        .param("note", note);
+        let builder = query(CREATE_SENSE_WORD_LINK)
+            .param("link_id", Uuid::new_v4().to_string())
+            .param("sense_id", sense_id)
+            .param("user_id", user_id)
+            .param("source_word_id", source_word_id)
+            .param("target_word_id", target_word_id)
+            .param("kind", kind.as_str())
+            .param("note", note);
 
+        // 没有返回行说明图中记录的来源单词与请求不一致，查询内已跳过写入
         let rows = self.run_with_timeout(builder).await?;
-        let mut record = rows
-            .into_iter()
+        rows.into_iter()
             .next()
             .map(Self::parse_sense_word_link)
             .transpose()?
             .ok_or_else(|| {
-                GraphRepositoryError::InvalidData("create_sense_word_link returned no rows".into())
-            })?;
-
-        if record.source_word_id != source_word_id {
-            record.source_word_id = source_word_id;
-        }
-        Ok(record)
+                GraphRepositoryError::Business(BusinessError::from(LinkError::TargetNotFound))
+            })
     }
 
     async fn delete_sense_word_link(
@@ -451,6 +738,26 @@ impl GraphRepository for Neo4jGraphRepository {
         rows.into_iter().map(Self::parse_sense_word_link).collect()
     }
 
+    async fn list_links_for_senses(
+        &self,
+        user_id: i64,
+        sense_ids: &[i64],
+        limit: i64,
+    ) -> GraphResult<Vec<SenseWordLinkRecord>> {
+        if sense_ids.is_empty() || limit <= 0 {
+            return Ok(Vec::new());
+        }
+        let builder = query(
+            "UNWIND $ids AS id\nMATCH (sense:UserSense { sense_id: id, user_id: $user_id })-[rel:SENSE_TO_WORD]->(word:Word)\nRETURN sense, word, rel\nORDER BY sense.sense_id, rel.created_at DESC\nLIMIT $limit",
+        )
+        .param("ids", sense_ids.to_vec())
+        .param("user_id", user_id)
+        .param("limit", limit);
+
+        let rows = self.run_with_row_limit(builder, limit as usize).await?;
+        rows.into_iter().map(Self::parse_sense_word_link).collect()
+    }
+
     async fn remove_links_for_sense(&self, sense_id: i64) -> GraphResult<()> {
         let query =
             query("MATCH (:UserSense { sense_id: $sense_id })-[rel:SENSE_TO_WORD]->()\nDELETE rel")
@@ -458,6 +765,191 @@ impl GraphRepository for Neo4jGraphRepository {
         self.run_with_timeout(query).await.map(|_| ())
     }
 
+    async fn neighbors(&self, filter: NeighborQuery) -> GraphResult<Neighborhood> {
+        if filter.limit <= 0 {
+            return Ok(Neighborhood::default());
+        }
+        // 变长路径的跳数无法参数化，depth 由服务层钳制后以字面量写入
+        let depth = filter.depth.max(1);
+        let kinds: Vec<&str> = match filter.kind {
+            Some(kind) => vec![kind.as_str()],
+            None => WordLinkKind::ALL.iter().map(|kind| kind.as_str()).collect(),
+        };
+        let nodes_query = query(&format!(
+            "MATCH (start:Word {{ word_id: $word_id }})-[rels:WORD_TO_WORD*1..{depth}]-(n:Word)\nWHERE n <> start AND all(r IN rels WHERE r.user_id = $user_id AND r.kind IN $kinds)\nWITH DISTINCT n\nLIMIT $limit\nRETURN n.word_id AS word_id"
+        ))
+        .param("word_id", filter.word_id)
+        .param("user_id", filter.user_id)
+        .param("kinds", kinds.clone())
+        .param("limit", filter.limit);
+
+        let rows = self
+            .run_with_row_limit(nodes_query, filter.limit as usize)
+            .await?;
+        let word_ids = rows
+            .into_iter()
+            .map(|row| {
+                row.get::<i64>("word_id")
+                    .map_err(|_| GraphRepositoryError::InvalidData("missing word_id".into()))
+            })
+            .collect::<GraphResult<Vec<_>>>()?;
+        if word_ids.is_empty() {
+            return Ok(Neighborhood::default());
+        }
+
+        let mut member_ids = word_ids.clone();
+        member_ids.push(filter.word_id);
+        let links_query = query(
+            "MATCH (word_a:Word)-[rel:WORD_TO_WORD { user_id: $user_id }]->(word_b:Word)\nWHERE word_a.word_id IN $ids AND word_b.word_id IN $ids AND rel.kind IN $kinds\nRETURN word_a, word_b, rel\nORDER BY rel.created_at DESC",
+        )
+        .param("user_id", filter.user_id)
+        .param("ids", member_ids)
+        .param("kinds", kinds);
+        let links = self
+            .run_with_timeout(links_query)
+            .await?
+            .into_iter()
+            .map(Self::parse_word_link)
+            .collect::<GraphResult<Vec<_>>>()?;
+
+        Ok(Neighborhood {
+            word_ids,
+            links,
+            truncated: false,
+        })
+    }
+
+    async fn total_link_count(&self, user_id: i64) -> GraphResult<LinkCounts> {
+        let builder = query(
+            "CALL { MATCH ()-[r:WORD_TO_WORD { user_id: $user_id }]->() RETURN count(r) AS word_links }\nCALL { MATCH ()-[r:SENSE_TO_WORD { user_id: $user_id }]->() RETURN count(r) AS sense_links }\nRETURN word_links, sense_links",
+        )
+        .param("user_id", user_id);
+        let row = self
+            .run_with_timeout(builder)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| GraphRepositoryError::InvalidData("missing link counts".into()))?;
+        let count = |column: &str| {
+            row.get::<i64>(column)
+                .map_err(|_| GraphRepositoryError::InvalidData(format!("missing {column}")))
+        };
+        Ok(LinkCounts {
+            word_links: count("word_links")?,
+            sense_links: count("sense_links")?,
+        })
+    }
+
+    async fn link_stats_by_kind(&self, user_id: i64) -> GraphResult<LinkKindStats> {
+        let builder = query(
+            "CALL { MATCH ()-[r:WORD_TO_WORD { user_id: $user_id }]->() WITH r.kind AS kind, count(r) AS links RETURN collect(kind) AS word_kinds, collect(links) AS word_counts }\nCALL { MATCH ()-[r:SENSE_TO_WORD { user_id: $user_id }]->() WITH r.kind AS kind, count(r) AS links RETURN collect(kind) AS sense_kinds, collect(links) AS sense_counts }\nRETURN word_kinds, word_counts, sense_kinds, sense_counts",
+        )
+        .param("user_id", user_id);
+        let row = self
+            .run_with_timeout(builder)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| GraphRepositoryError::InvalidData("missing link stats".into()))?;
+        let kinds = |name: &str| -> GraphResult<Vec<String>> {
+            row.get::<Vec<String>>(name)
+                .map_err(|_| GraphRepositoryError::InvalidData(format!("missing {name}")))
+        };
+        let counts = |name: &str| -> GraphResult<Vec<i64>> {
+            row.get::<Vec<i64>>(name)
+                .map_err(|_| GraphRepositoryError::InvalidData(format!("missing {name}")))
+        };
+
+        // 未知类型（历史数据）不计入
+        let mut stats = LinkKindStats::default();
+        for (kind, count) in kinds("word_kinds")?.iter().zip(counts("word_counts")?) {
+            if let Some(kind) = WordLinkKind::try_from_str(kind) {
+                stats.add_word_links(kind, count);
+            }
+        }
+        for (kind, count) in kinds("sense_kinds")?.iter().zip(counts("sense_counts")?) {
+            if let Some(kind) = SenseWordLinkKind::try_from_str(kind) {
+                stats.add_sense_links(kind, count);
+            }
+        }
+        Ok(stats)
+    }
+
+    async fn word_degrees(&self, user_id: i64, word_ids: &[i64]) -> GraphResult<HashMap<i64, i64>> {
+        if word_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let builder = query(&format!("{WORD_DEGREES}\nRETURN id AS word_id, degree"))
+            .param("ids", word_ids.to_vec())
+            .param("user_id", user_id);
+        self.run_with_timeout(builder)
+            .await?
+            .into_iter()
+            .map(|row| {
+                let word_id = row
+                    .get::<i64>("word_id")
+                    .map_err(|_| GraphRepositoryError::InvalidData("missing word_id".into()))?;
+                let degree = row
+                    .get::<i64>("degree")
+                    .map_err(|_| GraphRepositoryError::InvalidData("missing degree".into()))?;
+                Ok((word_id, degree))
+            })
+            .collect()
+    }
+
+    async fn linked_word_ids(&self, user_id: i64, word_ids: &[i64]) -> GraphResult<HashSet<i64>> {
+        if word_ids.is_empty() {
+            return Ok(HashSet::new());
+        }
+        let builder = query(&format!(
+            "{WORD_DEGREES}\nWITH id, degree WHERE degree > 0\nRETURN id AS word_id"
+        ))
+        .param("ids", word_ids.to_vec())
+        .param("user_id", user_id);
+        self.run_with_timeout(builder)
+            .await?
+            .into_iter()
+            .map(|row| {
+                row.get::<i64>("word_id")
+                    .map_err(|_| GraphRepositoryError::InvalidData("missing word_id".into()))
+            })
+            .collect()
+    }
+
+    async fn repoint_word_links(
+        &self,
+        user_id: i64,
+        from_word_id: i64,
+        to_word_id: i64,
+    ) -> GraphResult<()> {
+        Self::sort_word_ids(from_word_id, to_word_id)?;
+        let statements = [
+            // 两词之间的关联合并后会变成自环
+            "MATCH (:Word { word_id: $from_id })-[r:WORD_TO_WORD { user_id: $user_id }]-(:Word { word_id: $to_id })\nDELETE r",
+            "MERGE (t:Word { word_id: $to_id })\nWITH t\nMATCH (:Word { word_id: $from_id })-[r:WORD_TO_WORD { user_id: $user_id }]-(o:Word)\nWITH r, o, t, o.word_id < $to_id AS other_first\nWITH r, CASE WHEN other_first THEN o ELSE t END AS a, CASE WHEN other_first THEN t ELSE o END AS b\nMERGE (a)-[n:WORD_TO_WORD { user_id: $user_id, kind: r.kind }]->(b)\nON CREATE SET n.created_at = r.created_at, n.note = r.note, n.link_id = r.link_id\nON MATCH SET n.created_at = CASE WHEN r.created_at < n.created_at THEN r.created_at ELSE n.created_at END, n.note = coalesce(n.note, r.note)\nDELETE r",
+            // 挂在被合并单词下的义项改为归属目标单词
+            "MATCH (s:UserSense { user_id: $user_id, word_id: $from_id })\nSET s.word_id = $to_id",
+            "MATCH (s:UserSense { user_id: $user_id, word_id: $to_id })-[r:SENSE_TO_WORD { user_id: $user_id }]->(w:Word)\nWHERE w.word_id IN [$from_id, $to_id]\nDELETE r",
+            "MERGE (t:Word { word_id: $to_id })\nWITH t\nMATCH (s:UserSense)-[r:SENSE_TO_WORD { user_id: $user_id }]->(:Word { word_id: $from_id })\nMERGE (s)-[n:SENSE_TO_WORD { user_id: $user_id, kind: r.kind }]->(t)\nON CREATE SET n.created_at = r.created_at, n.note = r.note, n.link_id = r.link_id\nON MATCH SET n.created_at = CASE WHEN r.created_at < n.created_at THEN r.created_at ELSE n.created_at END, n.note = coalesce(n.note, r.note)\nDELETE r",
+        ];
+        let queries = statements.map(|statement| {
+            query(statement)
+                .param("user_id", user_id)
+                .param("from_id", from_word_id)
+                .param("to_id", to_word_id)
+        });
+
+        let work = async {
+            let mut txn = self.graph.start_txn().await?;
+            txn.run_queries(queries).await?;
+            txn.commit().await.map(|_| ())
+        };
+        match limited(timed(Dependency::Neo4j, timeout(self.timeout, work))).await {
+            Ok(result) => result.map_err(GraphRepositoryError::Database),
+            Err(_) => Err(GraphRepositoryError::Timeout),
+        }
+    }
+
     async fn upsert_node_word(&self, word_id: i64) -> GraphResult<()> {
         let query = query("MERGE (:Word { word_id: $word_id })").param("word_id", word_id);
         self.run_with_timeout(query).await.map(|_| ())
@@ -470,3 +962,226 @@ impl GraphRepository for Neo4jGraphRepository {
         self.run_with_timeout(query).await.map(|_| ())
     }
 }
+
+/// `features.graph_enabled = false` 时注入：节点维护与清理视为空操作，关联查询/写入返回 `GraphDisabled`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopGraphRepository;
+
+fn graph_disabled<T>() -> GraphResult<T> {
+    Err(GraphRepositoryError::Business(BusinessError::from(
+        LinkError::GraphDisabled,
+    )))
+}
+
+#[async_trait]
+impl GraphRepository for NoopGraphRepository {
+    async fn create_word_link(
+        &self,
+        _user_id: i64,
+        _word_a_id: i64,
+        _word_b_id: i64,
+        _kind: WordLinkKind,
+        _note: Option<String>,
+    ) -> GraphResult<WordLinkRecord> {
+        graph_disabled()
+    }
+
+    async fn delete_word_link(
+        &self,
+        _user_id: i64,
+        _word_a_id: i64,
+        _word_b_id: i64,
+        _kind: WordLinkKind,
+    ) -> GraphResult<u64> {
+        graph_disabled()
+    }
+
+    async fn update_word_link(
+        &self,
+        _user_id: i64,
+        _word_a_id: i64,
+        _word_b_id: i64,
+        _old_kind: WordLinkKind,
+        _new_kind: Option<WordLinkKind>,
+        _note: Option<Option<String>>,
+    ) -> GraphResult<WordLinkRecord> {
+        graph_disabled()
+    }
+
+    async fn list_word_links(&self, _filter: WordLinkFilter) -> GraphResult<Vec<WordLinkRecord>> {
+        graph_disabled()
+    }
+
+    async fn get_word_links_between(
+        &self,
+        _user_id: i64,
+        _word_a_id: i64,
+        _word_b_id: i64,
+    ) -> GraphResult<Vec<WordLinkRecord>> {
+        graph_disabled()
+    }
+
+    async fn list_word_links_from_words(
+        &self,
+        _user_id: i64,
+        _word_ids: &[i64],
+        _limit: i64,
+    ) -> GraphResult<Vec<WordLinkRecord>> {
+        graph_disabled()
+    }
+
+    async fn create_sense_word_link(
+        &self,
+        _user_id: i64,
+        _sense_id: i64,
+        _source_word_id: i64,
+        _target_word_id: i64,
+        _kind: SenseWordLinkKind,
+        _note: Option<String>,
+    ) -> GraphResult<SenseWordLinkRecord> {
+        graph_disabled()
+    }
+
+    async fn delete_sense_word_link(
+        &self,
+        _user_id: i64,
+        _sense_id: i64,
+        _target_word_id: i64,
+        _kind: SenseWordLinkKind,
+    ) -> GraphResult<()> {
+        graph_disabled()
+    }
+
+    async fn list_sense_word_links(
+        &self,
+        _filter: SenseLinkFilter,
+    ) -> GraphResult<Vec<SenseWordLinkRecord>> {
+        graph_disabled()
+    }
+
+    async fn list_links_for_senses(
+        &self,
+        _user_id: i64,
+        _sense_ids: &[i64],
+        _limit: i64,
+    ) -> GraphResult<Vec<SenseWordLinkRecord>> {
+        graph_disabled()
+    }
+
+    async fn remove_links_for_sense(&self, _sense_id: i64) -> GraphResult<()> {
+        Ok(())
+    }
+
+    async fn neighbors(&self, _query: NeighborQuery) -> GraphResult<Neighborhood> {
+        graph_disabled()
+    }
+
+    async fn total_link_count(&self, _user_id: i64) -> GraphResult<LinkCounts> {
+        graph_disabled()
+    }
+
+    async fn link_stats_by_kind(&self, _user_id: i64) -> GraphResult<LinkKindStats> {
+        graph_disabled()
+    }
+
+    async fn word_degrees(
+        &self,
+        _user_id: i64,
+        _word_ids: &[i64],
+    ) -> GraphResult<HashMap<i64, i64>> {
+        graph_disabled()
+    }
+
+    async fn linked_word_ids(&self, _user_id: i64, _word_ids: &[i64]) -> GraphResult<HashSet<i64>> {
+        graph_disabled()
+    }
+
+    async fn repoint_word_links(
+        &self,
+        _user_id: i64,
+        _from_word_id: i64,
+        _to_word_id: i64,
+    ) -> GraphResult<()> {
+        graph_disabled()
+    }
+
+    async fn upsert_node_word(&self, _word_id: i64) -> GraphResult<()> {
+        Ok(())
+    }
+
+    async fn upsert_node_sense(&self, _sense_id: i64, _user_id: i64) -> GraphResult<()> {
+        Ok(())
+    }
+
+    fn is_enabled(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use neo4rs::{BoltInteger, BoltMap, BoltRelation, BoltString, BoltType};
+
+    fn relation(internal_id: i64, link_id: Option<&str>) -> Relation {
+        let properties: BoltMap = link_id
+            .map(|value| {
+                (
+                    BoltString::from("link_id"),
+                    BoltType::String(BoltString::from(value)),
+                )
+            })
+            .into_iter()
+            .collect();
+        Relation::new(BoltRelation {
+            id: BoltInteger::new(internal_id),
+            start_node_id: BoltInteger::new(1),
+            end_node_id: BoltInteger::new(2),
+            typ: BoltString::from("WORD_TO_WORD"),
+            properties,
+        })
+    }
+
+    #[test]
+    fn parse_link_id_prefers_stable_property_over_internal_id() {
+        let link_id = Uuid::new_v4().to_string();
+
+        // 内部 id 变化（例如删除后复用、备份恢复）不影响对外的 link_id
+        let before = Neo4jGraphRepository::parse_link_id(&relation(42, Some(&link_id)));
+        let after = Neo4jGraphRepository::parse_link_id(&relation(7, Some(&link_id)));
+
+        assert_eq!(before, link_id);
+        assert_eq!(after, link_id);
+        assert!(Uuid::parse_str(&before).is_ok());
+    }
+
+    #[test]
+    fn sense_word_link_query_checks_source_word_before_writing() {
+        let guard = CREATE_SENSE_WORD_LINK
+            .find("WITH sense WHERE sense.word_id = $source_word_id")
+            .expect("source word guard");
+        let first_write = CREATE_SENSE_WORD_LINK
+            .find("MERGE (target")
+            .expect("target merge");
+        assert!(guard < first_write);
+    }
+
+    #[test]
+    fn word_degrees_count_every_kind_of_link() {
+        for pattern in [
+            "(:Word { word_id: id })-[r:WORD_TO_WORD",
+            "->(:Word { word_id: id })",
+            "(:UserSense { user_id: $user_id, word_id: id })-[o:SENSE_TO_WORD",
+        ] {
+            assert!(WORD_DEGREES.contains(pattern), "{pattern}");
+        }
+    }
+
+    #[test]
+    fn parse_link_id_falls_back_to_internal_id_when_missing() {
+        assert_eq!(
+            Neo4jGraphRepository::parse_link_id(&relation(42, None)),
+            "42"
+        );
+    }
+}
@@ -0,0 +1,1658 @@
+//! In-memory repository implementations shared by service and controller tests.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+use crate::domain::CanonicalKey;
+use crate::domain::word::{UserSense, UserWord, UserWordError};
+use crate::repository::graph::{
+    GraphRepository, GraphRepositoryError, GraphResult, LinkCounts, LinkKindStats, NeighborQuery,
+    Neighborhood, SenseLinkFilter, SenseWordLinkKind, SenseWordLinkRecord, WordLinkFilter,
+    WordLinkKind, WordLinkRecord,
+};
+use crate::repository::preferences::{
+    PreferencesRepository, PreferencesRepositoryError, UserPreferences,
+};
+use crate::repository::word::{
+    DuplicateCandidate, ImportUserWord, ImportedUserWord, MatchSide, NearDuplicatePair,
+    NewUserSense, OwnedResource, SearchMatch, SearchParams, SearchScope, SearchSort, SenseOrder,
+    SenseReorder, SenseUpdate, TagAction, UpsertUserWord, UpsertedUserWord, UserWordAggregate,
+    WordChanges, WordRecord, WordRepoint, WordRepository, WordRepositoryError, WordTombstone,
+};
+use crate::util::error::{BusinessError, LinkError};
+use crate::util::validation::MAX_TAGS;
+
+/// 与 pg_trgm 的 `similarity` 一致：按非字母数字切词，每个词前补两个空格、后补一个空格后取三元组，
+/// 结果为两组三元组的交集大小除以并集大小
+fn trigram_similarity(a: &str, b: &str) -> f32 {
+    fn trigrams(value: &str) -> HashSet<[char; 3]> {
+        let mut set = HashSet::new();
+        for word in value
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+        {
+            let padded: Vec<char> = "  "
+                .chars()
+                .chain(word.to_lowercase().chars())
+                .chain(std::iter::once(' '))
+                .collect();
+            for window in padded.windows(3) {
+                set.insert([window[0], window[1], window[2]]);
+            }
+        }
+        set
+    }
+
+    let (left, right) = (trigrams(a), trigrams(b));
+    let union = left.union(&right).count();
+    if union == 0 {
+        return 0.0;
+    }
+    left.intersection(&right).count() as f32 / union as f32
+}
+
+#[derive(Debug, Clone)]
+struct UserWordRow {
+    id: i64,
+    user_id: i64,
+    word_id: i64,
+    tags: Vec<String>,
+    note: Option<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    repointed_from_word_id: Option<i64>,
+}
+
+#[derive(Debug, Clone)]
+struct SenseRow {
+    id: i64,
+    user_word_id: i64,
+    text: String,
+    is_primary: bool,
+    sort_order: i32,
+    note: Option<String>,
+    source_url: Option<String>,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Default)]
+struct WordState {
+    next_id: i64,
+    words: Vec<WordRecord>,
+    user_words: Vec<UserWordRow>,
+    senses: Vec<SenseRow>,
+    tombstones: Vec<(i64, WordTombstone)>,
+}
+
+impl WordState {
+    fn next_id(&mut self) -> i64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    fn upsert_word(&mut self, canonical: &CanonicalKey, text: &str) -> WordRecord {
+        if let Some(word) = self
+            .words
+            .iter_mut()
+            .find(|word| &word.canonical_key == canonical)
+        {
+            word.text = text.to_string();
+            return word.clone();
+        }
+        let record = WordRecord {
+            id: self.next_id(),
+            text: text.to_string(),
+            canonical_key: canonical.clone(),
+            created_at: Utc::now(),
+        };
+        self.words.push(record.clone());
+        record
+    }
+
+    fn touch(&mut self, user_word_id: i64) {
+        if let Some(row) = self.user_words.iter_mut().find(|uw| uw.id == user_word_id) {
+            row.updated_at = Utc::now();
+        }
+    }
+
+    fn owner_of_sense(&self, user_id: i64, sense_id: i64) -> Option<usize> {
+        let position = self.senses.iter().position(|s| s.id == sense_id)?;
+        let user_word_id = self.senses[position].user_word_id;
+        self.user_words
+            .iter()
+            .any(|uw| uw.id == user_word_id && uw.user_id == user_id)
+            .then_some(position)
+    }
+
+    fn aggregate(&self, row: &UserWordRow) -> Result<UserWordAggregate, WordRepositoryError> {
+        let word = self
+            .words
+            .iter()
+            .find(|word| word.id == row.word_id)
+            .cloned()
+            .ok_or(WordRepositoryError::Database(sqlx::Error::RowNotFound))?;
+        let mut senses = self
+            .senses
+            .iter()
+            .filter(|sense| sense.user_word_id == row.id)
+            .map(to_user_sense)
+            .collect::<Result<Vec<_>, _>>()?;
+        senses.sort_by_key(|sense| sense.sort_order);
+        let user_word = UserWord::from_parts(
+            Some(row.id),
+            row.user_id,
+            row.word_id,
+            row.tags.clone(),
+            row.note.clone(),
+            senses,
+            row.created_at,
+        )?;
+        Ok(UserWordAggregate {
+            word,
+            user_word,
+            has_more_senses: false,
+        })
+    }
+}
+
+impl WordState {
+    /// 命中信息不做高亮，`snippet` 为命中的原文
+    fn matching(
+        &self,
+        params: &SearchParams,
+    ) -> Result<Vec<(UserWordAggregate, Option<SearchMatch>)>, WordRepositoryError> {
+        let query = params.query.trim().to_lowercase();
+        let mut matches = Vec::new();
+        for row in self
+            .user_words
+            .iter()
+            .filter(|uw| uw.user_id == params.user_id)
+        {
+            if !params.tags.iter().all(|tag| row.tags.contains(tag)) {
+                continue;
+            }
+            if let Some(word_ids) = &params.word_ids
+                && !word_ids.contains(&row.word_id)
+            {
+                continue;
+            }
+            let aggregate = self.aggregate(row)?;
+            let word_hit = aggregate
+                .word
+                .canonical_key
+                .as_str()
+                .contains(&query.replace(' ', "-"));
+            let sense_hit = aggregate
+                .user_word
+                .senses()
+                .iter()
+                .find(|sense| sense.text().to_lowercase().contains(&query))
+                .map(|sense| sense.text().to_string());
+            let search_match = match params.scope {
+                SearchScope::Word | SearchScope::Both if word_hit => Some(SearchMatch {
+                    side: MatchSide::Word,
+                    snippet: aggregate.word.text.clone(),
+                }),
+                SearchScope::Sense | SearchScope::Both => sense_hit.map(|snippet| SearchMatch {
+                    side: MatchSide::Sense,
+                    snippet,
+                }),
+                SearchScope::Word => None,
+            };
+            if query.is_empty() {
+                matches.push((row.id, aggregate, None));
+            } else if search_match.is_some() {
+                matches.push((row.id, aggregate, search_match));
+            }
+        }
+        match params.sort {
+            // 内存实现不计算相关度，按字母序返回
+            SearchSort::Alphabetical | SearchSort::Degree | SearchSort::Relevance => matches
+                .sort_by(|(_, a, _), (_, b, _)| {
+                    a.word
+                        .canonical_key
+                        .as_str()
+                        .cmp(b.word.canonical_key.as_str())
+                }),
+            // 内存实现中 id 单调递增，可代替 created_at 排序
+            SearchSort::Recent => matches.sort_by(|(a, _, _), (b, _, _)| b.cmp(a)),
+        }
+        Ok(matches
+            .into_iter()
+            .map(|(_, aggregate, search_match)| (aggregate, search_match))
+            .collect())
+    }
+}
+
+fn to_user_sense(row: &SenseRow) -> Result<UserSense, WordRepositoryError> {
+    Ok(UserSense::from_parts(
+        Some(row.id),
+        row.text.clone(),
+        row.is_primary,
+        row.sort_order,
+        row.note.clone(),
+        row.created_at,
+    )?
+    .with_source_url(row.source_url.clone())?)
+}
+
+fn in_range(at: DateTime<Utc>, from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>) -> bool {
+    from.is_none_or(|from| at >= from) && to.is_none_or(|to| at <= to)
+}
+
+/// Postgres 仓储的内存替身，语义尽量与 `PgWordRepository` 保持一致
+#[derive(Debug, Default, Clone)]
+pub(crate) struct InMemoryWordRepository {
+    state: Arc<Mutex<WordState>>,
+    max_senses: Option<usize>,
+}
+
+impl InMemoryWordRepository {
+    pub(crate) fn with_max_senses(mut self, limit: usize) -> Self {
+        self.max_senses = Some(limit);
+        self
+    }
+}
+
+#[async_trait]
+impl WordRepository for InMemoryWordRepository {
+    async fn upsert_word(
+        &self,
+        canonical: &CanonicalKey,
+        text: &str,
+    ) -> Result<WordRecord, WordRepositoryError> {
+        Ok(self.state.lock().unwrap().upsert_word(canonical, text))
+    }
+
+    async fn upsert_user_word(
+        &self,
+        payload: UpsertUserWord,
+    ) -> Result<UpsertedUserWord, WordRepositoryError> {
+        let mut state = self.state.lock().unwrap();
+        let word = state.upsert_word(&payload.canonical_key, &payload.word_text);
+        let existing = state
+            .user_words
+            .iter_mut()
+            .find(|uw| uw.user_id == payload.user_id && uw.word_id == word.id)
+            .map(|uw| {
+                uw.tags = payload.tags.clone();
+                uw.note = payload.note.clone();
+                uw.updated_at = Utc::now();
+                uw.clone()
+            });
+        let created = existing.is_none();
+        let row = match existing {
+            Some(row) => row,
+            None => {
+                let row = UserWordRow {
+                    id: state.next_id(),
+                    user_id: payload.user_id,
+                    word_id: word.id,
+                    tags: payload.tags,
+                    note: payload.note,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                    repointed_from_word_id: None,
+                };
+                state.user_words.push(row.clone());
+                row
+            }
+        };
+        Ok(UpsertedUserWord {
+            aggregate: state.aggregate(&row)?,
+            created,
+        })
+    }
+
+    /// 没有事务：逐项调用，失败项之前已写入的部分不会回滚
+    async fn import_user_words(
+        &self,
+        items: Vec<ImportUserWord>,
+    ) -> Result<Vec<Result<ImportedUserWord, WordRepositoryError>>, WordRepositoryError> {
+        let mut outcomes = Vec::with_capacity(items.len());
+        for ImportUserWord { word, senses } in items {
+            let user_id = word.user_id;
+            let outcome = async {
+                let upserted = self.upsert_user_word(word).await?;
+                let user_word_id = upserted.aggregate.user_word.id.unwrap_or_default();
+                let mut aggregate = upserted.aggregate;
+                let mut deduplicated_senses = 0;
+                for sense in senses {
+                    if aggregate
+                        .user_word
+                        .senses()
+                        .iter()
+                        .any(|s| s.text() == sense.text)
+                    {
+                        deduplicated_senses += 1;
+                        continue;
+                    }
+                    self.add_user_sense(NewUserSense {
+                        user_word_id,
+                        text: sense.text,
+                        is_primary: sense.is_primary,
+                        sort_order: sense.sort_order,
+                        note: sense.note,
+                        source_url: sense.source_url,
+                    })
+                    .await?;
+                    aggregate = self
+                        .find_user_word(user_id, user_word_id, SenseOrder::default())
+                        .await?
+                        .ok_or(WordRepositoryError::Database(sqlx::Error::RowNotFound))?;
+                }
+                Ok(ImportedUserWord {
+                    aggregate,
+                    created: upserted.created,
+                    deduplicated_senses,
+                })
+            };
+            outcomes.push(outcome.await);
+        }
+        Ok(outcomes)
+    }
+
+    async fn find_user_word(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        sense_order: SenseOrder,
+    ) -> Result<Option<UserWordAggregate>, WordRepositoryError> {
+        let state = self.state.lock().unwrap();
+        let found = state
+            .user_words
+            .iter()
+            .find(|uw| uw.id == user_word_id && uw.user_id == user_id)
+            .map(|row| state.aggregate(row))
+            .transpose()?;
+        Ok(found.map(|mut aggregate| {
+            sense_order.arrange(&mut aggregate.user_word);
+            aggregate
+        }))
+    }
+
+    async fn find_user_word_by_canonical(
+        &self,
+        user_id: i64,
+        canonical: &CanonicalKey,
+    ) -> Result<Option<UserWordAggregate>, WordRepositoryError> {
+        let state = self.state.lock().unwrap();
+        let Some(word) = state
+            .words
+            .iter()
+            .find(|word| &word.canonical_key == canonical)
+        else {
+            return Ok(None);
+        };
+        state
+            .user_words
+            .iter()
+            .find(|uw| uw.word_id == word.id && uw.user_id == user_id)
+            .map(|row| state.aggregate(row))
+            .transpose()
+    }
+
+    async fn remove_user_word(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+    ) -> Result<(), WordRepositoryError> {
+        let mut state = self.state.lock().unwrap();
+        let Some(position) = state
+            .user_words
+            .iter()
+            .position(|uw| uw.id == user_word_id && uw.user_id == user_id)
+        else {
+            return Ok(());
+        };
+        let removed = state.user_words.remove(position);
+        state
+            .senses
+            .retain(|sense| sense.user_word_id != user_word_id);
+        state.tombstones.push((
+            user_id,
+            WordTombstone {
+                user_word_id,
+                word_id: removed.word_id,
+                deleted_at: Utc::now(),
+            },
+        ));
+        Ok(())
+    }
+
+    async fn add_user_sense(&self, sense: NewUserSense) -> Result<UserSense, WordRepositoryError> {
+        let mut state = self.state.lock().unwrap();
+        if state
+            .senses
+            .iter()
+            .any(|s| s.user_word_id == sense.user_word_id && s.text == sense.text)
+        {
+            return Err(UserWordError::DuplicateSenseText(sense.text).into());
+        }
+        if let Some(max_senses) = self.max_senses
+            && state
+                .senses
+                .iter()
+                .filter(|s| s.user_word_id == sense.user_word_id)
+                .count()
+                >= max_senses
+        {
+            return Err(UserWordError::SenseLimitExceeded(max_senses).into());
+        }
+        if sense.is_primary {
+            for existing in state
+                .senses
+                .iter_mut()
+                .filter(|s| s.user_word_id == sense.user_word_id)
+            {
+                existing.is_primary = false;
+            }
+        }
+        let row = SenseRow {
+            id: state.next_id(),
+            user_word_id: sense.user_word_id,
+            text: sense.text,
+            is_primary: sense.is_primary,
+            sort_order: sense.sort_order,
+            note: sense.note,
+            source_url: sense.source_url,
+            created_at: Utc::now(),
+        };
+        state.senses.push(row.clone());
+        state.touch(row.user_word_id);
+        to_user_sense(&row)
+    }
+
+    async fn update_user_sense(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+        update: SenseUpdate,
+    ) -> Result<UserSense, WordRepositoryError> {
+        let mut state = self.state.lock().unwrap();
+        let position = state
+            .owner_of_sense(user_id, sense_id)
+            .ok_or(WordRepositoryError::Database(sqlx::Error::RowNotFound))?;
+        let row = &mut state.senses[position];
+        if let Some(text) = update.text {
+            row.text = text;
+        }
+        if let Some(sort_order) = update.sort_order {
+            row.sort_order = sort_order;
+        }
+        if let Some(note) = update.note {
+            row.note = note;
+        }
+        if let Some(source_url) = update.source_url {
+            row.source_url = source_url;
+        }
+        if let Some(is_primary) = update.is_primary {
+            row.is_primary = is_primary;
+        }
+        let updated = row.clone();
+        if updated.is_primary {
+            for sibling in state
+                .senses
+                .iter_mut()
+                .filter(|s| s.user_word_id == updated.user_word_id && s.id != sense_id)
+            {
+                sibling.is_primary = false;
+            }
+        }
+        state.touch(updated.user_word_id);
+        to_user_sense(&updated)
+    }
+
+    async fn remove_user_sense(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+    ) -> Result<UserSense, WordRepositoryError> {
+        let mut state = self.state.lock().unwrap();
+        let position = state
+            .owner_of_sense(user_id, sense_id)
+            .ok_or(WordRepositoryError::Database(sqlx::Error::RowNotFound))?;
+        let removed = state.senses.remove(position);
+        state.touch(removed.user_word_id);
+        to_user_sense(&removed)
+    }
+
+    async fn set_primary_sense(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+    ) -> Result<UserSense, WordRepositoryError> {
+        let mut state = self.state.lock().unwrap();
+        let position = state
+            .owner_of_sense(user_id, sense_id)
+            .ok_or(WordRepositoryError::Database(sqlx::Error::RowNotFound))?;
+        let user_word_id = state.senses[position].user_word_id;
+        let mut changed = false;
+        for sense in state
+            .senses
+            .iter_mut()
+            .filter(|s| s.user_word_id == user_word_id)
+        {
+            let is_primary = sense.id == sense_id;
+            changed |= sense.is_primary != is_primary;
+            sense.is_primary = is_primary;
+        }
+        if changed {
+            state.touch(user_word_id);
+        }
+        to_user_sense(&state.senses[position])
+    }
+
+    async fn search(
+        &self,
+        params: SearchParams,
+    ) -> Result<Vec<(UserWordAggregate, Option<SearchMatch>)>, WordRepositoryError> {
+        let mut matches = self.state.lock().unwrap().matching(&params)?;
+        if let Some(cursor) = &params.after {
+            matches.retain(|(aggregate, _)| {
+                (
+                    aggregate.word.canonical_key.as_str(),
+                    aggregate.user_word.id.unwrap_or_default(),
+                ) > (cursor.canonical_key.as_str(), cursor.user_word_id)
+            });
+        }
+        Ok(page(matches, params.offset, params.limit))
+    }
+
+    async fn count_search(&self, params: &SearchParams) -> Result<i64, WordRepositoryError> {
+        Ok(self.state.lock().unwrap().matching(params)?.len() as i64)
+    }
+
+    async fn search_word_ids(
+        &self,
+        params: &SearchParams,
+    ) -> Result<Vec<i64>, WordRepositoryError> {
+        let params = SearchParams {
+            sort: SearchSort::Alphabetical,
+            ..params.clone()
+        };
+        Ok(self
+            .state
+            .lock()
+            .unwrap()
+            .matching(&params)?
+            .into_iter()
+            .map(|(aggregate, _)| aggregate.word.id)
+            .collect())
+    }
+
+    async fn find_sense_word_id(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+    ) -> Result<Option<i64>, WordRepositoryError> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .owner_of_sense(user_id, sense_id)
+            .and_then(|position| {
+                let user_word_id = state.senses[position].user_word_id;
+                state
+                    .user_words
+                    .iter()
+                    .find(|uw| uw.id == user_word_id)
+                    .map(|uw| uw.word_id)
+            }))
+    }
+
+    async fn find_user_word_by_sense(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+        sense_order: SenseOrder,
+    ) -> Result<Option<UserWordAggregate>, WordRepositoryError> {
+        let user_word_id = {
+            let state = self.state.lock().unwrap();
+            state
+                .owner_of_sense(user_id, sense_id)
+                .map(|position| state.senses[position].user_word_id)
+        };
+        match user_word_id {
+            Some(user_word_id) => {
+                self.find_user_word(user_id, user_word_id, sense_order)
+                    .await
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn find_owner(
+        &self,
+        resource: OwnedResource,
+    ) -> Result<Option<i64>, WordRepositoryError> {
+        let state = self.state.lock().unwrap();
+        let user_word_id = match resource {
+            OwnedResource::UserWord(id) => Some(id),
+            OwnedResource::Sense(id) => state
+                .senses
+                .iter()
+                .find(|sense| sense.id == id)
+                .map(|sense| sense.user_word_id),
+        };
+        Ok(user_word_id.and_then(|user_word_id| {
+            state
+                .user_words
+                .iter()
+                .find(|uw| uw.id == user_word_id)
+                .map(|uw| uw.user_id)
+        }))
+    }
+
+    async fn count_owned_user_words(
+        &self,
+        user_id: i64,
+        user_word_ids: &[i64],
+    ) -> Result<i64, WordRepositoryError> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .user_words
+            .iter()
+            .filter(|uw| uw.user_id == user_id && user_word_ids.contains(&uw.id))
+            .count() as i64)
+    }
+
+    async fn existing_canonical_keys(
+        &self,
+        user_id: i64,
+        keys: &[CanonicalKey],
+    ) -> Result<Vec<CanonicalKey>, WordRepositoryError> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .words
+            .iter()
+            .filter(|word| keys.contains(&word.canonical_key))
+            .filter(|word| {
+                state
+                    .user_words
+                    .iter()
+                    .any(|uw| uw.user_id == user_id && uw.word_id == word.id)
+            })
+            .map(|word| word.canonical_key.clone())
+            .collect())
+    }
+
+    async fn find_near_duplicate_words(
+        &self,
+        user_id: i64,
+        threshold: f32,
+        limit: i64,
+    ) -> Result<Vec<NearDuplicatePair>, WordRepositoryError> {
+        let state = self.state.lock().unwrap();
+        let mut mine: Vec<DuplicateCandidate> = state
+            .user_words
+            .iter()
+            .filter(|uw| uw.user_id == user_id)
+            .filter_map(|uw| {
+                let word = state.words.iter().find(|word| word.id == uw.word_id)?;
+                Some(DuplicateCandidate {
+                    user_word_id: uw.id,
+                    word_id: word.id,
+                    text: word.text.clone(),
+                    canonical_key: word.canonical_key.clone(),
+                })
+            })
+            .collect();
+        mine.sort_by(|a, b| a.canonical_key.as_str().cmp(b.canonical_key.as_str()));
+
+        let mut pairs = Vec::new();
+        for (index, first) in mine.iter().enumerate() {
+            for second in &mine[index + 1..] {
+                let similarity =
+                    trigram_similarity(first.canonical_key.as_str(), second.canonical_key.as_str());
+                if similarity >= threshold {
+                    pairs.push(NearDuplicatePair {
+                        first: first.clone(),
+                        second: second.clone(),
+                        similarity,
+                    });
+                }
+            }
+        }
+        // 稳定排序：相似度相同的保持字典序
+        pairs.sort_by(|a, b| b.similarity.total_cmp(&a.similarity));
+        pairs.truncate(limit.max(0) as usize);
+        Ok(pairs)
+    }
+
+    async fn find_words_by_ids(
+        &self,
+        word_ids: &[i64],
+    ) -> Result<Vec<WordRecord>, WordRepositoryError> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .words
+            .iter()
+            .filter(|word| word_ids.contains(&word.id))
+            .cloned()
+            .collect())
+    }
+
+    async fn find_user_words_by_word_ids(
+        &self,
+        user_id: i64,
+        word_ids: &[i64],
+    ) -> Result<Vec<UserWordAggregate>, WordRepositoryError> {
+        let state = self.state.lock().unwrap();
+        state
+            .user_words
+            .iter()
+            .filter(|row| row.user_id == user_id && word_ids.contains(&row.word_id))
+            .map(|row| state.aggregate(row))
+            .collect()
+    }
+
+    async fn list_user_words_after(
+        &self,
+        user_id: i64,
+        after: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<UserWordAggregate>, WordRepositoryError> {
+        let state = self.state.lock().unwrap();
+        let mut rows: Vec<&UserWordRow> = state
+            .user_words
+            .iter()
+            .filter(|row| row.user_id == user_id && after.is_none_or(|after| row.id > after))
+            .collect();
+        rows.sort_by_key(|row| row.id);
+        rows.into_iter()
+            .take(limit.max(0) as usize)
+            .map(|row| state.aggregate(row))
+            .collect()
+    }
+
+    async fn find_words_in_range(
+        &self,
+        user_id: i64,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<UserWordAggregate>, WordRepositoryError> {
+        let state = self.state.lock().unwrap();
+        let mut rows: Vec<&UserWordRow> = state
+            .user_words
+            .iter()
+            .filter(|row| row.user_id == user_id && in_range(row.created_at, from, to))
+            .collect();
+        rows.sort_by_key(|row| (row.created_at, row.id));
+        rows.into_iter()
+            .skip(offset.max(0) as usize)
+            .take(limit.max(0) as usize)
+            .map(|row| state.aggregate(row))
+            .collect()
+    }
+
+    async fn find_word_ids_in_range(
+        &self,
+        user_id: i64,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<i64>, WordRepositoryError> {
+        let state = self.state.lock().unwrap();
+        let mut rows: Vec<&UserWordRow> = state
+            .user_words
+            .iter()
+            .filter(|row| row.user_id == user_id && in_range(row.created_at, from, to))
+            .collect();
+        rows.sort_by_key(|row| (row.created_at, row.id));
+        Ok(rows.into_iter().map(|row| row.word_id).collect())
+    }
+
+    async fn count_words_in_range(
+        &self,
+        user_id: i64,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<i64, WordRepositoryError> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .user_words
+            .iter()
+            .filter(|row| row.user_id == user_id && in_range(row.created_at, from, to))
+            .count() as i64)
+    }
+
+    async fn list_changes_since(
+        &self,
+        user_id: i64,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<WordChanges, WordRepositoryError> {
+        let state = self.state.lock().unwrap();
+        let mut rows: Vec<&UserWordRow> = state
+            .user_words
+            .iter()
+            .filter(|row| row.user_id == user_id && row.updated_at > since)
+            .collect();
+        rows.sort_by_key(|row| (row.updated_at, row.id));
+        let updated = rows
+            .into_iter()
+            .take(limit.max(0) as usize)
+            .map(|row| state.aggregate(row))
+            .collect::<Result<Vec<_>, _>>()?;
+        let deleted = state
+            .tombstones
+            .iter()
+            .filter(|(owner, tombstone)| *owner == user_id && tombstone.deleted_at > since)
+            .map(|(_, tombstone)| tombstone.clone())
+            .take(limit.max(0) as usize)
+            .collect();
+        Ok(WordChanges { updated, deleted })
+    }
+
+    async fn update_tag(
+        &self,
+        user_id: i64,
+        user_word_ids: &[i64],
+        tag: &str,
+        action: TagAction,
+    ) -> Result<u64, WordRepositoryError> {
+        let mut state = self.state.lock().unwrap();
+        let mut changed = 0;
+        for row in state
+            .user_words
+            .iter_mut()
+            .filter(|uw| uw.user_id == user_id && user_word_ids.contains(&uw.id))
+        {
+            let present = row.tags.iter().any(|existing| existing == tag);
+            match action {
+                TagAction::Add if !present && row.tags.len() < MAX_TAGS => {
+                    row.tags.push(tag.to_string());
+                    row.updated_at = Utc::now();
+                    changed += 1;
+                }
+                TagAction::Remove if present => {
+                    row.tags.retain(|existing| existing != tag);
+                    row.updated_at = Utc::now();
+                    changed += 1;
+                }
+                _ => {}
+            }
+        }
+        Ok(changed)
+    }
+
+    async fn replace_tags(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        tags: &[String],
+    ) -> Result<bool, WordRepositoryError> {
+        let mut state = self.state.lock().unwrap();
+        let Some(row) = state
+            .user_words
+            .iter_mut()
+            .find(|uw| uw.user_id == user_id && uw.id == user_word_id)
+        else {
+            return Ok(false);
+        };
+        row.tags = tags.to_vec();
+        row.updated_at = Utc::now();
+        Ok(true)
+    }
+
+    async fn update_user_word_metadata(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        tags: &[String],
+        note: Option<&str>,
+    ) -> Result<bool, WordRepositoryError> {
+        let mut state = self.state.lock().unwrap();
+        let Some(row) = state
+            .user_words
+            .iter_mut()
+            .find(|uw| uw.user_id == user_id && uw.id == user_word_id)
+        else {
+            return Ok(false);
+        };
+        row.tags = tags.to_vec();
+        row.note = note.map(str::to_string);
+        row.updated_at = Utc::now();
+        Ok(true)
+    }
+
+    async fn repoint_user_word(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        word_id: i64,
+    ) -> Result<WordRepoint, WordRepositoryError> {
+        let mut state = self.state.lock().unwrap();
+        let Some(position) = state
+            .user_words
+            .iter()
+            .position(|uw| uw.user_id == user_id && uw.id == user_word_id)
+        else {
+            return Ok(WordRepoint::WordNotFound);
+        };
+        let previous_word_id = state.user_words[position].word_id;
+        if previous_word_id == word_id {
+            return Ok(WordRepoint::Moved { previous_word_id });
+        }
+        if let Some(existing) = state
+            .user_words
+            .iter()
+            .find(|uw| uw.user_id == user_id && uw.word_id == word_id)
+        {
+            return Ok(WordRepoint::Collision(existing.id));
+        }
+        let row = &mut state.user_words[position];
+        row.word_id = word_id;
+        row.repointed_from_word_id = Some(previous_word_id);
+        row.updated_at = Utc::now();
+        Ok(WordRepoint::Moved { previous_word_id })
+    }
+
+    async fn pending_word_repoint(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+    ) -> Result<Option<i64>, WordRepositoryError> {
+        let state = self.state.lock().unwrap();
+        Ok(state
+            .user_words
+            .iter()
+            .find(|uw| uw.user_id == user_id && uw.id == user_word_id)
+            .and_then(|uw| uw.repointed_from_word_id))
+    }
+
+    async fn complete_word_repoint(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        previous_word_id: i64,
+    ) -> Result<(), WordRepositoryError> {
+        let mut state = self.state.lock().unwrap();
+        if let Some(row) = state.user_words.iter_mut().find(|uw| {
+            uw.user_id == user_id
+                && uw.id == user_word_id
+                && uw.repointed_from_word_id == Some(previous_word_id)
+        }) {
+            row.repointed_from_word_id = None;
+        }
+        if !state.user_words.iter().any(|uw| {
+            uw.word_id == previous_word_id || uw.repointed_from_word_id == Some(previous_word_id)
+        }) {
+            state.words.retain(|word| word.id != previous_word_id);
+        }
+        Ok(())
+    }
+
+    async fn reorder_senses(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        sense_ids: &[i64],
+    ) -> Result<SenseReorder, WordRepositoryError> {
+        let mut state = self.state.lock().unwrap();
+        if !state
+            .user_words
+            .iter()
+            .any(|uw| uw.user_id == user_id && uw.id == user_word_id)
+        {
+            return Ok(SenseReorder::WordNotFound);
+        }
+        let mut current: Vec<i64> = state
+            .senses
+            .iter()
+            .filter(|s| s.user_word_id == user_word_id)
+            .map(|s| s.id)
+            .collect();
+        let mut requested = sense_ids.to_vec();
+        current.sort_unstable();
+        requested.sort_unstable();
+        if current != requested {
+            return Ok(SenseReorder::Mismatch);
+        }
+        for (position, sense_id) in sense_ids.iter().enumerate() {
+            if let Some(row) = state.senses.iter_mut().find(|s| s.id == *sense_id) {
+                row.sort_order = position as i32;
+            }
+        }
+        state.touch(user_word_id);
+        Ok(SenseReorder::Reordered)
+    }
+
+    async fn repair_primaries(&self, user_id: i64) -> Result<u64, WordRepositoryError> {
+        let mut state = self.state.lock().unwrap();
+        let user_word_ids: Vec<i64> = state
+            .user_words
+            .iter()
+            .filter(|uw| uw.user_id == user_id)
+            .map(|uw| uw.id)
+            .collect();
+        let mut repaired = 0;
+        for user_word_id in user_word_ids {
+            let mut senses: Vec<&mut SenseRow> = state
+                .senses
+                .iter_mut()
+                .filter(|s| s.user_word_id == user_word_id)
+                .collect();
+            if senses.is_empty() || senses.iter().filter(|s| s.is_primary).count() == 1 {
+                continue;
+            }
+            senses.sort_by_key(|s| (!s.is_primary, s.sort_order, s.id));
+            for (position, sense) in senses.into_iter().enumerate() {
+                sense.is_primary = position == 0;
+            }
+            state.touch(user_word_id);
+            repaired += 1;
+        }
+        Ok(repaired)
+    }
+}
+
+#[derive(Debug, Default)]
+struct GraphState {
+    word_links: Vec<WordLinkRecord>,
+    sense_links: Vec<SenseWordLinkRecord>,
+    sense_nodes: Vec<(i64, i64)>,
+    calls: usize,
+}
+
+/// Neo4j 仓储的内存替身；`failing()` 构造的实例对所有调用返回超时（`set_failing` 可随时切换），
+/// `disabled()` 构造的实例模拟关闭图子系统（仍记录调用次数），
+/// `ignoring_deletes()` 构造的实例模拟列表与删除结果不一致（删除始终返回 0）
+#[derive(Debug, Default, Clone)]
+pub(crate) struct InMemoryGraphRepository {
+    state: Arc<Mutex<GraphState>>,
+    fail: Arc<AtomicBool>,
+    disabled: bool,
+    ignore_deletes: bool,
+}
+
+impl InMemoryGraphRepository {
+    pub(crate) fn failing() -> Self {
+        Self {
+            fail: Arc::new(AtomicBool::new(true)),
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn disabled() -> Self {
+        Self {
+            disabled: true,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn ignoring_deletes() -> Self {
+        Self {
+            ignore_deletes: true,
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn set_failing(&self, fail: bool) {
+        self.fail.store(fail, Ordering::SeqCst);
+    }
+
+    /// Number of repository calls made so far, including failed ones.
+    pub(crate) fn calls(&self) -> usize {
+        self.state.lock().unwrap().calls
+    }
+
+    pub(crate) fn word_links(&self) -> Vec<WordLinkRecord> {
+        self.state.lock().unwrap().word_links.clone()
+    }
+
+    pub(crate) fn sense_links(&self) -> Vec<SenseWordLinkRecord> {
+        self.state.lock().unwrap().sense_links.clone()
+    }
+
+    fn enter(&self) -> GraphResult<std::sync::MutexGuard<'_, GraphState>> {
+        let mut state = self.state.lock().unwrap();
+        state.calls += 1;
+        if self.fail.load(Ordering::SeqCst) {
+            return Err(GraphRepositoryError::Timeout);
+        }
+        Ok(state)
+    }
+}
+
+fn page<T>(items: Vec<T>, offset: i64, limit: i64) -> Vec<T> {
+    items
+        .into_iter()
+        .skip(offset.max(0) as usize)
+        .take(limit.max(0) as usize)
+        .collect()
+}
+
+#[async_trait]
+impl GraphRepository for InMemoryGraphRepository {
+    async fn create_word_link(
+        &self,
+        user_id: i64,
+        word_a_id: i64,
+        word_b_id: i64,
+        kind: WordLinkKind,
+        note: Option<String>,
+    ) -> GraphResult<WordLinkRecord> {
+        if word_a_id == word_b_id {
+            return Err(GraphRepositoryError::Business(BusinessError::from(
+                LinkError::SelfForbidden,
+            )));
+        }
+        let (min_id, max_id) = (word_a_id.min(word_b_id), word_a_id.max(word_b_id));
+        let mut state = self.enter()?;
+        if let Some(existing) = state.word_links.iter_mut().find(|link| {
+            link.user_id == user_id
+                && link.kind == kind
+                && link.word_a_id == min_id
+                && link.word_b_id == max_id
+        }) {
+            if note.is_some() {
+                existing.note = note;
+            }
+            return Ok(existing.clone());
+        }
+        let record = WordLinkRecord {
+            link_id: uuid::Uuid::new_v4().to_string(),
+            user_id,
+            kind,
+            note,
+            created_at: Utc::now(),
+            word_a_id: min_id,
+            word_b_id: max_id,
+        };
+        state.word_links.push(record.clone());
+        Ok(record)
+    }
+
+    async fn delete_word_link(
+        &self,
+        user_id: i64,
+        word_a_id: i64,
+        word_b_id: i64,
+        kind: WordLinkKind,
+    ) -> GraphResult<u64> {
+        let (min_id, max_id) = (word_a_id.min(word_b_id), word_a_id.max(word_b_id));
+        let mut state = self.enter()?;
+        if self.ignore_deletes {
+            return Ok(0);
+        }
+        let before = state.word_links.len();
+        state.word_links.retain(|link| {
+            !(link.user_id == user_id
+                && link.kind == kind
+                && link.word_a_id == min_id
+                && link.word_b_id == max_id)
+        });
+        Ok((before - state.word_links.len()) as u64)
+    }
+
+    async fn update_word_link(
+        &self,
+        user_id: i64,
+        word_a_id: i64,
+        word_b_id: i64,
+        old_kind: WordLinkKind,
+        new_kind: Option<WordLinkKind>,
+        note: Option<Option<String>>,
+    ) -> GraphResult<WordLinkRecord> {
+        if word_a_id == word_b_id {
+            return Err(GraphRepositoryError::Business(BusinessError::from(
+                LinkError::SelfForbidden,
+            )));
+        }
+        let (min_id, max_id) = (word_a_id.min(word_b_id), word_a_id.max(word_b_id));
+        let mut state = self.enter()?;
+        let find = |links: &[WordLinkRecord], kind: WordLinkKind| {
+            links.iter().position(|link| {
+                link.user_id == user_id
+                    && link.kind == kind
+                    && link.word_a_id == min_id
+                    && link.word_b_id == max_id
+            })
+        };
+        let position = find(&state.word_links, old_kind).ok_or(GraphRepositoryError::Business(
+            BusinessError::from(LinkError::TargetNotFound),
+        ))?;
+        if let Some(kind) = new_kind
+            && kind != old_kind
+            && find(&state.word_links, kind).is_some()
+        {
+            return Err(GraphRepositoryError::Business(BusinessError::from(
+                LinkError::Exists,
+            )));
+        }
+        let link = &mut state.word_links[position];
+        if let Some(kind) = new_kind {
+            link.kind = kind;
+        }
+        if let Some(note) = note {
+            link.note = note;
+        }
+        let mut record = link.clone();
+        record.word_a_id = word_a_id;
+        record.word_b_id = word_b_id;
+        Ok(record)
+    }
+
+    async fn list_word_links(&self, filter: WordLinkFilter) -> GraphResult<Vec<WordLinkRecord>> {
+        let state = self.enter()?;
+        let links = state
+            .word_links
+            .iter()
+            .filter(|link| {
+                link.user_id == filter.user_id
+                    && (link.word_a_id == filter.word_id || link.word_b_id == filter.word_id)
+                    && filter.kind.is_none_or(|kind| link.kind == kind)
+            })
+            .cloned()
+            .collect();
+        Ok(page(links, filter.offset, filter.limit))
+    }
+
+    async fn get_word_links_between(
+        &self,
+        user_id: i64,
+        word_a_id: i64,
+        word_b_id: i64,
+    ) -> GraphResult<Vec<WordLinkRecord>> {
+        if word_a_id == word_b_id {
+            return Err(GraphRepositoryError::Business(BusinessError::from(
+                LinkError::SelfForbidden,
+            )));
+        }
+        let (min_id, max_id) = (word_a_id.min(word_b_id), word_a_id.max(word_b_id));
+        let state = self.enter()?;
+        let mut links: Vec<WordLinkRecord> = state
+            .word_links
+            .iter()
+            .filter(|link| {
+                link.user_id == user_id && link.word_a_id == min_id && link.word_b_id == max_id
+            })
+            .cloned()
+            .collect();
+        links.sort_by_key(|link| link.created_at);
+        Ok(links)
+    }
+
+    async fn list_word_links_from_words(
+        &self,
+        user_id: i64,
+        word_ids: &[i64],
+        limit: i64,
+    ) -> GraphResult<Vec<WordLinkRecord>> {
+        let state = self.enter()?;
+        let mut links: Vec<WordLinkRecord> = state
+            .word_links
+            .iter()
+            .filter(|link| link.user_id == user_id && word_ids.contains(&link.word_a_id))
+            .cloned()
+            .collect();
+        links.sort_by_key(|link| (link.word_a_id, link.word_b_id, link.created_at));
+        Ok(page(links, 0, limit))
+    }
+
+    async fn create_sense_word_link(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+        source_word_id: i64,
+        target_word_id: i64,
+        kind: SenseWordLinkKind,
+        note: Option<String>,
+    ) -> GraphResult<SenseWordLinkRecord> {
+        if source_word_id == target_word_id {
+            return Err(GraphRepositoryError::Business(BusinessError::from(
+                LinkError::SelfForbidden,
+            )));
+        }
+        let mut state = self.enter()?;
+        // 与 Neo4j 实现一致：义项已归属其它单词时不写入
+        if state.sense_links.iter().any(|link| {
+            link.user_id == user_id
+                && link.sense_id == sense_id
+                && link.source_word_id != source_word_id
+        }) {
+            return Err(GraphRepositoryError::Business(BusinessError::from(
+                LinkError::TargetNotFound,
+            )));
+        }
+        if let Some(existing) = state.sense_links.iter_mut().find(|link| {
+            link.user_id == user_id
+                && link.sense_id == sense_id
+                && link.target_word_id == target_word_id
+                && link.kind == kind
+        }) {
+            if note.is_some() {
+                existing.note = note;
+            }
+            return Ok(existing.clone());
+        }
+        let record = SenseWordLinkRecord {
+            link_id: uuid::Uuid::new_v4().to_string(),
+            user_id,
+            kind,
+            note,
+            created_at: Utc::now(),
+            sense_id,
+            source_word_id,
+            target_word_id,
+        };
+        state.sense_links.push(record.clone());
+        Ok(record)
+    }
+
+    async fn delete_sense_word_link(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+        target_word_id: i64,
+        kind: SenseWordLinkKind,
+    ) -> GraphResult<()> {
+        let mut state = self.enter()?;
+        state.sense_links.retain(|link| {
+            !(link.user_id == user_id
+                && link.sense_id == sense_id
+                && link.target_word_id == target_word_id
+                && link.kind == kind)
+        });
+        Ok(())
+    }
+
+    async fn list_sense_word_links(
+        &self,
+        filter: SenseLinkFilter,
+    ) -> GraphResult<Vec<SenseWordLinkRecord>> {
+        let state = self.enter()?;
+        let links = state
+            .sense_links
+            .iter()
+            .filter(|link| {
+                link.user_id == filter.user_id
+                    && link.sense_id == filter.sense_id
+                    && filter.kind.is_none_or(|kind| link.kind == kind)
+            })
+            .cloned()
+            .collect();
+        Ok(page(links, filter.offset, filter.limit))
+    }
+
+    async fn list_links_for_senses(
+        &self,
+        user_id: i64,
+        sense_ids: &[i64],
+        limit: i64,
+    ) -> GraphResult<Vec<SenseWordLinkRecord>> {
+        let state = self.enter()?;
+        let mut links: Vec<SenseWordLinkRecord> = state
+            .sense_links
+            .iter()
+            .filter(|link| link.user_id == user_id && sense_ids.contains(&link.sense_id))
+            .cloned()
+            .collect();
+        links.sort_by(|a, b| {
+            a.sense_id
+                .cmp(&b.sense_id)
+                .then(b.created_at.cmp(&a.created_at))
+        });
+        Ok(page(links, 0, limit))
+    }
+
+    async fn remove_links_for_sense(&self, sense_id: i64) -> GraphResult<()> {
+        let mut state = self.enter()?;
+        state.sense_links.retain(|link| link.sense_id != sense_id);
+        Ok(())
+    }
+
+    async fn neighbors(&self, query: NeighborQuery) -> GraphResult<Neighborhood> {
+        let state = self.enter()?;
+        let user_links: Vec<&WordLinkRecord> = state
+            .word_links
+            .iter()
+            .filter(|link| {
+                link.user_id == query.user_id && query.kind.is_none_or(|kind| link.kind == kind)
+            })
+            .collect();
+
+        let mut visited = HashSet::from([query.word_id]);
+        let mut word_ids = Vec::new();
+        let mut frontier = VecDeque::from([(query.word_id, 0u32)]);
+        while let Some((current, depth)) = frontier.pop_front() {
+            if depth >= query.depth {
+                continue;
+            }
+            for link in &user_links {
+                let next = if link.word_a_id == current {
+                    link.word_b_id
+                } else if link.word_b_id == current {
+                    link.word_a_id
+                } else {
+                    continue;
+                };
+                if visited.insert(next) {
+                    word_ids.push(next);
+                    frontier.push_back((next, depth + 1));
+                }
+            }
+        }
+        word_ids.truncate(query.limit.max(0) as usize);
+
+        let members: HashSet<i64> = word_ids
+            .iter()
+            .copied()
+            .chain(std::iter::once(query.word_id))
+            .collect();
+        let links = user_links
+            .into_iter()
+            .filter(|link| members.contains(&link.word_a_id) && members.contains(&link.word_b_id))
+            .cloned()
+            .collect();
+
+        Ok(Neighborhood {
+            word_ids,
+            links,
+            truncated: false,
+        })
+    }
+
+    async fn total_link_count(&self, user_id: i64) -> GraphResult<LinkCounts> {
+        let state = self.enter()?;
+        Ok(LinkCounts {
+            word_links: state
+                .word_links
+                .iter()
+                .filter(|link| link.user_id == user_id)
+                .count() as i64,
+            sense_links: state
+                .sense_links
+                .iter()
+                .filter(|link| link.user_id == user_id)
+                .count() as i64,
+        })
+    }
+
+    async fn link_stats_by_kind(&self, user_id: i64) -> GraphResult<LinkKindStats> {
+        let state = self.enter()?;
+        let mut stats = LinkKindStats::default();
+        for link in state
+            .word_links
+            .iter()
+            .filter(|link| link.user_id == user_id)
+        {
+            stats.add_word_links(link.kind, 1);
+        }
+        for link in state
+            .sense_links
+            .iter()
+            .filter(|link| link.user_id == user_id)
+        {
+            stats.add_sense_links(link.kind, 1);
+        }
+        Ok(stats)
+    }
+
+    async fn word_degrees(&self, user_id: i64, word_ids: &[i64]) -> GraphResult<HashMap<i64, i64>> {
+        let state = self.enter()?;
+        let mut degrees = HashMap::new();
+        for link in state
+            .word_links
+            .iter()
+            .filter(|link| link.user_id == user_id)
+        {
+            for id in [link.word_a_id, link.word_b_id] {
+                if word_ids.contains(&id) {
+                    *degrees.entry(id).or_insert(0) += 1;
+                }
+            }
+        }
+        for link in state
+            .sense_links
+            .iter()
+            .filter(|link| link.user_id == user_id)
+        {
+            for id in [link.source_word_id, link.target_word_id] {
+                if word_ids.contains(&id) {
+                    *degrees.entry(id).or_insert(0) += 1;
+                }
+            }
+        }
+        Ok(degrees)
+    }
+
+    async fn linked_word_ids(&self, user_id: i64, word_ids: &[i64]) -> GraphResult<HashSet<i64>> {
+        let state = self.enter()?;
+        let word_ends = state
+            .word_links
+            .iter()
+            .filter(|link| link.user_id == user_id)
+            .flat_map(|link| [link.word_a_id, link.word_b_id]);
+        let sense_ends = state
+            .sense_links
+            .iter()
+            .filter(|link| link.user_id == user_id)
+            .flat_map(|link| [link.source_word_id, link.target_word_id]);
+        Ok(word_ends
+            .chain(sense_ends)
+            .filter(|id| word_ids.contains(id))
+            .collect())
+    }
+
+    async fn repoint_word_links(
+        &self,
+        user_id: i64,
+        from_word_id: i64,
+        to_word_id: i64,
+    ) -> GraphResult<()> {
+        if from_word_id == to_word_id {
+            return Err(GraphRepositoryError::Business(BusinessError::from(
+                LinkError::SelfForbidden,
+            )));
+        }
+        let mut state = self.enter()?;
+        let remap = |id: i64| if id == from_word_id { to_word_id } else { id };
+
+        let (moved, mut kept): (Vec<_>, Vec<_>) = std::mem::take(&mut state.word_links)
+            .into_iter()
+            .partition(|link| {
+                link.user_id == user_id
+                    && (link.word_a_id == from_word_id || link.word_b_id == from_word_id)
+            });
+        for mut link in moved {
+            let (a, b) = (remap(link.word_a_id), remap(link.word_b_id));
+            if a == b {
+                continue;
+            }
+            (link.word_a_id, link.word_b_id) = (a.min(b), a.max(b));
+            match kept.iter_mut().find(|existing| {
+                existing.user_id == user_id
+                    && existing.kind == link.kind
+                    && existing.word_a_id == link.word_a_id
+                    && existing.word_b_id == link.word_b_id
+            }) {
+                Some(existing) => {
+                    existing.created_at = existing.created_at.min(link.created_at);
+                    existing.note = existing.note.take().or(link.note);
+                }
+                None => kept.push(link),
+            }
+        }
+        state.word_links = kept;
+
+        let (moved, mut kept): (Vec<_>, Vec<_>) = std::mem::take(&mut state.sense_links)
+            .into_iter()
+            .partition(|link| {
+                link.user_id == user_id
+                    && (link.source_word_id == from_word_id || link.target_word_id == from_word_id)
+            });
+        for mut link in moved {
+            link.source_word_id = remap(link.source_word_id);
+            link.target_word_id = remap(link.target_word_id);
+            if link.source_word_id == link.target_word_id {
+                continue;
+            }
+            match kept.iter_mut().find(|existing| {
+                existing.user_id == user_id
+                    && existing.sense_id == link.sense_id
+                    && existing.target_word_id == link.target_word_id
+                    && existing.kind == link.kind
+            }) {
+                Some(existing) => {
+                    existing.created_at = existing.created_at.min(link.created_at);
+                    existing.note = existing.note.take().or(link.note);
+                }
+                None => kept.push(link),
+            }
+        }
+        state.sense_links = kept;
+        Ok(())
+    }
+
+    async fn upsert_node_word(&self, _word_id: i64) -> GraphResult<()> {
+        self.enter().map(|_| ())
+    }
+
+    async fn upsert_node_sense(&self, sense_id: i64, user_id: i64) -> GraphResult<()> {
+        let mut state = self.enter()?;
+        if !state.sense_nodes.contains(&(sense_id, user_id)) {
+            state.sense_nodes.push((sense_id, user_id));
+        }
+        Ok(())
+    }
+
+    fn is_enabled(&self) -> bool {
+        !self.disabled
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub(crate) struct InMemoryPreferencesRepository {
+    preferences: Arc<Mutex<HashMap<i64, UserPreferences>>>,
+}
+
+#[async_trait]
+impl PreferencesRepository for InMemoryPreferencesRepository {
+    async fn get(&self, user_id: i64) -> Result<UserPreferences, PreferencesRepositoryError> {
+        Ok(self
+            .preferences
+            .lock()
+            .unwrap()
+            .get(&user_id)
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn set(
+        &self,
+        user_id: i64,
+        preferences: UserPreferences,
+    ) -> Result<UserPreferences, PreferencesRepositoryError> {
+        self.preferences
+            .lock()
+            .unwrap()
+            .insert(user_id, preferences.clone());
+        Ok(preferences)
+    }
+}
@@ -1,16 +1,39 @@
+pub mod cached;
 pub mod graph;
+#[cfg(test)]
+pub(crate) mod memory;
+pub mod preferences;
+pub mod rate_limit;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod token_store;
 pub mod user;
 pub mod word;
 
+#[allow(unused_imports)]
+pub use cached::CachedWordRepository;
 #[allow(unused_imports)]
 pub use graph::{
-    GraphRepository, GraphRepositoryError, Neo4jGraphRepository, SenseWordLinkRecord,
-    WordLinkRecord,
+    GraphRepository, GraphRepositoryError, Neo4jGraphRepository, NoopGraphRepository,
+    SenseWordLinkRecord, WordLinkRecord,
+};
+#[allow(unused_imports)]
+pub use preferences::{
+    PgPreferencesRepository, PreferencesRepository, PreferencesRepositoryError, UserPreferences,
+};
+#[allow(unused_imports)]
+pub use rate_limit::{InMemoryRateLimitStore, RateLimitStore};
+#[allow(unused_imports)]
+pub use token_store::{
+    InMemoryRefreshTokenStore, InMemoryTokenStore, PgRefreshTokenStore, PgTokenStore,
+    RefreshTokenStore, TokenStore, TokenStoreError,
 };
 #[allow(unused_imports)]
 pub use user::{NewUser, PgUserRepository, RepositoryError, UserRepository};
 #[allow(unused_imports)]
 pub use word::{
-    NewUserSense, PgWordRepository, SearchParams, SearchScope, SenseUpdate, UpsertUserWord,
-    UserWordAggregate, WordRecord, WordRepository, WordRepositoryError,
+    ImportSense, ImportUserWord, MatchSide, NewUserSense, OwnedResource, PgWordRepository,
+    SearchMatch, SearchParams, SearchScope, SenseOrder, SenseReorder, SenseUpdate, TagAction,
+    UpsertUserWord, UpsertedUserWord, UserWordAggregate, WordRecord, WordRepoint, WordRepository,
+    WordRepositoryError,
 };
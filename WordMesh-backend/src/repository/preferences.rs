@@ -0,0 +1,130 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use sqlx::PgPool;
+use thiserror::Error;
+
+use crate::repository::word::{SearchScope, SearchSort};
+use crate::util::timing::{Dependency, timed};
+
+#[derive(Debug, Error)]
+pub enum PreferencesRepositoryError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("invalid stored preferences: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// 存储于 user_preferences.settings 的用户偏好；未设置的项为 None
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserPreferences {
+    #[serde(default)]
+    pub default_scope: Option<SearchScope>,
+    #[serde(default)]
+    pub default_sort: Option<SearchSort>,
+}
+
+#[async_trait]
+pub trait PreferencesRepository {
+    /// Returns the stored preferences, or the defaults when the user has none.
+    async fn get(&self, user_id: i64) -> Result<UserPreferences, PreferencesRepositoryError>;
+    async fn set(
+        &self,
+        user_id: i64,
+        preferences: UserPreferences,
+    ) -> Result<UserPreferences, PreferencesRepositoryError>;
+}
+
+#[derive(Clone)]
+pub struct PgPreferencesRepository {
+    pool: PgPool,
+}
+
+impl PgPreferencesRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PreferencesRepository for PgPreferencesRepository {
+    async fn get(&self, user_id: i64) -> Result<UserPreferences, PreferencesRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let settings: Option<JsonValue> =
+                sqlx::query_scalar("SELECT settings FROM user_preferences WHERE user_id = $1")
+                    .bind(user_id)
+                    .fetch_optional(&self.pool)
+                    .await?;
+
+            match settings {
+                Some(value) => Ok(serde_json::from_value(value)?),
+                None => Ok(UserPreferences::default()),
+            }
+        })
+        .await
+    }
+
+    async fn set(
+        &self,
+        user_id: i64,
+        preferences: UserPreferences,
+    ) -> Result<UserPreferences, PreferencesRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let settings: JsonValue = sqlx::query_scalar(
+                r#"
+                INSERT INTO user_preferences (user_id, settings)
+                VALUES ($1, $2)
+                ON CONFLICT (user_id)
+                DO UPDATE SET settings = EXCLUDED.settings, updated_at = NOW()
+                RETURNING settings
+                "#,
+            )
+            .bind(user_id)
+            .bind(serde_json::to_value(&preferences)?)
+            .fetch_one(&self.pool)
+            .await?;
+
+            Ok(serde_json::from_value(settings)?)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::test_support::{insert_user, migrate};
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn get_returns_defaults_until_preferences_are_set(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "prefs_owner").await;
+        let other_id = insert_user(&pool, "prefs_other").await;
+        let repo = PgPreferencesRepository::new(pool);
+
+        assert_eq!(repo.get(user_id).await.unwrap(), UserPreferences::default());
+
+        let preferences = UserPreferences {
+            default_scope: Some(SearchScope::Sense),
+            default_sort: Some(SearchSort::Recent),
+        };
+        assert_eq!(
+            repo.set(user_id, preferences.clone()).await.unwrap(),
+            preferences
+        );
+        assert_eq!(repo.get(user_id).await.unwrap(), preferences);
+        assert_eq!(
+            repo.get(other_id).await.unwrap(),
+            UserPreferences::default()
+        );
+
+        // 再次写入整体覆盖，未提供的项恢复为 None
+        let cleared = UserPreferences {
+            default_scope: None,
+            default_sort: Some(SearchSort::Alphabetical),
+        };
+        repo.set(user_id, cleared.clone()).await.unwrap();
+        assert_eq!(repo.get(user_id).await.unwrap(), cleared);
+    }
+}
@@ -0,0 +1,39 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+/// 按 user_id 计数的滑动窗口限流存储；多实例部署时可替换为共享存储的实现
+#[async_trait]
+pub trait RateLimitStore {
+    /// 在 `window` 内为该用户记一次；已达到 `max` 次时不计数，返回距最早一次过期还需等待的时长
+    async fn try_acquire(&self, user_id: i64, max: u32, window: Duration) -> Result<(), Duration>;
+}
+
+/// 进程内的滑动窗口日志，仅适用于单实例
+#[derive(Debug, Default)]
+pub struct InMemoryRateLimitStore {
+    hits: Mutex<HashMap<i64, VecDeque<Instant>>>,
+}
+
+#[async_trait]
+impl RateLimitStore for InMemoryRateLimitStore {
+    async fn try_acquire(&self, user_id: i64, max: u32, window: Duration) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut hits = self.hits.lock().unwrap();
+        let log = hits.entry(user_id).or_default();
+        while log
+            .front()
+            .is_some_and(|hit| now.duration_since(*hit) >= window)
+        {
+            log.pop_front();
+        }
+        if log.len() >= max as usize {
+            let oldest = log.front().copied().unwrap_or(now);
+            return Err(window.saturating_sub(now.duration_since(oldest)));
+        }
+        log.push_back(now);
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+//! Helpers for repository tests that run against a real Postgres.
+//!
+//! These tests are `#[ignore]`d by default; run them with
+//! `DATABASE_URL=postgres://... cargo test -- --ignored`.
+
+use sqlx::PgPool;
+
+/// 依次执行测试用 users 表迁移与正式迁移
+pub(crate) async fn migrate(pool: &PgPool) {
+    let mut fixtures = sqlx::migrate!("./tests/migrations");
+    fixtures.set_ignore_missing(true);
+    fixtures.run(pool).await.expect("test migrations failed");
+
+    let mut migrator = sqlx::migrate!("./migrations");
+    migrator.set_ignore_missing(true);
+    migrator.run(pool).await.expect("migrations failed");
+}
+
+pub(crate) async fn insert_user(pool: &PgPool, username: &str) -> i64 {
+    sqlx::query_scalar("INSERT INTO users (username, password) VALUES ($1, 'x') RETURNING id")
+        .bind(username)
+        .fetch_one(pool)
+        .await
+        .expect("insert user failed")
+}
@@ -0,0 +1,308 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use thiserror::Error;
+
+use crate::util::timing::{Dependency, timed};
+
+#[derive(Debug, Error)]
+pub enum TokenStoreError {
+    #[error("database error: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// 已吊销令牌（按 jti）的黑名单；记录只需保留到令牌自身过期
+#[async_trait]
+pub trait TokenStore {
+    /// 吊销 `jti`，`expires_at` 之后该记录可被清理；重复吊销不报错
+    async fn revoke(&self, jti: &str, expires_at: DateTime<Utc>) -> Result<(), TokenStoreError>;
+    async fn is_revoked(&self, jti: &str) -> Result<bool, TokenStoreError>;
+}
+
+/// refresh token 轮换记录：每个 jti 只能兑换一次，重放时吊销同一次登录派生出的整个家族
+#[async_trait]
+pub trait RefreshTokenStore {
+    /// 标记 `jti` 已兑换；此前已兑换过（重放）时返回 false
+    async fn consume(
+        &self,
+        family: &str,
+        jti: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, TokenStoreError>;
+    /// 吊销整个家族，`expires_at` 应不早于家族中最晚签发的令牌的过期时间
+    async fn revoke_family(
+        &self,
+        family: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), TokenStoreError>;
+    async fn is_family_revoked(&self, family: &str) -> Result<bool, TokenStoreError>;
+}
+
+/// 进程内黑名单，仅适用于单实例；写入时顺带清理已过期的记录
+#[derive(Debug, Default)]
+pub struct InMemoryTokenStore {
+    revoked: Mutex<HashMap<String, DateTime<Utc>>>,
+}
+
+#[async_trait]
+impl TokenStore for InMemoryTokenStore {
+    async fn revoke(&self, jti: &str, expires_at: DateTime<Utc>) -> Result<(), TokenStoreError> {
+        let now = Utc::now();
+        let mut revoked = self.revoked.lock().unwrap();
+        revoked.retain(|_, expiry| *expiry > now);
+        revoked.insert(jti.to_string(), expires_at);
+        Ok(())
+    }
+
+    async fn is_revoked(&self, jti: &str) -> Result<bool, TokenStoreError> {
+        let revoked = self.revoked.lock().unwrap();
+        Ok(revoked
+            .get(jti)
+            .is_some_and(|expires_at| *expires_at > Utc::now()))
+    }
+}
+
+/// 进程内的轮换记录，仅适用于单实例
+#[derive(Debug, Default)]
+pub struct InMemoryRefreshTokenStore {
+    consumed: Mutex<HashMap<String, DateTime<Utc>>>,
+    revoked_families: Mutex<HashMap<String, DateTime<Utc>>>,
+}
+
+#[async_trait]
+impl RefreshTokenStore for InMemoryRefreshTokenStore {
+    async fn consume(
+        &self,
+        _family: &str,
+        jti: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, TokenStoreError> {
+        let now = Utc::now();
+        let mut consumed = self.consumed.lock().unwrap();
+        consumed.retain(|_, expiry| *expiry > now);
+        Ok(consumed.insert(jti.to_string(), expires_at).is_none())
+    }
+
+    async fn revoke_family(
+        &self,
+        family: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), TokenStoreError> {
+        let now = Utc::now();
+        let mut revoked = self.revoked_families.lock().unwrap();
+        revoked.retain(|_, expiry| *expiry > now);
+        revoked.insert(family.to_string(), expires_at);
+        Ok(())
+    }
+
+    async fn is_family_revoked(&self, family: &str) -> Result<bool, TokenStoreError> {
+        let revoked = self.revoked_families.lock().unwrap();
+        Ok(revoked
+            .get(family)
+            .is_some_and(|expires_at| *expires_at > Utc::now()))
+    }
+}
+
+#[derive(Clone)]
+pub struct PgTokenStore {
+    pool: PgPool,
+}
+
+impl PgTokenStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TokenStore for PgTokenStore {
+    async fn revoke(&self, jti: &str, expires_at: DateTime<Utc>) -> Result<(), TokenStoreError> {
+        timed(Dependency::Postgres, async {
+            sqlx::query("DELETE FROM revoked_tokens WHERE expires_at <= NOW()")
+                .execute(&self.pool)
+                .await?;
+            sqlx::query(
+                r#"
+                INSERT INTO revoked_tokens (jti, expires_at)
+                VALUES ($1, $2)
+                ON CONFLICT (jti) DO NOTHING
+                "#,
+            )
+            .bind(jti)
+            .bind(expires_at)
+            .execute(&self.pool)
+            .await?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn is_revoked(&self, jti: &str) -> Result<bool, TokenStoreError> {
+        timed(Dependency::Postgres, async {
+            let revoked: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM revoked_tokens WHERE jti = $1 AND expires_at > NOW())",
+            )
+            .bind(jti)
+            .fetch_one(&self.pool)
+            .await?;
+            Ok(revoked)
+        })
+        .await
+    }
+}
+
+#[derive(Clone)]
+pub struct PgRefreshTokenStore {
+    pool: PgPool,
+}
+
+impl PgRefreshTokenStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RefreshTokenStore for PgRefreshTokenStore {
+    async fn consume(
+        &self,
+        family: &str,
+        jti: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<bool, TokenStoreError> {
+        timed(Dependency::Postgres, async {
+            sqlx::query("DELETE FROM consumed_refresh_tokens WHERE expires_at <= NOW()")
+                .execute(&self.pool)
+                .await?;
+            // 主键冲突即重放；并发兑换同一令牌时只有一方能插入成功
+            let result = sqlx::query(
+                r#"
+                INSERT INTO consumed_refresh_tokens (jti, family, expires_at)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (jti) DO NOTHING
+                "#,
+            )
+            .bind(jti)
+            .bind(family)
+            .bind(expires_at)
+            .execute(&self.pool)
+            .await?;
+            Ok(result.rows_affected() == 1)
+        })
+        .await
+    }
+
+    async fn revoke_family(
+        &self,
+        family: &str,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(), TokenStoreError> {
+        timed(Dependency::Postgres, async {
+            sqlx::query("DELETE FROM revoked_token_families WHERE expires_at <= NOW()")
+                .execute(&self.pool)
+                .await?;
+            sqlx::query(
+                r#"
+                INSERT INTO revoked_token_families (family, expires_at)
+                VALUES ($1, $2)
+                ON CONFLICT (family)
+                DO UPDATE SET expires_at = GREATEST(revoked_token_families.expires_at, EXCLUDED.expires_at)
+                "#,
+            )
+            .bind(family)
+            .bind(expires_at)
+            .execute(&self.pool)
+            .await?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn is_family_revoked(&self, family: &str) -> Result<bool, TokenStoreError> {
+        timed(Dependency::Postgres, async {
+            let revoked: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM revoked_token_families WHERE family = $1 AND expires_at > NOW())",
+            )
+            .bind(family)
+            .fetch_one(&self.pool)
+            .await?;
+            Ok(revoked)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::test_support::migrate;
+    use chrono::Duration;
+
+    #[actix_rt::test]
+    async fn in_memory_store_forgets_expired_revocations() {
+        let store = InMemoryTokenStore::default();
+        store
+            .revoke("live", Utc::now() + Duration::minutes(5))
+            .await
+            .unwrap();
+        store
+            .revoke("stale", Utc::now() - Duration::seconds(1))
+            .await
+            .unwrap();
+
+        assert!(store.is_revoked("live").await.unwrap());
+        assert!(!store.is_revoked("stale").await.unwrap());
+        assert!(!store.is_revoked("unknown").await.unwrap());
+    }
+
+    #[actix_rt::test]
+    async fn in_memory_refresh_store_detects_replay() {
+        let store = InMemoryRefreshTokenStore::default();
+        let expires_at = Utc::now() + Duration::minutes(5);
+        assert!(store.consume("fam", "a", expires_at).await.unwrap());
+        assert!(!store.consume("fam", "a", expires_at).await.unwrap());
+        assert!(store.consume("fam", "b", expires_at).await.unwrap());
+
+        assert!(!store.is_family_revoked("fam").await.unwrap());
+        store.revoke_family("fam", expires_at).await.unwrap();
+        assert!(store.is_family_revoked("fam").await.unwrap());
+        assert!(!store.is_family_revoked("other").await.unwrap());
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn pg_refresh_store_detects_replay_and_revokes_family(pool: PgPool) {
+        migrate(&pool).await;
+        let store = PgRefreshTokenStore::new(pool);
+        let expires_at = Utc::now() + Duration::minutes(5);
+        assert!(store.consume("fam-1", "jti-a", expires_at).await.unwrap());
+        assert!(!store.consume("fam-1", "jti-a", expires_at).await.unwrap());
+
+        store.revoke_family("fam-1", expires_at).await.unwrap();
+        store.revoke_family("fam-1", expires_at).await.unwrap();
+        assert!(store.is_family_revoked("fam-1").await.unwrap());
+        assert!(!store.is_family_revoked("fam-2").await.unwrap());
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn pg_store_revokes_until_expiry(pool: PgPool) {
+        migrate(&pool).await;
+        let store = PgTokenStore::new(pool);
+        let expires_at = Utc::now() + Duration::minutes(5);
+        store.revoke("jti-1", expires_at).await.unwrap();
+        // 重复吊销（例如重复登出）是幂等的
+        store.revoke("jti-1", expires_at).await.unwrap();
+        store
+            .revoke("jti-old", Utc::now() - Duration::seconds(1))
+            .await
+            .unwrap();
+
+        assert!(store.is_revoked("jti-1").await.unwrap());
+        assert!(!store.is_revoked("jti-old").await.unwrap());
+        assert!(!store.is_revoked("jti-2").await.unwrap());
+    }
+}
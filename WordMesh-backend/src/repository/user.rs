@@ -1,4 +1,5 @@
 use crate::domain::{HashedPassword, User, UserDomainError};
+use crate::util::timing::{Dependency, timed};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use sqlx::{PgPool, Row};
@@ -9,12 +10,67 @@ pub trait UserRepository {
     async fn create_user(&self, new_user: NewUser) -> Result<User, RepositoryError>;
     async fn find_by_username(&self, username: &str) -> Result<Option<User>, RepositoryError>;
     async fn find_by_id(&self, user_id: i64) -> Result<Option<User>, RepositoryError>;
+    /// 邮箱比较不区分大小写
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, RepositoryError>;
+    async fn update_password(
+        &self,
+        user_id: i64,
+        password_hash: HashedPassword,
+    ) -> Result<(), RepositoryError>;
+    /// 写入新密码并记录修改时间，使此前签发的会话失效
+    async fn reset_password(
+        &self,
+        user_id: i64,
+        password_hash: HashedPassword,
+    ) -> Result<(), RepositoryError>;
+    async fn find_stats(&self, user_id: i64) -> Result<UserStats, RepositoryError>;
+    async fn store_one_time_token(&self, token: NewOneTimeToken) -> Result<(), RepositoryError>;
+    /// 原子地消费一次性令牌：未过期且未使用时标记为已用并返回所属用户
+    async fn consume_one_time_token(
+        &self,
+        purpose: TokenPurpose,
+        token_hash: &str,
+    ) -> Result<Option<i64>, RepositoryError>;
+    async fn mark_email_verified(&self, user_id: i64) -> Result<(), RepositoryError>;
+}
+
+/// 一次性令牌的用途，不同用途的令牌互不通用
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenPurpose {
+    EmailVerification,
+    PasswordReset,
+}
+
+impl TokenPurpose {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TokenPurpose::EmailVerification => "email_verification",
+            TokenPurpose::PasswordReset => "password_reset",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NewOneTimeToken {
+    pub user_id: i64,
+    pub purpose: TokenPurpose,
+    /// 令牌的 SHA-256 摘要，原文只交给用户
+    pub token_hash: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// 用户网络的规模统计，供管理端查看
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UserStats {
+    pub word_count: i64,
+    pub sense_count: i64,
 }
 
 #[derive(Debug, Clone)]
 pub struct NewUser {
     pub username: String,
     pub password_hash: HashedPassword,
+    pub email: Option<String>,
 }
 
 pub struct PgUserRepository {
@@ -38,50 +94,208 @@ pub enum RepositoryError {
 #[async_trait]
 impl UserRepository for PgUserRepository {
     async fn create_user(&self, new_user: NewUser) -> Result<User, RepositoryError> {
-        let record = sqlx::query(
-            r#"
-            INSERT INTO users (username, password, created_at)
-            VALUES ($1, $2, $3)
-            RETURNING id, username, password, created_at
-            "#,
-        )
-        .bind(new_user.username)
-        .bind(new_user.password_hash.as_str())
-        .bind(Utc::now())
-        .fetch_one(&self.pool)
-        .await?;
-
-        map_row_to_user(record)
+        timed(Dependency::Postgres, async {
+            let record = sqlx::query(
+                r#"
+                INSERT INTO users (username, password, created_at, email)
+                VALUES ($1, $2, $3, $4)
+                RETURNING id, username, password, created_at, email, email_verified, password_changed_at
+                "#,
+            )
+            .bind(new_user.username)
+            .bind(new_user.password_hash.as_str())
+            .bind(Utc::now())
+            .bind(new_user.email)
+            .fetch_one(&self.pool)
+            .await?;
+
+            map_row_to_user(record)
+        })
+        .await
     }
 
     async fn find_by_username(&self, username: &str) -> Result<Option<User>, RepositoryError> {
-        let maybe_row = sqlx::query(
-            r#"
-            SELECT id, username, password, created_at
-            FROM users
-            WHERE username = $1
-            "#,
-        )
-        .bind(username)
-        .fetch_optional(&self.pool)
-        .await?;
-
-        maybe_row.map(map_row_to_user).transpose()
+        timed(Dependency::Postgres, async {
+            let maybe_row = sqlx::query(
+                r#"
+                SELECT id, username, password, created_at, email, email_verified, password_changed_at
+                FROM users
+                WHERE username = $1
+                "#,
+            )
+            .bind(username)
+            .fetch_optional(&self.pool)
+            .await?;
+
+            maybe_row.map(map_row_to_user).transpose()
+        })
+        .await
     }
 
     async fn find_by_id(&self, user_id: i64) -> Result<Option<User>, RepositoryError> {
-        let maybe_row = sqlx::query(
-            r#"
-            SELECT id, username, password, created_at
-            FROM users
-            WHERE id = $1
-            "#,
-        )
-        .bind(user_id)
-        .fetch_optional(&self.pool)
-        .await?;
-
-        maybe_row.map(map_row_to_user).transpose()
+        timed(Dependency::Postgres, async {
+            let maybe_row = sqlx::query(
+                r#"
+                SELECT id, username, password, created_at, email, email_verified, password_changed_at
+                FROM users
+                WHERE id = $1
+                "#,
+            )
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+            maybe_row.map(map_row_to_user).transpose()
+        })
+        .await
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, RepositoryError> {
+        timed(Dependency::Postgres, async {
+            let maybe_row = sqlx::query(
+                r#"
+                SELECT id, username, password, created_at, email, email_verified, password_changed_at
+                FROM users
+                WHERE lower(email) = lower($1)
+                "#,
+            )
+            .bind(email)
+            .fetch_optional(&self.pool)
+            .await?;
+
+            maybe_row.map(map_row_to_user).transpose()
+        })
+        .await
+    }
+
+    async fn update_password(
+        &self,
+        user_id: i64,
+        password_hash: HashedPassword,
+    ) -> Result<(), RepositoryError> {
+        timed(Dependency::Postgres, async {
+            sqlx::query(
+                r#"
+                UPDATE users
+                SET password = $1
+                WHERE id = $2
+                "#,
+            )
+            .bind(password_hash.as_str())
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn reset_password(
+        &self,
+        user_id: i64,
+        password_hash: HashedPassword,
+    ) -> Result<(), RepositoryError> {
+        timed(Dependency::Postgres, async {
+            sqlx::query(
+                r#"
+                UPDATE users
+                SET password = $1, password_changed_at = NOW()
+                WHERE id = $2
+                "#,
+            )
+            .bind(password_hash.as_str())
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn find_stats(&self, user_id: i64) -> Result<UserStats, RepositoryError> {
+        timed(Dependency::Postgres, async {
+            let row = sqlx::query(
+                r#"
+                SELECT
+                    COUNT(DISTINCT uw.id) AS word_count,
+                    COUNT(us.id) AS sense_count
+                FROM user_words uw
+                LEFT JOIN user_senses us ON us.user_word_id = uw.id
+                WHERE uw.user_id = $1
+                "#,
+            )
+            .bind(user_id)
+            .fetch_one(&self.pool)
+            .await?;
+
+            Ok(UserStats {
+                word_count: row.try_get("word_count")?,
+                sense_count: row.try_get("sense_count")?,
+            })
+        })
+        .await
+    }
+
+    async fn store_one_time_token(&self, token: NewOneTimeToken) -> Result<(), RepositoryError> {
+        timed(Dependency::Postgres, async {
+            sqlx::query(
+                r#"
+                INSERT INTO user_tokens (user_id, purpose, token_hash, expires_at)
+                VALUES ($1, $2, $3, $4)
+                "#,
+            )
+            .bind(token.user_id)
+            .bind(token.purpose.as_str())
+            .bind(token.token_hash)
+            .bind(token.expires_at)
+            .execute(&self.pool)
+            .await?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn consume_one_time_token(
+        &self,
+        purpose: TokenPurpose,
+        token_hash: &str,
+    ) -> Result<Option<i64>, RepositoryError> {
+        timed(Dependency::Postgres, async {
+            let user_id = sqlx::query_scalar(
+                r#"
+                UPDATE user_tokens
+                SET consumed_at = NOW()
+                WHERE token_hash = $1
+                  AND purpose = $2
+                  AND consumed_at IS NULL
+                  AND expires_at > NOW()
+                RETURNING user_id
+                "#,
+            )
+            .bind(token_hash)
+            .bind(purpose.as_str())
+            .fetch_optional(&self.pool)
+            .await?;
+            Ok(user_id)
+        })
+        .await
+    }
+
+    async fn mark_email_verified(&self, user_id: i64) -> Result<(), RepositoryError> {
+        timed(Dependency::Postgres, async {
+            sqlx::query(
+                r#"
+                UPDATE users
+                SET email_verified = TRUE
+                WHERE id = $1
+                "#,
+            )
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+            Ok(())
+        })
+        .await
     }
 }
 
@@ -90,8 +304,113 @@ fn map_row_to_user(row: sqlx::postgres::PgRow) -> Result<User, RepositoryError>
     let username: String = row.try_get("username")?;
     let password: String = row.try_get("password")?;
     let created_at: DateTime<Utc> = row.try_get("created_at")?;
+    let email: Option<String> = row.try_get("email")?;
+    let email_verified: bool = row.try_get("email_verified")?;
+    let password_changed_at: Option<DateTime<Utc>> = row.try_get("password_changed_at")?;
 
     let hashed_password = HashedPassword::new(password).map_err(UserDomainError::from)?;
 
-    Ok(User::new(id, username, hashed_password, created_at)?)
+    let mut user =
+        User::new(id, username, hashed_password, created_at)?.with_email(email, email_verified);
+    user.password_changed_at = password_changed_at;
+    Ok(user)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::test_support::migrate;
+    use chrono::Duration;
+
+    async fn create(repo: &PgUserRepository, username: &str) -> User {
+        repo.create_user(NewUser {
+            username: username.into(),
+            password_hash: HashedPassword::new("hash".into()).unwrap(),
+            email: Some(format!("{username}@example.com")),
+        })
+        .await
+        .unwrap()
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn one_time_token_is_consumed_once_and_only_before_expiry(pool: PgPool) {
+        migrate(&pool).await;
+        let repo = PgUserRepository::new(pool);
+        let user = create(&repo, "verifier").await;
+        assert_eq!(user.email.as_deref(), Some("verifier@example.com"));
+        assert!(!user.email_verified);
+
+        for (hash, ttl) in [("a".repeat(64), 60), ("b".repeat(64), -60)] {
+            repo.store_one_time_token(NewOneTimeToken {
+                user_id: user.id,
+                purpose: TokenPurpose::EmailVerification,
+                token_hash: hash,
+                expires_at: Utc::now() + Duration::seconds(ttl),
+            })
+            .await
+            .unwrap();
+        }
+
+        let purpose = TokenPurpose::EmailVerification;
+        let live = "a".repeat(64);
+        assert_eq!(
+            repo.consume_one_time_token(purpose, &live).await.unwrap(),
+            Some(user.id)
+        );
+        assert_eq!(
+            repo.consume_one_time_token(purpose, &live).await.unwrap(),
+            None
+        );
+        let expired = "b".repeat(64);
+        assert_eq!(
+            repo.consume_one_time_token(purpose, &expired)
+                .await
+                .unwrap(),
+            None
+        );
+
+        repo.mark_email_verified(user.id).await.unwrap();
+        let stored = repo.find_by_id(user.id).await.unwrap().unwrap();
+        assert!(stored.email_verified);
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn reset_password_records_change_and_email_lookup_ignores_case(pool: PgPool) {
+        migrate(&pool).await;
+        let repo = PgUserRepository::new(pool);
+        let user = create(&repo, "resetter").await;
+        assert!(user.password_changed_at.is_none());
+
+        let found = repo
+            .find_by_email("Resetter@Example.com")
+            .await
+            .unwrap()
+            .expect("user by email");
+        assert_eq!(found.id, user.id);
+
+        repo.store_one_time_token(NewOneTimeToken {
+            user_id: user.id,
+            purpose: TokenPurpose::PasswordReset,
+            token_hash: "c".repeat(64),
+            expires_at: Utc::now() + Duration::seconds(60),
+        })
+        .await
+        .unwrap();
+        // 不同用途的令牌不能互相消费
+        assert_eq!(
+            repo.consume_one_time_token(TokenPurpose::EmailVerification, &"c".repeat(64))
+                .await
+                .unwrap(),
+            None
+        );
+
+        repo.reset_password(user.id, HashedPassword::new("new-hash".into()).unwrap())
+            .await
+            .unwrap();
+        let stored = repo.find_by_id(user.id).await.unwrap().unwrap();
+        assert_eq!(stored.password_hash.as_str(), "new-hash");
+        assert!(stored.password_changed_at.is_some());
+    }
 }
@@ -1,10 +1,14 @@
 use crate::domain::word::{UserSense, UserSenseError, UserWord, UserWordError};
 use crate::domain::{CanonicalKey, CanonicalKeyError};
+use crate::util::timing::{Dependency, timed};
+use crate::util::validation::{MAX_TAGS, TextLimits};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use sqlx::{PgPool, Row, postgres::PgRow};
+use std::cmp::Reverse;
+use std::collections::HashMap;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -17,6 +21,9 @@ pub enum WordRepositoryError {
     UserSense(#[from] UserSenseError),
     #[error("canonical key error: {0}")]
     Canonical(#[from] CanonicalKeyError),
+    /// 写入成功后读回时记录已不存在（被并发事务删除）
+    #[error("user word {0} was removed before it could be read back")]
+    RemovedConcurrently(i64),
 }
 
 #[derive(Debug, Clone)]
@@ -31,6 +38,55 @@ pub struct WordRecord {
 pub struct UserWordAggregate {
     pub word: WordRecord,
     pub user_word: UserWord,
+    /// 列表查询按上限截断了义项时为 true；单词详情始终包含全部义项
+    pub has_more_senses: bool,
+}
+
+/// 近似重复建议中的一个单词
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateCandidate {
+    pub user_word_id: i64,
+    pub word_id: i64,
+    pub text: String,
+    pub canonical_key: CanonicalKey,
+}
+
+/// canonical_key 三元组相似度达到阈值的一对单词；`first` 的 canonical_key 按字典序在前
+#[derive(Debug, Clone, PartialEq)]
+pub struct NearDuplicatePair {
+    pub first: DuplicateCandidate,
+    pub second: DuplicateCandidate,
+    pub similarity: f32,
+}
+
+/// 从网络中移除的单词，供增量同步通知客户端删除本地副本
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordTombstone {
+    pub user_word_id: i64,
+    pub word_id: i64,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// 某个时间点之后发生的变化：`updated` 为新增或修改过（含义项变化）的单词，内嵌全部义项
+#[derive(Debug, Clone, Default)]
+pub struct WordChanges {
+    pub updated: Vec<UserWordAggregate>,
+    pub deleted: Vec<WordTombstone>,
+}
+
+/// `upsert_user_word` 的结果；`created` 为 false 表示已存在并被合并更新
+#[derive(Debug, Clone)]
+pub struct UpsertedUserWord {
+    pub aggregate: UserWordAggregate,
+    pub created: bool,
+}
+
+/// `import_user_words` 中一项的结果；`deduplicated_senses` 为因单词已有同文本义项而跳过的义项数
+#[derive(Debug, Clone)]
+pub struct ImportedUserWord {
+    pub aggregate: UserWordAggregate,
+    pub created: bool,
+    pub deduplicated_senses: u32,
 }
 
 #[derive(Debug, Clone)]
@@ -42,6 +98,51 @@ pub struct UpsertUserWord {
     pub note: Option<String>,
 }
 
+/// 批量导入的一项；`senses` 在单词写入后按顺序挂到对应的 user_word 下，
+/// 与单词已有义项文本相同的跳过
+#[derive(Debug, Clone)]
+pub struct ImportUserWord {
+    pub word: UpsertUserWord,
+    pub senses: Vec<ImportSense>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportSense {
+    pub text: String,
+    pub is_primary: bool,
+    pub sort_order: i32,
+    pub note: Option<String>,
+    pub source_url: Option<String>,
+}
+
+/// `reorder_senses` 的结果；后两种情况不做任何修改
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SenseReorder {
+    Reordered,
+    /// 单词不存在或不属于该用户
+    WordNotFound,
+    /// 给出的 id 集合与单词当前的义项不一致
+    Mismatch,
+}
+
+/// `repoint_user_word` 的结果；后两种情况不做任何修改
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WordRepoint {
+    /// 改挂成功，附带原来的 word_id
+    Moved { previous_word_id: i64 },
+    /// 单词不存在或不属于该用户
+    WordNotFound,
+    /// 用户已拥有目标单词，附带其 user_word id
+    Collision(i64),
+}
+
+/// 归属检查针对的资源
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OwnedResource {
+    UserWord(i64),
+    Sense(i64),
+}
+
 #[derive(Debug, Clone)]
 pub struct NewUserSense {
     pub user_word_id: i64,
@@ -49,6 +150,7 @@ pub struct NewUserSense {
     pub is_primary: bool,
     pub sort_order: i32,
     pub note: Option<String>,
+    pub source_url: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -57,30 +159,201 @@ pub struct SenseUpdate {
     pub is_primary: Option<bool>,
     pub sort_order: Option<i32>,
     pub note: Option<Option<String>>,
+    pub source_url: Option<Option<String>>,
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
 pub enum SearchScope {
     Word,
     Sense,
+    #[default]
     Both,
 }
 
-impl Default for SearchScope {
-    fn default() -> Self {
-        SearchScope::Both
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchSort {
+    /// 按 canonical_key 升序
+    #[default]
+    Alphabetical,
+    /// 按加入网络的时间倒序
+    Recent,
+    /// 按图中关联数倒序；SQL 无法排序，由服务层结合图查询完成，仓储层按字母序返回候选
+    Degree,
+    /// 按全文检索相关度倒序，相关度相同时按字母序；关键字为空时等同字母序
+    Relevance,
+}
+
+/// 关键字搜索命中的一侧；`Both` 范围下单词与义项都命中时记为单词
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchSide {
+    Word,
+    Sense,
+}
+
+/// 关键字搜索的命中信息，`snippet` 已按 HTML 转义，命中的词以 `<b>…</b>` 标出
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchMatch {
+    pub side: MatchSide,
+    pub snippet: String,
+}
+
+/// 聚合内嵌义项的排列顺序，供不同的界面模式选择
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SenseOrder {
+    /// 按用户设定的 sort_order 升序
+    #[default]
+    SortOrder,
+    /// 按创建时间升序
+    CreatedAt,
+    /// 按创建时间倒序
+    CreatedAtDesc,
+    /// 主义项置顶，其余按 sort_order 升序
+    PrimaryFirst,
+}
+
+impl SenseOrder {
+    /// `prefix` 为列名前缀（如 `us.`），子查询里直接查表时传空串
+    fn order_by(self, prefix: &str) -> String {
+        match self {
+            SenseOrder::SortOrder => format!("{prefix}sort_order, {prefix}id"),
+            SenseOrder::CreatedAt => format!("{prefix}created_at, {prefix}id"),
+            SenseOrder::CreatedAtDesc => format!("{prefix}created_at DESC, {prefix}id DESC"),
+            SenseOrder::PrimaryFirst => {
+                format!("{prefix}is_primary DESC, {prefix}sort_order, {prefix}id")
+            }
+        }
+    }
+
+    pub fn arrange(self, user_word: &mut UserWord) {
+        match self {
+            SenseOrder::SortOrder => user_word.sort_senses_by_key(|s| (s.sort_order, s.id)),
+            SenseOrder::CreatedAt => user_word.sort_senses_by_key(|s| (s.created_at, s.id)),
+            SenseOrder::CreatedAtDesc => {
+                user_word.sort_senses_by_key(|s| Reverse((s.created_at, s.id)))
+            }
+            SenseOrder::PrimaryFirst => {
+                user_word.sort_senses_by_key(|s| (!s.is_primary, s.sort_order, s.id))
+            }
+        }
+    }
+}
+
+/// 搜索关键字为空（或仅含空白）时的行为
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum EmptyQueryBehavior {
+    /// 返回全部单词，按排序方式分页
+    #[default]
+    ListAll,
+    /// 返回空结果，避免客户端误取整个网络
+    Empty,
+}
+
+impl SearchSort {
+    fn order_by(self) -> &'static str {
+        match self {
+            SearchSort::Alphabetical | SearchSort::Degree | SearchSort::Relevance => {
+                "w.canonical_key, uw.id"
+            }
+            SearchSort::Recent => "uw.created_at DESC, uw.id DESC",
+        }
     }
 }
 
+/// 批量标签操作
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TagAction {
+    Add,
+    Remove,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct SearchParams {
     pub user_id: i64,
     pub query: String,
     pub scope: SearchScope,
+    /// 仅返回同时带有全部这些标签的单词，空表示不过滤
+    pub tags: Vec<String>,
+    pub sort: SearchSort,
     pub limit: i64,
     pub offset: i64,
+    /// 键集分页：只返回排在该游标之后的单词，仅适用于字母序；不影响 `count_search`
+    pub after: Option<SearchCursor>,
+    /// 仅在这些 word_id 中检索，None 表示不限
+    pub word_ids: Option<Vec<i64>>,
+}
+
+/// 字母序下最后一条记录的位置，对外编码为不透明的十六进制字符串
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchCursor {
+    pub canonical_key: String,
+    pub user_word_id: i64,
+}
+
+impl SearchCursor {
+    pub fn from_aggregate(aggregate: &UserWordAggregate) -> Option<Self> {
+        Some(Self {
+            canonical_key: aggregate.word.canonical_key.as_str().to_string(),
+            user_word_id: aggregate.user_word.id?,
+        })
+    }
+
+    pub fn encode(&self) -> String {
+        hex_encode(&format!("{}:{}", self.user_word_id, self.canonical_key))
+    }
+
+    pub fn decode(value: &str) -> Option<Self> {
+        let decoded = hex_decode(value)?;
+        let (id, canonical_key) = decoded.split_once(':')?;
+        Some(Self {
+            canonical_key: canonical_key.to_string(),
+            user_word_id: id.parse().ok()?,
+        })
+    }
+}
+
+/// 全量同步时上一页最后一个单词的 user_word_id，编码方式与 `SearchCursor` 相同但互不通用
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncCursor {
+    pub user_word_id: i64,
+}
+
+impl SyncCursor {
+    pub fn encode(&self) -> String {
+        hex_encode(&format!("sync:{}", self.user_word_id))
+    }
+
+    pub fn decode(value: &str) -> Option<Self> {
+        let decoded = hex_decode(value)?;
+        Some(Self {
+            user_word_id: decoded.strip_prefix("sync:")?.parse().ok()?,
+        })
+    }
+}
+
+fn hex_encode(value: &str) -> String {
+    value.bytes().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn hex_decode(value: &str) -> Option<String> {
+    if !value.len().is_multiple_of(2) || !value.is_ascii() {
+        return None;
+    }
+    let bytes = (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&value[index..index + 2], 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    String::from_utf8(bytes).ok()
 }
 
+/// `list_all_user_words` 每次读取的单词数
+pub const LIST_ALL_PAGE_SIZE: i64 = 500;
+
 #[async_trait]
 pub trait WordRepository {
     async fn upsert_word(
@@ -91,11 +364,23 @@ pub trait WordRepository {
     async fn upsert_user_word(
         &self,
         payload: UpsertUserWord,
-    ) -> Result<UserWordAggregate, WordRepositoryError>;
+    ) -> Result<UpsertedUserWord, WordRepositoryError>;
+    /// 同一个事务内依次写入 `items`，每项用保存点隔离：失败的项只回滚自身并在对应位置返回错误，
+    /// 其余照常提交。单词已有同文本的义项时跳过该义项而不报重复，并计入 `deduplicated_senses`
+    async fn import_user_words(
+        &self,
+        items: Vec<ImportUserWord>,
+    ) -> Result<Vec<Result<ImportedUserWord, WordRepositoryError>>, WordRepositoryError>;
     async fn find_user_word(
         &self,
         user_id: i64,
         user_word_id: i64,
+        sense_order: SenseOrder,
+    ) -> Result<Option<UserWordAggregate>, WordRepositoryError>;
+    async fn find_user_word_by_canonical(
+        &self,
+        user_id: i64,
+        canonical: &CanonicalKey,
     ) -> Result<Option<UserWordAggregate>, WordRepositoryError>;
     async fn remove_user_word(
         &self,
@@ -115,28 +400,399 @@ pub trait WordRepository {
         user_id: i64,
         sense_id: i64,
     ) -> Result<UserSense, WordRepositoryError>;
+    /// 把义项设为所属单词唯一的主义项；已是主义项时不做修改。
+    /// 义项不存在或不属于该用户时返回 RowNotFound
+    async fn set_primary_sense(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+    ) -> Result<UserSense, WordRepositoryError>;
 
+    /// 关键字非空时每条结果附带命中信息，为空时为 None
     async fn search(
         &self,
         params: SearchParams,
+    ) -> Result<Vec<(UserWordAggregate, Option<SearchMatch>)>, WordRepositoryError>;
+    /// Counts all matches of `params`, ignoring `limit`/`offset`.
+    async fn count_search(&self, params: &SearchParams) -> Result<i64, WordRepositoryError>;
+    /// 满足 `params` 条件的全部 word_id，按字母序，忽略 `limit`/`offset`/`after`；
+    /// 供需要在全量候选上排序的调用方（按关联度排序）使用
+    async fn search_word_ids(&self, params: &SearchParams)
+    -> Result<Vec<i64>, WordRepositoryError>;
+
+    /// 义项所属单词的 word_id；义项不存在或不属于该用户时返回 None
+    async fn find_sense_word_id(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+    ) -> Result<Option<i64>, WordRepositoryError>;
+    /// 义项所属的单词及其全部义项，一次查询完成；义项不存在或不属于该用户时返回 None
+    async fn find_user_word_by_sense(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+        sense_order: SenseOrder,
+    ) -> Result<Option<UserWordAggregate>, WordRepositoryError>;
+    /// 资源的属主 user_id，不限定调用者；资源不存在时返回 None。
+    /// 仅供调试模式区分“不存在”与“属于其他用户”，常规读写一律按用户过滤
+    async fn find_owner(&self, resource: OwnedResource)
+    -> Result<Option<i64>, WordRepositoryError>;
+    /// `user_word_ids` 中属于该用户的条目数
+    async fn count_owned_user_words(
+        &self,
+        user_id: i64,
+        user_word_ids: &[i64],
+    ) -> Result<i64, WordRepositoryError>;
+    /// `keys` 中已在该用户网络里的那部分，一次查询完成；顺序不保证
+    async fn existing_canonical_keys(
+        &self,
+        user_id: i64,
+        keys: &[CanonicalKey],
+    ) -> Result<Vec<CanonicalKey>, WordRepositoryError>;
+    /// 用户网络中 canonical_key 三元组相似度（pg_trgm `similarity`）不低于 `threshold` 的单词对，
+    /// 按相似度倒序，最多 `limit` 对；每对只出现一次
+    async fn find_near_duplicate_words(
+        &self,
+        user_id: i64,
+        threshold: f32,
+        limit: i64,
+    ) -> Result<Vec<NearDuplicatePair>, WordRepositoryError>;
+    /// 按 id 批量取单词（全局词表，不区分用户）；不存在的 id 被忽略，顺序不保证
+    async fn find_words_by_ids(
+        &self,
+        word_ids: &[i64],
+    ) -> Result<Vec<WordRecord>, WordRepositoryError>;
+    /// 用户网络中 word_id 属于 `word_ids` 的单词（义项按列表上限内嵌）；
+    /// 不在网络中的 id 被忽略，顺序不保证
+    async fn find_user_words_by_word_ids(
+        &self,
+        user_id: i64,
+        word_ids: &[i64],
+    ) -> Result<Vec<UserWordAggregate>, WordRepositoryError>;
+    /// 按 user_word_id 升序列出 id 大于 `after` 的单词，内嵌全部义项；用于全量同步的键集分页
+    async fn list_user_words_after(
+        &self,
+        user_id: i64,
+        after: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<UserWordAggregate>, WordRepositoryError>;
+    /// 用户网络中的全部单词，按 user_word_id 升序并内嵌全部义项；
+    /// 经 `list_user_words_after` 按 [`LIST_ALL_PAGE_SIZE`] 分页读取，避免单条查询取回整个网络
+    async fn list_all_user_words(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<UserWordAggregate>, WordRepositoryError>
+    where
+        Self: Sync,
+    {
+        let mut words = Vec::new();
+        let mut after = None;
+        loop {
+            let page = self
+                .list_user_words_after(user_id, after, LIST_ALL_PAGE_SIZE)
+                .await?;
+            let exhausted = (page.len() as i64) < LIST_ALL_PAGE_SIZE;
+            after = page.last().and_then(|aggregate| aggregate.user_word.id);
+            words.extend(page);
+            if exhausted || after.is_none() {
+                return Ok(words);
+            }
+        }
+    }
+
+    /// 按加入网络的时间筛选单词，`from` 与 `to` 均为闭区间边界（含端点），缺省表示不限；
+    /// 按加入时间与 id 升序，义项按列表上限内嵌
+    async fn find_words_in_range(
+        &self,
+        user_id: i64,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: i64,
+        offset: i64,
     ) -> Result<Vec<UserWordAggregate>, WordRepositoryError>;
+    /// 与 `find_words_in_range` 条件一致的全部 word_id，顺序相同；供需要在全量候选上过滤的调用方使用
+    async fn find_word_ids_in_range(
+        &self,
+        user_id: i64,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<i64>, WordRepositoryError>;
+    /// 与 `find_words_in_range` 条件一致的总数
+    async fn count_words_in_range(
+        &self,
+        user_id: i64,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<i64, WordRepositoryError>;
+    /// `since` 之后（不含）新增、修改或移除的单词，两类各按时间升序最多 `limit` 条。
+    /// 同一个单词移除后又重新加入时，旧的 user_word_id 出现在墓碑中，新的出现在 `updated` 中
+    async fn list_changes_since(
+        &self,
+        user_id: i64,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<WordChanges, WordRepositoryError>;
+    /// 对用户名下的单词批量增删一个标签，返回实际发生变化的行数；
+    /// 已有（或没有）该标签、以及标签数已达上限的单词不受影响
+    async fn update_tag(
+        &self,
+        user_id: i64,
+        user_word_ids: &[i64],
+        tag: &str,
+        action: TagAction,
+    ) -> Result<u64, WordRepositoryError>;
+    /// 以 `tags` 整体替换单词的标签，调用方负责规范化；单词不属于该用户时返回 false
+    async fn replace_tags(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        tags: &[String],
+    ) -> Result<bool, WordRepositoryError>;
+    /// 同时写入单词的标签与备注（`None` 清空备注），调用方负责校验；单词不属于该用户时返回 false
+    async fn update_user_word_metadata(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        tags: &[String],
+        note: Option<&str>,
+    ) -> Result<bool, WordRepositoryError>;
+    /// 在一个事务内按 `sense_ids` 的顺序把单词各义项的 sort_order 重写为 0..n；
+    /// `sense_ids` 必须恰好是该单词当前的全部义项，调用方负责去重
+    async fn reorder_senses(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        sense_ids: &[i64],
+    ) -> Result<SenseReorder, WordRepositoryError>;
+    /// 把用户单词改挂到 `word_id`，义项、标签与备注随 user_word 一起保留。
+    /// 原 word_id 记为待完成，直到调用方在图库改挂后调用 `complete_word_repoint`
+    async fn repoint_user_word(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        word_id: i64,
+    ) -> Result<WordRepoint, WordRepositoryError>;
+    /// 尚未完成改挂时的原 word_id；单词不存在或没有待完成的改挂时返回 None
+    async fn pending_word_repoint(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+    ) -> Result<Option<i64>, WordRepositoryError>;
+    /// 清除待完成标记；原单词不再被任何用户引用时一并删除
+    async fn complete_word_repoint(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        previous_word_id: i64,
+    ) -> Result<(), WordRepositoryError>;
+    /// 数据修复：在一个事务内让该用户每个有义项的单词恰好有一个主义项。没有主义项时提升
+    /// sort_order 最小的义项，有多个时只保留 sort_order 最小的那个；返回被修改的单词数
+    async fn repair_primaries(&self, user_id: i64) -> Result<u64, WordRepositoryError>;
 }
 
 #[derive(Clone)]
 pub struct PgWordRepository {
     pool: PgPool,
+    max_senses_per_word: Option<i64>,
+    max_senses: Option<usize>,
 }
 
 impl PgWordRepository {
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            max_senses_per_word: None,
+            max_senses: None,
+        }
+    }
+
+    /// 限制 `search` 结果中每个单词内嵌的义项数
+    pub fn with_max_senses_per_word(mut self, limit: i64) -> Self {
+        self.max_senses_per_word = Some(limit);
+        self
+    }
+
+    /// 每个单词最多保存的义项数（`words.max_senses`），所有新增义项的写入都在行锁内检查
+    pub fn with_max_senses(mut self, limit: usize) -> Self {
+        self.max_senses = Some(limit);
+        self
+    }
+
+    /// 返回 (user_word_id, 是否新建)
+    async fn upsert_user_word_in(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        payload: &UpsertUserWord,
+    ) -> Result<(i64, bool), WordRepositoryError> {
+        let word_id: i64 = sqlx::query_scalar(
+            r#"
+            INSERT INTO words (text, canonical_key)
+            VALUES ($1, $2)
+            ON CONFLICT (canonical_key) DO UPDATE SET text = EXCLUDED.text
+            RETURNING id
+            "#,
+        )
+        .bind(&payload.word_text)
+        .bind(payload.canonical_key.as_str())
+        .fetch_one(&mut **tx)
+        .await?;
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO user_words (user_id, word_id, tags, note)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (user_id, word_id)
+            DO UPDATE SET tags = EXCLUDED.tags, note = EXCLUDED.note, updated_at = NOW()
+            RETURNING id, (xmax = 0) AS inserted
+            "#,
+        )
+        .bind(payload.user_id)
+        .bind(word_id)
+        .bind(&payload.tags)
+        .bind(&payload.note)
+        .fetch_one(&mut **tx)
+        .await?;
+        // 新插入行的 xmax 为 0，冲突更新的行则不是
+        Ok((inserted.try_get("id")?, inserted.try_get("inserted")?))
+    }
+
+    async fn import_one(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        item: &ImportUserWord,
+        max_senses: Option<usize>,
+    ) -> Result<(i64, bool, u32), WordRepositoryError> {
+        let (user_word_id, created) = Self::upsert_user_word_in(tx, &item.word).await?;
+        if item.senses.is_empty() {
+            return Ok((user_word_id, created, 0));
+        }
+
+        Self::lock_user_word(tx, user_word_id).await?;
+        let mut deduplicated = 0;
+        for sense in &item.senses {
+            let exists: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM user_senses WHERE user_word_id = $1 AND text = $2)",
+            )
+            .bind(user_word_id)
+            .bind(&sense.text)
+            .fetch_one(&mut **tx)
+            .await?;
+            if exists {
+                deduplicated += 1;
+                continue;
+            }
+            Self::ensure_sense_capacity(tx, user_word_id, max_senses).await?;
+            if sense.is_primary {
+                Self::clear_primary(tx, user_word_id, None).await?;
+            }
+            sqlx::query(
+                r#"
+                INSERT INTO user_senses (user_word_id, text, is_primary, sort_order, note, source_url)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                "#,
+            )
+            .bind(user_word_id)
+            .bind(&sense.text)
+            .bind(sense.is_primary)
+            .bind(sense.sort_order)
+            .bind(&sense.note)
+            .bind(&sense.source_url)
+            .execute(&mut **tx)
+            .await?;
+        }
+        if (deduplicated as usize) < item.senses.len() {
+            Self::touch_user_word(tx, user_word_id).await?;
+        }
+        Ok((user_word_id, created, deduplicated))
+    }
+
+    /// 锁住义项所属的 user_words 行，使同一单词的主义项变更串行执行。
+    /// 只锁义项行不够：单词还没有义项时两个并发插入都拿不到锁
+    async fn lock_user_word(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_word_id: i64,
+    ) -> Result<(), WordRepositoryError> {
+        sqlx::query("SELECT id FROM user_words WHERE id = $1 FOR UPDATE")
+            .bind(user_word_id)
+            .fetch_one(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// 调用方需已锁住 user_words 行，计数与随后的插入之间不会有并发写入
+    async fn ensure_sense_capacity(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_word_id: i64,
+        max_senses: Option<usize>,
+    ) -> Result<(), WordRepositoryError> {
+        let Some(max_senses) = max_senses else {
+            return Ok(());
+        };
+        let count: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM user_senses WHERE user_word_id = $1")
+                .bind(user_word_id)
+                .fetch_one(&mut **tx)
+                .await?;
+        if count as usize >= max_senses {
+            return Err(UserWordError::SenseLimitExceeded(max_senses).into());
+        }
+        Ok(())
+    }
+
+    /// 义项变化同样算作单词的修改，供增量同步识别
+    async fn touch_user_word(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_word_id: i64,
+    ) -> Result<(), WordRepositoryError> {
+        sqlx::query("UPDATE user_words SET updated_at = NOW() WHERE id = $1")
+            .bind(user_word_id)
+            .execute(&mut **tx)
+            .await?;
+        Ok(())
+    }
+
+    /// 必须在写入新的主义项之前执行，否则会与 `user_senses_primary_unique` 部分唯一索引冲突。
+    /// 返回被取消主义项的义项数
+    async fn clear_primary(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_word_id: i64,
+        keep_sense_id: Option<i64>,
+    ) -> Result<u64, WordRepositoryError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE user_senses
+            SET is_primary = FALSE
+            WHERE user_word_id = $1 AND is_primary AND id IS DISTINCT FROM $2
+            "#,
+        )
+        .bind(user_word_id)
+        .bind(keep_sense_id)
+        .execute(&mut **tx)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// 让已存在的义项成为单词唯一的主义项，所有“设为主义项”的写入都经过这里；
+    /// 调用方需已锁住 user_words 行。返回是否有改动，已是唯一主义项时为 false
+    async fn make_primary(
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        user_word_id: i64,
+        sense_id: i64,
+    ) -> Result<bool, WordRepositoryError> {
+        let cleared = Self::clear_primary(tx, user_word_id, Some(sense_id)).await?;
+        let promoted = sqlx::query(
+            "UPDATE user_senses SET is_primary = TRUE WHERE id = $1 AND NOT is_primary",
+        )
+        .bind(sense_id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected();
+        Ok(cleared + promoted > 0)
     }
 
     fn map_word_row(row: &PgRow) -> Result<WordRecord, WordRepositoryError> {
         Ok(WordRecord {
             id: row.try_get("id")?,
             text: row.try_get("text")?,
-            canonical_key: CanonicalKey::new(row.try_get::<String, _>("canonical_key")?)?,
+            canonical_key: CanonicalKey::from_stored(row.try_get("canonical_key")?)?,
             created_at: row.try_get("created_at")?,
         })
     }
@@ -158,38 +814,90 @@ impl PgWordRepository {
         let senses: Vec<JsonSenseRow> = serde_json::from_value(value).unwrap_or_default();
         let mut result = Vec::with_capacity(senses.len());
         for sense_row in senses {
-            result.push(UserSense::from_parts(
-                sense_row.id,
-                sense_row.text,
-                sense_row.is_primary,
-                sense_row.sort_order,
-                sense_row.note,
-                sense_row.created_at,
-            )?);
+            result.push(
+                UserSense::from_parts(
+                    sense_row.id,
+                    sense_row.text,
+                    sense_row.is_primary,
+                    sense_row.sort_order,
+                    sense_row.note,
+                    sense_row.created_at,
+                )?
+                .with_source_url(sense_row.source_url)?,
+            );
         }
         Ok(result)
     }
 
+    /// 从包含 user_senses 各列（含 source_url）的查询结果还原义项
+    fn map_sense(row: &PgRow) -> Result<UserSense, WordRepositoryError> {
+        UserSense::from_parts(
+            Some(row.try_get("id")?),
+            row.try_get("text")?,
+            row.try_get("is_primary")?,
+            row.try_get("sort_order")?,
+            row.try_get("note")?,
+            row.try_get("created_at")?,
+        )?
+        .with_source_url(row.try_get("source_url")?)
+        .map_err(WordRepositoryError::from)
+    }
+
     fn build_aggregate(row: PgRow) -> Result<UserWordAggregate, WordRepositoryError> {
+        Self::build_aggregate_ordered(row, SenseOrder::default())
+    }
+
+    fn build_aggregate_ordered(
+        row: PgRow,
+        sense_order: SenseOrder,
+    ) -> Result<UserWordAggregate, WordRepositoryError> {
         let mut user_word = Self::map_user_word(&row)?;
         let senses_value: JsonValue = row.try_get("senses")?;
         let senses = Self::map_senses(senses_value)?;
         for sense in senses {
-            user_word.add_sense(sense)?;
+            user_word.add_sense_with_limit(sense, usize::MAX)?;
         }
+        // add_sense 总是按 sort_order 重排，这里恢复查询要求的顺序
+        sense_order.arrange(&mut user_word);
 
         let word = WordRecord {
             id: row.try_get("word_id")?,
             text: row.try_get("word_text")?,
-            canonical_key: CanonicalKey::new(row.try_get::<String, _>("word_canonical")?)?,
+            canonical_key: CanonicalKey::from_stored(row.try_get("word_canonical")?)?,
             created_at: row.try_get("word_created_at")?,
         };
 
-        Ok(UserWordAggregate { word, user_word })
+        Ok(UserWordAggregate {
+            word,
+            user_word,
+            has_more_senses: row.try_get("has_more_senses")?,
+        })
+    }
+
+    fn aggregate_query(sense_limit: Option<i64>) -> String {
+        Self::aggregate_query_ordered(sense_limit, SenseOrder::default(), "")
     }
 
-    fn aggregate_query() -> &'static str {
-        r#"
+    /// `sense_limit` 为 None 时内嵌全部义项；上限来自配置，以字面量写入 LATERAL 子查询。
+    /// `extra_columns` 以逗号开头，追加在选择列表末尾
+    fn aggregate_query_ordered(
+        sense_limit: Option<i64>,
+        sense_order: SenseOrder,
+        extra_columns: &str,
+    ) -> String {
+        let (limit_clause, has_more) = match sense_limit {
+            Some(limit) => (
+                format!("LIMIT {limit}"),
+                format!(
+                    "EXISTS (SELECT 1 FROM user_senses more WHERE more.user_word_id = uw.id OFFSET {limit})"
+                ),
+            ),
+            None => (String::new(), "FALSE".to_string()),
+        };
+        let outer_order = sense_order.order_by("us.");
+        let inner_order = sense_order.order_by("");
+        format!(
+            r#"
         SELECT
             uw.id                AS user_word_id,
             uw.user_id,
@@ -200,30 +908,176 @@ impl PgWordRepository {
             w.text               AS word_text,
             w.canonical_key      AS word_canonical,
             w.created_at         AS word_created_at,
-            COALESCE(
-                json_agg(
-                    json_build_object(
-                        'id', us.id,
-                        'text', us.text,
-                        'is_primary', us.is_primary,
-                        'sort_order', us.sort_order,
-                        'note', us.note,
-                        'created_at', us.created_at
-                    )
-                    ORDER BY us.sort_order
-                ) FILTER (WHERE us.id IS NOT NULL),
-                '[]'
-            ) AS senses
+            COALESCE(s.senses, '[]') AS senses,
+            {has_more}           AS has_more_senses{extra_columns}
         FROM user_words uw
         JOIN words w ON w.id = uw.word_id
-        LEFT JOIN user_senses us ON us.user_word_id = uw.id
+        LEFT JOIN LATERAL (
+            SELECT json_agg(
+                json_build_object(
+                    'id', us.id,
+                    'text', us.text,
+                    'is_primary', us.is_primary,
+                    'sort_order', us.sort_order,
+                    'note', us.note,
+                    'source_url', us.source_url,
+                    'created_at', us.created_at
+                )
+                ORDER BY {outer_order}
+            ) AS senses
+            FROM (
+                SELECT *
+                FROM user_senses
+                WHERE user_word_id = uw.id
+                ORDER BY {inner_order}
+                {limit_clause}
+            ) us
+        ) s ON TRUE
         WHERE uw.user_id = $1
         "#
+        )
+    }
+
+    /// 读取 `SearchFilter::match_columns` 追加的列；单词一侧命中优先
+    fn map_search_match(row: &PgRow) -> Result<Option<SearchMatch>, WordRepositoryError> {
+        if row.try_get::<bool, _>("match_word")? {
+            return Ok(Some(SearchMatch {
+                side: MatchSide::Word,
+                snippet: row.try_get("match_word_snippet")?,
+            }));
+        }
+        let snippet: Option<String> = row.try_get("match_sense_snippet")?;
+        Ok(snippet.map(|snippet| SearchMatch {
+            side: MatchSide::Sense,
+            snippet,
+        }))
     }
 
     fn canonical_like_pattern(text: &str) -> String {
         text.trim().to_lowercase().replace(' ', "-")
     }
+
+    /// `ILIKE` 的包含匹配模式，转义输入中的通配符
+    fn contains_like_pattern(text: &str) -> String {
+        let escaped = text
+            .replace('\\', "\\\\")
+            .replace('%', "\\%")
+            .replace('_', "\\_");
+        format!("%{escaped}%")
+    }
+}
+
+/// `search` 与 `count_search` 共用的 WHERE 片段；占位符从 $2 开始依次编号
+struct SearchFilter {
+    condition: String,
+    /// `websearch_to_tsquery` 的输入
+    text_query: Option<String>,
+    /// 单词的子串匹配模式，仅在检索范围包含单词时绑定
+    pattern: Option<String>,
+    /// 义项文本的包含匹配模式，仅在检索范围包含义项时绑定；
+    /// 中文义项在 `english` 配置下切不出词，全靠它命中
+    sense_pattern: Option<String>,
+    /// 相关度表达式，仅在关键字非空时存在
+    rank: Option<String>,
+    /// 命中信息的附加列（见 [`PgWordRepository::map_search_match`]），关键字为空时为空串
+    match_columns: String,
+    tags: Option<Vec<String>>,
+    word_ids: Option<Vec<i64>>,
+    next_index: usize,
+}
+
+impl SearchFilter {
+    fn from_params(params: &SearchParams) -> Self {
+        let mut filter = Self {
+            condition: String::new(),
+            text_query: None,
+            pattern: None,
+            sense_pattern: None,
+            rank: None,
+            match_columns: String::new(),
+            tags: None,
+            word_ids: None,
+            next_index: 2,
+        };
+
+        let trimmed = params.query.trim();
+        if !trimmed.is_empty() {
+            let query = format!("websearch_to_tsquery('english', ${})", filter.next_index);
+            filter.text_query = Some(trimmed.to_string());
+            filter.next_index += 1;
+            if params.scope != SearchScope::Sense {
+                filter.pattern = Some(format!(
+                    "%{}%",
+                    PgWordRepository::canonical_like_pattern(trimmed)
+                ));
+            }
+            let word_pattern = filter.next_index;
+            if params.scope != SearchScope::Word {
+                filter.sense_pattern = Some(PgWordRepository::contains_like_pattern(trimmed));
+            }
+            let sense_pattern = word_pattern + usize::from(filter.pattern.is_some());
+            filter.next_index = sense_pattern + usize::from(filter.sense_pattern.is_some());
+
+            let sense_match =
+                format!("(sub.search_vector @@ {query} OR sub.text ILIKE ${sense_pattern})");
+            let sense_hit = format!(
+                "EXISTS (SELECT 1 FROM user_senses sub WHERE sub.user_word_id = uw.id AND {sense_match})"
+            );
+            let sense_rank = format!(
+                "COALESCE((SELECT MAX(ts_rank(sub.search_vector, {query})) FROM user_senses sub WHERE sub.user_word_id = uw.id), 0)"
+            );
+            let word_rank = format!("ts_rank(w.search_vector, {query})");
+            // 单词额外保留 canonical_key 的子串匹配（有三元组索引），输入半个单词也能命中
+            let word_hit =
+                format!("(w.search_vector @@ {query} OR w.canonical_key ILIKE ${word_pattern})");
+            let sense_snippet = format!(
+                "(SELECT ts_headline('english', {}, {query}) FROM user_senses sub WHERE sub.user_word_id = uw.id AND {sense_match} ORDER BY ts_rank(sub.search_vector, {query}) DESC, sub.sort_order, sub.id LIMIT 1)",
+                html_escaped("sub.text")
+            );
+            // 命中列只反映检索范围内的一侧，范围外的一侧固定为 FALSE / NULL
+            let (condition, rank, word_side, sense_side) = match params.scope {
+                SearchScope::Word => (word_hit.clone(), word_rank, word_hit, "NULL".into()),
+                SearchScope::Sense => (sense_hit, sense_rank, "FALSE".into(), sense_snippet),
+                SearchScope::Both => (
+                    format!("({word_hit} OR {sense_hit})"),
+                    format!("GREATEST({word_rank}, {sense_rank})"),
+                    word_hit,
+                    sense_snippet,
+                ),
+            };
+            filter.match_columns = format!(
+                ",\n            {word_side} AS match_word,\n            ts_headline('english', {}, {query}) AS match_word_snippet,\n            {sense_side} AS match_sense_snippet",
+                html_escaped("w.text")
+            );
+            filter.condition.push_str(&format!(" AND {condition}"));
+            filter.rank = Some(rank);
+        }
+
+        if !params.tags.is_empty() {
+            filter
+                .condition
+                .push_str(&format!(" AND uw.tags @> ${}", filter.next_index));
+            filter.tags = Some(params.tags.clone());
+            filter.next_index += 1;
+        }
+
+        if let Some(word_ids) = &params.word_ids {
+            filter
+                .condition
+                .push_str(&format!(" AND w.id = ANY(${})", filter.next_index));
+            filter.word_ids = Some(word_ids.clone());
+            filter.next_index += 1;
+        }
+
+        filter
+    }
+}
+
+/// 片段会被前端按 HTML 渲染以显示 `<b>` 高亮，用户文本须先转义再交给 `ts_headline`
+fn html_escaped(column: &str) -> String {
+    format!(
+        "replace(replace(replace(replace(replace({column}, '&', '&amp;'), '<', '&lt;'), '>', '&gt;'), '\"', '&quot;'), '''', '&#39;')"
+    )
 }
 
 #[derive(Debug, Deserialize)]
@@ -233,6 +1087,8 @@ struct JsonSenseRow {
     is_primary: bool,
     sort_order: i32,
     note: Option<String>,
+    #[serde(default)]
+    source_url: Option<String>,
     created_at: DateTime<Utc>,
 }
 
@@ -243,84 +1099,157 @@ impl WordRepository for PgWordRepository {
         canonical: &CanonicalKey,
         text: &str,
     ) -> Result<WordRecord, WordRepositoryError> {
-        let row = sqlx::query(
-            r#"
-            INSERT INTO words (text, canonical_key)
-            VALUES ($1, $2)
-            ON CONFLICT (canonical_key)
-            DO UPDATE SET text = EXCLUDED.text
-            RETURNING id, text, canonical_key, created_at
-            "#,
-        )
-        .bind(text)
-        .bind(canonical.as_str())
-        .fetch_one(&self.pool)
-        .await?;
+        timed(Dependency::Postgres, async {
+            let row = sqlx::query(
+                r#"
+                INSERT INTO words (text, canonical_key)
+                VALUES ($1, $2)
+                ON CONFLICT (canonical_key)
+                DO UPDATE SET text = EXCLUDED.text
+                RETURNING id, text, canonical_key, created_at
+                "#,
+            )
+            .bind(text)
+            .bind(canonical.as_str())
+            .fetch_one(&self.pool)
+            .await?;
 
-        Self::map_word_row(&row)
+            Self::map_word_row(&row)
+        })
+        .await
     }
 
     async fn upsert_user_word(
         &self,
         payload: UpsertUserWord,
-    ) -> Result<UserWordAggregate, WordRepositoryError> {
-        let mut tx = self.pool.begin().await?;
+    ) -> Result<UpsertedUserWord, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let mut tx = self.pool.begin().await?;
+            let (user_word_id, created) = Self::upsert_user_word_in(&mut tx, &payload).await?;
+            // 提交前在同一事务内读回：此时行锁仍在，其他事务无法在读回前删掉它
+            let sql = format!("{} AND uw.id = $2", Self::aggregate_query(None));
+            let row = sqlx::query(&sql)
+                .bind(payload.user_id)
+                .bind(user_word_id)
+                .fetch_optional(&mut *tx)
+                .await?
+                .ok_or(WordRepositoryError::RemovedConcurrently(user_word_id))?;
+            let aggregate = Self::build_aggregate(row)?;
+            tx.commit().await?;
+            Ok(UpsertedUserWord { aggregate, created })
+        })
+        .await
+    }
 
-        let word_row = sqlx::query(
-            r#"
-            INSERT INTO words (text, canonical_key)
-            VALUES ($1, $2)
-            ON CONFLICT (canonical_key) DO UPDATE SET text = EXCLUDED.text
-            RETURNING id, text, canonical_key, created_at
-            "#,
-        )
-        .bind(&payload.word_text)
-        .bind(payload.canonical_key.as_str())
-        .fetch_one(&mut *tx)
-        .await?;
-        let word = Self::map_word_row(&word_row)?;
+    async fn import_user_words(
+        &self,
+        items: Vec<ImportUserWord>,
+    ) -> Result<Vec<Result<ImportedUserWord, WordRepositoryError>>, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let mut tx = self.pool.begin().await?;
+            let mut outcomes = Vec::with_capacity(items.len());
+            for item in &items {
+                sqlx::query("SAVEPOINT import_item")
+                    .execute(&mut *tx)
+                    .await?;
+                match Self::import_one(&mut tx, item, self.max_senses).await {
+                    Ok(written) => {
+                        sqlx::query("RELEASE SAVEPOINT import_item")
+                            .execute(&mut *tx)
+                            .await?;
+                        outcomes.push(Ok(written));
+                    }
+                    Err(err) => {
+                        sqlx::query("ROLLBACK TO SAVEPOINT import_item")
+                            .execute(&mut *tx)
+                            .await?;
+                        outcomes.push(Err(err));
+                    }
+                }
+            }
 
-        let inserted = sqlx::query(
-            r#"
-            INSERT INTO user_words (user_id, word_id, tags, note)
-            VALUES ($1, $2, $3, $4)
-            ON CONFLICT (user_id, word_id)
-            DO UPDATE SET tags = EXCLUDED.tags, note = EXCLUDED.note
-            RETURNING id
-            "#,
-        )
-        .bind(payload.user_id)
-        .bind(word.id)
-        .bind(&payload.tags)
-        .bind(&payload.note)
-        .fetch_one(&mut *tx)
-        .await?;
-        let user_word_id: i64 = inserted.try_get("id")?;
+            // 与 upsert_user_word 一样在提交前读回，写入时拿到的行锁保证读回前不会被并发删除
+            let ids: Vec<i64> = outcomes
+                .iter()
+                .filter_map(|outcome| outcome.as_ref().ok().map(|(id, _, _)| *id))
+                .collect();
+            // 一次批量通常只属于一个用户，按用户各查一次写入后的聚合
+            let mut owners: Vec<i64> = items.iter().map(|item| item.word.user_id).collect();
+            owners.sort_unstable();
+            owners.dedup();
+            let sql = format!("{} AND uw.id = ANY($2)", Self::aggregate_query(None));
+            let mut aggregates = HashMap::new();
+            for owner in owners {
+                let rows = sqlx::query(&sql)
+                    .bind(owner)
+                    .bind(&ids)
+                    .fetch_all(&mut *tx)
+                    .await?;
+                for row in rows {
+                    let aggregate = Self::build_aggregate(row)?;
+                    aggregates.insert(aggregate.user_word.id, aggregate);
+                }
+            }
+            tx.commit().await?;
 
-        tx.commit().await?;
-        self.find_user_word(payload.user_id, user_word_id)
-            .await?
-            .ok_or_else(|| WordRepositoryError::Database(sqlx::Error::RowNotFound))
+            Ok(outcomes
+                .into_iter()
+                .map(|outcome| {
+                    let (user_word_id, created, deduplicated_senses) = outcome?;
+                    let aggregate = aggregates
+                        .get(&Some(user_word_id))
+                        .cloned()
+                        .ok_or(WordRepositoryError::RemovedConcurrently(user_word_id))?;
+                    Ok(ImportedUserWord {
+                        aggregate,
+                        created,
+                        deduplicated_senses,
+                    })
+                })
+                .collect())
+        })
+        .await
     }
 
     async fn find_user_word(
         &self,
         user_id: i64,
         user_word_id: i64,
+        sense_order: SenseOrder,
     ) -> Result<Option<UserWordAggregate>, WordRepositoryError> {
-        let sql = format!(
-            "{} AND uw.id = $2 GROUP BY uw.id, w.id",
-            Self::aggregate_query()
-        );
-        let maybe_row = sqlx::query(&sql)
-            .bind(user_id)
-            .bind(user_word_id)
-            .fetch_optional(&self.pool)
-            .await?;
-        match maybe_row {
-            Some(row) => Ok(Some(Self::build_aggregate(row)?)),
-            None => Ok(None),
-        }
+        timed(Dependency::Postgres, async {
+            let sql = format!(
+                "{} AND uw.id = $2",
+                Self::aggregate_query_ordered(None, sense_order, "")
+            );
+            let maybe_row = sqlx::query(&sql)
+                .bind(user_id)
+                .bind(user_word_id)
+                .fetch_optional(&self.pool)
+                .await?;
+            match maybe_row {
+                Some(row) => Ok(Some(Self::build_aggregate_ordered(row, sense_order)?)),
+                None => Ok(None),
+            }
+        })
+        .await
+    }
+
+    async fn find_user_word_by_canonical(
+        &self,
+        user_id: i64,
+        canonical: &CanonicalKey,
+    ) -> Result<Option<UserWordAggregate>, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let sql = format!("{} AND w.canonical_key = $2", Self::aggregate_query(None));
+            let maybe_row = sqlx::query(&sql)
+                .bind(user_id)
+                .bind(canonical.as_str())
+                .fetch_optional(&self.pool)
+                .await?;
+            maybe_row.map(Self::build_aggregate).transpose()
+        })
+        .await
     }
 
     async fn remove_user_word(
@@ -328,62 +1257,59 @@ impl WordRepository for PgWordRepository {
         user_id: i64,
         user_word_id: i64,
     ) -> Result<(), WordRepositoryError> {
-        sqlx::query(
-            r#"
-            DELETE FROM user_words
-            WHERE id = $1 AND user_id = $2
-            "#,
-        )
-        .bind(user_word_id)
-        .bind(user_id)
-        .execute(&self.pool)
-        .await?;
-        Ok(())
+        timed(Dependency::Postgres, async {
+            sqlx::query(
+                r#"
+                WITH removed AS (
+                    DELETE FROM user_words
+                    WHERE id = $1 AND user_id = $2
+                    RETURNING id, user_id, word_id
+                )
+                INSERT INTO user_word_tombstones (user_id, user_word_id, word_id)
+                SELECT user_id, id, word_id FROM removed
+                "#,
+            )
+            .bind(user_word_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await?;
+            Ok(())
+        })
+        .await
     }
 
     async fn add_user_sense(&self, sense: NewUserSense) -> Result<UserSense, WordRepositoryError> {
-        let mut tx = self.pool.begin().await?;
-
-        let row = sqlx::query(
-            r#"
-            INSERT INTO user_senses (user_word_id, text, is_primary, sort_order, note)
-            VALUES ($1, $2, $3, $4, $5)
-            RETURNING id, text, is_primary, sort_order, note, created_at
-            "#,
-        )
-        .bind(sense.user_word_id)
-        .bind(&sense.text)
-        .bind(sense.is_primary)
-        .bind(sense.sort_order)
-        .bind(&sense.note)
-        .fetch_one(&mut *tx)
-        .await?;
-
-        let mut created = UserSense::from_parts(
-            Some(row.try_get("id")?),
-            row.try_get("text")?,
-            row.try_get("is_primary")?,
-            row.try_get("sort_order")?,
-            row.try_get("note")?,
-            row.try_get("created_at")?,
-        )?;
+        timed(Dependency::Postgres, async {
+            let mut tx = self.pool.begin().await?;
+            Self::lock_user_word(&mut tx, sense.user_word_id).await?;
+            Self::ensure_sense_capacity(&mut tx, sense.user_word_id, self.max_senses).await?;
+            if sense.is_primary {
+                Self::clear_primary(&mut tx, sense.user_word_id, None).await?;
+            }
 
-        if created.is_primary {
-            sqlx::query(
+            let row = sqlx::query(
                 r#"
-                UPDATE user_senses
-                SET is_primary = FALSE
-                WHERE user_word_id = $1 AND id <> $2
+                INSERT INTO user_senses (user_word_id, text, is_primary, sort_order, note, source_url)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                RETURNING id, text, is_primary, sort_order, note, source_url, created_at
                 "#,
             )
             .bind(sense.user_word_id)
-            .bind(created.id().unwrap())
-            .execute(&mut *tx)
+            .bind(&sense.text)
+            .bind(sense.is_primary)
+            .bind(sense.sort_order)
+            .bind(&sense.note)
+            .bind(&sense.source_url)
+            .fetch_one(&mut *tx)
             .await?;
-        }
 
-        tx.commit().await?;
-        Ok(created)
+            let created = Self::map_sense(&row)?;
+
+            Self::touch_user_word(&mut tx, sense.user_word_id).await?;
+            tx.commit().await?;
+            Ok(created)
+        })
+        .await
     }
 
     async fn update_user_sense(
@@ -392,86 +1318,69 @@ impl WordRepository for PgWordRepository {
         sense_id: i64,
         update: SenseUpdate,
     ) -> Result<UserSense, WordRepositoryError> {
-        let mut tx = self.pool.begin().await?;
+        timed(Dependency::Postgres, async {
+            let mut tx = self.pool.begin().await?;
 
-        let row = sqlx::query(
-            r#"
-            SELECT us.id, us.user_word_id, us.text, us.is_primary, us.sort_order, us.note, us.created_at
-            FROM user_senses us
-            JOIN user_words uw ON uw.id = us.user_word_id
-            WHERE us.id = $1 AND uw.user_id = $2
-            "#,
-        )
-        .bind(sense_id)
-        .bind(user_id)
-        .fetch_one(&mut *tx)
-        .await?;
-
-        let mut sense = UserSense::from_parts(
-            Some(row.try_get("id")?),
-            row.try_get("text")?,
-            row.try_get("is_primary")?,
-            row.try_get("sort_order")?,
-            row.try_get("note")?,
-            row.try_get("created_at")?,
-        )?;
+            let row = sqlx::query(
+                r#"
+                SELECT us.id, us.user_word_id, us.text, us.is_primary, us.sort_order, us.note, us.source_url, us.created_at
+                FROM user_senses us
+                JOIN user_words uw ON uw.id = us.user_word_id
+                WHERE us.id = $1 AND uw.user_id = $2
+                FOR UPDATE OF uw, us
+                "#,
+            )
+            .bind(sense_id)
+            .bind(user_id)
+            .fetch_one(&mut *tx)
+            .await?;
+            let user_word_id: i64 = row.try_get("user_word_id")?;
 
-        if let Some(text) = update.text {
-            sense.set_text(text)?;
-        }
-        if let Some(sort_order) = update.sort_order {
-            sense.set_sort_order(sort_order);
-        }
-        if let Some(note) = update.note {
-            sense.set_note(note)?;
-        }
-        if let Some(is_primary) = update.is_primary {
-            sense.set_primary(is_primary);
-        }
+            let mut sense = Self::map_sense(&row)?;
 
-        let updated = sqlx::query(
-            r#"
-            UPDATE user_senses
-            SET text = $1, is_primary = $2, sort_order = $3, note = $4
-            WHERE id = $5
-            RETURNING id, text, is_primary, sort_order, note, created_at, user_word_id
-            "#,
-        )
-        .bind(sense.text())
-        .bind(sense.is_primary)
-        .bind(sense.sort_order)
-        .bind(sense.note())
-        .bind(sense_id)
-        .fetch_one(&mut *tx)
-        .await?;
+            // 长度已在服务层按配置校验，这里只做空白与控制字符检查
+            if let Some(text) = update.text {
+                sense.set_text(text, &TextLimits::unbounded())?;
+            }
+            if let Some(sort_order) = update.sort_order {
+                sense.set_sort_order(sort_order);
+            }
+            if let Some(note) = update.note {
+                sense.set_note(note, &TextLimits::unbounded())?;
+            }
+            if let Some(source_url) = update.source_url {
+                sense.set_source_url(source_url)?;
+            }
+            if let Some(is_primary) = update.is_primary {
+                sense.set_primary(is_primary);
+            }
+            if sense.is_primary {
+                Self::make_primary(&mut tx, user_word_id, sense_id).await?;
+            }
 
-        let user_word_id: i64 = updated.try_get("user_word_id")?;
-        if sense.is_primary {
-            sqlx::query(
+            let updated = sqlx::query(
                 r#"
                 UPDATE user_senses
-                SET is_primary = FALSE
-                WHERE user_word_id = $1 AND id <> $2
+                SET text = $1, is_primary = $2, sort_order = $3, note = $4, source_url = $5
+                WHERE id = $6
+                RETURNING id, text, is_primary, sort_order, note, source_url, created_at
                 "#,
             )
-            .bind(user_word_id)
+            .bind(sense.text())
+            .bind(sense.is_primary)
+            .bind(sense.sort_order)
+            .bind(sense.note())
+            .bind(sense.source_url())
             .bind(sense_id)
-            .execute(&mut *tx)
+            .fetch_one(&mut *tx)
             .await?;
-        }
 
-        tx.commit().await?;
+            Self::touch_user_word(&mut tx, user_word_id).await?;
+            tx.commit().await?;
 
-        let result = UserSense::from_parts(
-            Some(updated.try_get("id")?),
-            updated.try_get("text")?,
-            updated.try_get("is_primary")?,
-            updated.try_get("sort_order")?,
-            updated.try_get("note")?,
-            updated.try_get("created_at")?,
-        )?;
-
-        Ok(result)
+            Self::map_sense(&updated)
+        })
+        .await
     }
 
     async fn remove_user_sense(
@@ -479,84 +1388,2475 @@ impl WordRepository for PgWordRepository {
         user_id: i64,
         sense_id: i64,
     ) -> Result<UserSense, WordRepositoryError> {
-        let mut tx = self.pool.begin().await?;
+        timed(Dependency::Postgres, async {
+            let mut tx = self.pool.begin().await?;
 
-        let row = sqlx::query(
-            r#"
-            DELETE FROM user_senses
-            USING user_words
-            WHERE user_senses.id = $1
-              AND user_senses.user_word_id = user_words.id
-              AND user_words.user_id = $2
-            RETURNING user_senses.id, user_senses.text, user_senses.is_primary, user_senses.sort_order, user_senses.note, user_senses.created_at
-            "#,
-        )
-        .bind(sense_id)
-        .bind(user_id)
-        .fetch_one(&mut *tx)
-        .await?;
+            let row = sqlx::query(
+                r#"
+                DELETE FROM user_senses
+                USING user_words
+                WHERE user_senses.id = $1
+                  AND user_senses.user_word_id = user_words.id
+                  AND user_words.user_id = $2
+                RETURNING user_senses.id, user_senses.user_word_id, user_senses.text, user_senses.is_primary, user_senses.sort_order, user_senses.note, user_senses.source_url, user_senses.created_at
+                "#,
+            )
+            .bind(sense_id)
+            .bind(user_id)
+            .fetch_one(&mut *tx)
+            .await?;
 
-        tx.commit().await?;
+            Self::touch_user_word(&mut tx, row.try_get("user_word_id")?).await?;
+            tx.commit().await?;
 
-        UserSense::from_parts(
-            Some(row.try_get("id")?),
-            row.try_get("text")?,
-            row.try_get("is_primary")?,
-            row.try_get("sort_order")?,
-            row.try_get("note")?,
-            row.try_get("created_at")?,
-        )
-        .map_err(WordRepositoryError::from)
+            Self::map_sense(&row)
+        })
+        .await
+    }
+
+    async fn set_primary_sense(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+    ) -> Result<UserSense, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let mut tx = self.pool.begin().await?;
+            let user_word_id: i64 = sqlx::query_scalar(
+                r#"
+                SELECT us.user_word_id
+                FROM user_senses us
+                JOIN user_words uw ON uw.id = us.user_word_id
+                WHERE us.id = $1 AND uw.user_id = $2
+                FOR UPDATE OF uw, us
+                "#,
+            )
+            .bind(sense_id)
+            .bind(user_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+            // 已是唯一主义项时不更新 updated_at，避免增量同步收到空改动
+            if Self::make_primary(&mut tx, user_word_id, sense_id).await? {
+                Self::touch_user_word(&mut tx, user_word_id).await?;
+            }
+            let row = sqlx::query(
+                r#"
+                SELECT id, text, is_primary, sort_order, note, source_url, created_at
+                FROM user_senses
+                WHERE id = $1
+                "#,
+            )
+            .bind(sense_id)
+            .fetch_one(&mut *tx)
+            .await?;
+            tx.commit().await?;
+
+            Self::map_sense(&row)
+        })
+        .await
     }
 
     async fn search(
         &self,
         params: SearchParams,
-    ) -> Result<Vec<UserWordAggregate>, WordRepositoryError> {
-        let base = format!("{}", Self::aggregate_query());
-        let trimmed = params.query.trim();
-        let sql;
-        let rows = if trimmed.is_empty() {
-            sql = format!(
-                "{} GROUP BY uw.id, w.id ORDER BY w.canonical_key LIMIT $2 OFFSET $3",
-                base
+    ) -> Result<Vec<(UserWordAggregate, Option<SearchMatch>)>, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let filter = SearchFilter::from_params(&params);
+            let mut next_index = filter.next_index;
+            let mut condition = filter.condition;
+            if params.after.is_some() {
+                condition.push_str(&format!(
+                    " AND (w.canonical_key, uw.id) > (${}, ${})",
+                    next_index,
+                    next_index + 1
+                ));
+                next_index += 2;
+            }
+            let order_by = match (&filter.rank, params.sort) {
+                (Some(rank), SearchSort::Relevance) => {
+                    format!("{rank} DESC, {}", params.sort.order_by())
+                }
+                _ => params.sort.order_by().to_string(),
+            };
+            let sql = format!(
+                "{}{} ORDER BY {} LIMIT ${} OFFSET ${}",
+                Self::aggregate_query_ordered(
+                    self.max_senses_per_word,
+                    SenseOrder::default(),
+                    &filter.match_columns
+                ),
+                condition,
+                order_by,
+                next_index,
+                next_index + 1
             );
-            sqlx::query(&sql)
-                .bind(params.user_id)
+
+            let mut query = sqlx::query(&sql).bind(params.user_id);
+            if let Some(text_query) = filter.text_query {
+                query = query.bind(text_query);
+            }
+            if let Some(pattern) = filter.pattern {
+                query = query.bind(pattern);
+            }
+            if let Some(sense_pattern) = filter.sense_pattern {
+                query = query.bind(sense_pattern);
+            }
+            if let Some(tags) = filter.tags {
+                query = query.bind(tags);
+            }
+            if let Some(word_ids) = filter.word_ids {
+                query = query.bind(word_ids);
+            }
+            if let Some(cursor) = params.after {
+                query = query.bind(cursor.canonical_key).bind(cursor.user_word_id);
+            }
+            let rows = query
                 .bind(params.limit)
                 .bind(params.offset)
                 .fetch_all(&self.pool)
-                .await?
-        } else {
-            let condition = match params.scope {
-                SearchScope::Word => "w.canonical_key ILIKE $2".to_string(),
-                SearchScope::Sense => {
-                    "EXISTS (SELECT 1 FROM user_senses sub WHERE sub.user_word_id = uw.id AND sub.text ILIKE $2)".to_string()
-                }
-                SearchScope::Both => {
-                    "(w.canonical_key ILIKE $2 OR EXISTS (SELECT 1 FROM user_senses sub WHERE sub.user_word_id = uw.id AND sub.text ILIKE $2))".to_string()
-                }
-            };
+                .await?;
+
+            let with_matches = !filter.match_columns.is_empty();
+            rows.into_iter()
+                .map(|row| {
+                    let search_match = if with_matches {
+                        Self::map_search_match(&row)?
+                    } else {
+                        None
+                    };
+                    Ok((Self::build_aggregate(row)?, search_match))
+                })
+                .collect()
+        })
+        .await
+    }
+
+    async fn count_search(&self, params: &SearchParams) -> Result<i64, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let filter = SearchFilter::from_params(params);
+            let sql = format!(
+                r#"
+                SELECT COUNT(*)
+                FROM user_words uw
+                JOIN words w ON w.id = uw.word_id
+                WHERE uw.user_id = $1{}
+                "#,
+                filter.condition
+            );
+
+            let mut query = sqlx::query_scalar::<_, i64>(&sql).bind(params.user_id);
+            if let Some(text_query) = filter.text_query {
+                query = query.bind(text_query);
+            }
+            if let Some(pattern) = filter.pattern {
+                query = query.bind(pattern);
+            }
+            if let Some(sense_pattern) = filter.sense_pattern {
+                query = query.bind(sense_pattern);
+            }
+            if let Some(tags) = filter.tags {
+                query = query.bind(tags);
+            }
+            if let Some(word_ids) = filter.word_ids {
+                query = query.bind(word_ids);
+            }
+            Ok(query.fetch_one(&self.pool).await?)
+        })
+        .await
+    }
+
+    async fn search_word_ids(
+        &self,
+        params: &SearchParams,
+    ) -> Result<Vec<i64>, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let filter = SearchFilter::from_params(params);
+            let sql = format!(
+                r#"
+                SELECT w.id
+                FROM user_words uw
+                JOIN words w ON w.id = uw.word_id
+                WHERE uw.user_id = $1{}
+                ORDER BY w.canonical_key, uw.id
+                "#,
+                filter.condition
+            );
+
+            let mut query = sqlx::query_scalar::<_, i64>(&sql).bind(params.user_id);
+            if let Some(text_query) = filter.text_query {
+                query = query.bind(text_query);
+            }
+            if let Some(pattern) = filter.pattern {
+                query = query.bind(pattern);
+            }
+            if let Some(sense_pattern) = filter.sense_pattern {
+                query = query.bind(sense_pattern);
+            }
+            if let Some(tags) = filter.tags {
+                query = query.bind(tags);
+            }
+            if let Some(word_ids) = filter.word_ids {
+                query = query.bind(word_ids);
+            }
+            Ok(query.fetch_all(&self.pool).await?)
+        })
+        .await
+    }
+
+    async fn find_sense_word_id(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+    ) -> Result<Option<i64>, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let word_id = sqlx::query_scalar::<_, i64>(
+                r#"
+                SELECT uw.word_id
+                FROM user_senses us
+                JOIN user_words uw ON uw.id = us.user_word_id
+                WHERE us.id = $1 AND uw.user_id = $2
+                "#,
+            )
+            .bind(sense_id)
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+            Ok(word_id)
+        })
+        .await
+    }
 
-            sql = format!(
-                "{} AND {} GROUP BY uw.id, w.id ORDER BY w.canonical_key LIMIT $3 OFFSET $4",
-                base, condition
+    async fn find_user_word_by_sense(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+        sense_order: SenseOrder,
+    ) -> Result<Option<UserWordAggregate>, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let sql = format!(
+                "{} AND uw.id = (SELECT user_word_id FROM user_senses WHERE id = $2)",
+                Self::aggregate_query_ordered(None, sense_order, "")
             );
+            let maybe_row = sqlx::query(&sql)
+                .bind(user_id)
+                .bind(sense_id)
+                .fetch_optional(&self.pool)
+                .await?;
+            match maybe_row {
+                Some(row) => Ok(Some(Self::build_aggregate_ordered(row, sense_order)?)),
+                None => Ok(None),
+            }
+        })
+        .await
+    }
 
-            let pattern = match params.scope {
-                SearchScope::Word => format!("%{}%", Self::canonical_like_pattern(trimmed)),
-                _ => format!("%{}%", trimmed),
+    async fn find_owner(
+        &self,
+        resource: OwnedResource,
+    ) -> Result<Option<i64>, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let query = match resource {
+                OwnedResource::UserWord(id) => {
+                    sqlx::query_scalar::<_, i64>("SELECT user_id FROM user_words WHERE id = $1")
+                        .bind(id)
+                }
+                OwnedResource::Sense(id) => sqlx::query_scalar::<_, i64>(
+                    r#"
+                    SELECT uw.user_id
+                    FROM user_senses us
+                    JOIN user_words uw ON uw.id = us.user_word_id
+                    WHERE us.id = $1
+                    "#,
+                )
+                .bind(id),
             };
+            Ok(query.fetch_optional(&self.pool).await?)
+        })
+        .await
+    }
 
-            sqlx::query(&sql)
-                .bind(params.user_id)
-                .bind(pattern)
-                .bind(params.limit)
-                .bind(params.offset)
-                .fetch_all(&self.pool)
-                .await?
-        };
+    async fn count_owned_user_words(
+        &self,
+        user_id: i64,
+        user_word_ids: &[i64],
+    ) -> Result<i64, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let count = sqlx::query_scalar::<_, i64>(
+                r#"
+                SELECT COUNT(*)
+                FROM user_words
+                WHERE user_id = $1 AND id = ANY($2)
+                "#,
+            )
+            .bind(user_id)
+            .bind(user_word_ids)
+            .fetch_one(&self.pool)
+            .await?;
+            Ok(count)
+        })
+        .await
+    }
 
-        rows.into_iter().map(Self::build_aggregate).collect()
+    async fn existing_canonical_keys(
+        &self,
+        user_id: i64,
+        keys: &[CanonicalKey],
+    ) -> Result<Vec<CanonicalKey>, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let keys: Vec<&str> = keys.iter().map(CanonicalKey::as_str).collect();
+            let rows = sqlx::query_scalar::<_, String>(
+                r#"
+                SELECT w.canonical_key
+                FROM user_words uw
+                JOIN words w ON w.id = uw.word_id
+                WHERE uw.user_id = $1 AND w.canonical_key = ANY($2)
+                "#,
+            )
+            .bind(user_id)
+            .bind(&keys)
+            .fetch_all(&self.pool)
+            .await?;
+            rows.into_iter()
+                .map(|key| CanonicalKey::from_stored(key).map_err(WordRepositoryError::from))
+                .collect()
+        })
+        .await
+    }
+
+    async fn find_near_duplicate_words(
+        &self,
+        user_id: i64,
+        threshold: f32,
+        limit: i64,
+    ) -> Result<Vec<NearDuplicatePair>, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let rows = sqlx::query(
+                r#"
+                WITH mine AS (
+                    SELECT uw.id AS user_word_id, w.id AS word_id, w.text, w.canonical_key
+                    FROM user_words uw
+                    JOIN words w ON w.id = uw.word_id
+                    WHERE uw.user_id = $1
+                )
+                SELECT a.user_word_id AS a_user_word_id, a.word_id AS a_word_id,
+                       a.text AS a_text, a.canonical_key AS a_canonical_key,
+                       b.user_word_id AS b_user_word_id, b.word_id AS b_word_id,
+                       b.text AS b_text, b.canonical_key AS b_canonical_key,
+                       similarity(a.canonical_key, b.canonical_key) AS score
+                FROM mine a
+                JOIN mine b ON a.canonical_key < b.canonical_key
+                WHERE similarity(a.canonical_key, b.canonical_key) >= $2
+                ORDER BY score DESC, a.canonical_key, b.canonical_key
+                LIMIT $3
+                "#,
+            )
+            .bind(user_id)
+            .bind(threshold)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?;
+
+            let candidate =
+                |row: &PgRow, prefix: &str| -> Result<DuplicateCandidate, WordRepositoryError> {
+                    Ok(DuplicateCandidate {
+                        user_word_id: row.try_get(format!("{prefix}_user_word_id").as_str())?,
+                        word_id: row.try_get(format!("{prefix}_word_id").as_str())?,
+                        text: row.try_get(format!("{prefix}_text").as_str())?,
+                        canonical_key: CanonicalKey::from_stored(
+                            row.try_get(format!("{prefix}_canonical_key").as_str())?,
+                        )?,
+                    })
+                };
+            rows.iter()
+                .map(|row| {
+                    Ok(NearDuplicatePair {
+                        first: candidate(row, "a")?,
+                        second: candidate(row, "b")?,
+                        similarity: row.try_get("score")?,
+                    })
+                })
+                .collect()
+        })
+        .await
+    }
+
+    async fn find_words_by_ids(
+        &self,
+        word_ids: &[i64],
+    ) -> Result<Vec<WordRecord>, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            if word_ids.is_empty() {
+                return Ok(Vec::new());
+            }
+            let rows = sqlx::query(
+                r#"
+                SELECT id, text, canonical_key, created_at
+                FROM words
+                WHERE id = ANY($1)
+                "#,
+            )
+            .bind(word_ids)
+            .fetch_all(&self.pool)
+            .await?;
+            rows.iter().map(Self::map_word_row).collect()
+        })
+        .await
+    }
+
+    async fn find_user_words_by_word_ids(
+        &self,
+        user_id: i64,
+        word_ids: &[i64],
+    ) -> Result<Vec<UserWordAggregate>, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let sql = format!(
+                "{} AND uw.word_id = ANY($2)",
+                Self::aggregate_query(self.max_senses_per_word)
+            );
+            let rows = sqlx::query(&sql)
+                .bind(user_id)
+                .bind(word_ids)
+                .fetch_all(&self.pool)
+                .await?;
+            rows.into_iter().map(Self::build_aggregate).collect()
+        })
+        .await
+    }
+
+    async fn list_user_words_after(
+        &self,
+        user_id: i64,
+        after: Option<i64>,
+        limit: i64,
+    ) -> Result<Vec<UserWordAggregate>, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let sql = format!(
+                "{} AND ($2::BIGINT IS NULL OR uw.id > $2) ORDER BY uw.id LIMIT $3",
+                Self::aggregate_query(None)
+            );
+            let rows = sqlx::query(&sql)
+                .bind(user_id)
+                .bind(after)
+                .bind(limit)
+                .fetch_all(&self.pool)
+                .await?;
+            rows.into_iter().map(Self::build_aggregate).collect()
+        })
+        .await
+    }
+
+    async fn find_words_in_range(
+        &self,
+        user_id: i64,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<UserWordAggregate>, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let sql = format!(
+                r#"{}
+                AND ($2::TIMESTAMPTZ IS NULL OR uw.created_at >= $2)
+                AND ($3::TIMESTAMPTZ IS NULL OR uw.created_at <= $3)
+                ORDER BY uw.created_at, uw.id
+                LIMIT $4 OFFSET $5"#,
+                Self::aggregate_query(self.max_senses_per_word)
+            );
+            let rows = sqlx::query(&sql)
+                .bind(user_id)
+                .bind(from)
+                .bind(to)
+                .bind(limit)
+                .bind(offset)
+                .fetch_all(&self.pool)
+                .await?;
+            rows.into_iter().map(Self::build_aggregate).collect()
+        })
+        .await
+    }
+
+    async fn find_word_ids_in_range(
+        &self,
+        user_id: i64,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<Vec<i64>, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let word_ids = sqlx::query_scalar(
+                r#"
+                SELECT word_id
+                FROM user_words
+                WHERE user_id = $1
+                  AND ($2::TIMESTAMPTZ IS NULL OR created_at >= $2)
+                  AND ($3::TIMESTAMPTZ IS NULL OR created_at <= $3)
+                ORDER BY created_at, id
+                "#,
+            )
+            .bind(user_id)
+            .bind(from)
+            .bind(to)
+            .fetch_all(&self.pool)
+            .await?;
+            Ok(word_ids)
+        })
+        .await
+    }
+
+    async fn count_words_in_range(
+        &self,
+        user_id: i64,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+    ) -> Result<i64, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let count: i64 = sqlx::query_scalar(
+                r#"
+                SELECT COUNT(*)
+                FROM user_words
+                WHERE user_id = $1
+                  AND ($2::TIMESTAMPTZ IS NULL OR created_at >= $2)
+                  AND ($3::TIMESTAMPTZ IS NULL OR created_at <= $3)
+                "#,
+            )
+            .bind(user_id)
+            .bind(from)
+            .bind(to)
+            .fetch_one(&self.pool)
+            .await?;
+            Ok(count)
+        })
+        .await
+    }
+
+    async fn list_changes_since(
+        &self,
+        user_id: i64,
+        since: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<WordChanges, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let sql = format!(
+                "{} AND uw.updated_at > $2 ORDER BY uw.updated_at, uw.id LIMIT $3",
+                Self::aggregate_query(None)
+            );
+            let rows = sqlx::query(&sql)
+                .bind(user_id)
+                .bind(since)
+                .bind(limit)
+                .fetch_all(&self.pool)
+                .await?;
+            let updated = rows
+                .into_iter()
+                .map(Self::build_aggregate)
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let deleted = sqlx::query(
+                r#"
+                SELECT user_word_id, word_id, deleted_at
+                FROM user_word_tombstones
+                WHERE user_id = $1 AND deleted_at > $2
+                ORDER BY deleted_at, id
+                LIMIT $3
+                "#,
+            )
+            .bind(user_id)
+            .bind(since)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await?
+            .into_iter()
+            .map(|row| {
+                Ok(WordTombstone {
+                    user_word_id: row.try_get("user_word_id")?,
+                    word_id: row.try_get("word_id")?,
+                    deleted_at: row.try_get("deleted_at")?,
+                })
+            })
+            .collect::<Result<Vec<_>, sqlx::Error>>()?;
+
+            Ok(WordChanges { updated, deleted })
+        })
+        .await
+    }
+
+    async fn update_tag(
+        &self,
+        user_id: i64,
+        user_word_ids: &[i64],
+        tag: &str,
+        action: TagAction,
+    ) -> Result<u64, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let sql = match action {
+                TagAction::Add => {
+                    r#"
+                    UPDATE user_words
+                    SET tags = array_append(tags, $3), updated_at = NOW()
+                    WHERE user_id = $1
+                      AND id = ANY($2)
+                      AND NOT ($3 = ANY(tags))
+                      AND cardinality(tags) < $4
+                    "#
+                }
+                TagAction::Remove => {
+                    r#"
+                    UPDATE user_words
+                    SET tags = array_remove(tags, $3), updated_at = NOW()
+                    WHERE user_id = $1
+                      AND id = ANY($2)
+                      AND $3 = ANY(tags)
+                    "#
+                }
+            };
+            let mut query = sqlx::query(sql).bind(user_id).bind(user_word_ids).bind(tag);
+            if action == TagAction::Add {
+                query = query.bind(MAX_TAGS as i32);
+            }
+            let result = query.execute(&self.pool).await?;
+            Ok(result.rows_affected())
+        })
+        .await
+    }
+
+    async fn replace_tags(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        tags: &[String],
+    ) -> Result<bool, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let result = sqlx::query(
+                "UPDATE user_words SET tags = $3, updated_at = NOW() WHERE user_id = $1 AND id = $2",
+            )
+            .bind(user_id)
+            .bind(user_word_id)
+            .bind(tags)
+            .execute(&self.pool)
+            .await?;
+            Ok(result.rows_affected() > 0)
+        })
+        .await
+    }
+
+    async fn update_user_word_metadata(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        tags: &[String],
+        note: Option<&str>,
+    ) -> Result<bool, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let result = sqlx::query(
+                r#"
+                UPDATE user_words SET tags = $3, note = $4, updated_at = NOW()
+                WHERE user_id = $1 AND id = $2
+                "#,
+            )
+            .bind(user_id)
+            .bind(user_word_id)
+            .bind(tags)
+            .bind(note)
+            .execute(&self.pool)
+            .await?;
+            Ok(result.rows_affected() > 0)
+        })
+        .await
+    }
+
+    async fn repoint_user_word(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        word_id: i64,
+    ) -> Result<WordRepoint, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let mut tx = self.pool.begin().await?;
+            let previous: Option<i64> = sqlx::query_scalar(
+                "SELECT word_id FROM user_words WHERE id = $1 AND user_id = $2 FOR UPDATE",
+            )
+            .bind(user_word_id)
+            .bind(user_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+            let Some(previous_word_id) = previous else {
+                return Ok(WordRepoint::WordNotFound);
+            };
+            if previous_word_id == word_id {
+                return Ok(WordRepoint::Moved { previous_word_id });
+            }
+            let existing: Option<i64> =
+                sqlx::query_scalar("SELECT id FROM user_words WHERE user_id = $1 AND word_id = $2")
+                    .bind(user_id)
+                    .bind(word_id)
+                    .fetch_optional(&mut *tx)
+                    .await?;
+            if let Some(existing) = existing {
+                return Ok(WordRepoint::Collision(existing));
+            }
+
+            sqlx::query(
+                r#"
+                UPDATE user_words
+                SET word_id = $2, repointed_from_word_id = $3, updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(user_word_id)
+            .bind(word_id)
+            .bind(previous_word_id)
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            Ok(WordRepoint::Moved { previous_word_id })
+        })
+        .await
+    }
+
+    async fn pending_word_repoint(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+    ) -> Result<Option<i64>, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let pending: Option<Option<i64>> = sqlx::query_scalar(
+                "SELECT repointed_from_word_id FROM user_words WHERE id = $1 AND user_id = $2",
+            )
+            .bind(user_word_id)
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await?;
+            Ok(pending.flatten())
+        })
+        .await
+    }
+
+    async fn complete_word_repoint(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        previous_word_id: i64,
+    ) -> Result<(), WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let mut tx = self.pool.begin().await?;
+            sqlx::query(
+                r#"
+                UPDATE user_words SET repointed_from_word_id = NULL
+                WHERE id = $1 AND user_id = $2 AND repointed_from_word_id = $3
+                "#,
+            )
+            .bind(user_word_id)
+            .bind(user_id)
+            .bind(previous_word_id)
+            .execute(&mut *tx)
+            .await?;
+            sqlx::query(
+                r#"
+                DELETE FROM words w
+                WHERE w.id = $1
+                  AND NOT EXISTS (SELECT 1 FROM user_words uw WHERE uw.word_id = w.id)
+                  AND NOT EXISTS (
+                      SELECT 1 FROM user_words uw WHERE uw.repointed_from_word_id = w.id
+                  )
+                "#,
+            )
+            .bind(previous_word_id)
+            .execute(&mut *tx)
+            .await?;
+            tx.commit().await?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn reorder_senses(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        sense_ids: &[i64],
+    ) -> Result<SenseReorder, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let mut tx = self.pool.begin().await?;
+            let owned: Option<i64> = sqlx::query_scalar(
+                "SELECT id FROM user_words WHERE id = $1 AND user_id = $2 FOR UPDATE",
+            )
+            .bind(user_word_id)
+            .bind(user_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+            if owned.is_none() {
+                return Ok(SenseReorder::WordNotFound);
+            }
+
+            let mut current: Vec<i64> =
+                sqlx::query_scalar("SELECT id FROM user_senses WHERE user_word_id = $1")
+                    .bind(user_word_id)
+                    .fetch_all(&mut *tx)
+                    .await?;
+            let mut requested = sense_ids.to_vec();
+            current.sort_unstable();
+            requested.sort_unstable();
+            if current != requested {
+                return Ok(SenseReorder::Mismatch);
+            }
+
+            sqlx::query(
+                r#"
+                UPDATE user_senses us
+                SET sort_order = (o.position - 1)::INT
+                FROM unnest($2::BIGINT[]) WITH ORDINALITY AS o(id, position)
+                WHERE us.id = o.id AND us.user_word_id = $1
+                "#,
+            )
+            .bind(user_word_id)
+            .bind(sense_ids)
+            .execute(&mut *tx)
+            .await?;
+            Self::touch_user_word(&mut tx, user_word_id).await?;
+            tx.commit().await?;
+            Ok(SenseReorder::Reordered)
+        })
+        .await
+    }
+
+    async fn repair_primaries(&self, user_id: i64) -> Result<u64, WordRepositoryError> {
+        timed(Dependency::Postgres, async {
+            let mut tx = self.pool.begin().await?;
+            // 与单个单词的主义项变更一样先锁 user_words 行
+            sqlx::query("SELECT id FROM user_words WHERE user_id = $1 FOR UPDATE")
+                .bind(user_id)
+                .execute(&mut *tx)
+                .await?;
+
+            // 每个单词恰好改动一种方向：缺主义项时只提升一个，多个时只降级多余的，
+            // 因此不会与 `user_senses_primary_unique` 冲突
+            let mut repaired: Vec<i64> = sqlx::query_scalar(
+                r#"
+                WITH ranked AS (
+                    SELECT us.id,
+                           ROW_NUMBER() OVER (
+                               PARTITION BY us.user_word_id
+                               ORDER BY us.is_primary DESC, us.sort_order, us.id
+                           ) AS position,
+                           COUNT(*) FILTER (WHERE us.is_primary)
+                               OVER (PARTITION BY us.user_word_id) AS primaries
+                    FROM user_senses us
+                    JOIN user_words uw ON uw.id = us.user_word_id
+                    WHERE uw.user_id = $1
+                )
+                UPDATE user_senses us
+                SET is_primary = (ranked.position = 1)
+                FROM ranked
+                WHERE us.id = ranked.id
+                  AND ranked.primaries <> 1
+                  AND us.is_primary IS DISTINCT FROM (ranked.position = 1)
+                RETURNING us.user_word_id
+                "#,
+            )
+            .bind(user_id)
+            .fetch_all(&mut *tx)
+            .await?;
+            repaired.sort_unstable();
+            repaired.dedup();
+
+            sqlx::query("UPDATE user_words SET updated_at = NOW() WHERE id = ANY($1)")
+                .bind(&repaired)
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+            Ok(repaired.len() as u64)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::test_support::{insert_user, migrate};
+    use chrono::TimeZone;
+
+    async fn add_word(repo: &PgWordRepository, user_id: i64, text: &str, tags: &[&str]) {
+        repo.upsert_user_word(UpsertUserWord {
+            user_id,
+            word_text: text.into(),
+            canonical_key: CanonicalKey::new(text).unwrap(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            note: None,
+        })
+        .await
+        .unwrap();
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn list_user_words_after_pages_by_id(pool: PgPool) {
+        migrate(&pool).await;
+        let owner = insert_user(&pool, "sync_owner").await;
+        let other = insert_user(&pool, "sync_other").await;
+        let repo = PgWordRepository::new(pool);
+        for text in ["one", "two", "three"] {
+            add_word(&repo, owner, text, &[]).await;
+        }
+        add_word(&repo, other, "four", &[]).await;
+
+        let first = repo.list_user_words_after(owner, None, 2).await.unwrap();
+        let texts: Vec<&str> = first.iter().map(|item| item.word.text.as_str()).collect();
+        assert_eq!(texts, ["one", "two"]);
+        let rest = repo
+            .list_user_words_after(owner, first[1].user_word.id, 2)
+            .await
+            .unwrap();
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].word.text, "three");
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn find_words_in_range_includes_both_boundaries(pool: PgPool) {
+        migrate(&pool).await;
+        let owner = insert_user(&pool, "range_owner").await;
+        let repo = PgWordRepository::new(pool.clone());
+        for (text, day) in [
+            ("early", 1),
+            ("first", 2),
+            ("middle", 3),
+            ("last", 4),
+            ("late", 5),
+        ] {
+            add_word(&repo, owner, text, &[]).await;
+            sqlx::query(
+                "UPDATE user_words SET created_at = make_timestamptz(2025, 1, $1, 0, 0, 0, 'UTC') \
+                 WHERE user_id = $2 AND word_id = (SELECT id FROM words WHERE text = $3)",
+            )
+            .bind(day)
+            .bind(owner)
+            .bind(text)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        let day = |d: u32| Utc.with_ymd_and_hms(2025, 1, d, 0, 0, 0).unwrap();
+
+        let found = repo
+            .find_words_in_range(owner, Some(day(2)), Some(day(4)), 10, 0)
+            .await
+            .unwrap();
+        let texts: Vec<&str> = found.iter().map(|item| item.word.text.as_str()).collect();
+        assert_eq!(texts, ["first", "middle", "last"]);
+        assert_eq!(
+            repo.count_words_in_range(owner, Some(day(2)), Some(day(4)))
+                .await
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            repo.find_word_ids_in_range(owner, Some(day(2)), Some(day(4)))
+                .await
+                .unwrap(),
+            found.iter().map(|item| item.word.id).collect::<Vec<_>>()
+        );
+
+        let paged = repo
+            .find_words_in_range(owner, Some(day(2)), None, 2, 2)
+            .await
+            .unwrap();
+        let texts: Vec<&str> = paged.iter().map(|item| item.word.text.as_str()).collect();
+        assert_eq!(texts, ["last", "late"]);
+        assert_eq!(
+            repo.count_words_in_range(owner, None, None).await.unwrap(),
+            5
+        );
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn list_changes_since_reports_edits_and_removals(pool: PgPool) {
+        migrate(&pool).await;
+        let owner = insert_user(&pool, "delta_owner").await;
+        let repo = PgWordRepository::new(pool);
+        for text in ["old", "edited", "gone"] {
+            add_word(&repo, owner, text, &[]).await;
+        }
+        let words = repo.list_user_words_after(owner, None, 10).await.unwrap();
+        let id_of = |text: &str| {
+            words
+                .iter()
+                .find(|item| item.word.text == text)
+                .and_then(|item| item.user_word.id)
+                .unwrap()
+        };
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        let since = Utc::now();
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+        repo.add_user_sense(NewUserSense {
+            user_word_id: id_of("edited"),
+            text: "changed".into(),
+            is_primary: true,
+            sort_order: 0,
+            note: None,
+            source_url: None,
+        })
+        .await
+        .unwrap();
+        repo.remove_user_word(owner, id_of("gone")).await.unwrap();
+
+        let changes = repo.list_changes_since(owner, since, 10).await.unwrap();
+        assert_eq!(changes.updated.len(), 1);
+        assert_eq!(changes.updated[0].word.text, "edited");
+        assert_eq!(changes.updated[0].user_word.senses().len(), 1);
+        assert_eq!(changes.deleted.len(), 1);
+        assert_eq!(changes.deleted[0].user_word_id, id_of("gone"));
+        assert!(changes.deleted[0].deleted_at > since);
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn near_duplicates_pair_similar_keys_only(pool: PgPool) {
+        migrate(&pool).await;
+        let owner = insert_user(&pool, "dup_owner").await;
+        let other = insert_user(&pool, "dup_other").await;
+        let repo = PgWordRepository::new(pool);
+        for text in ["email", "e-mail", "banana"] {
+            add_word(&repo, owner, text, &[]).await;
+        }
+        // 其他用户的近似单词不参与配对
+        add_word(&repo, other, "emails", &[]).await;
+
+        let pairs = repo
+            .find_near_duplicate_words(owner, 0.3, 10)
+            .await
+            .unwrap();
+        assert_eq!(pairs.len(), 1, "{pairs:?}");
+        assert_eq!(pairs[0].first.text, "e-mail");
+        assert_eq!(pairs[0].second.text, "email");
+        assert!(pairs[0].similarity >= 0.3);
+
+        assert!(
+            repo.find_near_duplicate_words(owner, 0.9, 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn concurrent_primary_inserts_leave_exactly_one_primary(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "primary_racer").await;
+        let repo = PgWordRepository::new(pool.clone());
+        add_word(&repo, user_id, "run", &[]).await;
+        let user_word_id = repo
+            .find_user_word_by_canonical(user_id, &CanonicalKey::new("run").unwrap())
+            .await
+            .unwrap()
+            .unwrap()
+            .user_word
+            .id
+            .unwrap();
+
+        let sense = |text: &str| NewUserSense {
+            user_word_id,
+            text: text.into(),
+            is_primary: true,
+            sort_order: 0,
+            note: None,
+            source_url: None,
+        };
+        for round in 0..5 {
+            let (a, b) = tokio::join!(
+                repo.add_user_sense(sense(&format!("move fast {round}"))),
+                repo.add_user_sense(sense(&format!("operate {round}"))),
+            );
+            a.unwrap();
+            b.unwrap();
+
+            let primaries: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM user_senses WHERE user_word_id = $1 AND is_primary",
+            )
+            .bind(user_word_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+            assert_eq!(primaries, 1, "round {round}");
+        }
+
+        // 把另一个义项设为主义项同样只保留一个
+        let other = repo.add_user_sense(NewUserSense {
+            is_primary: false,
+            ..sense("sprint")
+        });
+        let other = other.await.unwrap();
+        let updated = repo
+            .update_user_sense(
+                user_id,
+                other.id().unwrap(),
+                SenseUpdate {
+                    text: None,
+                    is_primary: Some(true),
+                    sort_order: None,
+                    note: None,
+                    source_url: None,
+                },
+            )
+            .await
+            .unwrap();
+        assert!(updated.is_primary);
+        let primaries: Vec<i64> =
+            sqlx::query_scalar("SELECT id FROM user_senses WHERE user_word_id = $1 AND is_primary")
+                .bind(user_word_id)
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(primaries, vec![other.id().unwrap()]);
+    }
+
+    #[test]
+    fn search_cursor_round_trips_and_rejects_garbage() {
+        let cursor = SearchCursor {
+            canonical_key: "ice-cream:x".into(),
+            user_word_id: 42,
+        };
+        assert_eq!(SearchCursor::decode(&cursor.encode()), Some(cursor));
+        assert_eq!(SearchCursor::decode("zz"), None);
+        assert_eq!(SearchCursor::decode("abc"), None);
+        assert_eq!(SearchCursor::decode("616263"), None);
+    }
+
+    #[test]
+    fn sync_cursor_round_trips_and_rejects_search_cursors() {
+        let cursor = SyncCursor { user_word_id: 42 };
+        assert_eq!(SyncCursor::decode(&cursor.encode()), Some(cursor));
+        let search = SearchCursor {
+            canonical_key: "apple".into(),
+            user_word_id: 42,
+        };
+        assert_eq!(SyncCursor::decode(&search.encode()), None);
+        assert_eq!(SyncCursor::decode("zz"), None);
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn upsert_user_word_reports_creation(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "upserter").await;
+        let repo = PgWordRepository::new(pool);
+        let payload = |note: Option<&str>| UpsertUserWord {
+            user_id,
+            word_text: "apple".into(),
+            canonical_key: CanonicalKey::new("apple").unwrap(),
+            tags: vec![],
+            note: note.map(str::to_string),
+        };
+
+        let first = repo.upsert_user_word(payload(None)).await.unwrap();
+        assert!(first.created);
+
+        let second = repo.upsert_user_word(payload(Some("again"))).await.unwrap();
+        assert!(!second.created);
+        assert_eq!(second.aggregate.user_word.id, first.aggregate.user_word.id);
+        assert_eq!(second.aggregate.user_word.note(), Some("again"));
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn upsert_user_word_reports_a_row_removed_before_read_back(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "vanishing").await;
+        // 模拟并发删除：写入后立即删掉刚插入的行
+        sqlx::raw_sql(
+            r#"
+            CREATE FUNCTION drop_new_user_word() RETURNS trigger AS $$
+            BEGIN
+                DELETE FROM user_words WHERE id = NEW.id;
+                RETURN NULL;
+            END;
+            $$ LANGUAGE plpgsql;
+            CREATE TRIGGER drop_new_user_word AFTER INSERT ON user_words
+                FOR EACH ROW EXECUTE FUNCTION drop_new_user_word();
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let repo = PgWordRepository::new(pool);
+
+        let err = repo
+            .upsert_user_word(UpsertUserWord {
+                user_id,
+                word_text: "ghost".into(),
+                canonical_key: CanonicalKey::new("ghost").unwrap(),
+                tags: vec![],
+                note: None,
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, WordRepositoryError::RemovedConcurrently(_)));
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn import_user_words_reports_a_row_removed_before_read_back(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "vanishing-import").await;
+        sqlx::raw_sql(
+            r#"
+            CREATE FUNCTION drop_new_user_word() RETURNS trigger AS $$
+            BEGIN
+                DELETE FROM user_words WHERE id = NEW.id;
+                RETURN NULL;
+            END;
+            $$ LANGUAGE plpgsql;
+            CREATE TRIGGER drop_new_user_word AFTER INSERT ON user_words
+                FOR EACH ROW EXECUTE FUNCTION drop_new_user_word();
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let repo = PgWordRepository::new(pool);
+
+        let outcomes = repo
+            .import_user_words(vec![ImportUserWord {
+                word: UpsertUserWord {
+                    user_id,
+                    word_text: "ghost".into(),
+                    canonical_key: CanonicalKey::new("ghost").unwrap(),
+                    tags: vec![],
+                    note: None,
+                },
+                senses: vec![],
+            }])
+            .await
+            .unwrap();
+        assert!(matches!(
+            outcomes[0],
+            Err(WordRepositoryError::RemovedConcurrently(_))
+        ));
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn import_user_words_rolls_back_only_the_failing_item(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "importer").await;
+        let repo = PgWordRepository::new(pool.clone());
+        let item = |user_id: i64, text: &str, sense: Option<&str>| ImportUserWord {
+            word: UpsertUserWord {
+                user_id,
+                word_text: text.into(),
+                canonical_key: CanonicalKey::new(text).unwrap(),
+                tags: vec![],
+                note: None,
+            },
+            senses: sense
+                .map(|text| ImportSense {
+                    text: text.into(),
+                    is_primary: true,
+                    sort_order: 0,
+                    note: None,
+                    source_url: None,
+                })
+                .into_iter()
+                .collect(),
+        };
+
+        let outcomes = repo
+            .import_user_words(vec![
+                item(user_id, "apple", Some("a fruit")),
+                // 用户不存在，外键约束失败；保存点回滚连同已写入的 words 行
+                item(user_id + 1000, "orphan", Some("never stored")),
+                item(user_id, "Apple", Some("a fruit")),
+            ])
+            .await
+            .unwrap();
+
+        assert_eq!(outcomes.len(), 3);
+        let first = outcomes[0].as_ref().unwrap();
+        assert!(first.created);
+        assert_eq!(first.aggregate.user_word.senses().len(), 1);
+        assert_eq!(first.deduplicated_senses, 0);
+        assert!(outcomes[1].is_err());
+        let merged = outcomes[2].as_ref().unwrap();
+        assert!(!merged.created);
+        assert_eq!(merged.aggregate.user_word.id, first.aggregate.user_word.id);
+        assert_eq!(merged.aggregate.word.text, "Apple");
+        // 同文本义项不重复写入
+        assert_eq!(merged.aggregate.user_word.senses().len(), 1);
+        assert_eq!(merged.deduplicated_senses, 1);
+
+        let orphaned: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM words WHERE canonical_key = 'orphan'")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(orphaned, 0);
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn sense_cap_applies_to_every_insert_path(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "capped").await;
+        let repo = PgWordRepository::new(pool).with_max_senses(2);
+        add_word(&repo, user_id, "bank", &[]).await;
+        let user_word_id = repo
+            .find_user_word_by_canonical(user_id, &CanonicalKey::new("bank").unwrap())
+            .await
+            .unwrap()
+            .unwrap()
+            .user_word
+            .id
+            .unwrap();
+        let sense = |text: &str| NewUserSense {
+            user_word_id,
+            text: text.into(),
+            is_primary: false,
+            sort_order: 0,
+            note: None,
+            source_url: None,
+        };
+
+        // 并发新增时计数在行锁内完成，只有一个能占到最后一个名额
+        repo.add_user_sense(sense("river side")).await.unwrap();
+        let (a, b) = tokio::join!(
+            repo.add_user_sense(sense("money house")),
+            repo.add_user_sense(sense("tilt"))
+        );
+        assert!(a.is_ok() != b.is_ok());
+        let rejected = [a, b].into_iter().find_map(Result::err).unwrap();
+        assert!(matches!(
+            rejected,
+            WordRepositoryError::UserWord(UserWordError::SenseLimitExceeded(2))
+        ));
+
+        let outcomes = repo
+            .import_user_words(vec![ImportUserWord {
+                word: UpsertUserWord {
+                    user_id,
+                    word_text: "bank".into(),
+                    canonical_key: CanonicalKey::new("bank").unwrap(),
+                    tags: vec![],
+                    note: None,
+                },
+                senses: vec![ImportSense {
+                    text: "a row of keys".into(),
+                    is_primary: false,
+                    sort_order: 0,
+                    note: None,
+                    source_url: None,
+                }],
+            }])
+            .await
+            .unwrap();
+        assert!(matches!(
+            outcomes[0],
+            Err(WordRepositoryError::UserWord(
+                UserWordError::SenseLimitExceeded(2)
+            ))
+        ));
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn find_owner_ignores_the_caller(pool: PgPool) {
+        migrate(&pool).await;
+        let owner = insert_user(&pool, "owner_lookup").await;
+        let repo = PgWordRepository::new(pool);
+        add_word(&repo, owner, "lantern", &[]).await;
+        let aggregate = repo
+            .find_user_word_by_canonical(owner, &CanonicalKey::new("lantern").unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+        let user_word_id = aggregate.user_word.id.unwrap();
+        let sense = repo
+            .add_user_sense(NewUserSense {
+                user_word_id,
+                text: "a portable light".into(),
+                is_primary: true,
+                sort_order: 0,
+                note: None,
+                source_url: None,
+            })
+            .await
+            .unwrap();
+
+        for resource in [
+            OwnedResource::UserWord(user_word_id),
+            OwnedResource::Sense(sense.id().unwrap()),
+        ] {
+            assert_eq!(repo.find_owner(resource).await.unwrap(), Some(owner));
+        }
+        let missing = OwnedResource::Sense(sense.id().unwrap() + 100);
+        assert_eq!(repo.find_owner(missing).await.unwrap(), None);
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn existing_canonical_keys_returns_only_present_keys(pool: PgPool) {
+        migrate(&pool).await;
+        let owner = insert_user(&pool, "exists_owner").await;
+        let other = insert_user(&pool, "exists_other").await;
+        let repo = PgWordRepository::new(pool);
+        add_word(&repo, owner, "Apple", &[]).await;
+        add_word(&repo, owner, "Ice Cream", &[]).await;
+        // 单词存在于 words 表，但不在 owner 的网络中
+        add_word(&repo, other, "cherry", &[]).await;
+
+        let keys: Vec<CanonicalKey> = ["apple", "cherry", "ice cream", "durian"]
+            .into_iter()
+            .map(|text| CanonicalKey::new(text).unwrap())
+            .collect();
+        let mut found: Vec<String> = repo
+            .existing_canonical_keys(owner, &keys)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|key| key.as_str().to_string())
+            .collect();
+        found.sort();
+        assert_eq!(found, vec!["apple", "ice-cream"]);
+
+        assert!(
+            repo.existing_canonical_keys(owner, &[])
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn search_by_tag_returns_only_tagged_words(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "tagger").await;
+        let other_id = insert_user(&pool, "someone").await;
+        let repo = PgWordRepository::new(pool);
+        add_word(&repo, user_id, "apple", &["fruit", "red"]).await;
+        add_word(&repo, user_id, "banana", &["fruit"]).await;
+        add_word(&repo, user_id, "brick", &["red"]).await;
+        add_word(&repo, other_id, "cherry", &["fruit"]).await;
+
+        let params = SearchParams {
+            user_id,
+            tags: vec!["fruit".into()],
+            limit: 10,
+            ..SearchParams::default()
+        };
+        let found = repo.search(params.clone()).await.unwrap();
+        let texts: Vec<&str> = found.iter().map(|(a, _)| a.word.text.as_str()).collect();
+        assert_eq!(texts, vec!["apple", "banana"]);
+        assert_eq!(repo.count_search(&params).await.unwrap(), 2);
+
+        // 标签与关键字同时生效
+        let combined = SearchParams {
+            query: "ban".into(),
+            scope: SearchScope::Word,
+            ..params.clone()
+        };
+        let found = repo.search(combined.clone()).await.unwrap();
+        let texts: Vec<&str> = found.iter().map(|(a, _)| a.word.text.as_str()).collect();
+        assert_eq!(texts, vec!["banana"]);
+        assert_eq!(repo.count_search(&combined).await.unwrap(), 1);
+        let mismatched = SearchParams {
+            query: "brick".into(),
+            ..combined
+        };
+        assert!(repo.search(mismatched).await.unwrap().is_empty());
+
+        // 多个标签需要同时具备
+        let both = SearchParams {
+            tags: vec!["fruit".into(), "red".into()],
+            ..params.clone()
+        };
+        assert_eq!(repo.count_search(&both).await.unwrap(), 1);
+
+        // 空标签不过滤
+        let untagged = SearchParams {
+            tags: Vec::new(),
+            ..params.clone()
+        };
+        assert_eq!(repo.count_search(&untagged).await.unwrap(), 3);
+
+        let unused = SearchParams {
+            tags: vec!["unused".into()],
+            ..params
+        };
+        assert!(repo.search(unused.clone()).await.unwrap().is_empty());
+        assert_eq!(repo.count_search(&unused).await.unwrap(), 0);
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn full_text_search_matches_stemmed_senses(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "stemmer").await;
+        let repo = PgWordRepository::new(pool);
+        for (text, sense) in [
+            ("dash", "a short run"),
+            ("sprint", "run, run and keep running"),
+            ("stroll", "a slow walk"),
+        ] {
+            add_word(&repo, user_id, text, &[]).await;
+            let user_word_id = repo
+                .find_user_word_by_canonical(user_id, &CanonicalKey::new(text).unwrap())
+                .await
+                .unwrap()
+                .unwrap()
+                .user_word
+                .id
+                .unwrap();
+            repo.add_user_sense(NewUserSense {
+                user_word_id,
+                text: sense.into(),
+                is_primary: true,
+                sort_order: 0,
+                note: None,
+                source_url: None,
+            })
+            .await
+            .unwrap();
+        }
+        let params = |query: &str, scope: SearchScope, sort: SearchSort| SearchParams {
+            user_id,
+            query: query.into(),
+            scope,
+            sort,
+            limit: 10,
+            ..SearchParams::default()
+        };
+        let texts = |found: Vec<(UserWordAggregate, Option<SearchMatch>)>| -> Vec<String> {
+            found.into_iter().map(|(a, _)| a.word.text).collect()
+        };
+
+        let running = params("running", SearchScope::Sense, SearchSort::Alphabetical);
+        assert_eq!(
+            texts(repo.search(running.clone()).await.unwrap()),
+            ["dash", "sprint"]
+        );
+        assert_eq!(repo.count_search(&running).await.unwrap(), 2);
+        let running_ids = repo.search_word_ids(&running).await.unwrap();
+        assert_eq!(running_ids.len(), 2);
+        let only_sprint = SearchParams {
+            word_ids: Some(running_ids[1..].to_vec()),
+            ..running.clone()
+        };
+        assert_eq!(texts(repo.search(only_sprint).await.unwrap()), ["sprint"]);
+
+        // 相关度：词频更高的义项排在前面
+        let ranked = params("running", SearchScope::Both, SearchSort::Relevance);
+        assert_eq!(
+            texts(repo.search(ranked).await.unwrap()),
+            ["sprint", "dash"]
+        );
+
+        // 单词范围仍支持输入半个单词
+        let partial = params("spri", SearchScope::Word, SearchSort::Relevance);
+        assert_eq!(texts(repo.search(partial).await.unwrap()), ["sprint"]);
+        let sense_only = params("spri", SearchScope::Sense, SearchSort::Alphabetical);
+        assert!(repo.search(sense_only).await.unwrap().is_empty());
+
+        // 关键字为空时按字母序返回全部
+        let empty = params("  ", SearchScope::Both, SearchSort::Relevance);
+        assert_eq!(
+            texts(repo.search(empty).await.unwrap()),
+            ["dash", "sprint", "stroll"]
+        );
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn sense_search_matches_chinese_text_by_substring(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "cjk").await;
+        let repo = PgWordRepository::new(pool);
+        for (text, sense) in [
+            ("sprint", "快速地跑 一步"),
+            ("stroll", "慢慢地走"),
+            ("pct", "100%_完成"),
+        ] {
+            add_word(&repo, user_id, text, &[]).await;
+            let user_word_id = repo
+                .find_user_word_by_canonical(user_id, &CanonicalKey::new(text).unwrap())
+                .await
+                .unwrap()
+                .unwrap()
+                .user_word
+                .id
+                .unwrap();
+            repo.add_user_sense(NewUserSense {
+                user_word_id,
+                text: sense.into(),
+                is_primary: true,
+                sort_order: 0,
+                note: None,
+                source_url: None,
+            })
+            .await
+            .unwrap();
+        }
+        let params = |query: &str, scope: SearchScope| SearchParams {
+            user_id,
+            query: query.into(),
+            scope,
+            limit: 10,
+            ..SearchParams::default()
+        };
+        let texts = |found: Vec<(UserWordAggregate, Option<SearchMatch>)>| -> Vec<String> {
+            found.into_iter().map(|(a, _)| a.word.text).collect()
+        };
+
+        let contains = params("跑", SearchScope::Sense);
+        let found = repo.search(contains.clone()).await.unwrap();
+        assert_eq!(found[0].1.as_ref().unwrap().side, MatchSide::Sense);
+        assert_eq!(texts(found), ["sprint"]);
+        assert_eq!(repo.count_search(&contains).await.unwrap(), 1);
+
+        let prefix = params("慢慢", SearchScope::Both);
+        assert_eq!(texts(repo.search(prefix).await.unwrap()), ["stroll"]);
+
+        // 通配符按字面匹配
+        let wildcard = params("%_", SearchScope::Sense);
+        assert_eq!(texts(repo.search(wildcard).await.unwrap()), ["pct"]);
+        let word_scope = params("跑", SearchScope::Word);
+        assert!(repo.search(word_scope).await.unwrap().is_empty());
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn search_reports_which_side_matched(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "highlighter").await;
+        let repo = PgWordRepository::new(pool);
+        for (text, sense) in [("river", "a natural stream"), ("creek", "a small river")] {
+            add_word(&repo, user_id, text, &[]).await;
+            let user_word_id = repo
+                .find_user_word_by_canonical(user_id, &CanonicalKey::new(text).unwrap())
+                .await
+                .unwrap()
+                .unwrap()
+                .user_word
+                .id
+                .unwrap();
+            repo.add_user_sense(NewUserSense {
+                user_word_id,
+                text: sense.into(),
+                is_primary: true,
+                sort_order: 0,
+                note: None,
+                source_url: None,
+            })
+            .await
+            .unwrap();
+        }
+        let search = |query: &str, scope: SearchScope| {
+            repo.search(SearchParams {
+                user_id,
+                query: query.into(),
+                scope,
+                limit: 10,
+                ..SearchParams::default()
+            })
+        };
+        let matched = |found: Vec<(UserWordAggregate, Option<SearchMatch>)>| {
+            found
+                .into_iter()
+                .map(|(a, m)| {
+                    let m = m.expect("keyword search carries match metadata");
+                    (a.word.text, m.side, m.snippet)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // river 作为单词命中，creek 只在义项中命中
+        assert_eq!(
+            matched(search("river", SearchScope::Both).await.unwrap()),
+            [
+                (
+                    "creek".into(),
+                    MatchSide::Sense,
+                    "a small <b>river</b>".into()
+                ),
+                ("river".into(), MatchSide::Word, "<b>river</b>".into()),
+            ]
+        );
+        assert_eq!(
+            matched(search("river", SearchScope::Sense).await.unwrap()),
+            [(
+                "creek".into(),
+                MatchSide::Sense,
+                "a small <b>river</b>".into()
+            )]
+        );
+        assert_eq!(
+            matched(search("stream", SearchScope::Both).await.unwrap()),
+            [(
+                "river".into(),
+                MatchSide::Sense,
+                "a natural <b>stream</b>".into()
+            )]
+        );
+
+        let all = search("", SearchScope::Both).await.unwrap();
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().all(|(_, m)| m.is_none()));
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn search_snippets_escape_markup_in_user_text(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "escaper").await;
+        let repo = PgWordRepository::new(pool);
+        add_word(&repo, user_id, "creek", &[]).await;
+        let user_word_id = repo
+            .find_user_word_by_canonical(user_id, &CanonicalKey::new("creek").unwrap())
+            .await
+            .unwrap()
+            .unwrap()
+            .user_word
+            .id
+            .unwrap();
+        repo.add_user_sense(NewUserSense {
+            user_word_id,
+            text: "<img src=x onerror=\"alert(1)\"> river & 'bank'".into(),
+            is_primary: true,
+            sort_order: 0,
+            note: None,
+            source_url: None,
+        })
+        .await
+        .unwrap();
+
+        let found = repo
+            .search(SearchParams {
+                user_id,
+                query: "river".into(),
+                scope: SearchScope::Sense,
+                limit: 10,
+                ..SearchParams::default()
+            })
+            .await
+            .unwrap();
+        let snippet = &found[0].1.as_ref().unwrap().snippet;
+        assert!(
+            snippet.contains("<b>river</b> &amp; &#39;bank&#39;"),
+            "{snippet}"
+        );
+        assert!(!snippet.contains("<img"), "{snippet}");
+        assert!(!snippet.contains('"'), "{snippet}");
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn cursor_paging_visits_each_row_once_despite_inserts(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "pager").await;
+        let repo = PgWordRepository::new(pool);
+        for text in ["apple", "cherry", "elder"] {
+            add_word(&repo, user_id, text, &[]).await;
+        }
+        let page = |after: Option<SearchCursor>| SearchParams {
+            user_id,
+            limit: 2,
+            after,
+            ..SearchParams::default()
+        };
+
+        let first = repo.search(page(None)).await.unwrap();
+        // 在两页之间插入：一条排在游标之前，一条之后
+        add_word(&repo, user_id, "banana", &[]).await;
+        add_word(&repo, user_id, "date", &[]).await;
+        let cursor = SearchCursor::from_aggregate(&first.last().unwrap().0).unwrap();
+        let decoded = SearchCursor::decode(&cursor.encode()).unwrap();
+        let second = repo.search(page(Some(decoded))).await.unwrap();
+        let cursor = SearchCursor::from_aggregate(&second.last().unwrap().0).unwrap();
+        let third = repo.search(page(Some(cursor))).await.unwrap();
+
+        let visited: Vec<&str> = first
+            .iter()
+            .chain(&second)
+            .chain(&third)
+            .map(|(a, _)| a.word.text.as_str())
+            .collect();
+        assert_eq!(visited, vec!["apple", "cherry", "date", "elder"]);
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn search_caps_embedded_senses(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "polysemy").await;
+        let repo = PgWordRepository::new(pool).with_max_senses_per_word(3);
+        let upserted = repo
+            .upsert_user_word(UpsertUserWord {
+                user_id,
+                word_text: "set".into(),
+                canonical_key: CanonicalKey::new("set").unwrap(),
+                tags: vec![],
+                note: None,
+            })
+            .await
+            .unwrap();
+        let user_word_id = upserted.aggregate.user_word.id.unwrap();
+        for order in 0..5 {
+            repo.add_user_sense(NewUserSense {
+                user_word_id,
+                text: format!("meaning {order}"),
+                is_primary: order == 0,
+                sort_order: order,
+                note: None,
+                source_url: None,
+            })
+            .await
+            .unwrap();
+        }
+        add_word(&repo, user_id, "single", &[]).await;
+
+        let found = repo
+            .search(SearchParams {
+                user_id,
+                limit: 10,
+                ..SearchParams::default()
+            })
+            .await
+            .unwrap();
+        let (set, _) = found.iter().find(|(a, _)| a.word.text == "set").unwrap();
+        let texts: Vec<&str> = set.user_word.senses().iter().map(|s| s.text()).collect();
+        assert_eq!(texts, vec!["meaning 0", "meaning 1", "meaning 2"]);
+        assert!(set.has_more_senses);
+        let (single, _) = found.iter().find(|(a, _)| a.word.text == "single").unwrap();
+        assert!(!single.has_more_senses);
+
+        let detail = repo
+            .find_user_word(user_id, user_word_id, SenseOrder::default())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(detail.user_word.senses().len(), 5);
+        assert!(!detail.has_more_senses);
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn update_tag_only_touches_owned_words_without_duplicates(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "librarian").await;
+        let other_id = insert_user(&pool, "neighbor").await;
+        let repo = PgWordRepository::new(pool);
+        add_word(&repo, user_id, "apple", &["fruit"]).await;
+        add_word(&repo, user_id, "banana", &[]).await;
+        add_word(&repo, other_id, "cherry", &[]).await;
+        let id_of = |user: i64, text: &'static str| {
+            let repo = repo.clone();
+            async move {
+                repo.find_user_word_by_canonical(user, &CanonicalKey::new(text).unwrap())
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .user_word
+                    .id
+                    .unwrap()
+            }
+        };
+        let apple = id_of(user_id, "apple").await;
+        let banana = id_of(user_id, "banana").await;
+        let cherry = id_of(other_id, "cherry").await;
+        let ids = [apple, banana, cherry];
+
+        assert_eq!(repo.count_owned_user_words(user_id, &ids).await.unwrap(), 2);
+        let changed = repo
+            .update_tag(user_id, &ids, "fruit", TagAction::Add)
+            .await
+            .unwrap();
+        assert_eq!(changed, 1);
+
+        let tags_of = |user: i64, id: i64| {
+            let repo = repo.clone();
+            async move {
+                repo.find_user_word(user, id, SenseOrder::default())
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .user_word
+                    .tags()
+                    .to_vec()
+            }
+        };
+        assert_eq!(tags_of(user_id, apple).await, vec!["fruit"]);
+        assert_eq!(tags_of(user_id, banana).await, vec!["fruit"]);
+        assert!(tags_of(other_id, cherry).await.is_empty());
+
+        let removed = repo
+            .update_tag(user_id, &ids, "fruit", TagAction::Remove)
+            .await
+            .unwrap();
+        assert_eq!(removed, 2);
+        assert!(tags_of(user_id, apple).await.is_empty());
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn replace_tags_overwrites_only_the_owned_word(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "tagger").await;
+        let other_id = insert_user(&pool, "bystander").await;
+        let repo = PgWordRepository::new(pool);
+        add_word(&repo, user_id, "river", &["water", "nature"]).await;
+        let river = repo
+            .find_user_word_by_canonical(user_id, &CanonicalKey::new("river").unwrap())
+            .await
+            .unwrap()
+            .unwrap()
+            .user_word
+            .id
+            .unwrap();
+
+        let tags = vec!["geography".to_string(), "water".to_string()];
+        assert!(repo.replace_tags(user_id, river, &tags).await.unwrap());
+        assert!(!repo.replace_tags(other_id, river, &[]).await.unwrap());
+
+        let stored = repo
+            .find_user_word(user_id, river, SenseOrder::default())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.user_word.tags(), ["geography", "water"]);
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn repoint_user_word_moves_the_row_and_drops_orphaned_words(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "repoint_owner").await;
+        let other_id = insert_user(&pool, "repoint_other").await;
+        let repo = PgWordRepository::new(pool.clone());
+        let find = |user_id: i64, text: &'static str| {
+            let repo = repo.clone();
+            async move {
+                repo.find_user_word_by_canonical(user_id, &CanonicalKey::new(text).unwrap())
+                    .await
+                    .unwrap()
+            }
+        };
+        add_word(&repo, user_id, "recieve", &["verb"]).await;
+        add_word(&repo, user_id, "wierd", &[]).await;
+        add_word(&repo, other_id, "wierd", &[]).await;
+        add_word(&repo, user_id, "weird", &[]).await;
+        let typo = find(user_id, "recieve").await.unwrap();
+        let typo_id = typo.user_word.id.unwrap();
+        let target = repo
+            .upsert_word(&CanonicalKey::new("receive").unwrap(), "receive")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            repo.repoint_user_word(other_id, typo_id, target.id)
+                .await
+                .unwrap(),
+            WordRepoint::WordNotFound
+        );
+        assert_eq!(
+            repo.repoint_user_word(user_id, typo_id, target.id)
+                .await
+                .unwrap(),
+            WordRepoint::Moved {
+                previous_word_id: typo.word.id
+            }
+        );
+        let moved = find(user_id, "receive").await.unwrap();
+        assert_eq!(moved.user_word.id, Some(typo_id));
+        assert_eq!(moved.user_word.tags(), ["verb"]);
+        assert!(find(user_id, "recieve").await.is_none());
+        // 原单词保留到改挂完成，之后不再被引用时删除
+        assert_eq!(
+            repo.pending_word_repoint(user_id, typo_id).await.unwrap(),
+            Some(typo.word.id)
+        );
+        assert_eq!(
+            repo.find_words_by_ids(&[typo.word.id]).await.unwrap().len(),
+            1
+        );
+        repo.complete_word_repoint(user_id, typo_id, typo.word.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.pending_word_repoint(user_id, typo_id).await.unwrap(),
+            None
+        );
+        assert!(
+            repo.find_words_by_ids(&[typo.word.id])
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // 其他用户仍引用的单词保留；已拥有目标单词时不做修改
+        let wierd = find(user_id, "wierd").await.unwrap();
+        let weird = find(user_id, "weird").await.unwrap();
+        assert_eq!(
+            repo.repoint_user_word(user_id, wierd.user_word.id.unwrap(), weird.word.id)
+                .await
+                .unwrap(),
+            WordRepoint::Collision(weird.user_word.id.unwrap())
+        );
+        let receive_id = moved.word.id;
+        assert!(matches!(
+            repo.repoint_user_word(user_id, wierd.user_word.id.unwrap(), receive_id)
+                .await
+                .unwrap(),
+            WordRepoint::Collision(_)
+        ));
+        let other_wierd = find(other_id, "wierd").await.unwrap();
+        assert_eq!(
+            repo.repoint_user_word(other_id, other_wierd.user_word.id.unwrap(), weird.word.id)
+                .await
+                .unwrap(),
+            WordRepoint::Moved {
+                previous_word_id: wierd.word.id
+            }
+        );
+        repo.complete_word_repoint(other_id, other_wierd.user_word.id.unwrap(), wierd.word.id)
+            .await
+            .unwrap();
+        assert_eq!(
+            repo.find_words_by_ids(&[wierd.word.id])
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn update_user_word_metadata_writes_tags_and_note(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "metadata_owner").await;
+        let other_id = insert_user(&pool, "metadata_other").await;
+        let repo = PgWordRepository::new(pool);
+        add_word(&repo, user_id, "river", &["water"]).await;
+        let id = repo
+            .find_user_word_by_canonical(user_id, &CanonicalKey::new("river").unwrap())
+            .await
+            .unwrap()
+            .unwrap()
+            .user_word
+            .id
+            .unwrap();
+
+        let tags = vec!["geography".to_string()];
+        assert!(
+            repo.update_user_word_metadata(user_id, id, &tags, Some("a note"))
+                .await
+                .unwrap()
+        );
+        assert!(
+            !repo
+                .update_user_word_metadata(other_id, id, &[], None)
+                .await
+                .unwrap()
+        );
+        let stored = repo
+            .find_user_word(user_id, id, SenseOrder::default())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.user_word.tags(), ["geography"]);
+        assert_eq!(stored.user_word.note(), Some("a note"));
+
+        assert!(
+            repo.update_user_word_metadata(user_id, id, &tags, None)
+                .await
+                .unwrap()
+        );
+        let cleared = repo
+            .find_user_word(user_id, id, SenseOrder::default())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cleared.user_word.note(), None);
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn find_user_word_orders_senses_as_requested(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "orderer").await;
+        let repo = PgWordRepository::new(pool.clone());
+        add_word(&repo, user_id, "run", &[]).await;
+        let run = repo
+            .find_user_word_by_canonical(user_id, &CanonicalKey::new("run").unwrap())
+            .await
+            .unwrap()
+            .unwrap()
+            .user_word
+            .id
+            .unwrap();
+        // (文本, sort_order, 创建日)
+        for (text, sort_order, day) in [("jog", 1, 1), ("sprint", 0, 3), ("operate", 2, 2)] {
+            let sense = repo
+                .add_user_sense(NewUserSense {
+                    user_word_id: run,
+                    text: text.into(),
+                    is_primary: false,
+                    sort_order,
+                    note: None,
+                    source_url: None,
+                })
+                .await
+                .unwrap();
+            sqlx::query("UPDATE user_senses SET created_at = $2 WHERE id = $1")
+                .bind(sense.id().unwrap())
+                .bind(Utc.with_ymd_and_hms(2025, 1, day, 0, 0, 0).unwrap())
+                .execute(&pool)
+                .await
+                .unwrap();
+        }
+
+        for (order, expected) in [
+            (SenseOrder::SortOrder, ["sprint", "jog", "operate"]),
+            (SenseOrder::CreatedAt, ["jog", "operate", "sprint"]),
+            (SenseOrder::CreatedAtDesc, ["sprint", "operate", "jog"]),
+        ] {
+            let found = repo
+                .find_user_word(user_id, run, order)
+                .await
+                .unwrap()
+                .unwrap();
+            let texts: Vec<&str> = found
+                .user_word
+                .senses()
+                .iter()
+                .map(UserSense::text)
+                .collect();
+            assert_eq!(texts, expected, "{order:?}");
+        }
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn primary_first_puts_the_primary_sense_on_top(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "primary_first").await;
+        let repo = PgWordRepository::new(pool);
+        add_word(&repo, user_id, "bark", &[]).await;
+        let bark = repo
+            .find_user_word_by_canonical(user_id, &CanonicalKey::new("bark").unwrap())
+            .await
+            .unwrap()
+            .unwrap()
+            .user_word
+            .id
+            .unwrap();
+        for (text, sort_order, is_primary) in [
+            ("tree skin", 0, false),
+            ("ship", 1, false),
+            ("dog sound", 2, true),
+        ] {
+            repo.add_user_sense(NewUserSense {
+                user_word_id: bark,
+                text: text.into(),
+                is_primary,
+                sort_order,
+                note: None,
+                source_url: None,
+            })
+            .await
+            .unwrap();
+        }
+
+        for (order, expected) in [
+            (SenseOrder::SortOrder, ["tree skin", "ship", "dog sound"]),
+            (SenseOrder::PrimaryFirst, ["dog sound", "tree skin", "ship"]),
+        ] {
+            let found = repo
+                .find_user_word(user_id, bark, order)
+                .await
+                .unwrap()
+                .unwrap();
+            let texts: Vec<&str> = found
+                .user_word
+                .senses()
+                .iter()
+                .map(UserSense::text)
+                .collect();
+            assert_eq!(texts, expected, "{order:?}");
+        }
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn sense_source_url_round_trips(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "citer").await;
+        let repo = PgWordRepository::new(pool);
+        add_word(&repo, user_id, "mesh", &[]).await;
+        let mesh = repo
+            .find_user_word_by_canonical(user_id, &CanonicalKey::new("mesh").unwrap())
+            .await
+            .unwrap()
+            .unwrap()
+            .user_word
+            .id
+            .unwrap();
+
+        let url = "https://dictionary.example.com/mesh";
+        let created = repo
+            .add_user_sense(NewUserSense {
+                user_word_id: mesh,
+                text: "a network".into(),
+                is_primary: true,
+                sort_order: 0,
+                note: None,
+                source_url: Some(url.into()),
+            })
+            .await
+            .unwrap();
+        assert_eq!(created.source_url(), Some(url));
+        let sense_id = created.id().unwrap();
+
+        let found = repo
+            .find_user_word(user_id, mesh, SenseOrder::default())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.user_word.senses()[0].source_url(), Some(url));
+
+        // 只改备注时保留链接，显式传 None 时清除
+        let keep = SenseUpdate {
+            text: None,
+            is_primary: None,
+            sort_order: None,
+            note: Some(Some("checked".into())),
+            source_url: None,
+        };
+        let updated = repo
+            .update_user_sense(user_id, sense_id, keep)
+            .await
+            .unwrap();
+        assert_eq!(updated.source_url(), Some(url));
+        let clear = SenseUpdate {
+            text: None,
+            is_primary: None,
+            sort_order: None,
+            note: None,
+            source_url: Some(None),
+        };
+        let cleared = repo
+            .update_user_sense(user_id, sense_id, clear)
+            .await
+            .unwrap();
+        assert_eq!(cleared.source_url(), None);
+        let found = repo
+            .find_user_word(user_id, mesh, SenseOrder::default())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.user_word.senses()[0].source_url(), None);
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn set_primary_sense_moves_the_primary_flag(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "primary").await;
+        let other_user = insert_user(&pool, "stranger").await;
+        let repo = PgWordRepository::new(pool);
+        add_word(&repo, user_id, "spring", &[]).await;
+        let spring = repo
+            .find_user_word_by_canonical(user_id, &CanonicalKey::new("spring").unwrap())
+            .await
+            .unwrap()
+            .unwrap()
+            .user_word
+            .id
+            .unwrap();
+        let mut ids = Vec::new();
+        for (sort_order, text) in ["season", "coil"].iter().enumerate() {
+            let sense = repo
+                .add_user_sense(NewUserSense {
+                    user_word_id: spring,
+                    text: text.to_string(),
+                    is_primary: sort_order == 0,
+                    sort_order: sort_order as i32,
+                    note: None,
+                    source_url: None,
+                })
+                .await
+                .unwrap();
+            ids.push(sense.id().unwrap());
+        }
+
+        for _ in 0..2 {
+            let primary = repo.set_primary_sense(user_id, ids[1]).await.unwrap();
+            assert!(primary.is_primary);
+            let found = repo
+                .find_user_word(user_id, spring, SenseOrder::SortOrder)
+                .await
+                .unwrap()
+                .unwrap();
+            let flags: Vec<bool> = found
+                .user_word
+                .senses()
+                .iter()
+                .map(|sense| sense.is_primary)
+                .collect();
+            assert_eq!(flags, [false, true]);
+        }
+        let owning = repo
+            .find_user_word_by_sense(user_id, ids[0], SenseOrder::SortOrder)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(owning.user_word.id, Some(spring));
+        assert_eq!(owning.user_word.senses().len(), 2);
+        assert!(
+            repo.find_user_word_by_sense(other_user, ids[0], SenseOrder::SortOrder)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let err = repo
+            .set_primary_sense(other_user, ids[0])
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            WordRepositoryError::Database(sqlx::Error::RowNotFound)
+        ));
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn reorder_senses_requires_the_exact_sense_set(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "reorderer").await;
+        let other = insert_user(&pool, "reorder_other").await;
+        let repo = PgWordRepository::new(pool);
+        add_word(&repo, user_id, "bank", &[]).await;
+        let bank = repo
+            .find_user_word_by_canonical(user_id, &CanonicalKey::new("bank").unwrap())
+            .await
+            .unwrap()
+            .unwrap()
+            .user_word
+            .id
+            .unwrap();
+        let mut ids = Vec::new();
+        for (sort_order, text) in ["river edge", "institution", "reserve"].iter().enumerate() {
+            let sense = repo
+                .add_user_sense(NewUserSense {
+                    user_word_id: bank,
+                    text: text.to_string(),
+                    is_primary: false,
+                    sort_order: sort_order as i32,
+                    note: None,
+                    source_url: None,
+                })
+                .await
+                .unwrap();
+            ids.push(sense.id().unwrap());
+        }
+
+        assert_eq!(
+            repo.reorder_senses(user_id, bank, &ids[..2]).await.unwrap(),
+            SenseReorder::Mismatch
+        );
+        assert_eq!(
+            repo.reorder_senses(other, bank, &ids).await.unwrap(),
+            SenseReorder::WordNotFound
+        );
+        assert_eq!(
+            repo.reorder_senses(user_id, bank, &[ids[2], ids[0], ids[1]])
+                .await
+                .unwrap(),
+            SenseReorder::Reordered
+        );
+
+        let found = repo
+            .find_user_word(user_id, bank, SenseOrder::SortOrder)
+            .await
+            .unwrap()
+            .unwrap();
+        let orders: Vec<(&str, i32)> = found
+            .user_word
+            .senses()
+            .iter()
+            .map(|sense| (sense.text(), sense.sort_order))
+            .collect();
+        assert_eq!(
+            orders,
+            [("reserve", 0), ("river edge", 1), ("institution", 2)]
+        );
+    }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn repair_primaries_leaves_exactly_one_primary_per_word(pool: PgPool) {
+        migrate(&pool).await;
+        let user_id = insert_user(&pool, "drifted").await;
+        let repo = PgWordRepository::new(pool.clone());
+        // 模拟部分唯一索引建立之前遗留的数据
+        sqlx::query("DROP INDEX user_senses_primary_unique")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let mut user_word_ids = Vec::new();
+        for text in ["double", "none", "fine"] {
+            add_word(&repo, user_id, text, &[]).await;
+            let aggregate = repo
+                .find_user_word_by_canonical(user_id, &CanonicalKey::new(text).unwrap())
+                .await
+                .unwrap()
+                .unwrap();
+            user_word_ids.push(aggregate.user_word.id.unwrap());
+        }
+        // (单词, 释义, sort_order, 是否主义项)
+        for (word, text, sort_order, is_primary) in [
+            (0, "late primary", 2, true),
+            (0, "early primary", 1, true),
+            (0, "plain", 0, false),
+            (1, "second", 1, false),
+            (1, "first", 0, false),
+            (2, "only", 0, true),
+        ] {
+            sqlx::query(
+                "INSERT INTO user_senses (user_word_id, text, is_primary, sort_order) \
+                 VALUES ($1, $2, $3, $4)",
+            )
+            .bind(user_word_ids[word])
+            .bind(text)
+            .bind(is_primary)
+            .bind(sort_order)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(repo.repair_primaries(user_id).await.unwrap(), 2);
+
+        let primaries = |id: i64| {
+            let repo = repo.clone();
+            async move {
+                repo.find_user_word(user_id, id, SenseOrder::SortOrder)
+                    .await
+                    .unwrap()
+                    .unwrap()
+                    .user_word
+                    .senses()
+                    .iter()
+                    .filter(|sense| sense.is_primary)
+                    .map(|sense| sense.text().to_string())
+                    .collect::<Vec<_>>()
+            }
+        };
+        assert_eq!(primaries(user_word_ids[0]).await, ["early primary"]);
+        assert_eq!(primaries(user_word_ids[1]).await, ["first"]);
+        assert_eq!(primaries(user_word_ids[2]).await, ["only"]);
+        assert_eq!(repo.repair_primaries(user_id).await.unwrap(), 0);
     }
 }
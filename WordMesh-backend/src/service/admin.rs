@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use tracing::instrument;
+
+use crate::dto::admin::{AdminUserResponse, UserStatsResponse};
+use crate::dto::auth::ProfileResponse;
+use crate::repository::graph::{GraphRepository, LinkCounts};
+use crate::repository::user::UserRepository;
+use crate::repository::word::WordRepository;
+use crate::service::auth::map_repository_error;
+use crate::service::word::map_word_error;
+use crate::util::AppError;
+use crate::util::error::{BusinessError, UserError};
+
+/// 管理端按 id 或用户名查找账号
+#[derive(Debug, Clone)]
+pub enum UserLookup {
+    Id(i64),
+    Username(String),
+}
+
+pub struct AdminService<R: UserRepository + Send + Sync + 'static> {
+    repository: Arc<R>,
+    word_repository: Arc<dyn WordRepository + Send + Sync>,
+    graph_repository: Arc<dyn GraphRepository + Send + Sync>,
+}
+
+impl<R: UserRepository + Send + Sync + 'static> AdminService<R> {
+    pub fn new(
+        repository: R,
+        word_repository: Arc<dyn WordRepository + Send + Sync>,
+        graph_repository: Arc<dyn GraphRepository + Send + Sync>,
+    ) -> Self {
+        Self {
+            repository: Arc::new(repository),
+            word_repository,
+            graph_repository,
+        }
+    }
+
+    #[instrument(skip(self))]
+    pub async fn find_user(&self, lookup: UserLookup) -> Result<AdminUserResponse, AppError> {
+        let user = match &lookup {
+            UserLookup::Id(user_id) => self.repository.find_by_id(*user_id).await,
+            UserLookup::Username(username) => self.repository.find_by_username(username).await,
+        }
+        .map_err(map_repository_error)?
+        .ok_or_else(|| AppError::from(BusinessError::User(UserError::UserNotFound)))?;
+
+        let stats = self
+            .repository
+            .find_stats(user.id)
+            .await
+            .map_err(map_repository_error)?;
+        let links = self.link_counts(user.id).await;
+
+        Ok(AdminUserResponse {
+            profile: ProfileResponse::from(user),
+            stats: UserStatsResponse::new(stats, links),
+        })
+    }
+
+    /// 统计用户的关联总数；图库不可用时返回 None，统计中标为不可用而非整体失败
+    async fn link_counts(&self, user_id: i64) -> Option<LinkCounts> {
+        match self.graph_repository.total_link_count(user_id).await {
+            Ok(counts) => Some(counts),
+            Err(err) => {
+                tracing::warn!(error = %err, user_id, "link counts unavailable");
+                None
+            }
+        }
+    }
+
+    /// 修复该用户主义项数量不为 1 的单词，返回修复的单词数
+    #[instrument(skip(self))]
+    pub async fn repair_primaries(&self, user_id: i64) -> Result<u64, AppError> {
+        self.repository
+            .find_by_id(user_id)
+            .await
+            .map_err(map_repository_error)?
+            .ok_or_else(|| AppError::from(BusinessError::User(UserError::UserNotFound)))?;
+
+        let repaired = self
+            .word_repository
+            .repair_primaries(user_id)
+            .await
+            .map_err(map_word_error)?;
+        if repaired > 0 {
+            tracing::info!(user_id, repaired, "repaired primary senses");
+        }
+        Ok(repaired)
+    }
+}
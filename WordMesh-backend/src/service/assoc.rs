@@ -0,0 +1,1164 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tracing::instrument;
+
+use crate::config::settings::GraphSettings;
+use crate::repository::graph::{
+    GraphRepository, LinkKindStats, NeighborQuery, Neighborhood, SenseLinkFilter,
+    SenseWordLinkKind, SenseWordLinkRecord, WordLinkFilter, WordLinkKind, WordLinkRecord,
+};
+use crate::repository::word::WordRepository;
+use crate::service::word::{map_graph_error, map_word_error, validation_error};
+use crate::util::error::{AppError, BusinessError, LinkError};
+
+/// 关联列表的分页上限
+pub const MAX_LINK_PAGE_SIZE: i64 = 100;
+
+/// 单次导入的每类关联上限，与导出的上限一致，完整的导出文档可以一次导回
+pub const MAX_IMPORTED_LINKS: usize = crate::service::word::MAX_EXPORTED_LINKS as usize;
+
+/// 导入的单词关联，两端是导出文档中的 word_id
+#[derive(Debug, Clone)]
+pub struct ImportWordLink {
+    pub word_a_id: i64,
+    pub word_b_id: i64,
+    pub kind: String,
+    pub note: Option<String>,
+}
+
+/// 导入的义项关联，各 id 均为导出文档中的 id
+#[derive(Debug, Clone)]
+pub struct ImportSenseLink {
+    pub sense_id: i64,
+    pub source_word_id: i64,
+    pub target_word_id: i64,
+    pub kind: String,
+    pub note: Option<String>,
+}
+
+/// 导出文档中的 id 到导入后 id 的映射
+#[derive(Debug, Clone, Default)]
+pub struct ImportedIds {
+    pub words: HashMap<i64, i64>,
+    pub senses: HashMap<i64, i64>,
+}
+
+/// 导入关联的逐项结果，顺序同请求
+#[derive(Debug)]
+pub struct LinkImport {
+    pub word_links: Vec<Result<WordLinkRecord, AppError>>,
+    pub sense_links: Vec<Result<SenseWordLinkRecord, AppError>>,
+}
+
+/// 邻域遍历的跳数上限；`graph.max_depth` 更大时同样按此钳制，避免变长路径查询失控
+pub const MAX_NEIGHBOR_DEPTH: u32 = 3;
+
+#[allow(dead_code)]
+pub struct AssocService<W, G>
+where
+    W: WordRepository + Send + Sync + 'static,
+    G: GraphRepository + Send + Sync + 'static,
+{
+    word_repository: Arc<W>,
+    graph_repository: Arc<G>,
+    max_depth: u32,
+    max_results: i64,
+    word_link_kinds: Vec<WordLinkKind>,
+    sense_link_kinds: Vec<SenseWordLinkKind>,
+    note_required_kinds: Vec<String>,
+    reverse_synonym_links: bool,
+    max_kinds_per_pair: usize,
+    default_word_link_kind: WordLinkKind,
+    default_sense_link_kind: SenseWordLinkKind,
+}
+
+impl<W, G> AssocService<W, G>
+where
+    W: WordRepository + Send + Sync + 'static,
+    G: GraphRepository + Send + Sync + 'static,
+{
+    #[allow(dead_code)]
+    pub fn new(word_repository: W, graph_repository: G, settings: &GraphSettings) -> Self {
+        Self {
+            word_repository: Arc::new(word_repository),
+            graph_repository: Arc::new(graph_repository),
+            max_depth: settings.max_depth,
+            max_results: settings.max_results,
+            word_link_kinds: WordLinkKind::ALL
+                .into_iter()
+                .filter(|kind| !is_disabled(settings, kind.as_str()))
+                .collect(),
+            sense_link_kinds: SenseWordLinkKind::ALL
+                .into_iter()
+                .filter(|kind| !is_disabled(settings, kind.as_str()))
+                .collect(),
+            note_required_kinds: settings.note_required_link_kinds.clone(),
+            reverse_synonym_links: settings.reverse_synonym_links,
+            max_kinds_per_pair: settings.max_link_kinds_per_pair,
+            // 配置加载时已校验；未经校验的设置回退到内置默认值
+            default_word_link_kind: WordLinkKind::try_from_str(&settings.default_word_link_kind)
+                .unwrap_or(WordLinkKind::SimilarForm),
+            default_sense_link_kind: SenseWordLinkKind::try_from_str(
+                &settings.default_sense_link_kind,
+            )
+            .unwrap_or(SenseWordLinkKind::Related),
+        }
+    }
+
+    /// 当前启用的单词关联类型，按枚举声明顺序
+    pub fn word_link_kinds(&self) -> &[WordLinkKind] {
+        &self.word_link_kinds
+    }
+
+    /// 当前启用的义项-单词关联类型，按枚举声明顺序
+    pub fn sense_link_kinds(&self) -> &[SenseWordLinkKind] {
+        &self.sense_link_kinds
+    }
+
+    /// 创建单词关联时请求省略类型所用的类型
+    pub fn default_word_link_kind(&self) -> WordLinkKind {
+        self.default_word_link_kind
+    }
+
+    /// 创建义项关联时请求省略类型所用的类型
+    pub fn default_sense_link_kind(&self) -> SenseWordLinkKind {
+        self.default_sense_link_kind
+    }
+
+    /// 查询邻域：depth 钳制到 1..=min(max_depth, MAX_NEIGHBOR_DEPTH)，
+    /// 结果数超过 max_results 时截断并标记 truncated
+    #[instrument(skip(self), fields(user_id = user_id, word_id = word_id))]
+    pub async fn neighbors(
+        &self,
+        user_id: i64,
+        word_id: i64,
+        depth: u32,
+        kind: Option<WordLinkKind>,
+    ) -> Result<Neighborhood, AppError> {
+        let depth = depth.clamp(1, self.max_depth.min(MAX_NEIGHBOR_DEPTH));
+        // 多取一条用于判断是否触达上限
+        let mut neighborhood = self
+            .graph_repository
+            .neighbors(NeighborQuery {
+                user_id,
+                word_id,
+                depth,
+                kind,
+                limit: self.max_results + 1,
+            })
+            .await
+            .map_err(map_graph_error)?;
+
+        let cap = self.max_results as usize;
+        if neighborhood.word_ids.len() > cap {
+            neighborhood.word_ids.truncate(cap);
+            let kept = &neighborhood.word_ids;
+            neighborhood.links.retain(|link| {
+                (link.word_a_id == word_id || kept.contains(&link.word_a_id))
+                    && (link.word_b_id == word_id || kept.contains(&link.word_b_id))
+            });
+            neighborhood.truncated = true;
+        }
+
+        Ok(neighborhood)
+    }
+
+    /// 创建单词之间的关联；自关联在访问图库前直接拒绝，
+    /// 新类型会使该对单词的关联类型数超过 `graph.max_link_kinds_per_pair` 时返回 LimitExceeded
+    #[instrument(skip(self, note), fields(user_id = user_id))]
+    pub async fn create_word_link(
+        &self,
+        user_id: i64,
+        word_a_id: i64,
+        word_b_id: i64,
+        kind: WordLinkKind,
+        note: Option<String>,
+    ) -> Result<WordLinkRecord, AppError> {
+        if word_a_id == word_b_id {
+            return Err(self_forbidden());
+        }
+        self.ensure_note_policy(kind.as_str(), note.as_deref())?;
+        // 上限不小于类型总数时不可能触达，省去一次图库查询
+        if self.max_kinds_per_pair < WordLinkKind::ALL.len() {
+            let existing = self
+                .graph_repository
+                .get_word_links_between(user_id, word_a_id, word_b_id)
+                .await
+                .map_err(map_graph_error)?;
+            // 已有的类型不计入新增，重复创建按图库原有语义处理
+            if !existing.iter().any(|link| link.kind == kind)
+                && existing.len() >= self.max_kinds_per_pair
+            {
+                return Err(AppError::from(BusinessError::Link(
+                    LinkError::LimitExceeded,
+                )));
+            }
+        }
+        self.graph_repository
+            .create_word_link(user_id, word_a_id, word_b_id, kind, note)
+            .await
+            .map_err(map_graph_error)
+    }
+
+    /// 删除两个单词之间指定类型的关联；不存在（含他人的关联）时返回 TargetNotFound
+    #[instrument(skip(self), fields(user_id = user_id))]
+    pub async fn delete_word_link(
+        &self,
+        user_id: i64,
+        word_a_id: i64,
+        word_b_id: i64,
+        kind: WordLinkKind,
+    ) -> Result<(), AppError> {
+        if word_a_id == word_b_id {
+            return Err(self_forbidden());
+        }
+        let deleted = self
+            .graph_repository
+            .delete_word_link(user_id, word_a_id, word_b_id, kind)
+            .await
+            .map_err(map_graph_error)?;
+        if deleted == 0 {
+            return Err(AppError::from(BusinessError::Link(
+                LinkError::TargetNotFound,
+            )));
+        }
+        Ok(())
+    }
+
+    /// 编辑一条单词关联的类型与备注，两项修改一起生效或都不生效。
+    /// 备注策略按修改后的类型检查；未修改备注时沿用已有备注判断
+    #[instrument(skip(self, note), fields(user_id = user_id))]
+    pub async fn update_word_link(
+        &self,
+        user_id: i64,
+        word_a_id: i64,
+        word_b_id: i64,
+        old_kind: WordLinkKind,
+        new_kind: Option<WordLinkKind>,
+        note: Option<Option<String>>,
+    ) -> Result<WordLinkRecord, AppError> {
+        if word_a_id == word_b_id {
+            return Err(self_forbidden());
+        }
+        let kind = new_kind.unwrap_or(old_kind);
+        match &note {
+            Some(note) => self.ensure_note_policy(kind.as_str(), note.as_deref())?,
+            None if new_kind.is_some() => {
+                let current = self
+                    .graph_repository
+                    .get_word_links_between(user_id, word_a_id, word_b_id)
+                    .await
+                    .map_err(map_graph_error)?
+                    .into_iter()
+                    .find(|link| link.kind == old_kind)
+                    .ok_or_else(|| {
+                        AppError::from(BusinessError::Link(LinkError::TargetNotFound))
+                    })?;
+                self.ensure_note_policy(kind.as_str(), current.note.as_deref())?;
+            }
+            None => {}
+        }
+        self.graph_repository
+            .update_word_link(user_id, word_a_id, word_b_id, old_kind, new_kind, note)
+            .await
+            .map_err(map_graph_error)
+    }
+
+    /// 两个单词之间的全部关联详情；关联按 user_id 隔离，他人的关联与不存在同样返回 TargetNotFound
+    #[instrument(skip(self), fields(user_id = user_id))]
+    pub async fn get_word_links(
+        &self,
+        user_id: i64,
+        word_a_id: i64,
+        word_b_id: i64,
+    ) -> Result<Vec<WordLinkRecord>, AppError> {
+        if word_a_id == word_b_id {
+            return Err(self_forbidden());
+        }
+        let links = self
+            .graph_repository
+            .get_word_links_between(user_id, word_a_id, word_b_id)
+            .await
+            .map_err(map_graph_error)?;
+        if links.is_empty() {
+            return Err(AppError::from(BusinessError::Link(
+                LinkError::TargetNotFound,
+            )));
+        }
+        Ok(links)
+    }
+
+    /// 以该单词为任一端的关联，按创建时间倒序；limit 钳制到 1..=MAX_LINK_PAGE_SIZE
+    #[instrument(skip(self), fields(user_id = user_id, word_id = word_id))]
+    pub async fn list_word_links(
+        &self,
+        user_id: i64,
+        word_id: i64,
+        kind: Option<WordLinkKind>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<WordLinkRecord>, AppError> {
+        self.graph_repository
+            .list_word_links(WordLinkFilter {
+                user_id,
+                kind,
+                word_id,
+                limit: limit.clamp(1, MAX_LINK_PAGE_SIZE),
+                offset: offset.max(0),
+            })
+            .await
+            .map_err(map_graph_error)
+    }
+
+    /// 该义项发出的关联，按创建时间倒序；limit 钳制到 1..=MAX_LINK_PAGE_SIZE
+    #[instrument(skip(self), fields(user_id = user_id, sense_id = sense_id))]
+    pub async fn list_sense_links(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+        kind: Option<SenseWordLinkKind>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<SenseWordLinkRecord>, AppError> {
+        self.graph_repository
+            .list_sense_word_links(SenseLinkFilter {
+                user_id,
+                sense_id,
+                kind,
+                limit: limit.clamp(1, MAX_LINK_PAGE_SIZE),
+                offset: offset.max(0),
+            })
+            .await
+            .map_err(map_graph_error)
+    }
+
+    /// 创建义项到单词的关联；先在 Postgres 中确认义项属于该用户且挂在 `source_word_id` 下，
+    /// 且目标单词在该用户的网络中
+    #[instrument(skip(self, note), fields(user_id = user_id, sense_id = sense_id))]
+    pub async fn create_sense_link(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+        source_word_id: i64,
+        target_word_id: i64,
+        kind: SenseWordLinkKind,
+        note: Option<String>,
+    ) -> Result<SenseWordLinkRecord, AppError> {
+        // 义项关联回自身所属单词同样视为自关联
+        if source_word_id == target_word_id {
+            return Err(self_forbidden());
+        }
+        self.ensure_note_policy(kind.as_str(), note.as_deref())?;
+        let owner = self
+            .word_repository
+            .find_sense_word_id(user_id, sense_id)
+            .await
+            .map_err(map_word_error)?;
+        if owner != Some(source_word_id) {
+            return Err(AppError::from(BusinessError::Link(
+                LinkError::TargetNotFound,
+            )));
+        }
+        let target_owned = !self
+            .word_repository
+            .find_user_words_by_word_ids(user_id, &[target_word_id])
+            .await
+            .map_err(map_word_error)?
+            .is_empty();
+        if !target_owned {
+            return Err(AppError::from(BusinessError::Link(
+                LinkError::TargetNotFound,
+            )));
+        }
+
+        let record = self
+            .graph_repository
+            .create_sense_word_link(
+                user_id,
+                sense_id,
+                source_word_id,
+                target_word_id,
+                kind,
+                note,
+            )
+            .await
+            .map_err(map_graph_error)?;
+
+        if self.reverse_synonym_links
+            && kind == SenseWordLinkKind::Synonym
+            && let Err(err) = self.create_reverse_synonym(&record).await
+        {
+            tracing::warn!(error = %err, link_id = %record.link_id, "reverse synonym link skipped");
+        }
+        Ok(record)
+    }
+
+    /// 从目标单词的主义项建立指回源单词的 synonym 关联；直接写图库而不经过 `create_sense_link`，
+    /// 因此不会再触发反向创建，但同样受类型开关与备注策略约束。
+    /// 目标单词不在用户网络中或没有主义项时什么也不做
+    async fn create_reverse_synonym(&self, record: &SenseWordLinkRecord) -> Result<(), AppError> {
+        if !self.sense_link_kinds.contains(&SenseWordLinkKind::Synonym) {
+            return Ok(());
+        }
+        // 反向关联不复制备注，要求备注的类型因此不会自动反向创建
+        self.ensure_note_policy(SenseWordLinkKind::Synonym.as_str(), None)?;
+        let Some(target) = self
+            .word_repository
+            .find_words_by_ids(&[record.target_word_id])
+            .await
+            .map_err(map_word_error)?
+            .pop()
+        else {
+            return Ok(());
+        };
+        let Some(aggregate) = self
+            .word_repository
+            .find_user_word_by_canonical(record.user_id, &target.canonical_key)
+            .await
+            .map_err(map_word_error)?
+        else {
+            return Ok(());
+        };
+        let Some(primary_id) = aggregate
+            .user_word
+            .senses()
+            .iter()
+            .find(|sense| sense.is_primary)
+            .and_then(|sense| sense.id())
+        else {
+            return Ok(());
+        };
+
+        // 备注不复制：反向关联已存在时保留其原有备注
+        self.graph_repository
+            .create_sense_word_link(
+                record.user_id,
+                primary_id,
+                record.target_word_id,
+                record.source_word_id,
+                SenseWordLinkKind::Synonym,
+                None,
+            )
+            .await
+            .map_err(map_graph_error)?;
+        Ok(())
+    }
+
+    /// 按导入后的 id 重建导出文档中的关联，逐条经过与创建接口相同的检查，每条单独成败。
+    /// 端点不在本次导入中时返回 TargetNotFound，未知或已停用的类型返回 TypeInvalid
+    #[instrument(skip_all, fields(user_id = user_id))]
+    pub async fn import_links(
+        &self,
+        user_id: i64,
+        ids: &ImportedIds,
+        word_links: Vec<ImportWordLink>,
+        sense_links: Vec<ImportSenseLink>,
+    ) -> Result<LinkImport, AppError> {
+        validate_link_import(word_links.len(), sense_links.len())?;
+        let word = |id: i64| ids.words.get(&id).copied().ok_or_else(target_not_found);
+
+        let mut imported = LinkImport {
+            word_links: Vec::with_capacity(word_links.len()),
+            sense_links: Vec::with_capacity(sense_links.len()),
+        };
+        for link in word_links {
+            let result = async {
+                let kind = WordLinkKind::try_from_str(&link.kind)
+                    .filter(|kind| self.word_link_kinds.contains(kind))
+                    .ok_or_else(type_invalid)?;
+                self.create_word_link(
+                    user_id,
+                    word(link.word_a_id)?,
+                    word(link.word_b_id)?,
+                    kind,
+                    link.note,
+                )
+                .await
+            };
+            imported.word_links.push(result.await);
+        }
+        for link in sense_links {
+            let result = async {
+                let kind = SenseWordLinkKind::try_from_str(&link.kind)
+                    .filter(|kind| self.sense_link_kinds.contains(kind))
+                    .ok_or_else(type_invalid)?;
+                let sense_id = ids
+                    .senses
+                    .get(&link.sense_id)
+                    .copied()
+                    .ok_or_else(target_not_found)?;
+                self.create_sense_link(
+                    user_id,
+                    sense_id,
+                    word(link.source_word_id)?,
+                    word(link.target_word_id)?,
+                    kind,
+                    link.note,
+                )
+                .await
+            };
+            imported.sense_links.push(result.await);
+        }
+        Ok(imported)
+    }
+
+    /// 合并单词时把 `from_word_id` 上的关联改挂到 `to_word_id`，重复关联保留较早创建的一条
+    #[instrument(skip(self), fields(user_id = user_id))]
+    pub async fn repoint_links(
+        &self,
+        user_id: i64,
+        from_word_id: i64,
+        to_word_id: i64,
+    ) -> Result<(), AppError> {
+        if from_word_id == to_word_id {
+            return Err(AppError::from(BusinessError::Link(
+                LinkError::SelfForbidden,
+            )));
+        }
+        self.graph_repository
+            .repoint_word_links(user_id, from_word_id, to_word_id)
+            .await
+            .map_err(map_graph_error)
+    }
+
+    /// `graph.note_required_link_kinds` 中的类型必须带非空备注
+    fn ensure_note_policy(&self, kind: &str, note: Option<&str>) -> Result<(), AppError> {
+        let required = self.note_required_kinds.iter().any(|value| value == kind);
+        if required && note.is_none_or(|note| note.trim().is_empty()) {
+            return Err(validation_error(
+                "note",
+                format!("{kind} 类型的关联必须填写备注"),
+            ));
+        }
+        Ok(())
+    }
+
+    /// 按类型统计用户的关联数；图库不可用时返回 None，由调用方展示为“暂不可用”而非整体失败
+    #[instrument(skip(self), fields(user_id = user_id))]
+    pub async fn link_stats_by_kind(&self, user_id: i64) -> Option<LinkKindStats> {
+        match self.graph_repository.link_stats_by_kind(user_id).await {
+            Ok(stats) => Some(stats),
+            Err(err) => {
+                tracing::warn!(error = %err, "link stats unavailable");
+                None
+            }
+        }
+    }
+}
+
+fn self_forbidden() -> AppError {
+    AppError::from(BusinessError::Link(LinkError::SelfForbidden))
+}
+
+/// 导入的关联条数超过 [`MAX_IMPORTED_LINKS`] 时返回校验错误；
+/// 调用方可在写入单词之前先检查，避免单词已导入而关联整体被拒
+pub fn validate_link_import(word_links: usize, sense_links: usize) -> Result<(), AppError> {
+    for (field, count) in [("word_links", word_links), ("sense_links", sense_links)] {
+        if count > MAX_IMPORTED_LINKS {
+            return Err(validation_error(
+                field,
+                format!("单次最多 {MAX_IMPORTED_LINKS} 条关联，当前 {count}"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn target_not_found() -> AppError {
+    AppError::from(BusinessError::Link(LinkError::TargetNotFound))
+}
+
+fn type_invalid() -> AppError {
+    AppError::from(BusinessError::Link(LinkError::TypeInvalid))
+}
+
+fn is_disabled(settings: &GraphSettings, kind: &str) -> bool {
+    settings
+        .disabled_link_kinds
+        .iter()
+        .any(|value| value == kind)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::graph::GraphRepositoryError;
+    use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+    fn settings(max_depth: u32, max_results: i64) -> GraphSettings {
+        GraphSettings {
+            max_depth,
+            max_results,
+            ..GraphSettings::default()
+        }
+    }
+
+    async fn star(graph: &InMemoryGraphRepository, center: i64, spokes: i64) {
+        for target in 1..=spokes {
+            graph
+                .create_word_link(7, center, center + target, WordLinkKind::SimilarForm, None)
+                .await
+                .unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn neighbors_reports_truncation_when_exceeding_cap() {
+        let graph = InMemoryGraphRepository::default();
+        star(&graph, 100, 5).await;
+        let service = AssocService::new(InMemoryWordRepository::default(), graph, &settings(3, 3));
+
+        let result = service.neighbors(7, 100, 1, None).await.unwrap();
+
+        assert!(result.truncated);
+        assert_eq!(result.word_ids.len(), 3);
+        assert!(result.links.iter().all(|link| {
+            [link.word_a_id, link.word_b_id]
+                .iter()
+                .all(|id| *id == 100 || result.word_ids.contains(id))
+        }));
+        assert_eq!(result.links.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn neighbors_within_cap_is_not_truncated() {
+        let graph = InMemoryGraphRepository::default();
+        star(&graph, 100, 3).await;
+        let service = AssocService::new(InMemoryWordRepository::default(), graph, &settings(3, 3));
+
+        let result = service.neighbors(7, 100, 1, None).await.unwrap();
+
+        assert!(!result.truncated);
+        assert_eq!(result.word_ids.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn neighbors_clamps_depth_to_max_depth() {
+        let graph = InMemoryGraphRepository::default();
+        // 1 - 2 - 3 - 4 链式关联
+        for (a, b) in [(1, 2), (2, 3), (3, 4)] {
+            graph
+                .create_word_link(7, a, b, WordLinkKind::SimilarForm, None)
+                .await
+                .unwrap();
+        }
+        let service = AssocService::new(InMemoryWordRepository::default(), graph, &settings(2, 10));
+
+        let result = service.neighbors(7, 1, 10, None).await.unwrap();
+
+        assert_eq!(result.word_ids, vec![2, 3]);
+    }
+
+    #[tokio::test]
+    async fn neighbors_of_a_diamond_are_deduplicated_and_depth_is_capped() {
+        let graph = InMemoryGraphRepository::default();
+        //   2
+        //  / \
+        // 1   4 - 5 - 6
+        //  \ /
+        //   3
+        for (a, b) in [(1, 2), (1, 3), (2, 4), (3, 4), (4, 5), (5, 6)] {
+            graph
+                .create_word_link(7, a, b, WordLinkKind::SimilarForm, None)
+                .await
+                .unwrap();
+        }
+        let service = AssocService::new(InMemoryWordRepository::default(), graph, &settings(5, 10));
+
+        let result = service.neighbors(7, 1, 2, None).await.unwrap();
+        let mut ids = result.word_ids.clone();
+        ids.sort_unstable();
+        assert_eq!(ids, vec![2, 3, 4]);
+        assert_eq!(result.links.len(), 4);
+
+        // graph.max_depth 为 5，但跳数仍不超过 MAX_NEIGHBOR_DEPTH
+        let result = service.neighbors(7, 1, 10, None).await.unwrap();
+        let mut ids = result.word_ids;
+        ids.sort_unstable();
+        assert_eq!(ids, vec![2, 3, 4, 5]);
+
+        let result = service.neighbors(7, 1, 0, None).await.unwrap();
+        assert_eq!(result.word_ids.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn link_stats_by_kind_counts_every_kind_for_user_only() {
+        let graph = InMemoryGraphRepository::default();
+        star(&graph, 100, 2).await;
+        graph
+            .create_word_link(7, 100, 200, WordLinkKind::RootAffix, None)
+            .await
+            .unwrap();
+        graph
+            .create_word_link(8, 1, 2, WordLinkKind::RootAffix, None)
+            .await
+            .unwrap();
+        for target in [101, 102] {
+            graph
+                .create_sense_word_link(7, 5, 100, target, SenseWordLinkKind::Antonym, None)
+                .await
+                .unwrap();
+        }
+        graph
+            .create_sense_word_link(8, 9, 1, 2, SenseWordLinkKind::Synonym, None)
+            .await
+            .unwrap();
+        let service = AssocService::new(InMemoryWordRepository::default(), graph, &settings(3, 10));
+
+        let stats = service.link_stats_by_kind(7).await.unwrap();
+
+        assert_eq!(
+            stats.word_links,
+            [(WordLinkKind::SimilarForm, 2), (WordLinkKind::RootAffix, 1)]
+        );
+        assert_eq!(
+            stats.sense_links,
+            [
+                (SenseWordLinkKind::Synonym, 0),
+                (SenseWordLinkKind::Antonym, 2),
+                (SenseWordLinkKind::Related, 0),
+            ]
+        );
+        assert_eq!(
+            service.link_stats_by_kind(9).await,
+            Some(LinkKindStats::default())
+        );
+    }
+
+    async fn word_with_sense(words: &InMemoryWordRepository, text: &str) -> (i64, i64) {
+        use crate::domain::CanonicalKey;
+        use crate::repository::word::{NewUserSense, UpsertUserWord};
+
+        let added = words
+            .upsert_user_word(UpsertUserWord {
+                user_id: 7,
+                word_text: text.into(),
+                canonical_key: CanonicalKey::new(text).unwrap(),
+                tags: vec![],
+                note: None,
+            })
+            .await
+            .unwrap();
+        let sense = words
+            .add_user_sense(NewUserSense {
+                user_word_id: added.aggregate.user_word.id.unwrap(),
+                text: "river side".into(),
+                is_primary: true,
+                sort_order: 0,
+                note: None,
+                source_url: None,
+            })
+            .await
+            .unwrap();
+        (added.aggregate.word.id, sense.id().unwrap())
+    }
+
+    #[tokio::test]
+    async fn create_sense_link_rejects_mismatched_source_word() {
+        let words = InMemoryWordRepository::default();
+        let (word_id, sense_id) = word_with_sense(&words, "bank").await;
+        let (target_id, _) = word_with_sense(&words, "shore").await;
+        let graph = InMemoryGraphRepository::default();
+        let service = AssocService::new(words, graph.clone(), &settings(3, 10));
+
+        let err = service
+            .create_sense_link(
+                7,
+                sense_id,
+                word_id + 100,
+                target_id,
+                SenseWordLinkKind::Related,
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::BusinessError(BusinessError::Link(LinkError::TargetNotFound))
+        ));
+        assert!(graph.sense_links().is_empty());
+
+        let record = service
+            .create_sense_link(
+                7,
+                sense_id,
+                word_id,
+                target_id,
+                SenseWordLinkKind::Related,
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(record.source_word_id, word_id);
+    }
+
+    #[tokio::test]
+    async fn graph_source_word_drift_writes_no_edge() {
+        let words = InMemoryWordRepository::default();
+        let (word_id, sense_id) = word_with_sense(&words, "bank").await;
+        let (target_id, _) = word_with_sense(&words, "shore").await;
+        let graph = InMemoryGraphRepository::default();
+        graph
+            .create_sense_word_link(
+                7,
+                sense_id,
+                word_id,
+                target_id,
+                SenseWordLinkKind::Related,
+                None,
+            )
+            .await
+            .unwrap();
+
+        // 图中该义项已归属 bank；以其它来源单词写入时不应留下新的边
+        let err = graph
+            .create_sense_word_link(
+                7,
+                sense_id,
+                target_id,
+                word_id + 100,
+                SenseWordLinkKind::Synonym,
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            GraphRepositoryError::Business(BusinessError::Link(LinkError::TargetNotFound))
+        ));
+        let links = graph.sense_links();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].target_word_id, target_id);
+    }
+
+    #[tokio::test]
+    async fn create_sense_link_rejects_target_outside_network() {
+        let words = InMemoryWordRepository::default();
+        let (word_id, sense_id) = word_with_sense(&words, "bank").await;
+        let (target_id, _) = word_with_sense(&words, "shore").await;
+        let graph = InMemoryGraphRepository::default();
+        let service = AssocService::new(words.clone(), graph.clone(), &settings(3, 10));
+
+        // 目标单词存在于全局词表，但已从该用户网络中移除
+        let target = words
+            .find_user_words_by_word_ids(7, &[target_id])
+            .await
+            .unwrap()
+            .pop()
+            .unwrap();
+        words
+            .remove_user_word(7, target.user_word.id.unwrap())
+            .await
+            .unwrap();
+
+        for target_word_id in [target_id, 9_999] {
+            let err = service
+                .create_sense_link(
+                    7,
+                    sense_id,
+                    word_id,
+                    target_word_id,
+                    SenseWordLinkKind::Related,
+                    None,
+                )
+                .await
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                AppError::BusinessError(BusinessError::Link(LinkError::TargetNotFound))
+            ));
+        }
+        assert!(graph.sense_links().is_empty());
+    }
+
+    #[tokio::test]
+    async fn create_sense_link_rejects_other_users_sense() {
+        let words = InMemoryWordRepository::default();
+        let (word_id, sense_id) = word_with_sense(&words, "bank").await;
+        let service =
+            AssocService::new(words, InMemoryGraphRepository::default(), &settings(3, 10));
+
+        let err = service
+            .create_sense_link(8, sense_id, word_id, 500, SenseWordLinkKind::Related, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::BusinessError(BusinessError::Link(LinkError::TargetNotFound))
+        ));
+    }
+
+    async fn link_synonym(reverse: bool) -> (InMemoryGraphRepository, [(i64, i64); 2]) {
+        let words = InMemoryWordRepository::default();
+        let bank = word_with_sense(&words, "bank").await;
+        let shore = word_with_sense(&words, "shore").await;
+        let graph = InMemoryGraphRepository::default();
+        let settings = GraphSettings {
+            reverse_synonym_links: reverse,
+            ..GraphSettings::default()
+        };
+        let service = AssocService::new(words, graph.clone(), &settings);
+
+        service
+            .create_sense_link(7, bank.1, bank.0, shore.0, SenseWordLinkKind::Synonym, None)
+            .await
+            .unwrap();
+        (graph, [bank, shore])
+    }
+
+    #[tokio::test]
+    async fn synonym_link_creates_reverse_from_target_primary_sense_when_enabled() {
+        let (graph, [bank, shore]) = link_synonym(true).await;
+
+        let links = graph.sense_links();
+        assert_eq!(links.len(), 2);
+        let reverse = links
+            .iter()
+            .find(|link| link.sense_id == shore.1)
+            .expect("reverse link");
+        assert_eq!(reverse.source_word_id, shore.0);
+        assert_eq!(reverse.target_word_id, bank.0);
+        assert_eq!(reverse.kind, SenseWordLinkKind::Synonym);
+    }
+
+    #[tokio::test]
+    async fn synonym_link_has_no_reverse_by_default() {
+        let (graph, [bank, _]) = link_synonym(false).await;
+
+        let links = graph.sense_links();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].sense_id, bank.1);
+    }
+
+    #[tokio::test]
+    async fn reverse_synonym_follows_the_link_policy() {
+        let words = InMemoryWordRepository::default();
+        let bank = word_with_sense(&words, "bank").await;
+        let shore = word_with_sense(&words, "shore").await;
+        let graph = InMemoryGraphRepository::default();
+        let settings = GraphSettings {
+            reverse_synonym_links: true,
+            note_required_link_kinds: vec!["synonym".into()],
+            ..GraphSettings::default()
+        };
+        let service = AssocService::new(words, graph.clone(), &settings);
+
+        service
+            .create_sense_link(
+                7,
+                bank.1,
+                bank.0,
+                shore.0,
+                SenseWordLinkKind::Synonym,
+                Some("same meaning".into()),
+            )
+            .await
+            .unwrap();
+
+        // 反向关联没有备注，不满足备注策略，只保留正向关联
+        let links = graph.sense_links();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].sense_id, bank.1);
+    }
+
+    fn assert_note_required(err: AppError) {
+        let AppError::BusinessError(BusinessError::Validation(fields)) = err else {
+            panic!("expected validation error, got {err:?}");
+        };
+        assert_eq!(fields[0].field, "note");
+    }
+
+    #[tokio::test]
+    async fn note_required_kinds_reject_missing_notes() {
+        let words = InMemoryWordRepository::default();
+        let (word_id, sense_id) = word_with_sense(&words, "bank").await;
+        let (target_id, _) = word_with_sense(&words, "shore").await;
+        let graph = InMemoryGraphRepository::default();
+        let service = AssocService::new(
+            words,
+            graph.clone(),
+            &GraphSettings {
+                note_required_link_kinds: vec!["root_affix".into(), "antonym".into()],
+                ..GraphSettings::default()
+            },
+        );
+
+        for note in [None, Some("   ".to_string())] {
+            let err = service
+                .create_word_link(7, 1, 2, WordLinkKind::RootAffix, note.clone())
+                .await
+                .unwrap_err();
+            assert_note_required(err);
+            let err = service
+                .create_sense_link(
+                    7,
+                    sense_id,
+                    word_id,
+                    target_id,
+                    SenseWordLinkKind::Antonym,
+                    note,
+                )
+                .await
+                .unwrap_err();
+            assert_note_required(err);
+        }
+        assert!(graph.sense_links().is_empty());
+
+        service
+            .create_word_link(7, 1, 2, WordLinkKind::RootAffix, Some("shared root".into()))
+            .await
+            .unwrap();
+        service
+            .create_sense_link(
+                7,
+                sense_id,
+                word_id,
+                target_id,
+                SenseWordLinkKind::Antonym,
+                Some("opposite".into()),
+            )
+            .await
+            .unwrap();
+        // 未列出的类型备注仍可选
+        service
+            .create_word_link(7, 1, 3, WordLinkKind::SimilarForm, None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn word_link_kinds_per_pair_are_capped() {
+        let graph = InMemoryGraphRepository::default();
+        let service = AssocService::new(
+            InMemoryWordRepository::default(),
+            graph.clone(),
+            &GraphSettings {
+                max_link_kinds_per_pair: 1,
+                ..GraphSettings::default()
+            },
+        );
+
+        service
+            .create_word_link(7, 1, 2, WordLinkKind::SimilarForm, None)
+            .await
+            .unwrap();
+        let err = service
+            .create_word_link(7, 2, 1, WordLinkKind::RootAffix, None)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::BusinessError(BusinessError::Link(LinkError::LimitExceeded))
+        ));
+        // 已有类型的重复创建不占用新的名额
+        service
+            .create_word_link(7, 1, 2, WordLinkKind::SimilarForm, None)
+            .await
+            .unwrap();
+        // 上限按单词对计算，其他单词对与其他用户不受影响
+        service
+            .create_word_link(7, 1, 3, WordLinkKind::RootAffix, None)
+            .await
+            .unwrap();
+        service
+            .create_word_link(8, 1, 2, WordLinkKind::RootAffix, None)
+            .await
+            .unwrap();
+        assert_eq!(graph.word_links().len(), 3);
+
+        let unlimited =
+            AssocService::new(InMemoryWordRepository::default(), graph, &settings(3, 10));
+        unlimited
+            .create_word_link(7, 1, 2, WordLinkKind::RootAffix, None)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn repoint_links_moves_and_collapses_links() {
+        let graph = InMemoryGraphRepository::default();
+        let older = graph
+            .create_word_link(7, 9, 5, WordLinkKind::SimilarForm, Some("merged".into()))
+            .await
+            .unwrap();
+        graph
+            .create_word_link(7, 2, 9, WordLinkKind::SimilarForm, None)
+            .await
+            .unwrap();
+        graph
+            .create_word_link(7, 5, 1, WordLinkKind::RootAffix, None)
+            .await
+            .unwrap();
+        graph
+            .create_word_link(7, 2, 5, WordLinkKind::RootAffix, None)
+            .await
+            .unwrap();
+        graph
+            .create_sense_word_link(7, 40, 30, 5, SenseWordLinkKind::Related, None)
+            .await
+            .unwrap();
+        let service = AssocService::new(
+            InMemoryWordRepository::default(),
+            graph.clone(),
+            &settings(3, 10),
+        );
+
+        service.repoint_links(7, 5, 2).await.unwrap();
+
+        let mut links = graph.word_links();
+        links.sort_by_key(|link| (link.word_a_id, link.word_b_id));
+        let pairs: Vec<_> = links
+            .iter()
+            .map(|link| (link.word_a_id, link.word_b_id, link.kind))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![
+                (1, 2, WordLinkKind::RootAffix),
+                (2, 9, WordLinkKind::SimilarForm)
+            ]
+        );
+        assert_eq!(links[1].created_at, older.created_at);
+        assert_eq!(links[1].note.as_deref(), Some("merged"));
+        let sense_links = graph.sense_links();
+        assert_eq!(sense_links.len(), 1);
+        assert_eq!(sense_links[0].target_word_id, 2);
+    }
+
+    #[tokio::test]
+    async fn repoint_links_rejects_same_word() {
+        let service = AssocService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+            &settings(3, 10),
+        );
+
+        let err = service.repoint_links(7, 5, 5).await.unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::BusinessError(BusinessError::Link(LinkError::SelfForbidden))
+        ));
+    }
+
+    #[tokio::test]
+    async fn neighbors_reports_graph_disabled() {
+        use crate::repository::graph::NoopGraphRepository;
+        use crate::util::error::{BusinessError, LinkError};
+
+        let service = AssocService::new(
+            InMemoryWordRepository::default(),
+            NoopGraphRepository,
+            &settings(3, 10),
+        );
+
+        let result = service.neighbors(7, 1, 1, None).await;
+
+        assert!(matches!(
+            result,
+            Err(AppError::BusinessError(BusinessError::Link(
+                LinkError::GraphDisabled
+            )))
+        ));
+    }
+}
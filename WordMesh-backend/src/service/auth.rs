@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use chrono::{DateTime, Utc};
 use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey};
 use thiserror::Error;
 use validator::{Validate, ValidationErrors};
@@ -7,14 +8,29 @@ use validator::{Validate, ValidationErrors};
 use crate::config::settings::{AuthJwtSettings, AuthSettings};
 use crate::domain::HashedPassword;
 use crate::dto::auth::{
-    AuthTokens, LoginRequest, ProfileResponse, RefreshRequest, RegisterRequest,
+    AuthTokens, ChangePasswordRequest, LoginRequest, LogoutRequest, ProfileResponse,
+    RefreshRequest, RegisterRequest, VerifyEmailRequest,
 };
-use crate::repository::user::{NewUser, RepositoryError, UserRepository};
+use crate::repository::token_store::{
+    InMemoryTokenStore, RefreshTokenStore, TokenStore, TokenStoreError,
+};
+use crate::repository::user::{
+    NewOneTimeToken, NewUser, RepositoryError, TokenPurpose, UserRepository,
+};
+use crate::service::mailer::{LoggingMailer, Mailer};
+use crate::service::word::validation_error;
 use crate::util::AppError;
-use crate::util::error::{AuthFlowError, BusinessError, InternalError, ValidationField};
-use crate::util::password::{PasswordError, hash_password, verify_password};
+use crate::util::error::{
+    AuthFlowError, BusinessError, InternalError, ValidationField, is_statement_timeout,
+};
+use crate::util::password::{
+    PasswordError, PasswordPolicyError, check_password_entropy, check_password_policy,
+    hash_password, needs_rehash, verify_password,
+};
+use crate::util::response::REQUEST_ID;
 use crate::util::token::{
-    TokenConfig, TokenError, generate_access_token, generate_refresh_token, validate_token,
+    Claims, TokenConfig, TokenError, generate_access_token, generate_one_time_token,
+    generate_refresh_token, hash_one_time_token, validate_token,
 };
 
 #[derive(Clone)]
@@ -22,6 +38,14 @@ pub struct AuthService<R: UserRepository + Send + Sync + 'static> {
     repository: Arc<R>,
     token_config: Arc<TokenConfig>,
     password_cost: u32,
+    rehash_on_login: bool,
+    password_min_length: usize,
+    password_require_complexity: bool,
+    password_min_entropy_bits: Option<u32>,
+    email_verification_ttl_secs: u64,
+    password_reset_ttl_secs: u64,
+    mailer: Arc<dyn Mailer>,
+    refresh_tokens: Arc<dyn RefreshTokenStore + Send + Sync>,
     pub auth_enabled: bool,
 }
 
@@ -72,18 +96,40 @@ where
 {
     pub fn new(
         repository: R,
+        refresh_tokens: Arc<dyn RefreshTokenStore + Send + Sync>,
         auth_settings: &AuthSettings,
         jwt_settings: &AuthJwtSettings,
     ) -> Result<Self, AppError> {
-        let token_config = build_token_config(jwt_settings)?;
+        let mut token_config = build_token_config(jwt_settings, auth_settings.enabled)?;
+        token_config.revocations = Some(Arc::new(InMemoryTokenStore::default()));
         Ok(Self {
             repository: Arc::new(repository),
             token_config: Arc::new(token_config),
-            password_cost: auth_settings.password.min_length.max(8) as u32,
+            password_cost: auth_settings.password.hash_cost,
+            rehash_on_login: auth_settings.password.rehash_on_login,
+            password_min_length: auth_settings.password.min_length as usize,
+            password_require_complexity: auth_settings.password.require_complexity,
+            password_min_entropy_bits: auth_settings.password.min_entropy_bits,
+            email_verification_ttl_secs: auth_settings.email_verification_ttl_secs,
+            password_reset_ttl_secs: auth_settings.password_reset_ttl_secs,
+            mailer: Arc::new(LoggingMailer),
+            refresh_tokens,
             auth_enabled: auth_settings.enabled,
         })
     }
 
+    /// 替换默认的日志投递，接入真实的邮件发送
+    pub fn with_mailer(mut self, mailer: Arc<dyn Mailer>) -> Self {
+        self.mailer = mailer;
+        self
+    }
+
+    /// 替换默认的进程内黑名单；须在取出 token_config 交给各控制器之前调用
+    pub fn with_token_store(mut self, store: Arc<dyn TokenStore + Send + Sync>) -> Self {
+        Arc::make_mut(&mut self.token_config).revocations = Some(store);
+        self
+    }
+
     pub fn token_config(&self) -> Arc<TokenConfig> {
         self.token_config.clone()
     }
@@ -93,6 +139,8 @@ where
         payload
             .validate()
             .map_err(|err| AppError::from(BusinessError::Validation(validation_errors(err))))?;
+        check_password_entropy(&payload.password, self.password_min_entropy_bits)
+            .map_err(|err| map_password_policy_error("password", err))?;
 
         let hashed =
             hash_password(&payload.password, self.password_cost).map_err(map_password_error)?;
@@ -102,6 +150,7 @@ where
         let new_user = NewUser {
             username: payload.username.clone(),
             password_hash,
+            email: payload.email.clone(),
         };
 
         let user = self
@@ -110,11 +159,197 @@ where
             .await
             .map_err(map_repository_error)?;
 
-        Ok(ProfileResponse {
-            id: user.id,
-            username: user.username,
-            created_at: user.created_at,
-        })
+        // 注册已经成功，验证邮件发送失败不回滚账号，用户可稍后重新申请
+        if let Some(email) = user.email.as_deref() {
+            match self.issue_verification_token(user.id).await {
+                Ok(token) => {
+                    if let Err(err) = self.mailer.send_email_verification(email, &token).await {
+                        tracing::warn!(
+                            user_id = user.id,
+                            error = %err,
+                            "failed to send verification email"
+                        );
+                    }
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        user_id = user.id,
+                        error = %err,
+                        "failed to issue verification token"
+                    );
+                }
+            }
+        }
+
+        Ok(ProfileResponse::from(user))
+    }
+
+    /// 为账号签发新的邮箱验证令牌并返回原文；库中只保存摘要
+    pub async fn issue_verification_token(&self, user_id: i64) -> Result<String, AppError> {
+        self.ensure_enabled()?;
+        let user = self
+            .repository
+            .find_by_id(user_id)
+            .await
+            .map_err(map_repository_error)?
+            .ok_or_else(|| {
+                AppError::from(BusinessError::Auth(AuthFlowError::InvalidCredentials))
+            })?;
+        if user.email.is_none() {
+            return Err(validation_error("email", "账号未绑定邮箱"));
+        }
+        if user.email_verified {
+            return Err(validation_error("email", "邮箱已验证"));
+        }
+
+        self.store_one_time_token(
+            user_id,
+            TokenPurpose::EmailVerification,
+            self.email_verification_ttl_secs,
+        )
+        .await
+    }
+
+    /// 消费验证令牌；过期、已用或不存在的令牌统一按无效处理
+    pub async fn verify_email(
+        &self,
+        payload: VerifyEmailRequest,
+    ) -> Result<ProfileResponse, AppError> {
+        self.ensure_enabled()?;
+        payload
+            .validate()
+            .map_err(|err| AppError::from(BusinessError::Validation(validation_errors(err))))?;
+
+        let user_id = self
+            .repository
+            .consume_one_time_token(
+                TokenPurpose::EmailVerification,
+                &hash_one_time_token(&payload.token),
+            )
+            .await
+            .map_err(map_repository_error)?
+            .ok_or_else(|| AppError::from(BusinessError::Auth(AuthFlowError::TokenInvalid)))?;
+
+        self.repository
+            .mark_email_verified(user_id)
+            .await
+            .map_err(map_repository_error)?;
+        self.profile(user_id).await
+    }
+
+    /// 申请重置密码；无论邮箱是否存在都返回成功，避免被用来探测账号
+    pub async fn request_password_reset(&self, email: &str) -> Result<(), AppError> {
+        self.ensure_enabled()?;
+        let Some(user) = self
+            .repository
+            .find_by_email(email.trim())
+            .await
+            .map_err(map_repository_error)?
+        else {
+            return Ok(());
+        };
+        let Some(address) = user.email.as_deref() else {
+            return Ok(());
+        };
+
+        let token = self
+            .store_one_time_token(
+                user.id,
+                TokenPurpose::PasswordReset,
+                self.password_reset_ttl_secs,
+            )
+            .await?;
+        if let Err(err) = self.mailer.send_password_reset(address, &token).await {
+            tracing::warn!(
+                user_id = user.id,
+                error = %err,
+                "failed to send password reset email"
+            );
+        }
+        Ok(())
+    }
+
+    /// 用重置令牌设置新密码；成功后此前签发的 refresh token 全部失效
+    pub async fn reset_password(&self, token: &str, new_password: &str) -> Result<(), AppError> {
+        self.ensure_enabled()?;
+        // 先校验密码，避免不合规的密码白白消耗掉令牌
+        let password_hash = self.hash_new_password(new_password)?;
+
+        let user_id = self
+            .repository
+            .consume_one_time_token(TokenPurpose::PasswordReset, &hash_one_time_token(token))
+            .await
+            .map_err(map_repository_error)?
+            .ok_or_else(|| AppError::from(BusinessError::Auth(AuthFlowError::TokenInvalid)))?;
+
+        self.repository
+            .reset_password(user_id, password_hash)
+            .await
+            .map_err(map_repository_error)
+    }
+
+    /// 已登录用户修改密码；与重置一样记录修改时间，此前签发的 refresh token 全部失效
+    pub async fn change_password(
+        &self,
+        user_id: i64,
+        payload: ChangePasswordRequest,
+    ) -> Result<(), AppError> {
+        self.ensure_enabled()?;
+        let user = self
+            .repository
+            .find_by_id(user_id)
+            .await
+            .map_err(map_repository_error)?
+            .ok_or_else(|| {
+                AppError::from(BusinessError::Auth(AuthFlowError::InvalidCredentials))
+            })?;
+        let current_ok = verify_password(&payload.current_password, user.password_hash.as_str())
+            .map_err(map_password_error)?;
+        if !current_ok {
+            return Err(AppError::from(BusinessError::Auth(
+                AuthFlowError::InvalidCredentials,
+            )));
+        }
+
+        let password_hash = self.hash_new_password(&payload.new_password)?;
+        self.repository
+            .reset_password(user.id, password_hash)
+            .await
+            .map_err(map_repository_error)
+    }
+
+    /// 按密码策略校验新密码并以当前 cost 哈希
+    fn hash_new_password(&self, new_password: &str) -> Result<HashedPassword, AppError> {
+        check_password_policy(
+            new_password,
+            self.password_min_length,
+            self.password_require_complexity,
+        )
+        .and_then(|()| check_password_entropy(new_password, self.password_min_entropy_bits))
+        .map_err(|err| map_password_policy_error("new_password", err))?;
+        let hashed = hash_password(new_password, self.password_cost).map_err(map_password_error)?;
+        HashedPassword::new(hashed)
+            .map_err(|_| AppError::from(BusinessError::Auth(AuthFlowError::InvalidCredentials)))
+    }
+
+    async fn store_one_time_token(
+        &self,
+        user_id: i64,
+        purpose: TokenPurpose,
+        ttl_secs: u64,
+    ) -> Result<String, AppError> {
+        let token = generate_one_time_token();
+        let expires_at = chrono::Utc::now() + chrono::Duration::seconds(ttl_secs as i64);
+        self.repository
+            .store_one_time_token(NewOneTimeToken {
+                user_id,
+                purpose,
+                token_hash: hash_one_time_token(&token),
+                expires_at,
+            })
+            .await
+            .map_err(map_repository_error)?;
+        Ok(token)
     }
 
     pub async fn login(&self, payload: LoginRequest) -> Result<AuthTokens, AppError> {
@@ -140,6 +375,10 @@ where
             )));
         }
 
+        if self.rehash_on_login && needs_rehash(user.password_hash.as_str(), self.password_cost) {
+            self.rehash_password(user.id, &payload.password).await;
+        }
+
         let access_token =
             generate_access_token(&self.token_config, &user.id.to_string(), None, None)
                 .map_err(map_token_error)?;
@@ -147,7 +386,7 @@ where
             .token_config
             .refresh_ttl_secs
             .map(|_| {
-                generate_refresh_token(&self.token_config, &user.id.to_string(), None)
+                generate_refresh_token(&self.token_config, &user.id.to_string(), None, None)
                     .map_err(map_token_error)
             })
             .transpose()?;
@@ -155,17 +394,45 @@ where
         Ok(AuthTokens {
             access_token,
             refresh_token,
+            refresh_enabled: self.token_config.refresh_ttl_secs.is_some(),
         })
     }
 
     pub async fn refresh(&self, payload: RefreshRequest) -> Result<AuthTokens, AppError> {
         self.ensure_enabled()?;
+        // 关闭刷新后签发过的 refresh token 也不再接受，明确告知而不是报令牌无效
+        if self.token_config.refresh_ttl_secs.is_none() {
+            return Err(AppError::from(BusinessError::Auth(
+                AuthFlowError::RefreshDisabled,
+            )));
+        }
+        // 复制粘贴带入的首尾空白不算令牌的一部分；内部空白仍会解码失败
+        let payload = RefreshRequest {
+            refresh_token: payload.refresh_token.trim().to_string(),
+        };
         payload
             .validate()
             .map_err(|err| AppError::from(BusinessError::Validation(validation_errors(err))))?;
 
         let claims =
             validate_token(&self.token_config, &payload.refresh_token).map_err(map_token_error)?;
+        self.ensure_not_revoked(&claims).await?;
+        // 引入轮换之前签发的 refresh token 无法检测重放，要求重新登录
+        let (Some(jti), Some(family)) = (claims.jti.clone(), claims.family.clone()) else {
+            return Err(AppError::from(BusinessError::Auth(
+                AuthFlowError::TokenInvalid,
+            )));
+        };
+        if self
+            .refresh_tokens
+            .is_family_revoked(&family)
+            .await
+            .map_err(map_token_store_error)?
+        {
+            return Err(AppError::from(BusinessError::Auth(
+                AuthFlowError::TokenInvalid,
+            )));
+        }
 
         let user_id = claims
             .sub
@@ -180,6 +447,18 @@ where
             .ok_or_else(|| {
                 AppError::from(BusinessError::Auth(AuthFlowError::InvalidCredentials))
             })?;
+        // iat 只精确到秒：与重置同一秒内签发的令牌仍然有效
+        if user
+            .password_changed_at
+            .is_some_and(|changed_at| claims.iat < changed_at.timestamp())
+        {
+            return Err(AppError::from(BusinessError::Auth(
+                AuthFlowError::TokenInvalid,
+            )));
+        }
+
+        self.consume_refresh_token(&family, &jti, claims.exp)
+            .await?;
 
         let access_token = generate_access_token(
             &self.token_config,
@@ -196,6 +475,7 @@ where
                     &self.token_config,
                     &user.id.to_string(),
                     claims.request_id.clone(),
+                    Some(family.clone()),
                 )
                 .map_err(map_token_error)
             })
@@ -204,6 +484,7 @@ where
         Ok(AuthTokens {
             access_token,
             refresh_token,
+            refresh_enabled: self.token_config.refresh_ttl_secs.is_some(),
         })
     }
 
@@ -218,17 +499,133 @@ where
                 AppError::from(BusinessError::Auth(AuthFlowError::InvalidCredentials))
             })?;
 
-        Ok(ProfileResponse {
-            id: user.id,
-            username: user.username,
-            created_at: user.created_at,
-        })
+        Ok(ProfileResponse::from(user))
+    }
+
+    /// 吊销当前 access token；提交了同一用户的 refresh token 时尽力吊销其所属家族，
+    /// 无法解析或已过期的 refresh token 直接忽略，不影响登出
+    pub async fn logout(
+        &self,
+        access_claims: &Claims,
+        payload: LogoutRequest,
+    ) -> Result<(), AppError> {
+        self.ensure_enabled()?;
+        // 引入 jti 之前签发的令牌无法单独吊销，只能等待其自然过期
+        if let (Some(store), Some(jti)) = (&self.token_config.revocations, &access_claims.jti) {
+            let expires_at =
+                DateTime::from_timestamp(access_claims.exp, 0).unwrap_or_else(Utc::now);
+            store
+                .revoke(jti, expires_at)
+                .await
+                .map_err(map_token_store_error)?;
+        }
+
+        if let Some(refresh_token) = payload.refresh_token {
+            self.revoke_refresh_family(access_claims, refresh_token.trim())
+                .await;
+        }
+        Ok(())
+    }
+
+    async fn revoke_refresh_family(&self, access_claims: &Claims, refresh_token: &str) {
+        let refresh_claims = match validate_token(&self.token_config, refresh_token) {
+            Ok(claims) => claims,
+            Err(err) => {
+                tracing::debug!(error = %err, "ignoring unusable refresh token on logout");
+                return;
+            }
+        };
+        if refresh_claims.sub != access_claims.sub {
+            tracing::warn!(
+                user_id = %access_claims.sub,
+                "ignoring refresh token of another user on logout"
+            );
+            return;
+        }
+        let Some(family) = refresh_claims.family.as_deref() else {
+            return;
+        };
+        let ttl = self.token_config.refresh_ttl_secs.unwrap_or_default();
+        let family_expires_at = Utc::now() + chrono::Duration::seconds(ttl as i64);
+        if let Err(err) = self
+            .refresh_tokens
+            .revoke_family(family, family_expires_at)
+            .await
+        {
+            tracing::warn!(family, error = %err, "failed to revoke refresh token family on logout");
+        }
+    }
+
+    /// 已兑换过的 refresh token 再次出现，说明它可能已泄露：吊销整个家族，
+    /// 合法持有者与攻击者手中的后续令牌一并失效
+    async fn consume_refresh_token(
+        &self,
+        family: &str,
+        jti: &str,
+        exp: i64,
+    ) -> Result<(), AppError> {
+        let expires_at = DateTime::from_timestamp(exp, 0).unwrap_or_else(Utc::now);
+        let first_use = self
+            .refresh_tokens
+            .consume(family, jti, expires_at)
+            .await
+            .map_err(map_token_store_error)?;
+        if first_use {
+            return Ok(());
+        }
+
+        tracing::warn!(
+            family,
+            "refresh token reuse detected; revoking token family"
+        );
+        let ttl = self.token_config.refresh_ttl_secs.unwrap_or_default();
+        let family_expires_at = Utc::now() + chrono::Duration::seconds(ttl as i64);
+        self.refresh_tokens
+            .revoke_family(family, family_expires_at)
+            .await
+            .map_err(map_token_store_error)?;
+        Err(AppError::from(BusinessError::Auth(
+            AuthFlowError::TokenInvalid,
+        )))
+    }
+
+    async fn ensure_not_revoked(&self, claims: &Claims) -> Result<(), AppError> {
+        let (Some(store), Some(jti)) = (&self.token_config.revocations, &claims.jti) else {
+            return Ok(());
+        };
+        if store.is_revoked(jti).await.map_err(map_token_store_error)? {
+            return Err(AppError::from(BusinessError::Auth(
+                AuthFlowError::TokenInvalid,
+            )));
+        }
+        Ok(())
+    }
+
+    /// 登录成功后将低于当前 cost 的哈希升级；写入失败只记录日志，不影响登录
+    async fn rehash_password(&self, user_id: i64, raw: &str) {
+        let rehashed = match hash_password(raw, self.password_cost) {
+            Ok(hash) => hash,
+            Err(err) => {
+                tracing::warn!(user_id, error = %err, "password rehash failed");
+                return;
+            }
+        };
+        let Ok(password_hash) = HashedPassword::new(rehashed) else {
+            return;
+        };
+        if let Err(err) = self
+            .repository
+            .update_password(user_id, password_hash)
+            .await
+        {
+            tracing::warn!(user_id, error = %err, "failed to persist rehashed password");
+        }
     }
 
     fn ensure_enabled(&self) -> Result<(), AppError> {
         if !self.auth_enabled {
             Err(AppError::from(BusinessError::Auth(
-                AuthFlowError::RefreshDisabled,
+                AuthFlowError::AuthDisabled,
             )))
         } else {
             Ok(())
@@ -236,9 +633,14 @@ where
     }
 }
 
-fn map_repository_error(err: RepositoryError) -> AppError {
+pub(crate) fn map_repository_error(err: RepositoryError) -> AppError {
     match err {
-        RepositoryError::Database(_) => AppError::from(InternalError::Unknown),
+        RepositoryError::Database(err) => {
+            if is_statement_timeout(&err) {
+                tracing::error!(error = %err, "user query cancelled by statement timeout");
+            }
+            AppError::from(InternalError::Unknown)
+        }
         RepositoryError::Domain(_) => {
             AppError::from(BusinessError::Auth(AuthFlowError::InvalidCredentials))
         }
@@ -251,10 +653,27 @@ fn map_password_error(err: PasswordError) -> AppError {
             field: "password".into(),
             message: "密码不能为空".into(),
         }])),
-        PasswordError::Hash(_) | PasswordError::Verify => AppError::from(InternalError::Unknown),
+        // 多半是 bcrypt 配置问题（如 cost 越界）；错误信息不含密码，细节只进日志，客户端仍只看到 5000
+        PasswordError::Hash(_) | PasswordError::Verify => {
+            let request_id = REQUEST_ID.try_with(|id| id.clone()).unwrap_or_default();
+            tracing::error!(error = %err, request_id = %request_id, "password hash operation failed");
+            AppError::from(InternalError::Unknown)
+        }
     }
 }
 
+fn map_password_policy_error(field: &str, err: PasswordPolicyError) -> AppError {
+    let message = match err {
+        PasswordPolicyError::TooShort(min) => format!("密码长度至少 {min} 位"),
+        PasswordPolicyError::NotComplex => "密码必须同时包含字母和数字".to_string(),
+        PasswordPolicyError::TooWeak {
+            estimated,
+            required,
+        } => format!("密码太容易被猜到：估计强度约 {estimated} 位，至少需要 {required} 位"),
+    };
+    validation_error(field, message)
+}
+
 fn validation_errors(err: ValidationErrors) -> Vec<ValidationField> {
     let mut fields = Vec::new();
     for (field, errors) in err.field_errors() {
@@ -269,10 +688,15 @@ fn validation_errors(err: ValidationErrors) -> Vec<ValidationField> {
     fields
 }
 
-fn build_token_config(jwt_settings: &AuthJwtSettings) -> Result<TokenConfig, AppError> {
+fn build_token_config(
+    jwt_settings: &AuthJwtSettings,
+    auth_enabled: bool,
+) -> Result<TokenConfig, AppError> {
     let algorithm = match jwt_settings.algorithm.to_uppercase().as_str() {
         "HS256" => Algorithm::HS256,
         "RS256" => Algorithm::RS256,
+        "ES256" => Algorithm::ES256,
+        "EDDSA" => Algorithm::EdDSA,
         other => {
             let _ = other;
             return Err(AppError::from(InternalError::Unknown));
@@ -291,14 +715,7 @@ fn build_token_config(jwt_settings: &AuthJwtSettings) -> Result<TokenConfig, App
             )
         }
         Algorithm::RS256 => {
-            let private = jwt_settings
-                .private_key
-                .clone()
-                .ok_or_else(|| AppError::from(InternalError::Unknown))?;
-            let public = jwt_settings
-                .public_key
-                .clone()
-                .ok_or_else(|| AppError::from(InternalError::Unknown))?;
+            let (private, public) = pem_key_pair(jwt_settings)?;
             (
                 EncodingKey::from_rsa_pem(private.as_bytes())
                     .map_err(|_| AppError::from(InternalError::Unknown))?,
@@ -306,6 +723,25 @@ fn build_token_config(jwt_settings: &AuthJwtSettings) -> Result<TokenConfig, App
                     .map_err(|_| AppError::from(InternalError::Unknown))?,
             )
         }
+        // ES256 的私钥须为 PKCS#8 PEM（`openssl genpkey -algorithm EC ...` 的默认输出）
+        Algorithm::ES256 => {
+            let (private, public) = pem_key_pair(jwt_settings)?;
+            (
+                EncodingKey::from_ec_pem(private.as_bytes())
+                    .map_err(|_| AppError::from(InternalError::Unknown))?,
+                DecodingKey::from_ec_pem(public.as_bytes())
+                    .map_err(|_| AppError::from(InternalError::Unknown))?,
+            )
+        }
+        Algorithm::EdDSA => {
+            let (private, public) = pem_key_pair(jwt_settings)?;
+            (
+                EncodingKey::from_ed_pem(private.as_bytes())
+                    .map_err(|_| AppError::from(InternalError::Unknown))?,
+                DecodingKey::from_ed_pem(public.as_bytes())
+                    .map_err(|_| AppError::from(InternalError::Unknown))?,
+            )
+        }
         _ => unreachable!(),
     };
 
@@ -320,9 +756,28 @@ fn build_token_config(jwt_settings: &AuthJwtSettings) -> Result<TokenConfig, App
         encoding_key,
         decoding_key,
         issuer: Some("wordmesh".to_string()),
+        auth_enabled,
+        revocations: None,
     })
 }
 
+fn pem_key_pair(jwt_settings: &AuthJwtSettings) -> Result<(&str, &str), AppError> {
+    let private = jwt_settings
+        .private_key
+        .as_deref()
+        .ok_or_else(|| AppError::from(InternalError::Unknown))?;
+    let public = jwt_settings
+        .public_key
+        .as_deref()
+        .ok_or_else(|| AppError::from(InternalError::Unknown))?;
+    Ok((private, public))
+}
+
+fn map_token_store_error(err: TokenStoreError) -> AppError {
+    tracing::error!(error = %err, "token store operation failed");
+    AppError::from(InternalError::Unknown)
+}
+
 fn map_token_error(err: TokenError) -> AppError {
     match err {
         TokenError::RefreshDisabled => {
@@ -338,17 +793,22 @@ mod tests {
     use super::*;
     use crate::config::settings::{AuthJwtSettings, AuthPasswordSettings, AuthSettings};
     use crate::domain::User;
-    use crate::util::error::{AppError, BusinessError, InternalError};
-    use crate::util::token::TokenConfig;
+    use crate::repository::token_store::InMemoryRefreshTokenStore;
+    use crate::util::error::{AppError, BusinessError};
+    use crate::util::password::hash_cost;
     use async_trait::async_trait;
     use chrono::Utc;
     use std::collections::HashMap;
     use tokio::sync::RwLock;
 
+    /// (user_id, purpose, token_hash, expires_at, consumed)
+    type StoredToken = (i64, TokenPurpose, String, chrono::DateTime<Utc>, bool);
+
     #[derive(Default, Clone)]
     struct InMemoryUserRepository {
         users: Arc<RwLock<HashMap<i64, User>>>,
         username_index: Arc<RwLock<HashMap<String, i64>>>,
+        tokens: Arc<RwLock<Vec<StoredToken>>>,
     }
 
     #[async_trait]
@@ -370,7 +830,8 @@ mod tests {
                 new_user.password_hash,
                 Utc::now(),
             )
-            .unwrap();
+            .unwrap()
+            .with_email(new_user.email, false);
             username_idx.insert(user.username.clone(), user.id);
             users.insert(id, user.clone());
             Ok(user)
@@ -389,6 +850,89 @@ mod tests {
             let users = self.users.read().await;
             Ok(users.get(&user_id).cloned())
         }
+
+        async fn find_by_email(&self, email: &str) -> Result<Option<User>, RepositoryError> {
+            let users = self.users.read().await;
+            Ok(users
+                .values()
+                .find(|user| {
+                    user.email
+                        .as_deref()
+                        .is_some_and(|stored| stored.eq_ignore_ascii_case(email))
+                })
+                .cloned())
+        }
+
+        async fn update_password(
+            &self,
+            user_id: i64,
+            password_hash: HashedPassword,
+        ) -> Result<(), RepositoryError> {
+            let mut users = self.users.write().await;
+            if let Some(user) = users.get_mut(&user_id) {
+                user.password_hash = password_hash;
+            }
+            Ok(())
+        }
+
+        async fn reset_password(
+            &self,
+            user_id: i64,
+            password_hash: HashedPassword,
+        ) -> Result<(), RepositoryError> {
+            let mut users = self.users.write().await;
+            if let Some(user) = users.get_mut(&user_id) {
+                user.password_hash = password_hash;
+                user.password_changed_at = Some(Utc::now());
+            }
+            Ok(())
+        }
+
+        async fn find_stats(
+            &self,
+            _user_id: i64,
+        ) -> Result<crate::repository::user::UserStats, RepositoryError> {
+            Ok(Default::default())
+        }
+
+        async fn store_one_time_token(
+            &self,
+            token: NewOneTimeToken,
+        ) -> Result<(), RepositoryError> {
+            self.tokens.write().await.push((
+                token.user_id,
+                token.purpose,
+                token.token_hash,
+                token.expires_at,
+                false,
+            ));
+            Ok(())
+        }
+
+        async fn consume_one_time_token(
+            &self,
+            purpose: TokenPurpose,
+            token_hash: &str,
+        ) -> Result<Option<i64>, RepositoryError> {
+            let mut tokens = self.tokens.write().await;
+            let now = Utc::now();
+            Ok(tokens
+                .iter_mut()
+                .find(|(_, p, hash, expires_at, consumed)| {
+                    *p == purpose && hash == token_hash && !*consumed && *expires_at > now
+                })
+                .map(|entry| {
+                    entry.4 = true;
+                    entry.0
+                }))
+        }
+
+        async fn mark_email_verified(&self, user_id: i64) -> Result<(), RepositoryError> {
+            if let Some(user) = self.users.write().await.get_mut(&user_id) {
+                user.email_verified = true;
+            }
+            Ok(())
+        }
     }
 
     fn default_settings() -> AuthSettings {
@@ -405,13 +949,24 @@ mod tests {
             password: AuthPasswordSettings {
                 min_length: 8,
                 require_complexity: false,
+                min_entropy_bits: None,
+                hash_cost: 4,
+                rehash_on_login: true,
             },
+            email_verification_ttl_secs: 3600,
+            password_reset_ttl_secs: 600,
         }
     }
 
     fn service(repo: InMemoryUserRepository) -> AuthService<InMemoryUserRepository> {
         let settings = default_settings();
-        AuthService::new(repo, &settings, &settings.jwt).unwrap()
+        AuthService::new(
+            repo,
+            Arc::new(InMemoryRefreshTokenStore::default()),
+            &settings,
+            &settings.jwt,
+        )
+        .unwrap()
     }
 
     #[tokio::test]
@@ -422,6 +977,7 @@ mod tests {
         let register = RegisterRequest {
             username: "user123".into(),
             password: "password123".into(),
+            email: None,
         };
         let profile = service.register(register).await.unwrap();
         assert_eq!(profile.username, "user123");
@@ -443,6 +999,7 @@ mod tests {
             .register(RegisterRequest {
                 username: "user_refresh".into(),
                 password: "password123".into(),
+                email: None,
             })
             .await
             .unwrap();
@@ -464,88 +1021,346 @@ mod tests {
         assert!(!refreshed.access_token.is_empty());
     }
 
-    #[tokio::test]
-    async fn profile_returns_user() {
-        let repo = InMemoryUserRepository::default();
-        let service = service(repo.clone());
-
-        let profile = service
+    async fn login_refresh_token(service: &AuthService<InMemoryUserRepository>) -> String {
+        service
             .register(RegisterRequest {
-                username: "profile_user".into(),
+                username: "user_rotate".into(),
                 password: "password123".into(),
+                email: None,
             })
             .await
             .unwrap();
-
-        let fetched = service.profile(profile.id).await.unwrap();
-        assert_eq!(fetched.username, "profile_user");
+        service
+            .login(LoginRequest {
+                username: "user_rotate".into(),
+                password: "password123".into(),
+            })
+            .await
+            .unwrap()
+            .refresh_token
+            .expect("refresh token")
     }
 
     #[tokio::test]
-    async fn login_with_wrong_password_fails() {
-        let repo = InMemoryUserRepository::default();
-        let service = service(repo.clone());
+    async fn refresh_accepts_token_padded_with_whitespace() {
+        let service = service(InMemoryUserRepository::default());
+        let token = login_refresh_token(&service).await;
 
-        service
-            .register(RegisterRequest {
-                username: "user_wrong".into(),
-                password: "password123".into(),
+        let rotated = service
+            .refresh(RefreshRequest {
+                refresh_token: format!("  {token}\n"),
             })
             .await
+            .unwrap()
+            .refresh_token
             .unwrap();
 
+        // 令牌内部的空白不做处理
+        let (head, tail) = rotated.split_at(10);
         let err = service
-            .login(LoginRequest {
-                username: "user_wrong".into(),
-                password: "wrongpass".into(),
+            .refresh(RefreshRequest {
+                refresh_token: format!("{head} {tail}"),
             })
             .await
             .unwrap_err();
-
-        match err {
-            AppError::BusinessError(BusinessError::Auth(AuthFlowError::InvalidCredentials)) => {}
-            other => panic!("unexpected error: {:?}", other),
-        }
+        assert_token_invalid(err);
     }
 
     #[tokio::test]
-    async fn refresh_disabled_returns_error() {
-        let mut settings = default_settings();
-        settings.jwt.refresh_ttl_secs = 0;
-        let repo = InMemoryUserRepository::default();
-        let service = AuthService::new(repo.clone(), &settings, &settings.jwt).unwrap();
+    async fn refresh_rotates_tokens_within_one_family() {
+        let service = service(InMemoryUserRepository::default());
+        let first = login_refresh_token(&service).await;
 
-        service
-            .register(RegisterRequest {
-                username: "refresh_disabled".into(),
-                password: "password123".into(),
+        let second = service
+            .refresh(RefreshRequest {
+                refresh_token: first.clone(),
             })
             .await
+            .unwrap()
+            .refresh_token
             .unwrap();
-
-        let login_tokens = service
-            .login(LoginRequest {
-                username: "refresh_disabled".into(),
-                password: "password123".into(),
+        let third = service
+            .refresh(RefreshRequest {
+                refresh_token: second.clone(),
             })
             .await
+            .unwrap()
+            .refresh_token
             .unwrap();
 
-        assert!(login_tokens.refresh_token.is_none());
+        let config = service.token_config();
+        let family = |token: &str| validate_token(&config, token).unwrap().family;
+        assert_ne!(first, second);
+        assert_ne!(second, third);
+        assert!(family(&first).is_some());
+        assert_eq!(family(&first), family(&second));
+        assert_eq!(family(&second), family(&third));
+    }
 
-        let err = service
+    #[tokio::test]
+    async fn reused_refresh_token_is_rejected_and_revokes_family() {
+        let service = service(InMemoryUserRepository::default());
+        let first = login_refresh_token(&service).await;
+        let second = service
             .refresh(RefreshRequest {
-                refresh_token: "0000000000".into(),
+                refresh_token: first.clone(),
             })
             .await
-            .unwrap_err();
-        match err {
-            AppError::BusinessError(BusinessError::Auth(AuthFlowError::TokenInvalid)) => {}
-            other => panic!("unexpected error: {:?}", other),
-        }
-    }
+            .unwrap()
+            .refresh_token
+            .unwrap();
 
-    #[tokio::test]
+        // 旧令牌被重放：拒绝，且同一家族中尚未使用的新令牌也随之失效
+        assert_token_invalid(
+            service
+                .refresh(RefreshRequest {
+                    refresh_token: first,
+                })
+                .await
+                .unwrap_err(),
+        );
+        assert_token_invalid(
+            service
+                .refresh(RefreshRequest {
+                    refresh_token: second,
+                })
+                .await
+                .unwrap_err(),
+        );
+
+        // 重新登录开启新的家族，不受影响
+        let fresh = service
+            .login(LoginRequest {
+                username: "user_rotate".into(),
+                password: "password123".into(),
+            })
+            .await
+            .unwrap()
+            .refresh_token
+            .unwrap();
+        assert!(
+            service
+                .refresh(RefreshRequest {
+                    refresh_token: fresh
+                })
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn refresh_token_without_family_is_rejected() {
+        let service = service(InMemoryUserRepository::default());
+        let config = service.token_config();
+        let issued_at = chrono::Utc::now().timestamp();
+        let legacy = jsonwebtoken::encode(
+            &jsonwebtoken::Header::new(config.algorithm),
+            &serde_json::json!({ "sub": "1", "iat": issued_at, "exp": issued_at + 60 }),
+            &config.encoding_key,
+        )
+        .unwrap();
+
+        assert_token_invalid(
+            service
+                .refresh(RefreshRequest {
+                    refresh_token: legacy,
+                })
+                .await
+                .unwrap_err(),
+        );
+    }
+
+    #[tokio::test]
+    async fn logout_ignores_an_expired_refresh_token() {
+        let repo = InMemoryUserRepository::default();
+        let service = service(repo.clone());
+        service
+            .register(RegisterRequest {
+                username: "logout_expired".into(),
+                password: "password123".into(),
+                email: None,
+            })
+            .await
+            .unwrap();
+        let tokens = service
+            .login(LoginRequest {
+                username: "logout_expired".into(),
+                password: "password123".into(),
+            })
+            .await
+            .unwrap();
+        let config = service.token_config();
+        let access_claims = validate_token(&config, &tokens.access_token).unwrap();
+        let issued_at = chrono::Utc::now().timestamp() - 7200;
+        let expired = jsonwebtoken::encode(
+            &jsonwebtoken::Header::new(config.algorithm),
+            &serde_json::json!({
+                "sub": access_claims.sub,
+                "iat": issued_at,
+                "exp": issued_at + 60,
+                "jti": "expired-refresh",
+                "family": "expired-family",
+            }),
+            &config.encoding_key,
+        )
+        .unwrap();
+
+        service
+            .logout(
+                &access_claims,
+                LogoutRequest {
+                    refresh_token: Some(expired),
+                },
+            )
+            .await
+            .unwrap();
+
+        let store = config.revocations.as_ref().unwrap();
+        assert!(
+            store
+                .is_revoked(access_claims.jti.as_deref().unwrap())
+                .await
+                .unwrap()
+        );
+        // 未过期的 refresh token 不受影响，可以继续换发
+        assert!(
+            service
+                .refresh(RefreshRequest {
+                    refresh_token: tokens.refresh_token.unwrap(),
+                })
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn profile_returns_user() {
+        let repo = InMemoryUserRepository::default();
+        let service = service(repo.clone());
+
+        let profile = service
+            .register(RegisterRequest {
+                username: "profile_user".into(),
+                password: "password123".into(),
+                email: None,
+            })
+            .await
+            .unwrap();
+
+        let fetched = service.profile(profile.id).await.unwrap();
+        assert_eq!(fetched.username, "profile_user");
+    }
+
+    #[tokio::test]
+    async fn login_rehashes_password_below_configured_cost() {
+        let repo = InMemoryUserRepository::default();
+        let mut settings = default_settings();
+        settings.password.hash_cost = 5;
+        let service = AuthService::new(
+            repo.clone(),
+            Arc::new(InMemoryRefreshTokenStore::default()),
+            &settings,
+            &settings.jwt,
+        )
+        .unwrap();
+
+        let weak_hash = hash_password("password123", 4).unwrap();
+        let user = repo
+            .create_user(NewUser {
+                username: "user_rehash".into(),
+                password_hash: HashedPassword::new(weak_hash).unwrap(),
+                email: None,
+            })
+            .await
+            .unwrap();
+
+        service
+            .login(LoginRequest {
+                username: "user_rehash".into(),
+                password: "password123".into(),
+            })
+            .await
+            .unwrap();
+
+        let stored = repo.find_by_id(user.id).await.unwrap().unwrap();
+        assert_eq!(hash_cost(stored.password_hash.as_str()), Some(5));
+        assert!(verify_password("password123", stored.password_hash.as_str()).unwrap());
+    }
+
+    #[tokio::test]
+    async fn login_with_wrong_password_fails() {
+        let repo = InMemoryUserRepository::default();
+        let service = service(repo.clone());
+
+        service
+            .register(RegisterRequest {
+                username: "user_wrong".into(),
+                password: "password123".into(),
+                email: None,
+            })
+            .await
+            .unwrap();
+
+        let err = service
+            .login(LoginRequest {
+                username: "user_wrong".into(),
+                password: "wrongpass".into(),
+            })
+            .await
+            .unwrap_err();
+
+        match err {
+            AppError::BusinessError(BusinessError::Auth(AuthFlowError::InvalidCredentials)) => {}
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn refresh_disabled_returns_error() {
+        let mut settings = default_settings();
+        settings.jwt.refresh_ttl_secs = 0;
+        let repo = InMemoryUserRepository::default();
+        let service = AuthService::new(
+            repo.clone(),
+            Arc::new(InMemoryRefreshTokenStore::default()),
+            &settings,
+            &settings.jwt,
+        )
+        .unwrap();
+
+        service
+            .register(RegisterRequest {
+                username: "refresh_disabled".into(),
+                password: "password123".into(),
+                email: None,
+            })
+            .await
+            .unwrap();
+
+        let login_tokens = service
+            .login(LoginRequest {
+                username: "refresh_disabled".into(),
+                password: "password123".into(),
+            })
+            .await
+            .unwrap();
+
+        assert!(login_tokens.refresh_token.is_none());
+        assert!(!login_tokens.refresh_enabled);
+
+        let err = service
+            .refresh(RefreshRequest {
+                refresh_token: "0000000000".into(),
+            })
+            .await
+            .unwrap_err();
+        match err {
+            AppError::BusinessError(BusinessError::Auth(AuthFlowError::RefreshDisabled)) => {}
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
     async fn register_validation_error() {
         let repo = InMemoryUserRepository::default();
         let service = service(repo.clone());
@@ -554,6 +1369,7 @@ mod tests {
             .register(RegisterRequest {
                 username: "ab".into(),
                 password: "pwd".into(),
+                email: None,
             })
             .await
             .unwrap_err();
@@ -565,4 +1381,467 @@ mod tests {
             other => panic!("expected validation error, got {:?}", other),
         }
     }
+
+    #[derive(Default)]
+    struct RecordingMailer {
+        sent: std::sync::Mutex<Vec<(String, String)>>,
+        resets: std::sync::Mutex<Vec<(String, String)>>,
+    }
+
+    #[async_trait]
+    impl Mailer for RecordingMailer {
+        async fn send_email_verification(
+            &self,
+            to: &str,
+            token: &str,
+        ) -> Result<(), crate::service::mailer::MailerError> {
+            self.sent
+                .lock()
+                .unwrap()
+                .push((to.to_string(), token.to_string()));
+            Ok(())
+        }
+
+        async fn send_password_reset(
+            &self,
+            to: &str,
+            token: &str,
+        ) -> Result<(), crate::service::mailer::MailerError> {
+            self.resets
+                .lock()
+                .unwrap()
+                .push((to.to_string(), token.to_string()));
+            Ok(())
+        }
+    }
+
+    async fn register_with_email(
+        settings: &AuthSettings,
+        username: &str,
+    ) -> (AuthService<InMemoryUserRepository>, String) {
+        let mailer = Arc::new(RecordingMailer::default());
+        let service = AuthService::new(
+            InMemoryUserRepository::default(),
+            Arc::new(InMemoryRefreshTokenStore::default()),
+            settings,
+            &settings.jwt,
+        )
+        .unwrap()
+        .with_mailer(mailer.clone());
+        let profile = service
+            .register(RegisterRequest {
+                username: username.into(),
+                password: "password123".into(),
+                email: Some(format!("{username}@example.com")),
+            })
+            .await
+            .unwrap();
+        assert!(!profile.email_verified);
+
+        let sent = mailer.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].0, format!("{username}@example.com"));
+        let token = sent[0].1.clone();
+        (service, token)
+    }
+
+    fn assert_token_invalid(err: AppError) {
+        match err {
+            AppError::BusinessError(BusinessError::Auth(AuthFlowError::TokenInvalid)) => {}
+            other => panic!("expected invalid token, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn verify_email_marks_account_verified() {
+        let (service, token) = register_with_email(&default_settings(), "verify_ok").await;
+
+        let profile = service
+            .verify_email(VerifyEmailRequest { token })
+            .await
+            .unwrap();
+        assert!(profile.email_verified);
+        assert!(service.profile(profile.id).await.unwrap().email_verified);
+
+        let err = service
+            .issue_verification_token(profile.id)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::BusinessError(BusinessError::Validation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn verify_email_rejects_expired_token() {
+        let mut settings = default_settings();
+        settings.email_verification_ttl_secs = 0;
+        let (service, token) = register_with_email(&settings, "verify_expired").await;
+
+        let err = service
+            .verify_email(VerifyEmailRequest { token })
+            .await
+            .unwrap_err();
+        assert_token_invalid(err);
+    }
+
+    #[tokio::test]
+    async fn verify_email_rejects_reused_token() {
+        let (service, token) = register_with_email(&default_settings(), "verify_twice").await;
+
+        service
+            .verify_email(VerifyEmailRequest {
+                token: token.clone(),
+            })
+            .await
+            .unwrap();
+        let err = service
+            .verify_email(VerifyEmailRequest { token })
+            .await
+            .unwrap_err();
+        assert_token_invalid(err);
+    }
+
+    #[tokio::test]
+    async fn register_without_email_skips_verification() {
+        let mailer = Arc::new(RecordingMailer::default());
+        let service = service(InMemoryUserRepository::default()).with_mailer(mailer.clone());
+
+        let profile = service
+            .register(RegisterRequest {
+                username: "no_email".into(),
+                password: "password123".into(),
+                email: None,
+            })
+            .await
+            .unwrap();
+        assert!(profile.email.is_none());
+        assert!(mailer.sent.lock().unwrap().is_empty());
+    }
+
+    async fn request_reset(
+        settings: &AuthSettings,
+        username: &str,
+    ) -> (
+        AuthService<InMemoryUserRepository>,
+        Arc<RecordingMailer>,
+        String,
+    ) {
+        let mailer = Arc::new(RecordingMailer::default());
+        let service = AuthService::new(
+            InMemoryUserRepository::default(),
+            Arc::new(InMemoryRefreshTokenStore::default()),
+            settings,
+            &settings.jwt,
+        )
+        .unwrap()
+        .with_mailer(mailer.clone());
+        service
+            .register(RegisterRequest {
+                username: username.into(),
+                password: "password123".into(),
+                email: Some(format!("{username}@example.com")),
+            })
+            .await
+            .unwrap();
+
+        // 大小写不同的邮箱也能匹配
+        service
+            .request_password_reset(&format!("{}@EXAMPLE.com", username))
+            .await
+            .unwrap();
+        let token = mailer.resets.lock().unwrap()[0].1.clone();
+        (service, mailer, token)
+    }
+
+    fn login_request(username: &str, password: &str) -> LoginRequest {
+        LoginRequest {
+            username: username.into(),
+            password: password.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn reset_password_replaces_credentials() {
+        let (service, _, token) = request_reset(&default_settings(), "reset_ok").await;
+
+        service
+            .reset_password(&token, "newpassword456")
+            .await
+            .unwrap();
+
+        assert!(
+            service
+                .login(login_request("reset_ok", "password123"))
+                .await
+                .is_err()
+        );
+        service
+            .login(login_request("reset_ok", "newpassword456"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn reset_password_rejects_expired_token() {
+        let mut settings = default_settings();
+        settings.password_reset_ttl_secs = 0;
+        let (service, _, token) = request_reset(&settings, "reset_expired").await;
+
+        let err = service
+            .reset_password(&token, "newpassword456")
+            .await
+            .unwrap_err();
+        assert_token_invalid(err);
+    }
+
+    #[tokio::test]
+    async fn reset_password_rejects_reused_token() {
+        let (service, _, token) = request_reset(&default_settings(), "reset_twice").await;
+
+        service
+            .reset_password(&token, "newpassword456")
+            .await
+            .unwrap();
+        let err = service
+            .reset_password(&token, "anotherpass789")
+            .await
+            .unwrap_err();
+        assert_token_invalid(err);
+    }
+
+    #[tokio::test]
+    async fn reset_password_enforces_policy_without_consuming_token() {
+        let mut settings = default_settings();
+        settings.password.require_complexity = true;
+        let (service, _, token) = request_reset(&settings, "reset_weak").await;
+
+        let err = service
+            .reset_password(&token, "lettersonly")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::BusinessError(BusinessError::Validation(_))
+        ));
+        service
+            .reset_password(&token, "letters4nd5digits")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn register_enforces_min_entropy_on_password_field() {
+        let mut settings = default_settings();
+        settings.password.min_entropy_bits = Some(40);
+        let service = AuthService::new(
+            InMemoryUserRepository::default(),
+            Arc::new(InMemoryRefreshTokenStore::default()),
+            &settings,
+            &settings.jwt,
+        )
+        .unwrap();
+
+        let err = service
+            .register(RegisterRequest {
+                username: "weak_user".into(),
+                password: "Password1".into(),
+                email: None,
+            })
+            .await
+            .unwrap_err();
+        let AppError::BusinessError(BusinessError::Validation(fields)) = err else {
+            panic!("expected validation error, got {err:?}");
+        };
+        assert_eq!(fields[0].field, "password");
+        assert!(fields[0].message.contains("40"));
+
+        service
+            .register(RegisterRequest {
+                username: "strong_user".into(),
+                password: "correct horse battery staple".into(),
+                email: None,
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn password_reset_request_for_unknown_email_succeeds_silently() {
+        let mailer = Arc::new(RecordingMailer::default());
+        let service = service(InMemoryUserRepository::default()).with_mailer(mailer.clone());
+
+        service
+            .request_password_reset("nobody@example.com")
+            .await
+            .unwrap();
+        assert!(mailer.resets.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reset_password_revokes_existing_refresh_tokens() {
+        let (service, _, token) = request_reset(&default_settings(), "reset_sessions").await;
+        let before = service
+            .login(login_request("reset_sessions", "password123"))
+            .await
+            .unwrap()
+            .refresh_token
+            .unwrap();
+
+        // iat 精确到秒，等到下一秒再重置以保证旧令牌早于修改时间
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+        service
+            .reset_password(&token, "newpassword456")
+            .await
+            .unwrap();
+
+        let err = service
+            .refresh(RefreshRequest {
+                refresh_token: before,
+            })
+            .await
+            .unwrap_err();
+        assert_token_invalid(err);
+
+        let after = service
+            .login(login_request("reset_sessions", "newpassword456"))
+            .await
+            .unwrap()
+            .refresh_token
+            .unwrap();
+        service
+            .refresh(RefreshRequest {
+                refresh_token: after,
+            })
+            .await
+            .unwrap();
+    }
+
+    async fn registered_user(
+        settings: &AuthSettings,
+        username: &str,
+    ) -> (AuthService<InMemoryUserRepository>, i64) {
+        let service = AuthService::new(
+            InMemoryUserRepository::default(),
+            Arc::new(InMemoryRefreshTokenStore::default()),
+            settings,
+            &settings.jwt,
+        )
+        .unwrap();
+        let profile = service
+            .register(RegisterRequest {
+                username: username.into(),
+                password: "password123".into(),
+                email: None,
+            })
+            .await
+            .unwrap();
+        (service, profile.id)
+    }
+
+    fn change_request(current: &str, new: &str) -> ChangePasswordRequest {
+        ChangePasswordRequest {
+            current_password: current.into(),
+            new_password: new.into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn change_password_replaces_credentials() {
+        let (service, user_id) = registered_user(&default_settings(), "change_ok").await;
+
+        service
+            .change_password(user_id, change_request("password123", "newpassword456"))
+            .await
+            .unwrap();
+
+        assert!(
+            service
+                .login(login_request("change_ok", "password123"))
+                .await
+                .is_err()
+        );
+        service
+            .login(login_request("change_ok", "newpassword456"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn change_password_requires_current_password() {
+        let (service, user_id) = registered_user(&default_settings(), "change_wrong").await;
+
+        let err = service
+            .change_password(user_id, change_request("not-my-password", "newpassword456"))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::BusinessError(BusinessError::Auth(AuthFlowError::InvalidCredentials))
+        ));
+        service
+            .login(login_request("change_wrong", "password123"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn change_password_enforces_policy() {
+        let mut settings = default_settings();
+        settings.password.require_complexity = true;
+        let (service, user_id) = registered_user(&settings, "change_weak").await;
+
+        for weak in ["short1", "lettersonly"] {
+            let err = service
+                .change_password(user_id, change_request("password123", weak))
+                .await
+                .unwrap_err();
+            assert!(matches!(
+                err,
+                AppError::BusinessError(BusinessError::Validation(_))
+            ));
+        }
+        service
+            .login(login_request("change_weak", "password123"))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn hash_failures_are_logged_but_stay_opaque() {
+        let logs = crate::util::test_support::capture_logs();
+
+        // 配置校验会拒绝越界的 cost，这里绕过校验直接构造
+        let mut settings = default_settings();
+        settings.password.hash_cost = 40;
+        let service = AuthService::new(
+            InMemoryUserRepository::default(),
+            Arc::new(InMemoryRefreshTokenStore::default()),
+            &settings,
+            &settings.jwt,
+        )
+        .unwrap();
+        let err = REQUEST_ID
+            .scope(
+                "hash-req-1".into(),
+                service.register(RegisterRequest {
+                    username: "user_cost".into(),
+                    password: "secret-pass-42".into(),
+                    email: None,
+                }),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), 5000);
+
+        let output = logs.output();
+        let line = output
+            .lines()
+            .find(|line| line.contains("password hash operation failed"))
+            .expect("hash failure logged");
+        assert!(line.contains("ERROR"), "{line}");
+        assert!(line.contains("request_id=hash-req-1"), "{line}");
+        assert!(!output.contains("secret-pass-42"), "{output}");
+    }
 }
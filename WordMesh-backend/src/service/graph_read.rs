@@ -0,0 +1,64 @@
+use std::future::Future;
+
+use crate::repository::graph::GraphResult;
+
+/// 用图数据补充 Postgres 结果时的读取结果。图库关闭或查询失败时为 `Unavailable`，
+/// 调用方照常返回 Postgres 部分并标记关联不可用，而不是让整个请求失败
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GraphReadResult<T> {
+    Available(T),
+    Unavailable,
+}
+
+impl<T> GraphReadResult<T> {
+    /// 图库关闭时不发起查询；查询失败时记录告警并降级，`context` 用于区分日志来源
+    pub async fn read<F>(enabled: bool, context: &'static str, query: F) -> Self
+    where
+        F: Future<Output = GraphResult<T>>,
+    {
+        if !enabled {
+            return Self::Unavailable;
+        }
+        match query.await {
+            Ok(value) => Self::Available(value),
+            Err(err) => {
+                tracing::warn!(error = %err, context, "graph data unavailable");
+                Self::Unavailable
+            }
+        }
+    }
+
+    pub fn is_available(&self) -> bool {
+        matches!(self, Self::Available(_))
+    }
+}
+
+impl<T: Default> GraphReadResult<T> {
+    /// 拆成 (数据, 是否可用)，不可用时数据为空
+    pub fn into_parts(self) -> (T, bool) {
+        match self {
+            Self::Available(value) => (value, true),
+            Self::Unavailable => (T::default(), false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::graph::GraphRepositoryError;
+
+    #[tokio::test]
+    async fn failed_or_disabled_reads_degrade_to_unavailable() {
+        let ok = GraphReadResult::read(true, "test", async { Ok(vec![1]) }).await;
+        assert_eq!(ok, GraphReadResult::Available(vec![1]));
+
+        let failed: GraphReadResult<Vec<i64>> =
+            GraphReadResult::read(true, "test", async { Err(GraphRepositoryError::Timeout) }).await;
+        assert_eq!(failed.into_parts(), (Vec::new(), false));
+
+        let disabled: GraphReadResult<Vec<i64>> =
+            GraphReadResult::read(false, "test", async { unreachable!() }).await;
+        assert!(!disabled.is_available());
+    }
+}
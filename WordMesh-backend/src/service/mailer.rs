@@ -0,0 +1,34 @@
+use async_trait::async_trait;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum MailerError {
+    #[error("mail delivery failed: {0}")]
+    Delivery(String),
+}
+
+/// 向用户投递带令牌的邮件；实现方负责拼接链接与模板
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send_email_verification(&self, to: &str, token: &str) -> Result<(), MailerError>;
+    async fn send_password_reset(&self, to: &str, token: &str) -> Result<(), MailerError>;
+}
+
+/// 默认实现：不发送邮件，只记录日志，便于本地开发直接取用令牌
+#[derive(Debug, Default, Clone)]
+pub struct LoggingMailer;
+
+#[async_trait]
+impl Mailer for LoggingMailer {
+    async fn send_email_verification(&self, to: &str, token: &str) -> Result<(), MailerError> {
+        tracing::info!(to, "email verification requested");
+        tracing::debug!(to, token, "email verification token");
+        Ok(())
+    }
+
+    async fn send_password_reset(&self, to: &str, token: &str) -> Result<(), MailerError> {
+        tracing::info!(to, "password reset requested");
+        tracing::debug!(to, token, "password reset token");
+        Ok(())
+    }
+}
@@ -1,6 +1,16 @@
+pub mod admin;
+pub mod assoc;
 pub mod auth;
+pub mod graph_read;
+pub mod mailer;
+pub mod preferences;
 pub mod sense;
 pub mod word;
 
+pub use admin::{AdminService, UserLookup};
+pub use assoc::AssocService;
+pub use graph_read::GraphReadResult;
+pub use mailer::{LoggingMailer, Mailer};
+pub use preferences::PreferencesService;
 pub use sense::{SenseService, SenseUpdateInput};
-pub use word::{AddWordInput, SearchOptions, SenseInput, WordService};
+pub use word::{AddWordInput, AddedWord, CanonicalMatch, SearchOptions, SenseInput, WordService};
@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use tracing::instrument;
+
+use crate::repository::preferences::{PreferencesRepository, UserPreferences};
+use crate::service::word::map_preferences_error;
+use crate::util::error::AppError;
+
+pub struct PreferencesService<P>
+where
+    P: PreferencesRepository + Send + Sync + 'static,
+{
+    repository: Arc<P>,
+}
+
+impl<P> PreferencesService<P>
+where
+    P: PreferencesRepository + Send + Sync + 'static,
+{
+    pub fn new(repository: P) -> Self {
+        Self {
+            repository: Arc::new(repository),
+        }
+    }
+
+    #[instrument(skip(self))]
+    pub async fn get(&self, user_id: i64) -> Result<UserPreferences, AppError> {
+        self.repository
+            .get(user_id)
+            .await
+            .map_err(map_preferences_error)
+    }
+
+    /// 整体覆盖用户偏好，未提供的项恢复为系统默认
+    #[instrument(skip(self))]
+    pub async fn update(
+        &self,
+        user_id: i64,
+        preferences: UserPreferences,
+    ) -> Result<UserPreferences, AppError> {
+        self.repository
+            .set(user_id, preferences)
+            .await
+            .map_err(map_preferences_error)
+    }
+}
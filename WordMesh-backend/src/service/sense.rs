@@ -2,15 +2,19 @@ use std::sync::Arc;
 
 use tracing::instrument;
 
-use crate::domain::word::UserSense;
+use crate::domain::word::{UserSense, UserWord, UserWordError};
 use crate::repository::graph::GraphRepository;
-use crate::repository::word::{NewUserSense, SenseUpdate, WordRepository};
+use crate::repository::word::{
+    OwnedResource, SenseOrder, SenseReorder, SenseUpdate, WordRepository, WordRepositoryError,
+};
 use crate::service::word::{
-    SenseInput, build_new_sense_payload, map_graph_error, map_user_sense_error,
-    map_validation_error, map_word_error,
+    FieldErrors, SenseInput, build_new_sense_payload, map_graph_error, map_user_word_error,
+    map_word_error, ownership_error, validation_error,
+};
+use crate::util::error::AppError;
+use crate::util::validation::{
+    MAX_SENSES, TextLimits, validate_non_empty_text, validate_note, validate_source_url,
 };
-use crate::util::error::{AppError, BusinessError, WordError};
-use crate::util::validation::{validate_non_empty_text, validate_note};
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
@@ -19,6 +23,7 @@ pub struct SenseUpdateInput {
     pub is_primary: Option<bool>,
     pub sort_order: Option<i32>,
     pub note: Option<Option<String>>,
+    pub source_url: Option<Option<String>>,
 }
 
 #[allow(dead_code)]
@@ -29,6 +34,9 @@ where
 {
     word_repository: Arc<W>,
     graph_repository: Arc<G>,
+    limits: TextLimits,
+    max_senses: usize,
+    reveal_ownership: bool,
 }
 
 impl<W, G> SenseService<W, G>
@@ -41,9 +49,30 @@ where
         Self {
             word_repository: Arc::new(word_repository),
             graph_repository: Arc::new(graph_repository),
+            limits: TextLimits::default(),
+            max_senses: MAX_SENSES,
+            reveal_ownership: false,
         }
     }
 
+    /// 区分单词/义项不存在与属于其他用户（见 [`ownership_error`]），仅用于调试
+    pub fn with_reveal_ownership(mut self, reveal: bool) -> Self {
+        self.reveal_ownership = reveal;
+        self
+    }
+
+    /// 使用 `words` 配置中的文本长度上限替换默认值
+    pub fn with_text_limits(mut self, limits: TextLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// 使用 `words.max_senses` 替换默认的每词义项数上限
+    pub fn with_max_senses(mut self, max_senses: usize) -> Self {
+        self.max_senses = max_senses;
+        self
+    }
+
     #[allow(dead_code)]
     #[instrument(skip(self, input), fields(user_id = user_id, user_word_id = user_word_id))]
     pub async fn add_sense(
@@ -52,20 +81,31 @@ where
         user_word_id: i64,
         input: SenseInput,
     ) -> Result<UserSense, AppError> {
-        self.word_repository
-            .find_user_word(user_id, user_word_id)
+        let found = self
+            .word_repository
+            .find_user_word(user_id, user_word_id, SenseOrder::default())
             .await
-            .map_err(map_word_error)?
-            .ok_or_else(|| AppError::from(BusinessError::Word(WordError::NotInNetwork)))?;
+            .map_err(map_word_error)?;
+        let Some(aggregate) = found else {
+            return Err(self
+                .missing(user_id, OwnedResource::UserWord(user_word_id))
+                .await);
+        };
+        // 提前拒绝明显超限的请求；并发下的上限由仓储在行锁内保证
+        if aggregate.user_word.senses().len() >= self.max_senses {
+            return Err(map_user_word_error(UserWordError::SenseLimitExceeded(
+                self.max_senses,
+            )));
+        }
 
-        let new_sense = build_new_sense_payload(user_word_id, input)?;
+        let new_sense = build_new_sense_payload(user_word_id, input, &self.limits)?;
         let created = self
             .word_repository
             .add_user_sense(new_sense)
             .await
             .map_err(map_word_error)?;
 
-        if let Some(sense_id) = created.id() {
+        if let Some(sense_id) = created.id().filter(|_| self.graph_repository.is_enabled()) {
             self.graph_repository
                 .upsert_node_sense(sense_id, user_id)
                 .await
@@ -83,14 +123,71 @@ where
         sense_id: i64,
         input: SenseUpdateInput,
     ) -> Result<UserSense, AppError> {
-        let update = build_sense_update(input)?;
+        let update = build_sense_update(input, &self.limits)?;
         let updated = self
             .word_repository
             .update_user_sense(user_id, sense_id, update)
+            .await;
+        let updated = self.map_sense_error(user_id, sense_id, updated).await?;
+
+        Ok(updated)
+    }
+
+    /// 按 `sense_ids` 的顺序一次性重排单词的全部义项，返回重排后按 sort_order 排列的义项。
+    /// `sense_ids` 必须恰好是单词当前的义项，缺少、多出或重复都返回校验错误
+    #[instrument(skip(self, sense_ids), fields(user_id = user_id, user_word_id = user_word_id))]
+    pub async fn reorder_senses(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        sense_ids: Vec<i64>,
+    ) -> Result<Vec<UserSense>, AppError> {
+        validate_sense_order(&sense_ids)?;
+        let outcome = self
+            .word_repository
+            .reorder_senses(user_id, user_word_id, &sense_ids)
             .await
             .map_err(map_word_error)?;
+        match outcome {
+            SenseReorder::Reordered => {}
+            SenseReorder::WordNotFound => {
+                return Err(self
+                    .missing(user_id, OwnedResource::UserWord(user_word_id))
+                    .await);
+            }
+            SenseReorder::Mismatch => {
+                return Err(validation_error(
+                    "sense_ids",
+                    "必须恰好包含该单词当前的全部义项",
+                ));
+            }
+        }
 
-        Ok(updated)
+        let found = self
+            .word_repository
+            .find_user_word(user_id, user_word_id, SenseOrder::SortOrder)
+            .await
+            .map_err(map_word_error)?;
+        match found {
+            Some(aggregate) => Ok(aggregate.user_word.senses().to_vec()),
+            None => Err(self
+                .missing(user_id, OwnedResource::UserWord(user_word_id))
+                .await),
+        }
+    }
+
+    /// 把义项设为所属单词唯一的主义项，返回更新后的单词及其全部义项；
+    /// 对已是主义项的义项重复调用不产生改动
+    #[instrument(skip(self), fields(user_id = user_id, sense_id = sense_id))]
+    pub async fn set_primary(&self, user_id: i64, sense_id: i64) -> Result<UserWord, AppError> {
+        let persisted = self
+            .word_repository
+            .set_primary_sense(user_id, sense_id)
+            .await;
+        self.map_sense_error(user_id, sense_id, persisted).await?;
+
+        // 返回写入后重新读取的状态，而不是写入前的快照
+        self.owning_word(user_id, sense_id).await
     }
 
     #[allow(dead_code)]
@@ -99,10 +196,10 @@ where
         let removed = self
             .word_repository
             .remove_user_sense(user_id, sense_id)
-            .await
-            .map_err(map_word_error)?;
+            .await;
+        let removed = self.map_sense_error(user_id, sense_id, removed).await?;
 
-        if let Some(id) = removed.id() {
+        if let Some(id) = removed.id().filter(|_| self.graph_repository.is_enabled()) {
             self.graph_repository
                 .remove_links_for_sense(id)
                 .await
@@ -113,43 +210,114 @@ where
     }
 }
 
-fn build_sense_update(input: SenseUpdateInput) -> Result<SenseUpdate, AppError> {
-    let mut update = SenseUpdate {
-        text: None,
-        is_primary: input.is_primary,
-        sort_order: input.sort_order,
-        note: None,
-    };
+impl<W, G> SenseService<W, G>
+where
+    W: WordRepository + Send + Sync + 'static,
+    G: GraphRepository + Send + Sync + 'static,
+{
+    async fn missing(&self, user_id: i64, resource: OwnedResource) -> AppError {
+        ownership_error(
+            self.word_repository.as_ref(),
+            self.reveal_ownership,
+            user_id,
+            resource,
+        )
+        .await
+    }
 
-    if let Some(text) = input.text {
-        let value =
-            validate_non_empty_text(text).map_err(|err| map_validation_error("sense.text", err))?;
-        update.text = Some(value);
+    /// 义项所属的单词，包含全部义项
+    async fn owning_word(&self, user_id: i64, sense_id: i64) -> Result<UserWord, AppError> {
+        let found = self
+            .word_repository
+            .find_user_word_by_sense(user_id, sense_id, SenseOrder::SortOrder)
+            .await
+            .map_err(map_word_error)?;
+        match found {
+            Some(aggregate) => Ok(aggregate.user_word),
+            None => Err(self.missing(user_id, OwnedResource::Sense(sense_id)).await),
+        }
     }
 
-    if let Some(note_option) = input.note {
-        let value =
-            validate_note(note_option).map_err(|err| map_validation_error("sense.note", err))?;
-        update.note = Some(value);
+    /// 仓储对不属于该用户的义项返回 RowNotFound，按归属错误处理
+    async fn map_sense_error<T>(
+        &self,
+        user_id: i64,
+        sense_id: i64,
+        result: Result<T, WordRepositoryError>,
+    ) -> Result<T, AppError> {
+        match result {
+            Ok(value) => Ok(value),
+            Err(WordRepositoryError::Database(sqlx::Error::RowNotFound)) => {
+                Err(self.missing(user_id, OwnedResource::Sense(sense_id)).await)
+            }
+            Err(err) => Err(map_word_error(err)),
+        }
     }
+}
 
-    Ok(update)
+fn validate_sense_order(sense_ids: &[i64]) -> Result<(), AppError> {
+    if sense_ids.is_empty() {
+        return Err(validation_error("sense_ids", "不能为空"));
+    }
+    let mut seen = std::collections::HashSet::with_capacity(sense_ids.len());
+    if let Some(duplicate) = sense_ids.iter().find(|id| !seen.insert(**id)) {
+        return Err(validation_error(
+            "sense_ids",
+            format!("义项 {duplicate} 重复出现"),
+        ));
+    }
+    Ok(())
+}
+
+fn build_sense_update(
+    input: SenseUpdateInput,
+    limits: &TextLimits,
+) -> Result<SenseUpdate, AppError> {
+    let mut errors = FieldErrors::default();
+    let text = input.text.map(|text| {
+        errors.check(
+            "sense.text",
+            validate_non_empty_text(text, limits.sense_text, limits.text_bytes),
+        )
+    });
+    let note = input.note.map(|note| {
+        errors.check(
+            "sense.note",
+            validate_note(note, limits.sense_note, limits.note_bytes),
+        )
+    });
+    let source_url = input
+        .source_url
+        .map(|url| errors.check("sense.source_url", validate_source_url(url)));
+
+    if !errors.is_empty() {
+        return Err(errors.into_error());
+    }
+
+    Ok(SenseUpdate {
+        text: text.flatten(),
+        is_primary: input.is_primary,
+        sort_order: input.sort_order,
+        note: note.flatten(),
+        source_url: source_url.flatten(),
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::word::{CanonicalKey, UserWord, UserWordError};
+    use crate::domain::word::{CanonicalKey, UserWord};
     use crate::repository::graph::{
         GraphRepository, GraphResult, SenseLinkFilter, SenseWordLinkKind, WordLinkFilter,
         WordLinkKind,
     };
     use crate::repository::word::{
-        SearchParams, SearchScope, UpsertUserWord, UserWordAggregate, WordRecord, WordRepository,
+        NewUserSense, SearchParams, UpsertUserWord, UserWordAggregate, WordRecord, WordRepository,
         WordRepositoryError,
     };
+    use crate::util::error::{BusinessError, WordError};
     use async_trait::async_trait;
-    use chrono::{DateTime, Utc};
+    use chrono::Utc;
 
     struct StubWordRepository {
         user_word: Option<UserWordAggregate>,
@@ -166,6 +334,7 @@ mod tests {
                 },
                 user_word: UserWord::from_parts(None, 1, 10, vec![], None, vec![], Utc::now())
                     .unwrap(),
+                has_more_senses: false,
             };
             Self {
                 user_word: Some(aggregate),
@@ -186,7 +355,17 @@ mod tests {
         async fn upsert_user_word(
             &self,
             _payload: UpsertUserWord,
-        ) -> Result<UserWordAggregate, WordRepositoryError> {
+        ) -> Result<crate::repository::word::UpsertedUserWord, WordRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn import_user_words(
+            &self,
+            _items: Vec<crate::repository::word::ImportUserWord>,
+        ) -> Result<
+            Vec<Result<crate::repository::word::ImportedUserWord, WordRepositoryError>>,
+            WordRepositoryError,
+        > {
             unimplemented!()
         }
 
@@ -194,10 +373,19 @@ mod tests {
             &self,
             _user_id: i64,
             _user_word_id: i64,
+            _sense_order: SenseOrder,
         ) -> Result<Option<UserWordAggregate>, WordRepositoryError> {
             Ok(self.user_word.clone())
         }
 
+        async fn find_user_word_by_canonical(
+            &self,
+            _user_id: i64,
+            _canonical: &CanonicalKey,
+        ) -> Result<Option<UserWordAggregate>, WordRepositoryError> {
+            Ok(None)
+        }
+
         async fn remove_user_word(
             &self,
             _user_id: i64,
@@ -210,7 +398,7 @@ mod tests {
             &self,
             _sense: NewUserSense,
         ) -> Result<UserSense, WordRepositoryError> {
-            let sense = UserSense::new("meaning", true, 0, None).unwrap();
+            let sense = UserSense::new("meaning", true, 0, None, &TextLimits::default()).unwrap();
             Ok(sense)
         }
 
@@ -220,8 +408,9 @@ mod tests {
             _sense_id: i64,
             _update: SenseUpdate,
         ) -> Result<UserSense, WordRepositoryError> {
-            let mut sense = UserSense::new("meaning", true, 0, None).unwrap();
-            sense.set_text("updated").unwrap();
+            let mut sense =
+                UserSense::new("meaning", true, 0, None, &TextLimits::default()).unwrap();
+            sense.set_text("updated", &TextLimits::default()).unwrap();
             Ok(sense)
         }
 
@@ -235,12 +424,216 @@ mod tests {
             ))
         }
 
+        async fn set_primary_sense(
+            &self,
+            _user_id: i64,
+            _sense_id: i64,
+        ) -> Result<UserSense, WordRepositoryError> {
+            Err(WordRepositoryError::Database(sqlx::Error::RowNotFound))
+        }
+
         async fn search(
             &self,
             _params: SearchParams,
+        ) -> Result<
+            Vec<(
+                UserWordAggregate,
+                Option<crate::repository::word::SearchMatch>,
+            )>,
+            WordRepositoryError,
+        > {
+            Ok(vec![])
+        }
+
+        async fn count_search(&self, _params: &SearchParams) -> Result<i64, WordRepositoryError> {
+            Ok(0)
+        }
+
+        async fn search_word_ids(
+            &self,
+            _params: &SearchParams,
+        ) -> Result<Vec<i64>, WordRepositoryError> {
+            Ok(vec![])
+        }
+
+        async fn find_sense_word_id(
+            &self,
+            _user_id: i64,
+            _sense_id: i64,
+        ) -> Result<Option<i64>, WordRepositoryError> {
+            Ok(None)
+        }
+
+        async fn find_user_word_by_sense(
+            &self,
+            _user_id: i64,
+            _sense_id: i64,
+            _sense_order: SenseOrder,
+        ) -> Result<Option<UserWordAggregate>, WordRepositoryError> {
+            Ok(self.user_word.clone())
+        }
+
+        async fn find_owner(
+            &self,
+            _resource: crate::repository::word::OwnedResource,
+        ) -> Result<Option<i64>, WordRepositoryError> {
+            Ok(None)
+        }
+
+        async fn count_owned_user_words(
+            &self,
+            _user_id: i64,
+            _user_word_ids: &[i64],
+        ) -> Result<i64, WordRepositoryError> {
+            Ok(0)
+        }
+
+        async fn existing_canonical_keys(
+            &self,
+            _user_id: i64,
+            _keys: &[CanonicalKey],
+        ) -> Result<Vec<CanonicalKey>, WordRepositoryError> {
+            Ok(Vec::new())
+        }
+
+        async fn find_near_duplicate_words(
+            &self,
+            _user_id: i64,
+            _threshold: f32,
+            _limit: i64,
+        ) -> Result<Vec<crate::repository::word::NearDuplicatePair>, WordRepositoryError> {
+            Ok(Vec::new())
+        }
+
+        async fn find_words_by_ids(
+            &self,
+            _word_ids: &[i64],
+        ) -> Result<Vec<crate::repository::word::WordRecord>, WordRepositoryError> {
+            Ok(Vec::new())
+        }
+
+        async fn find_user_words_by_word_ids(
+            &self,
+            _user_id: i64,
+            _word_ids: &[i64],
         ) -> Result<Vec<UserWordAggregate>, WordRepositoryError> {
+            Ok(Vec::new())
+        }
+
+        async fn list_user_words_after(
+            &self,
+            _user_id: i64,
+            _after: Option<i64>,
+            _limit: i64,
+        ) -> Result<Vec<UserWordAggregate>, WordRepositoryError> {
+            Ok(Vec::new())
+        }
+
+        async fn find_words_in_range(
+            &self,
+            _user_id: i64,
+            _from: Option<chrono::DateTime<chrono::Utc>>,
+            _to: Option<chrono::DateTime<chrono::Utc>>,
+            _limit: i64,
+            _offset: i64,
+        ) -> Result<Vec<UserWordAggregate>, WordRepositoryError> {
+            Ok(Vec::new())
+        }
+
+        async fn find_word_ids_in_range(
+            &self,
+            _user_id: i64,
+            _from: Option<chrono::DateTime<chrono::Utc>>,
+            _to: Option<chrono::DateTime<chrono::Utc>>,
+        ) -> Result<Vec<i64>, WordRepositoryError> {
             Ok(vec![])
         }
+
+        async fn count_words_in_range(
+            &self,
+            _user_id: i64,
+            _from: Option<chrono::DateTime<chrono::Utc>>,
+            _to: Option<chrono::DateTime<chrono::Utc>>,
+        ) -> Result<i64, WordRepositoryError> {
+            Ok(0)
+        }
+
+        async fn list_changes_since(
+            &self,
+            _user_id: i64,
+            _since: chrono::DateTime<chrono::Utc>,
+            _limit: i64,
+        ) -> Result<crate::repository::word::WordChanges, WordRepositoryError> {
+            Ok(Default::default())
+        }
+
+        async fn update_tag(
+            &self,
+            _user_id: i64,
+            _user_word_ids: &[i64],
+            _tag: &str,
+            _action: crate::repository::word::TagAction,
+        ) -> Result<u64, WordRepositoryError> {
+            Ok(0)
+        }
+
+        async fn replace_tags(
+            &self,
+            _user_id: i64,
+            _user_word_id: i64,
+            _tags: &[String],
+        ) -> Result<bool, WordRepositoryError> {
+            Ok(false)
+        }
+
+        async fn update_user_word_metadata(
+            &self,
+            _user_id: i64,
+            _user_word_id: i64,
+            _tags: &[String],
+            _note: Option<&str>,
+        ) -> Result<bool, WordRepositoryError> {
+            Ok(false)
+        }
+
+        async fn repoint_user_word(
+            &self,
+            _user_id: i64,
+            _user_word_id: i64,
+            _word_id: i64,
+        ) -> Result<crate::repository::word::WordRepoint, WordRepositoryError> {
+            Ok(crate::repository::word::WordRepoint::WordNotFound)
+        }
+
+        async fn pending_word_repoint(
+            &self,
+            _user_id: i64,
+            _user_word_id: i64,
+        ) -> Result<Option<i64>, WordRepositoryError> {
+            Ok(None)
+        }
+
+        async fn complete_word_repoint(
+            &self,
+            _user_id: i64,
+            _user_word_id: i64,
+            _previous_word_id: i64,
+        ) -> Result<(), WordRepositoryError> {
+            Ok(())
+        }
+
+        async fn reorder_senses(
+            &self,
+            _user_id: i64,
+            _user_word_id: i64,
+            _sense_ids: &[i64],
+        ) -> Result<crate::repository::word::SenseReorder, WordRepositoryError> {
+            Ok(crate::repository::word::SenseReorder::WordNotFound)
+        }
+
+        async fn repair_primaries(&self, _user_id: i64) -> Result<u64, WordRepositoryError> {
+            Ok(0)
+        }
     }
 
     struct StubGraphRepository;
@@ -264,8 +657,20 @@ mod tests {
             _word_a_id: i64,
             _word_b_id: i64,
             _kind: WordLinkKind,
-        ) -> GraphResult<()> {
-            Ok(())
+        ) -> GraphResult<u64> {
+            Ok(0)
+        }
+
+        async fn update_word_link(
+            &self,
+            _user_id: i64,
+            _word_a_id: i64,
+            _word_b_id: i64,
+            _old_kind: WordLinkKind,
+            _new_kind: Option<WordLinkKind>,
+            _note: Option<Option<String>>,
+        ) -> GraphResult<crate::repository::graph::WordLinkRecord> {
+            unimplemented!()
         }
 
         async fn list_word_links(
@@ -275,6 +680,24 @@ mod tests {
             Ok(vec![])
         }
 
+        async fn get_word_links_between(
+            &self,
+            _user_id: i64,
+            _word_a_id: i64,
+            _word_b_id: i64,
+        ) -> GraphResult<Vec<crate::repository::graph::WordLinkRecord>> {
+            Ok(vec![])
+        }
+
+        async fn list_word_links_from_words(
+            &self,
+            _user_id: i64,
+            _word_ids: &[i64],
+            _limit: i64,
+        ) -> GraphResult<Vec<crate::repository::graph::WordLinkRecord>> {
+            Ok(vec![])
+        }
+
         async fn create_sense_word_link(
             &self,
             _user_id: i64,
@@ -304,10 +727,66 @@ mod tests {
             Ok(vec![])
         }
 
+        async fn list_links_for_senses(
+            &self,
+            _user_id: i64,
+            _sense_ids: &[i64],
+            _limit: i64,
+        ) -> GraphResult<Vec<crate::repository::graph::SenseWordLinkRecord>> {
+            Ok(vec![])
+        }
+
         async fn remove_links_for_sense(&self, _sense_id: i64) -> GraphResult<()> {
             Ok(())
         }
 
+        async fn neighbors(
+            &self,
+            _query: crate::repository::graph::NeighborQuery,
+        ) -> GraphResult<crate::repository::graph::Neighborhood> {
+            Ok(Default::default())
+        }
+
+        async fn word_degrees(
+            &self,
+            _user_id: i64,
+            _word_ids: &[i64],
+        ) -> crate::repository::graph::GraphResult<std::collections::HashMap<i64, i64>> {
+            Ok(Default::default())
+        }
+
+        async fn linked_word_ids(
+            &self,
+            _user_id: i64,
+            _word_ids: &[i64],
+        ) -> crate::repository::graph::GraphResult<std::collections::HashSet<i64>> {
+            Ok(Default::default())
+        }
+
+        async fn total_link_count(
+            &self,
+            _user_id: i64,
+        ) -> crate::repository::graph::GraphResult<crate::repository::graph::LinkCounts> {
+            Ok(Default::default())
+        }
+
+        async fn link_stats_by_kind(
+            &self,
+            _user_id: i64,
+        ) -> crate::repository::graph::GraphResult<crate::repository::graph::LinkKindStats>
+        {
+            Ok(Default::default())
+        }
+
+        async fn repoint_word_links(
+            &self,
+            _user_id: i64,
+            _from_word_id: i64,
+            _to_word_id: i64,
+        ) -> GraphResult<()> {
+            Ok(())
+        }
+
         async fn upsert_node_word(&self, _word_id: i64) -> GraphResult<()> {
             Ok(())
         }
@@ -332,6 +811,7 @@ mod tests {
                     is_primary: true,
                     sort_order: 0,
                     note: None,
+                    source_url: None,
                 },
             )
             .await
@@ -339,6 +819,57 @@ mod tests {
         assert_eq!(sense.text(), "meaning");
     }
 
+    #[tokio::test]
+    async fn add_sense_stops_at_the_sense_limit() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let repository = InMemoryWordRepository::default();
+        let user_word_id = repository
+            .upsert_user_word(UpsertUserWord {
+                user_id: 1,
+                word_text: "set".into(),
+                canonical_key: CanonicalKey::new("set").unwrap(),
+                tags: vec![],
+                note: None,
+            })
+            .await
+            .unwrap()
+            .aggregate
+            .user_word
+            .id
+            .unwrap();
+        let service = SenseService::new(repository.clone(), InMemoryGraphRepository::default())
+            .with_max_senses(3);
+        let input = |index: i32| SenseInput {
+            text: format!("meaning {index}"),
+            is_primary: false,
+            sort_order: index,
+            note: None,
+            source_url: None,
+        };
+
+        for index in 0..3 {
+            service
+                .add_sense(1, user_word_id, input(index))
+                .await
+                .unwrap();
+        }
+        let err = service
+            .add_sense(1, user_word_id, input(3))
+            .await
+            .unwrap_err();
+        let AppError::BusinessError(BusinessError::Validation(fields)) = err else {
+            panic!("expected validation error, got {err:?}");
+        };
+        assert_eq!(fields[0].field, "senses");
+        let stored = repository
+            .find_user_word(1, user_word_id, SenseOrder::SortOrder)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.user_word.senses().len(), 3);
+    }
+
     #[tokio::test]
     async fn remove_sense_maps_error() {
         let service = SenseService::new(
@@ -351,4 +882,225 @@ mod tests {
             AppError::BusinessError(BusinessError::Validation(_))
         ));
     }
+
+    #[tokio::test]
+    async fn other_users_senses_are_indistinguishable_unless_revealed() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let repository = InMemoryWordRepository::default();
+        let upserted = repository
+            .upsert_user_word(UpsertUserWord {
+                user_id: 1,
+                word_text: "lantern".into(),
+                canonical_key: CanonicalKey::new("lantern").unwrap(),
+                tags: vec![],
+                note: None,
+            })
+            .await
+            .unwrap();
+        let user_word_id = upserted.aggregate.user_word.id.unwrap();
+        let sense_id = repository
+            .add_user_sense(NewUserSense {
+                user_word_id,
+                text: "a portable light".into(),
+                is_primary: true,
+                sort_order: 0,
+                note: None,
+                source_url: None,
+            })
+            .await
+            .unwrap()
+            .id()
+            .unwrap();
+        let input = || SenseInput {
+            text: "another meaning".into(),
+            is_primary: false,
+            sort_order: 1,
+            note: None,
+            source_url: None,
+        };
+        let update = || SenseUpdateInput {
+            text: Some("changed".into()),
+            is_primary: None,
+            sort_order: None,
+            note: None,
+            source_url: None,
+        };
+        let code = |err: AppError| match err {
+            AppError::BusinessError(BusinessError::Word(WordError::NotInNetwork)) => 4202,
+            AppError::BusinessError(BusinessError::Word(WordError::NotOwned)) => 4206,
+            other => panic!("unexpected error: {other:?}"),
+        };
+
+        let safe = SenseService::new(repository.clone(), InMemoryGraphRepository::default());
+        let debug = SenseService::new(repository, InMemoryGraphRepository::default())
+            .with_reveal_ownership(true);
+        for (service, owned_code) in [(&safe, 4202), (&debug, 4206)] {
+            let add = service.add_sense(2, user_word_id, input()).await;
+            assert_eq!(code(add.unwrap_err()), owned_code);
+            let updated = service.update_sense(2, sense_id, update()).await;
+            assert_eq!(code(updated.unwrap_err()), owned_code);
+            let removed = service.remove_sense(2, sense_id).await;
+            assert_eq!(code(removed.unwrap_err()), owned_code);
+            // 确实不存在的 id 在两种模式下都是 NotInNetwork
+            let missing = service.remove_sense(2, sense_id + 100).await;
+            assert_eq!(code(missing.unwrap_err()), 4202);
+        }
+        assert!(debug.remove_sense(1, sense_id).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn reorder_senses_rewrites_the_whole_order() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let repository = InMemoryWordRepository::default();
+        let user_word_id = repository
+            .upsert_user_word(UpsertUserWord {
+                user_id: 1,
+                word_text: "bank".into(),
+                canonical_key: CanonicalKey::new("bank").unwrap(),
+                tags: vec![],
+                note: None,
+            })
+            .await
+            .unwrap()
+            .aggregate
+            .user_word
+            .id
+            .unwrap();
+        let mut ids = Vec::new();
+        for (sort_order, text) in ["river edge", "institution", "reserve"].iter().enumerate() {
+            let sense = repository
+                .add_user_sense(NewUserSense {
+                    user_word_id,
+                    text: text.to_string(),
+                    is_primary: false,
+                    sort_order: sort_order as i32,
+                    note: None,
+                    source_url: None,
+                })
+                .await
+                .unwrap();
+            ids.push(sense.id().unwrap());
+        }
+        let service = SenseService::new(repository.clone(), InMemoryGraphRepository::default());
+
+        let reordered = service
+            .reorder_senses(1, user_word_id, vec![ids[2], ids[0], ids[1]])
+            .await
+            .unwrap();
+        let texts: Vec<&str> = reordered.iter().map(UserSense::text).collect();
+        assert_eq!(texts, ["reserve", "river edge", "institution"]);
+        let stored = repository
+            .find_user_word(1, user_word_id, SenseOrder::SortOrder)
+            .await
+            .unwrap()
+            .unwrap();
+        let orders: Vec<(&str, i32)> = stored
+            .user_word
+            .senses()
+            .iter()
+            .map(|sense| (sense.text(), sense.sort_order))
+            .collect();
+        assert_eq!(
+            orders,
+            [("reserve", 0), ("river edge", 1), ("institution", 2)]
+        );
+
+        // 缺少、多出或重复的 id 都整体拒绝，顺序保持不变
+        for invalid in [
+            vec![ids[0], ids[1]],
+            vec![ids[0], ids[1], ids[2], ids[2] + 100],
+            vec![ids[0], ids[0], ids[1]],
+            vec![],
+        ] {
+            let err = service
+                .reorder_senses(1, user_word_id, invalid.clone())
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, AppError::BusinessError(BusinessError::Validation(_))),
+                "{invalid:?}"
+            );
+        }
+        let unchanged = repository
+            .find_user_word(1, user_word_id, SenseOrder::SortOrder)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(unchanged.user_word.senses()[0].text(), "reserve");
+
+        let err = service
+            .reorder_senses(2, user_word_id, vec![ids[0], ids[1], ids[2]])
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::BusinessError(BusinessError::Word(WordError::NotInNetwork))
+        ));
+    }
+
+    #[test]
+    fn build_sense_update_reports_all_field_errors() {
+        let err = build_sense_update(
+            SenseUpdateInput {
+                text: Some("  ".into()),
+                is_primary: None,
+                sort_order: None,
+                note: Some(Some(" ".into())),
+                source_url: Some(Some("www.example.com".into())),
+            },
+            &TextLimits::default(),
+        )
+        .unwrap_err();
+
+        let AppError::BusinessError(BusinessError::Validation(fields)) = err else {
+            panic!("expected validation error");
+        };
+        assert_eq!(fields.len(), 3);
+        assert_eq!(fields[0].field, "sense.text");
+        assert_eq!(fields[1].field, "sense.note");
+        assert_eq!(fields[2].field, "sense.source_url");
+    }
+
+    #[test]
+    fn build_sense_update_uses_configured_limits() {
+        let limits = TextLimits {
+            sense_text: 1024,
+            sense_note: 16,
+            word_note: 16,
+            ..TextLimits::default()
+        };
+        let long_text = "x".repeat(1000);
+
+        let update = build_sense_update(
+            SenseUpdateInput {
+                text: Some(long_text.clone()),
+                is_primary: None,
+                sort_order: None,
+                note: None,
+                source_url: None,
+            },
+            &limits,
+        )
+        .unwrap();
+        assert_eq!(update.text.as_deref(), Some(long_text.as_str()));
+
+        let err = build_sense_update(
+            SenseUpdateInput {
+                text: None,
+                is_primary: None,
+                sort_order: None,
+                note: Some(Some("n".repeat(17))),
+                source_url: None,
+            },
+            &limits,
+        )
+        .unwrap_err();
+        let AppError::BusinessError(BusinessError::Validation(fields)) = err else {
+            panic!("expected validation error");
+        };
+        assert_eq!(fields[0].field, "sense.note");
+        assert!(fields[0].message.contains("16"), "{}", fields[0].message);
+    }
 }
@@ -1,21 +1,83 @@
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 
 use tracing::instrument;
 
 use crate::domain::word::{
     CanonicalKey, CanonicalKeyError, UserSense, UserSenseError, UserWordError,
 };
-use crate::repository::graph::{GraphRepository, GraphRepositoryError, WordLinkFilter};
+use crate::repository::graph::{
+    GraphRepository, GraphRepositoryError, SenseWordLinkRecord, WordLinkFilter, WordLinkKind,
+    WordLinkRecord,
+};
+use crate::repository::preferences::{
+    PreferencesRepository, PreferencesRepositoryError, UserPreferences,
+};
+use crate::repository::rate_limit::RateLimitStore;
 use crate::repository::word::{
-    NewUserSense, SearchParams, SearchScope, UpsertUserWord, UserWordAggregate, WordRepository,
-    WordRepositoryError,
+    EmptyQueryBehavior, ImportSense, ImportUserWord, ImportedUserWord, NearDuplicatePair,
+    NewUserSense, OwnedResource, SearchCursor, SearchMatch, SearchParams, SearchScope, SearchSort,
+    SenseOrder, SyncCursor, TagAction, UpsertUserWord, UpsertedUserWord, UserWordAggregate,
+    WordChanges, WordRecord, WordRepoint, WordRepository, WordRepositoryError,
+};
+use crate::service::graph_read::GraphReadResult;
+use crate::util::canonical::{CanonicalMode, normalize_nfc};
+use crate::util::error::{
+    AppError, BusinessError, ExternalError, InternalError, LinkError, ValidationField, WordError,
+    is_statement_timeout,
 };
-use crate::util::error::{AppError, BusinessError, LinkError, ValidationField, WordError};
 use crate::util::validation::{
-    MAX_NOTE_LENGTH, MAX_SENSE_NOTE_LENGTH, MAX_SENSE_TEXT_LENGTH, MAX_TAGS, ValidationError,
-    normalize_tags, validate_non_empty_text, validate_note,
+    MAX_TAGS, TagRules, TextLimits, ValidationError, normalize_tags, validate_non_empty_text,
+    validate_note, validate_source_url,
 };
 
+/// 搜索单页上限
+pub const MAX_SEARCH_LIMIT: i64 = 100;
+
+/// 单次批量打标签最多涉及的单词数
+pub const MAX_BULK_TAG_WORDS: usize = 100;
+
+/// 单次批量存在性检查最多的文本数
+pub const MAX_EXISTS_BATCH_TEXTS: usize = 200;
+
+/// 单次批量加词最多的单词数
+pub const MAX_BATCH_ADD_WORDS: usize = 200;
+
+/// 单词详情中内嵌的义项关联总数上限
+pub const MAX_INLINED_SENSE_LINKS: i64 = 500;
+
+/// 近似重复建议的相似度阈值：未指定时的默认值与允许的最小值（最大为 1.0）
+pub const DEFAULT_DUPLICATE_THRESHOLD: f32 = 0.4;
+pub const MIN_DUPLICATE_THRESHOLD: f32 = 0.2;
+
+/// 近似重复建议单次返回的单词对上限与默认值
+pub const MAX_DUPLICATE_PAIRS: u32 = 100;
+pub const DEFAULT_DUPLICATE_PAIRS: u32 = 20;
+
+/// 关联单词列表单页上限
+pub const MAX_LINKED_WORDS: i64 = 100;
+
+/// 单词导出中每类关联的条数上限
+pub const MAX_EXPORTED_LINKS: i64 = 500;
+
+/// 全量同步单页的单词数：未指定时的默认值与上限
+pub const DEFAULT_SYNC_PAGE_SIZE: u32 = 100;
+pub const MAX_SYNC_PAGE_SIZE: u32 = 500;
+
+/// 全量同步单页中每类关联的条数上限
+pub const MAX_SYNC_LINKS_PER_PAGE: i64 = 5000;
+
+/// 增量同步单次返回的修改与删除各自的条数上限
+pub const MAX_DELTA_CHANGES: i64 = 1000;
+
+/// 整网导出与按关联过滤时每次图查询覆盖的单词数
+const EXPORT_LINK_BATCH_WORDS: usize = 500;
+
+/// 删除单词时分页清理图关联的页大小与最大轮数
+const LINK_CLEANUP_PAGE_SIZE: i64 = 100;
+const MAX_LINK_CLEANUP_PASSES: usize = 1000;
+
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct AddWordInput {
@@ -25,6 +87,22 @@ pub struct AddWordInput {
     pub first_sense: Option<SenseInput>,
 }
 
+/// 批量加词的一项：`senses` 是 `first_sense` 之外要一并写入的义项，用于导回导出文档
+#[derive(Debug, Clone)]
+pub struct BatchWordInput {
+    pub word: AddWordInput,
+    pub senses: Vec<SenseInput>,
+}
+
+impl From<AddWordInput> for BatchWordInput {
+    fn from(word: AddWordInput) -> Self {
+        Self {
+            word,
+            senses: Vec::new(),
+        }
+    }
+}
+
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct SenseInput {
@@ -32,13 +110,18 @@ pub struct SenseInput {
     pub is_primary: bool,
     pub sort_order: i32,
     pub note: Option<String>,
+    pub source_url: Option<String>,
 }
 
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct SearchOptions {
     pub query: String,
-    pub scope: SearchScope,
+    /// 为空时回退到用户偏好中的默认范围，再回退到 `SearchScope::Both`
+    pub scope: Option<SearchScope>,
+    pub sort: Option<SearchSort>,
+    /// 只返回同时带有全部这些标签的单词，按标签规则规范化；空表示不过滤
+    pub tags: Vec<String>,
     pub limit: i64,
     pub offset: i64,
 }
@@ -47,13 +130,157 @@ impl Default for SearchOptions {
     fn default() -> Self {
         Self {
             query: String::new(),
-            scope: SearchScope::Both,
+            scope: None,
+            sort: None,
+            tags: Vec::new(),
             limit: 20,
             offset: 0,
         }
     }
 }
 
+/// `add_to_my_network` 的结果；`created` 为 false 表示已存在并被合并更新
+#[derive(Debug, Clone)]
+pub struct AddedWord {
+    pub aggregate: UserWordAggregate,
+    pub created: bool,
+    /// 输入按 canonical_key 归并到了用户已有的单词，便于前端提示用户
+    pub canonical_match: Option<CanonicalMatch>,
+}
+
+/// 单词元数据的修改；外层 `None` 表示不修改该字段，`note: Some(None)` 清空备注
+#[derive(Debug, Clone, Default)]
+pub struct WordMetadataUpdate {
+    pub tags: Option<Vec<String>>,
+    pub note: Option<Option<String>>,
+}
+
+/// 批量加词中一项的结果，`index` 为该项在请求数组中的下标；
+/// `deduplicated_senses` 为因单词已有同文本义项而跳过的义项数
+#[derive(Debug)]
+pub struct BatchAddItem {
+    pub index: usize,
+    pub result: Result<AddedWord, AppError>,
+    pub deduplicated_senses: u32,
+}
+
+/// 校验并规范化后的加词输入
+struct ValidatedWord {
+    canonical: CanonicalKey,
+    text: String,
+    tags: Vec<String>,
+    note: Option<String>,
+    first_sense: Option<SenseInput>,
+}
+
+/// 归并命中的已有单词
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanonicalMatch {
+    /// 已有单词的显示文本，可能与本次输入的大小写、空白或连字符不同
+    pub existing_text: String,
+}
+
+/// 批量存在性检查中单条输入的结果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordExistence {
+    pub text: String,
+    pub canonical_key: CanonicalKey,
+    /// 该单词是否已在用户网络中
+    pub exists: bool,
+}
+
+/// 义项的一条关联及其目标单词
+#[derive(Debug, Clone)]
+pub struct SenseLinkTarget {
+    pub link: SenseWordLinkRecord,
+    pub target: WordRecord,
+}
+
+#[derive(Debug, Clone)]
+pub struct SenseWithLinks {
+    pub sense: UserSense,
+    pub links: Vec<SenseLinkTarget>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WordSenses {
+    pub word: WordRecord,
+    pub user_word_id: i64,
+    pub senses: Vec<SenseWithLinks>,
+    /// 图库关闭或查询失败时为 false（见 [`GraphReadResult`]），此时所有义项的 links 为空
+    pub links_available: bool,
+}
+
+/// 与某个单词相连的单词及连接它们的关联
+#[derive(Debug, Clone)]
+pub struct LinkedWord {
+    pub link: WordLinkRecord,
+    pub word: UserWordAggregate,
+}
+
+/// 单个单词的导出文档：单词与全部义项、与它相连的单词关联、其义项发出的义项关联，
+/// 以及这些关联另一端单词的文本，便于脱离网络单独分享
+#[derive(Debug, Clone)]
+pub struct WordExport {
+    pub exported_at: chrono::DateTime<chrono::Utc>,
+    pub word: UserWordAggregate,
+    pub word_links: Vec<WordLinkRecord>,
+    pub sense_links: Vec<SenseWordLinkRecord>,
+    pub related_words: Vec<WordRecord>,
+    /// 图库关闭或查询失败时为 false，此时两类关联均为空
+    pub links_available: bool,
+    /// 某类关联达到导出上限时为 true
+    pub links_truncated: bool,
+}
+
+/// 用户整个网络的导出文档，单词按 user_word_id 升序
+#[derive(Debug, Clone)]
+pub struct NetworkExport {
+    pub exported_at: chrono::DateTime<chrono::Utc>,
+    pub words: Vec<UserWordAggregate>,
+    pub word_links: Vec<WordLinkRecord>,
+    pub sense_links: Vec<SenseWordLinkRecord>,
+    /// 图库关闭或查询失败时为 false，此时两类关联均为空
+    pub links_available: bool,
+    /// 某一批单词的关联达到上限时为 true
+    pub links_truncated: bool,
+}
+
+/// 全量同步的一页：按 user_word_id 升序的单词（含全部义项），
+/// 以及以这些单词为归属的单词关联和义项关联
+#[derive(Debug, Clone)]
+pub struct SyncPage {
+    pub server_time: chrono::DateTime<chrono::Utc>,
+    pub words: Vec<UserWordAggregate>,
+    pub word_links: Vec<WordLinkRecord>,
+    pub sense_links: Vec<SenseWordLinkRecord>,
+    /// 图库关闭或查询失败时为 false，此时两类关联均为空
+    pub links_available: bool,
+    /// 某类关联达到单页上限时为 true，客户端应改用更小的页
+    pub links_truncated: bool,
+    pub next_cursor: Option<String>,
+}
+
+/// 增量同步结果；`truncated` 为 true 时变化过多未能全部返回，客户端应改做全量同步
+#[derive(Debug, Clone)]
+pub struct SyncDelta {
+    pub server_time: chrono::DateTime<chrono::Utc>,
+    pub changes: WordChanges,
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct WordPage {
+    pub items: Vec<UserWordAggregate>,
+    pub page: u32,
+    pub page_size: u32,
+    pub total: u64,
+    /// 字母序且本页已满时，传给下一次请求的 `after` 游标
+    pub next_cursor: Option<String>,
+    /// 按 user_word_id 索引的命中信息，仅在关键字搜索时填充
+    pub matches: HashMap<i64, SearchMatch>,
+}
+
 #[allow(dead_code)]
 pub struct WordService<W, G>
 where
@@ -62,6 +289,21 @@ where
 {
     word_repository: Arc<W>,
     graph_repository: Arc<G>,
+    preferences: Option<Arc<dyn PreferencesRepository + Send + Sync>>,
+    limits: TextLimits,
+    tag_rules: TagRules,
+    canonical_mode: CanonicalMode,
+    creation_limit: Option<CreationLimit>,
+    strict_limits: bool,
+    empty_query: EmptyQueryBehavior,
+    reveal_ownership: bool,
+}
+
+/// 单词创建限流：每个用户在 `window` 内最多 `max` 次
+struct CreationLimit {
+    store: Arc<dyn RateLimitStore + Send + Sync>,
+    max: u32,
+    window: Duration,
 }
 
 impl<W, G> WordService<W, G>
@@ -74,26 +316,91 @@ where
         Self {
             word_repository: Arc::new(word_repository),
             graph_repository: Arc::new(graph_repository),
+            preferences: None,
+            limits: TextLimits::default(),
+            tag_rules: TagRules::default(),
+            canonical_mode: CanonicalMode::default(),
+            creation_limit: None,
+            strict_limits: false,
+            empty_query: EmptyQueryBehavior::default(),
+            reveal_ownership: false,
         }
     }
 
+    /// 使用 `words` 配置中的文本长度上限替换默认值
+    pub fn with_text_limits(mut self, limits: TextLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// 使用 `words` 配置中的标签字符规则替换默认的 ASCII 规则
+    pub fn with_tag_rules(mut self, rules: TagRules) -> Self {
+        self.tag_rules = rules;
+        self
+    }
+
+    /// 使用 `words.canonical_mode` 计算单词的 canonical_key；切换模式不会改写已存储的键
+    pub fn with_canonical_mode(mut self, mode: CanonicalMode) -> Self {
+        self.canonical_mode = mode;
+        self
+    }
+
+    /// 开启后越界的分页 limit 返回参数校验错误，而不是静默截断到允许范围
+    pub fn with_strict_limits(mut self, strict: bool) -> Self {
+        self.strict_limits = strict;
+        self
+    }
+
+    /// 区分单词不存在与属于其他用户（见 [`ownership_error`]），仅用于调试
+    pub fn with_reveal_ownership(mut self, reveal: bool) -> Self {
+        self.reveal_ownership = reveal;
+        self
+    }
+
+    /// 使用 `search.empty_query_behavior` 决定空关键字搜索返回全部单词还是空结果
+    pub fn with_empty_query_behavior(mut self, behavior: EmptyQueryBehavior) -> Self {
+        self.empty_query = behavior;
+        self
+    }
+
+    /// 启用用户偏好：搜索未指定 scope/sort 时使用用户保存的默认值
+    pub fn with_preferences(
+        mut self,
+        preferences: Arc<dyn PreferencesRepository + Send + Sync>,
+    ) -> Self {
+        self.preferences = Some(preferences);
+        self
+    }
+
+    /// 限制每个用户添加单词的速率；`max` 为 0 时不限流
+    pub fn with_creation_rate_limit(
+        mut self,
+        store: Arc<dyn RateLimitStore + Send + Sync>,
+        max: u32,
+        window: Duration,
+    ) -> Self {
+        self.creation_limit = (max > 0).then_some(CreationLimit { store, max, window });
+        self
+    }
+
     #[allow(dead_code)]
     #[instrument(skip(self, input), fields(user_id = user_id))]
     pub async fn add_to_my_network(
         &self,
         user_id: i64,
         input: AddWordInput,
-    ) -> Result<UserWordAggregate, AppError> {
-        let AddWordInput {
+    ) -> Result<AddedWord, AppError> {
+        let ValidatedWord {
+            canonical,
             text,
             tags,
             note,
             first_sense,
-        } = input;
+        } = self.validate_word_input(input)?;
+        self.ensure_creation_allowed(user_id).await?;
 
-        let canonical = CanonicalKey::new(&text).map_err(map_canonical_error)?;
-        let tags = normalize_tags(tags).map_err(|err| map_validation_error("tags", err))?;
-        let note = validate_note(note).map_err(|err| map_validation_error("note", err))?;
+        // 合并会以本次输入覆盖显示文本，先记下用户原先看到的那个
+        let canonical_match = self.find_canonical_match(user_id, &canonical).await?;
 
         let payload = UpsertUserWord {
             user_id,
@@ -103,16 +410,18 @@ where
             note,
         };
 
-        let aggregate = self
+        let UpsertedUserWord { aggregate, created } = self
             .word_repository
             .upsert_user_word(payload)
             .await
             .map_err(map_word_error)?;
 
-        self.graph_repository
-            .upsert_node_word(aggregate.word.id)
-            .await
-            .map_err(map_graph_error)?;
+        if self.graph_repository.is_enabled() {
+            self.graph_repository
+                .upsert_node_word(aggregate.word.id)
+                .await
+                .map_err(map_graph_error)?;
+        }
 
         let user_word_id = aggregate
             .user_word
@@ -120,13 +429,13 @@ where
             .ok_or_else(|| AppError::from(BusinessError::Word(WordError::NotInNetwork)))?;
 
         if let Some(sense_input) = first_sense {
-            let new_sense = build_new_sense_payload(user_word_id, sense_input)?;
-            let created = self
+            let new_sense = sense_input.into_new_sense(user_word_id);
+            let sense = self
                 .word_repository
                 .add_user_sense(new_sense)
                 .await
                 .map_err(map_word_error)?;
-            if let Some(sense_id) = created.id() {
+            if let Some(sense_id) = sense.id().filter(|_| self.graph_repository.is_enabled()) {
                 self.graph_repository
                     .upsert_node_sense(sense_id, user_id)
                     .await
@@ -134,11 +443,284 @@ where
             }
         }
 
-        self.word_repository
-            .find_user_word(user_id, user_word_id)
+        let aggregate = self
+            .word_repository
+            .find_user_word(user_id, user_word_id, SenseOrder::default())
+            .await
+            .map_err(map_word_error)?
+            .ok_or_else(|| AppError::from(BusinessError::Word(WordError::NotInNetwork)))?;
+
+        Ok(AddedWord {
+            aggregate,
+            created,
+            canonical_match,
+        })
+    }
+
+    /// 校验单条加词输入，一次性收集所有字段错误，而不是在第一个错误处中断
+    fn validate_word_input(&self, input: AddWordInput) -> Result<ValidatedWord, AppError> {
+        let AddWordInput {
+            text,
+            tags,
+            note,
+            first_sense,
+        } = input;
+
+        let mut errors = FieldErrors::default();
+        let canonical = match CanonicalKey::new_with(&text, self.canonical_mode) {
+            Ok(canonical) => Some(canonical),
+            Err(err) => {
+                errors.push("text", canonical_error_message(&err));
+                None
+            }
+        };
+        let tags = errors.check("tags", normalize_tags(tags, &self.tag_rules));
+        let note = errors.check(
+            "note",
+            validate_note(note, self.limits.word_note, self.limits.note_bytes),
+        );
+        let first_sense = match first_sense {
+            Some(input) => {
+                validate_sense_input("first_sense", input, &self.limits, &mut errors).map(Some)
+            }
+            None => Some(None),
+        };
+        let (Some(canonical), Some(tags), Some(note), Some(first_sense)) =
+            (canonical, tags, note, first_sense)
+        else {
+            return Err(errors.into_error());
+        };
+        Ok(ValidatedWord {
+            canonical,
+            text: normalize_nfc(&text),
+            tags,
+            note,
+            first_sense,
+        })
+    }
+
+    async fn find_canonical_match(
+        &self,
+        user_id: i64,
+        canonical: &CanonicalKey,
+    ) -> Result<Option<CanonicalMatch>, AppError> {
+        Ok(self
+            .word_repository
+            .find_user_word_by_canonical(user_id, canonical)
+            .await
+            .map_err(map_word_error)?
+            .map(|existing| CanonicalMatch {
+                existing_text: existing.word.text,
+            }))
+    }
+
+    /// 批量加词：先逐项校验，通过的项在同一个事务内写入，每项单独成败。
+    /// 只有批量本身不合法或数据库整体不可用时才返回 `Err`，单项失败记录在对应的结果里
+    #[instrument(skip(self, inputs), fields(count = inputs.len()))]
+    pub async fn add_many_to_network(
+        &self,
+        user_id: i64,
+        inputs: Vec<BatchWordInput>,
+    ) -> Result<Vec<BatchAddItem>, AppError> {
+        if inputs.is_empty() {
+            return Err(validation_error("words", "不能为空"));
+        }
+        if inputs.len() > MAX_BATCH_ADD_WORDS {
+            return Err(validation_error(
+                "words",
+                format!(
+                    "单次最多 {MAX_BATCH_ADD_WORDS} 个单词，当前 {}",
+                    inputs.len()
+                ),
+            ));
+        }
+
+        let mut results: Vec<Option<Result<AddedWord, AppError>>> =
+            inputs.iter().map(|_| None).collect();
+        let mut deduplicated = vec![0; inputs.len()];
+        let mut pending = Vec::new();
+        let mut imports = Vec::new();
+        // 同一批中归并到同一单词的后续项，提示的是前面那项的文本
+        let mut batch_texts: HashMap<CanonicalKey, String> = HashMap::new();
+        for (index, BatchWordInput { word, senses }) in inputs.into_iter().enumerate() {
+            let validated = match self.validate_word_input(word) {
+                Ok(validated) => validated,
+                Err(err) => {
+                    results[index] = Some(Err(err));
+                    continue;
+                }
+            };
+            let mut errors = FieldErrors::default();
+            let senses: Vec<SenseInput> = senses
+                .into_iter()
+                .enumerate()
+                .filter_map(|(position, sense)| {
+                    let prefix = format!("senses[{position}]");
+                    validate_sense_input(&prefix, sense, &self.limits, &mut errors)
+                })
+                .collect();
+            if !errors.is_empty() {
+                results[index] = Some(Err(errors.into_error()));
+                continue;
+            }
+            if let Err(err) = self.ensure_creation_allowed(user_id).await {
+                results[index] = Some(Err(err));
+                continue;
+            }
+            let canonical_match = match self
+                .find_canonical_match(user_id, &validated.canonical)
+                .await?
+            {
+                Some(found) => Some(found),
+                None => batch_texts
+                    .get(&validated.canonical)
+                    .map(|existing_text| CanonicalMatch {
+                        existing_text: existing_text.clone(),
+                    }),
+            };
+            batch_texts
+                .entry(validated.canonical.clone())
+                .or_insert_with(|| validated.text.clone());
+
+            let senses: Vec<SenseInput> = validated.first_sense.into_iter().chain(senses).collect();
+            let sense_texts: Vec<String> = senses.iter().map(|sense| sense.text.clone()).collect();
+            pending.push((index, canonical_match, sense_texts));
+            imports.push(ImportUserWord {
+                word: UpsertUserWord {
+                    user_id,
+                    word_text: validated.text,
+                    canonical_key: validated.canonical,
+                    tags: validated.tags,
+                    note: validated.note,
+                },
+                senses: senses
+                    .into_iter()
+                    .map(|sense| ImportSense {
+                        text: sense.text,
+                        is_primary: sense.is_primary,
+                        sort_order: sense.sort_order,
+                        note: sense.note,
+                        source_url: sense.source_url,
+                    })
+                    .collect(),
+            });
+        }
+
+        if !imports.is_empty() {
+            let outcomes = self
+                .word_repository
+                .import_user_words(imports)
+                .await
+                .map_err(map_word_error)?;
+            for ((index, canonical_match, sense_texts), outcome) in
+                pending.into_iter().zip(outcomes)
+            {
+                let result = match outcome.map_err(map_word_error) {
+                    Ok(ImportedUserWord {
+                        aggregate,
+                        created,
+                        deduplicated_senses,
+                    }) => {
+                        deduplicated[index] = deduplicated_senses;
+                        self.sync_imported_nodes(user_id, &aggregate, &sense_texts)
+                            .await
+                            .map(|()| AddedWord {
+                                aggregate,
+                                created,
+                                canonical_match,
+                            })
+                    }
+                    Err(err) => Err(err),
+                };
+                results[index] = Some(result);
+            }
+        }
+
+        Ok(results
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, result)| {
+                result.map(|result| BatchAddItem {
+                    index,
+                    result,
+                    deduplicated_senses: deduplicated[index],
+                })
+            })
+            .collect())
+    }
+
+    /// 导入后补建图节点；义项按文本找回，已存在而被跳过的义项同样会补建
+    async fn sync_imported_nodes(
+        &self,
+        user_id: i64,
+        aggregate: &UserWordAggregate,
+        sense_texts: &[String],
+    ) -> Result<(), AppError> {
+        if !self.graph_repository.is_enabled() {
+            return Ok(());
+        }
+        self.graph_repository
+            .upsert_node_word(aggregate.word.id)
+            .await
+            .map_err(map_graph_error)?;
+        // 同一批里重复的文本只对应一个义项
+        let sense_ids: Vec<i64> = aggregate
+            .user_word
+            .senses()
+            .iter()
+            .filter(|sense| sense_texts.iter().any(|text| sense.text() == text))
+            .filter_map(|sense| sense.id())
+            .collect();
+        for sense_id in sense_ids {
+            self.graph_repository
+                .upsert_node_sense(sense_id, user_id)
+                .await
+                .map_err(map_graph_error)?;
+        }
+        Ok(())
+    }
+
+    /// 读取用户网络中的单词；不在网络中时的错误见 [`ownership_error`]
+    async fn require_user_word(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        sense_order: SenseOrder,
+    ) -> Result<UserWordAggregate, AppError> {
+        match self
+            .word_repository
+            .find_user_word(user_id, user_word_id, sense_order)
             .await
             .map_err(map_word_error)?
-            .ok_or_else(|| AppError::from(BusinessError::Word(WordError::NotInNetwork)))
+        {
+            Some(aggregate) => Ok(aggregate),
+            None => Err(ownership_error(
+                self.word_repository.as_ref(),
+                self.reveal_ownership,
+                user_id,
+                OwnedResource::UserWord(user_word_id),
+            )
+            .await),
+        }
+    }
+
+    /// 校验通过后才计数，参数错误的请求不消耗配额
+    async fn ensure_creation_allowed(&self, user_id: i64) -> Result<(), AppError> {
+        let Some(limit) = &self.creation_limit else {
+            return Ok(());
+        };
+        limit
+            .store
+            .try_acquire(user_id, limit.max, limit.window)
+            .await
+            .map_err(|wait| {
+                tracing::warn!(user_id, "word creation rate limit exceeded");
+                // 向上取整，避免客户端按 0 秒立即重试
+                let retry_after = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+                AppError::from(BusinessError::Word(WordError::CreationRateLimited(
+                    retry_after.max(1),
+                )))
+            })
     }
 
     #[allow(dead_code)]
@@ -149,12 +731,24 @@ where
         user_word_id: i64,
     ) -> Result<(), AppError> {
         let aggregate = self
-            .word_repository
-            .find_user_word(user_id, user_word_id)
+            .require_user_word(user_id, user_word_id, SenseOrder::default())
+            .await?;
+
+        if self.graph_repository.is_enabled() {
+            self.remove_graph_links(user_id, &aggregate).await?;
+        }
+
+        self.word_repository
+            .remove_user_word(user_id, user_word_id)
             .await
-            .map_err(map_word_error)?
-            .ok_or_else(|| AppError::from(BusinessError::Word(WordError::NotInNetwork)))?;
+            .map_err(map_word_error)
+    }
 
+    async fn remove_graph_links(
+        &self,
+        user_id: i64,
+        aggregate: &UserWordAggregate,
+    ) -> Result<(), AppError> {
         for sense in aggregate.user_word.senses() {
             if let Some(sense_id) = sense.id() {
                 self.graph_repository
@@ -165,37 +759,63 @@ where
         }
 
         let mut offset = 0;
-        loop {
+        for _ in 0..MAX_LINK_CLEANUP_PASSES {
             let links = self
                 .graph_repository
                 .list_word_links(WordLinkFilter {
                     user_id,
                     word_id: aggregate.word.id,
                     kind: None,
-                    limit: 100,
+                    limit: LINK_CLEANUP_PAGE_SIZE,
                     offset,
                 })
                 .await
                 .map_err(map_graph_error)?;
 
             if links.is_empty() {
-                break;
+                return Ok(());
             }
 
+            let mut deleted = 0;
             for link in &links {
-                self.graph_repository
+                deleted += self
+                    .graph_repository
                     .delete_word_link(user_id, link.word_a_id, link.word_b_id, link.kind)
                     .await
                     .map_err(map_graph_error)?;
             }
 
-            offset += links.len() as i64;
+            // 整页都未删除说明列表与删除不一致，继续只会原地打转；
+            // 此时尚未删除 Postgres 中的单词，调用方可以重试
+            if deleted == 0 {
+                tracing::error!(
+                    user_id,
+                    word_id = aggregate.word.id,
+                    listed = links.len(),
+                    "word link cleanup made no progress"
+                );
+                return Err(AppError::from(InternalError::Unknown));
+            }
+
+            // 已删除的关联不会再出现在下一页；部分未能删除的跳过
+            let remaining = links.len() as i64 - deleted as i64;
+            if remaining > 0 {
+                tracing::warn!(
+                    user_id,
+                    word_id = aggregate.word.id,
+                    remaining,
+                    "word links listed but not deleted"
+                );
+            }
+            offset += remaining.max(0);
         }
 
-        self.word_repository
-            .remove_user_word(user_id, user_word_id)
-            .await
-            .map_err(map_word_error)
+        tracing::error!(
+            user_id,
+            word_id = aggregate.word.id,
+            "word link cleanup exceeded pass limit"
+        );
+        Err(AppError::from(InternalError::Unknown))
     }
 
     #[allow(dead_code)]
@@ -205,319 +825,3897 @@ where
         user_id: i64,
         options: SearchOptions,
     ) -> Result<Vec<UserWordAggregate>, AppError> {
-        let limit = options.limit.clamp(1, 100);
+        let params = self.search_params(user_id, options).await?;
+        if self.skips_query(&params) {
+            return Ok(Vec::new());
+        }
+        let hits = self.run_search(params).await?;
+        Ok(hits.into_iter().map(|(aggregate, _)| aggregate).collect())
+    }
+
+    /// 与 `search_in_my_network` 相同的搜索，附带满足条件的总数；page 由 offset/limit 推算
+    #[instrument(skip(self, options), fields(user_id = user_id))]
+    pub async fn search_page(
+        &self,
+        user_id: i64,
+        options: SearchOptions,
+    ) -> Result<WordPage, AppError> {
+        let params = self.search_params(user_id, options).await?;
+        let (limit, offset) = (params.limit, params.offset);
+        let page = (offset / limit + 1) as u32;
+        if self.skips_query(&params) {
+            return Ok(WordPage {
+                items: Vec::new(),
+                page,
+                page_size: limit as u32,
+                total: 0,
+                next_cursor: None,
+                matches: HashMap::new(),
+            });
+        }
+        let total = self
+            .word_repository
+            .count_search(&params)
+            .await
+            .map_err(map_word_error)?;
+        let mut items = Vec::new();
+        let mut matches = HashMap::new();
+        for (aggregate, search_match) in self.run_search(params).await? {
+            if let (Some(user_word_id), Some(search_match)) = (aggregate.user_word.id, search_match)
+            {
+                matches.insert(user_word_id, search_match);
+            }
+            items.push(aggregate);
+        }
+
+        Ok(WordPage {
+            items,
+            page,
+            page_size: limit as u32,
+            total: total.max(0) as u64,
+            next_cursor: None,
+            matches,
+        })
+    }
+
+    /// 只按标签过滤不算空查询
+    fn skips_query(&self, params: &SearchParams) -> bool {
+        self.empty_query == EmptyQueryBehavior::Empty
+            && params.query.trim().is_empty()
+            && params.tags.is_empty()
+    }
+
+    async fn search_params(
+        &self,
+        user_id: i64,
+        options: SearchOptions,
+    ) -> Result<SearchParams, AppError> {
+        if self.strict_limits && !(1..=MAX_SEARCH_LIMIT).contains(&options.limit) {
+            return Err(validation_error(
+                "limit",
+                format!("must be between 1 and {MAX_SEARCH_LIMIT}"),
+            ));
+        }
+        let limit = options.limit.clamp(1, MAX_SEARCH_LIMIT);
         let offset = options.offset.clamp(0, 10_000);
+        let tags = normalize_tags(options.tags, &self.tag_rules)
+            .map_err(|err| map_validation_error("tag", err))?;
 
-        let params = SearchParams {
+        // 显式参数优先，其次用户偏好，最后系统默认
+        let stored = match &self.preferences {
+            Some(preferences) if options.scope.is_none() || options.sort.is_none() => preferences
+                .get(user_id)
+                .await
+                .map_err(map_preferences_error)?,
+            _ => UserPreferences::default(),
+        };
+
+        Ok(SearchParams {
             user_id,
             query: options.query,
-            scope: options.scope,
+            scope: options.scope.or(stored.default_scope).unwrap_or_default(),
+            tags,
+            sort: options.sort.or(stored.default_sort).unwrap_or_default(),
             limit,
             offset,
-        };
+            after: None,
+            word_ids: None,
+        })
+    }
 
-        self.word_repository
-            .search(params)
+    /// `SearchSort::Degree` 需要图中的关联数，无法在 SQL 中完成：
+    /// 先取出全部候选的 word_id 批量查询关联度并排序，再只为当前页读取聚合
+    async fn run_search(
+        &self,
+        params: SearchParams,
+    ) -> Result<Vec<(UserWordAggregate, Option<SearchMatch>)>, AppError> {
+        if params.sort != SearchSort::Degree {
+            return self
+                .word_repository
+                .search(params)
+                .await
+                .map_err(map_word_error);
+        }
+        if !self.graph_repository.is_enabled() {
+            return Err(AppError::from(BusinessError::Link(
+                LinkError::GraphDisabled,
+            )));
+        }
+
+        let mut word_ids = self
+            .word_repository
+            .search_word_ids(&params)
             .await
-            .map_err(map_word_error)
+            .map_err(map_word_error)?;
+        let degrees = self
+            .graph_repository
+            .word_degrees(params.user_id, &word_ids)
+            .await
+            .map_err(map_graph_error)?;
+
+        // 稳定排序：关联度相同的单词保持字母序
+        word_ids.sort_by_key(|id| std::cmp::Reverse(degrees.get(id).copied().unwrap_or(0)));
+        let page_ids: Vec<i64> = word_ids
+            .into_iter()
+            .skip(params.offset.max(0) as usize)
+            .take(params.limit.max(0) as usize)
+            .collect();
+        if page_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut hits = self
+            .word_repository
+            .search(SearchParams {
+                sort: SearchSort::Alphabetical,
+                limit: page_ids.len() as i64,
+                offset: 0,
+                after: None,
+                word_ids: Some(page_ids.clone()),
+                ..params
+            })
+            .await
+            .map_err(map_word_error)?;
+        hits.sort_by_key(|(a, _)| page_ids.iter().position(|id| *id == a.word.id));
+        Ok(hits)
     }
-}
 
-pub(crate) fn build_new_sense_payload(
-    user_word_id: i64,
-    sense: SenseInput,
-) -> Result<NewUserSense, AppError> {
-    let SenseInput {
-        text,
-        is_primary,
-        sort_order,
-        note,
-    } = sense;
-    let text = validate_non_empty_text(text)
-        .map_err(|err| map_validation_error("first_sense.text", err))?;
-    let note = validate_note(note).map_err(|err| map_validation_error("first_sense.note", err))?;
+    #[allow(dead_code)]
+    #[instrument(skip(self), fields(user_id = user_id))]
+    pub async fn list_words_by_tag(
+        &self,
+        user_id: i64,
+        tag: String,
+        page: u32,
+        page_size: u32,
+        sort: SearchSort,
+        after: Option<String>,
+    ) -> Result<WordPage, AppError> {
+        let tags = normalize_tags(vec![tag], &self.tag_rules)
+            .map_err(|err| map_validation_error("tag", err))?;
+        let page = page.max(1);
+        let page_size = page_size.clamp(1, 100);
+        // 传入游标时按键集分页，忽略 page
+        let after = after
+            .map(|value| {
+                if sort != SearchSort::Alphabetical {
+                    return Err(validation_error(
+                        "after",
+                        "cursor paging requires alphabetical sort",
+                    ));
+                }
+                SearchCursor::decode(&value)
+                    .ok_or_else(|| validation_error("after", "invalid cursor"))
+            })
+            .transpose()?;
+        let offset = match after {
+            Some(_) => 0,
+            None => (i64::from(page - 1) * i64::from(page_size)).min(10_000),
+        };
 
-    Ok(NewUserSense {
-        user_word_id,
-        text,
-        is_primary,
-        sort_order,
-        note,
-    })
-}
+        let params = SearchParams {
+            user_id,
+            query: String::new(),
+            scope: SearchScope::default(),
+            tags,
+            sort,
+            limit: i64::from(page_size),
+            offset,
+            after,
+            word_ids: None,
+        };
 
-pub(crate) fn validation_error(field: &str, message: impl Into<String>) -> AppError {
-    AppError::from(BusinessError::Validation(vec![ValidationField {
-        field: field.into(),
-        message: message.into(),
-    }]))
-}
+        let total = self
+            .word_repository
+            .count_search(&params)
+            .await
+            .map_err(map_word_error)?;
+        let items: Vec<UserWordAggregate> = self
+            .run_search(params)
+            .await?
+            .into_iter()
+            .map(|(aggregate, _)| aggregate)
+            .collect();
+        let next_cursor = items
+            .last()
+            .filter(|_| sort == SearchSort::Alphabetical && items.len() == page_size as usize)
+            .and_then(SearchCursor::from_aggregate)
+            .map(|cursor| cursor.encode());
 
-pub(crate) fn map_validation_error(field: &str, error: ValidationError) -> AppError {
-    match error {
-        ValidationError::Blank => validation_error(field, "不能为空"),
-        ValidationError::TextTooLong(len) => validation_error(
-            field,
-            format!("文本长度不能超过 {MAX_SENSE_TEXT_LENGTH} 字符，当前 {len}"),
-        ),
-        ValidationError::NoteTooLong(len) => validation_error(
-            field,
-            format!("备注长度不能超过 {MAX_SENSE_NOTE_LENGTH} 字符，当前 {len}"),
-        ),
-        ValidationError::InvalidTag(tag) => validation_error(field, format!("无效标签: {tag}")),
-        ValidationError::TagLimitExceeded(count) => {
-            validation_error(field, format!("标签数量不能超过 {MAX_TAGS}，当前 {count}"))
-        }
+        Ok(WordPage {
+            items,
+            page,
+            page_size,
+            total: total.max(0) as u64,
+            next_cursor,
+            matches: HashMap::new(),
+        })
     }
-}
 
-pub(crate) fn map_canonical_error(err: CanonicalKeyError) -> AppError {
-    match err {
-        CanonicalKeyError::Empty => validation_error("text", "文本不能为空"),
-        CanonicalKeyError::Validation(_) => validation_error("text", "文本不合法"),
+    /// 为多个单词追加同一标签，返回实际新增标签的单词数
+    pub async fn add_tag_to_words(
+        &self,
+        user_id: i64,
+        ids: Vec<i64>,
+        tag: String,
+    ) -> Result<u64, AppError> {
+        self.update_tag_for_words(user_id, ids, tag, TagAction::Add)
+            .await
     }
-}
 
-pub(crate) fn map_user_word_error(err: UserWordError) -> AppError {
-    match err {
-        UserWordError::TagLimitExceeded(count) => {
-            validation_error("tags", format!("标签数量不能超过 {MAX_TAGS}，当前 {count}"))
-        }
-        UserWordError::InvalidTag(tag) => validation_error("tags", format!("无效标签: {tag}")),
-        UserWordError::InvalidNote => validation_error("note", "备注不能为空"),
-        UserWordError::NoteTooLong(len) => validation_error(
-            "note",
-            format!("备注长度不能超过 {MAX_NOTE_LENGTH} 字符，当前 {len}"),
-        ),
-        UserWordError::DuplicateSenseText(_) => {
-            AppError::from(BusinessError::Word(WordError::SenseDuplicate))
-        }
-        UserWordError::SenseNotFound(_) => {
-            AppError::from(BusinessError::Word(WordError::NotInNetwork))
+    /// 从多个单词移除同一标签，返回实际移除标签的单词数
+    pub async fn remove_tag_from_words(
+        &self,
+        user_id: i64,
+        ids: Vec<i64>,
+        tag: String,
+    ) -> Result<u64, AppError> {
+        self.update_tag_for_words(user_id, ids, tag, TagAction::Remove)
+            .await
+    }
+
+    #[instrument(skip(self, ids, tag), fields(user_id = user_id, count = ids.len()))]
+    pub async fn update_tag_for_words(
+        &self,
+        user_id: i64,
+        mut ids: Vec<i64>,
+        tag: String,
+        action: TagAction,
+    ) -> Result<u64, AppError> {
+        let mut errors = FieldErrors::default();
+        let tag = errors
+            .check("tag", normalize_tags(vec![tag], &self.tag_rules))
+            .and_then(|tags| tags.into_iter().next());
+        // 先按原始长度拒绝超大数组，再排序去重
+        if ids.len() > MAX_BULK_TAG_WORDS {
+            errors.push(
+                "user_word_ids",
+                format!("单次最多 {MAX_BULK_TAG_WORDS} 个单词，当前 {}", ids.len()),
+            );
+        } else {
+            ids.sort_unstable();
+            ids.dedup();
+            if ids.is_empty() {
+                errors.push("user_word_ids", "不能为空");
+            }
         }
-        UserWordError::SenseIndexOutOfBounds(_) => {
-            AppError::from(BusinessError::Word(WordError::PrimaryConflict))
+        let (Some(tag), true) = (tag, errors.is_empty()) else {
+            return Err(errors.into_error());
+        };
+
+        // 任一单词不属于当前用户则整体拒绝，不做部分更新
+        let owned = self
+            .word_repository
+            .count_owned_user_words(user_id, &ids)
+            .await
+            .map_err(map_word_error)?;
+        if owned != ids.len() as i64 {
+            return Err(AppError::from(BusinessError::Word(WordError::NotInNetwork)));
         }
-        UserWordError::Sense(err) => map_user_sense_error(err),
+
+        self.word_repository
+            .update_tag(user_id, &ids, &tag, action)
+            .await
+            .map_err(map_word_error)
     }
-}
 
-pub(crate) fn map_user_sense_error(err: UserSenseError) -> AppError {
-    match err {
-        UserSenseError::EmptyText => validation_error("sense.text", "义项文本不能为空"),
-        UserSenseError::TextTooLong(len) => validation_error(
-            "sense.text",
-            format!("义项文本长度不能超过 {MAX_SENSE_TEXT_LENGTH} 字符，当前 {len}"),
-        ),
-        UserSenseError::InvalidNote => validation_error("sense.note", "义项备注不能为空"),
-        UserSenseError::NoteTooLong(len) => validation_error(
-            "sense.note",
-            format!("义项备注长度不能超过 {MAX_SENSE_NOTE_LENGTH} 字符，当前 {len}"),
-        ),
+    /// 读取单词详情，包含全部义项
+    #[instrument(skip(self))]
+    pub async fn get_word(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+    ) -> Result<UserWordAggregate, AppError> {
+        self.require_user_word(user_id, user_word_id, SenseOrder::default())
+            .await
     }
-}
 
-pub(crate) fn map_word_error(err: WordRepositoryError) -> AppError {
-    match err {
-        WordRepositoryError::UserWord(inner) => map_user_word_error(inner),
-        WordRepositoryError::UserSense(inner) => map_user_sense_error(inner),
-        WordRepositoryError::Canonical(inner) => map_canonical_error(inner),
-        WordRepositoryError::Database(_) => {
-            AppError::from(BusinessError::Word(WordError::AlreadyExists))
+    /// 以 `tags` 整体替换单词的标签（不在列表中的原有标签被移除），返回更新后的单词
+    #[instrument(skip(self, tags), fields(user_id = user_id, count = tags.len()))]
+    pub async fn set_tags(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        tags: Vec<String>,
+    ) -> Result<UserWordAggregate, AppError> {
+        let mut aggregate = self
+            .require_user_word(user_id, user_word_id, SenseOrder::default())
+            .await?;
+        aggregate
+            .user_word
+            .update_tags(tags, &self.tag_rules)
+            .map_err(map_user_word_error)?;
+
+        let updated = self
+            .word_repository
+            .replace_tags(user_id, user_word_id, aggregate.user_word.tags())
+            .await
+            .map_err(map_word_error)?;
+        // 读取与写入之间单词被删除
+        if !updated {
+            return Err(AppError::from(BusinessError::Word(WordError::NotInNetwork)));
         }
+        Ok(aggregate)
     }
-}
 
-pub(crate) fn map_graph_error(err: GraphRepositoryError) -> AppError {
-    match err {
-        GraphRepositoryError::Business(BusinessError::Link(link_err)) => {
-            AppError::from(BusinessError::Link(link_err))
+    /// 按纠正后的文本重新计算 canonical_key，把单词连同义项、标签与图中的关联改挂到新单词上。
+    /// 用户已拥有新单词时返回 `RewriteCollision`，由客户端改为合并；键不变时原样返回。
+    /// 上次改写在图库一步失败时，重试会先补做那次图库改挂
+    #[instrument(skip(self, text), fields(user_id = user_id))]
+    pub async fn rewrite_word_text(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        text: String,
+    ) -> Result<UserWordAggregate, AppError> {
+        let canonical = CanonicalKey::new_with(&text, self.canonical_mode)
+            .map_err(|err| validation_error("text", canonical_error_message(&err)))?;
+        let aggregate = self
+            .require_user_word(user_id, user_word_id, SenseOrder::default())
+            .await?;
+        if let Some(previous_word_id) = self
+            .word_repository
+            .pending_word_repoint(user_id, user_word_id)
+            .await
+            .map_err(map_word_error)?
+        {
+            self.complete_repoint(user_id, user_word_id, previous_word_id, aggregate.word.id)
+                .await?;
         }
-        GraphRepositoryError::Business(other) => AppError::from(other),
-        GraphRepositoryError::Database(_) | GraphRepositoryError::Timeout => {
-            AppError::from(BusinessError::Link(LinkError::TargetNotFound))
+        if aggregate.word.canonical_key == canonical {
+            return Ok(aggregate);
         }
-        GraphRepositoryError::InvalidData(_) => {
-            AppError::from(BusinessError::Link(LinkError::TargetNotFound))
+        if let Some(existing) = self
+            .word_repository
+            .find_user_word_by_canonical(user_id, &canonical)
+            .await
+            .map_err(map_word_error)?
+            .and_then(|existing| existing.user_word.id)
+        {
+            return Err(AppError::from(BusinessError::Word(
+                WordError::RewriteCollision(existing),
+            )));
         }
-    }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use async_trait::async_trait;
-    use chrono::{DateTime, Utc};
+        let word = self
+            .word_repository
+            .upsert_word(&canonical, &normalize_nfc(&text))
+            .await
+            .map_err(map_word_error)?;
+        let previous_word_id = match self
+            .word_repository
+            .repoint_user_word(user_id, user_word_id, word.id)
+            .await
+            .map_err(map_word_error)?
+        {
+            WordRepoint::Moved { previous_word_id } => previous_word_id,
+            WordRepoint::WordNotFound => {
+                return Err(AppError::from(BusinessError::Word(WordError::NotInNetwork)));
+            }
+            // 检查之后被并发加入网络
+            WordRepoint::Collision(existing) => {
+                return Err(AppError::from(BusinessError::Word(
+                    WordError::RewriteCollision(existing),
+                )));
+            }
+        };
+        self.complete_repoint(user_id, user_word_id, previous_word_id, word.id)
+            .await?;
 
-    struct StubWordRepository;
+        self.require_user_word(user_id, user_word_id, SenseOrder::default())
+            .await
+    }
 
-    #[async_trait]
-    impl WordRepository for StubWordRepository {
-        async fn upsert_word(
-            &self,
-            _canonical: &CanonicalKey,
-            _text: &str,
-        ) -> Result<crate::repository::word::WordRecord, WordRepositoryError> {
-            unimplemented!()
+    /// 把图中挂在原单词上的关联与义项改挂到新单词，成功后才清除 Postgres 中的待完成标记；
+    /// 图库改挂可重复执行，失败时标记保留，下次改写时重试
+    async fn complete_repoint(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        previous_word_id: i64,
+        word_id: i64,
+    ) -> Result<(), AppError> {
+        if self.graph_repository.is_enabled() {
+            self.graph_repository
+                .upsert_node_word(word_id)
+                .await
+                .map_err(map_graph_error)?;
+            self.graph_repository
+                .repoint_word_links(user_id, previous_word_id, word_id)
+                .await
+                .map_err(map_graph_error)?;
         }
+        self.word_repository
+            .complete_word_repoint(user_id, user_word_id, previous_word_id)
+            .await
+            .map_err(map_word_error)
+    }
 
-        async fn upsert_user_word(
-            &self,
-            _payload: UpsertUserWord,
-        ) -> Result<UserWordAggregate, WordRepositoryError> {
-            Err(WordRepositoryError::UserWord(UserWordError::InvalidNote))
+    /// 修改单词的标签与备注，省略的字段保持不变；两个字段的校验错误一并返回
+    #[instrument(skip(self, update), fields(user_id = user_id))]
+    pub async fn update_word_metadata(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        update: WordMetadataUpdate,
+    ) -> Result<UserWordAggregate, AppError> {
+        let mut aggregate = self
+            .require_user_word(user_id, user_word_id, SenseOrder::default())
+            .await?;
+        let WordMetadataUpdate { tags, note } = update;
+        if tags.is_none() && note.is_none() {
+            return Ok(aggregate);
         }
 
-        async fn find_user_word(
-            &self,
-            _user_id: i64,
-            _user_word_id: i64,
-        ) -> Result<Option<UserWordAggregate>, WordRepositoryError> {
-            Ok(None)
+        let user_word = &mut aggregate.user_word;
+        let mut errors = FieldErrors::default();
+        if let Some(tags) = tags {
+            errors.merge(
+                user_word
+                    .update_tags(tags, &self.tag_rules)
+                    .map_err(map_user_word_error),
+            )?;
         }
-
-        async fn remove_user_word(
-            &self,
-            _user_id: i64,
-            _user_word_id: i64,
-        ) -> Result<(), WordRepositoryError> {
-            Ok(())
+        if let Some(note) = note {
+            errors.merge(
+                user_word
+                    .update_note(note, &self.limits)
+                    .map_err(map_user_word_error),
+            )?;
         }
-
-        async fn add_user_sense(
-            &self,
-            _sense: NewUserSense,
-        ) -> Result<UserSense, WordRepositoryError> {
-            unimplemented!()
+        if !errors.is_empty() {
+            return Err(errors.into_error());
         }
 
-        async fn update_user_sense(
-            &self,
-            _user_id: i64,
-            _sense_id: i64,
-            _update: crate::repository::word::SenseUpdate,
-        ) -> Result<UserSense, WordRepositoryError> {
-            unimplemented!()
+        let updated = self
+            .word_repository
+            .update_user_word_metadata(user_id, user_word_id, user_word.tags(), user_word.note())
+            .await
+            .map_err(map_word_error)?;
+        // 读取与写入之间单词被删除
+        if !updated {
+            return Err(AppError::from(BusinessError::Word(WordError::NotInNetwork)));
         }
+        Ok(aggregate)
+    }
 
-        async fn remove_user_sense(
-            &self,
-            _user_id: i64,
-            _sense_id: i64,
-        ) -> Result<UserSense, WordRepositoryError> {
-            unimplemented!()
-        }
+    /// 单词的全部义项及各自指向的目标单词：关联经一次图查询批量取回，
+    /// 目标单词的文本再经一次 Postgres 查询补齐；图不可用时义项照常返回
+    #[instrument(skip(self))]
+    pub async fn get_senses_with_links(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+        sense_order: SenseOrder,
+    ) -> Result<WordSenses, AppError> {
+        let UserWordAggregate {
+            word, user_word, ..
+        } = self
+            .require_user_word(user_id, user_word_id, sense_order)
+            .await?;
 
-        async fn search(
-            &self,
-            _params: SearchParams,
-        ) -> Result<Vec<UserWordAggregate>, WordRepositoryError> {
-            Ok(vec![])
-        }
-    }
+        let sense_ids: Vec<i64> = user_word
+            .senses()
+            .iter()
+            .filter_map(UserSense::id)
+            .collect();
+        let (links, links_available) =
+            GraphReadResult::read(self.graph_repository.is_enabled(), "sense links", async {
+                if sense_ids.is_empty() {
+                    return Ok(Vec::new());
+                }
+                self.graph_repository
+                    .list_links_for_senses(user_id, &sense_ids, MAX_INLINED_SENSE_LINKS)
+                    .await
+            })
+            .await
+            .into_parts();
 
-    struct StubGraphRepository;
+        let mut target_ids: Vec<i64> = links.iter().map(|link| link.target_word_id).collect();
+        target_ids.sort_unstable();
+        target_ids.dedup();
+        let targets: std::collections::HashMap<i64, WordRecord> = self
+            .word_repository
+            .find_words_by_ids(&target_ids)
+            .await
+            .map_err(map_word_error)?
+            .into_iter()
+            .map(|target| (target.id, target))
+            .collect();
 
-    #[async_trait]
-    impl GraphRepository for StubGraphRepository {
-        async fn create_word_link(
-            &self,
-            _user_id: i64,
-            _word_a_id: i64,
-            _word_b_id: i64,
-            _kind: crate::repository::graph::WordLinkKind,
-            _note: Option<String>,
-        ) -> crate::repository::graph::GraphResult<crate::repository::graph::WordLinkRecord>
-        {
-            unimplemented!()
+        let mut grouped: std::collections::HashMap<i64, Vec<SenseLinkTarget>> =
+            std::collections::HashMap::new();
+        for link in links {
+            // 图中的目标单词在 Postgres 中不存在说明数据已漂移，跳过而不是返回无法展示的关联
+            let Some(target) = targets.get(&link.target_word_id) else {
+                tracing::warn!(
+                    link_id = %link.link_id,
+                    target_word_id = link.target_word_id,
+                    "sense link target word missing"
+                );
+                continue;
+            };
+            grouped
+                .entry(link.sense_id)
+                .or_default()
+                .push(SenseLinkTarget {
+                    target: target.clone(),
+                    link,
+                });
         }
 
-        async fn delete_word_link(
-            &self,
-            _user_id: i64,
-            _word_a_id: i64,
-            _word_b_id: i64,
-            _kind: crate::repository::graph::WordLinkKind,
-        ) -> crate::repository::graph::GraphResult<()> {
-            Ok(())
-        }
+        let senses = user_word
+            .senses()
+            .iter()
+            .map(|sense| SenseWithLinks {
+                links: sense
+                    .id()
+                    .and_then(|id| grouped.remove(&id))
+                    .unwrap_or_default(),
+                sense: sense.clone(),
+            })
+            .collect();
 
-        async fn list_word_links(
-            &self,
-            _filter: WordLinkFilter,
-        ) -> crate::repository::graph::GraphResult<Vec<crate::repository::graph::WordLinkRecord>>
-        {
-            Ok(vec![])
-        }
+        Ok(WordSenses {
+            word,
+            user_word_id,
+            senses,
+            links_available,
+        })
+    }
 
-        async fn create_sense_word_link(
-            &self,
-            _user_id: i64,
-            _sense_id: i64,
-            _source_word_id: i64,
-            _target_word_id: i64,
-            _kind: crate::repository::graph::SenseWordLinkKind,
-            _note: Option<String>,
-        ) -> crate::repository::graph::GraphResult<crate::repository::graph::SenseWordLinkRecord>
+    /// 与 `word_id` 相连的单词：先分页取图中的关联，再一次 Postgres 查询补齐邻居单词；
+    /// 顺序与关联列表一致。图中存在但已从用户网络删除的邻居被跳过
+    #[instrument(skip(self), fields(user_id = user_id, word_id = word_id))]
+    pub async fn linked_words(
+        &self,
+        user_id: i64,
+        word_id: i64,
+        kind: Option<WordLinkKind>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<LinkedWord>, AppError> {
+        let links = self
+            .graph_repository
+            .list_word_links(WordLinkFilter {
+                user_id,
+                kind,
+                word_id,
+                limit: limit.clamp(1, MAX_LINKED_WORDS),
+                offset: offset.max(0),
+            })
+            .await
+            .map_err(map_graph_error)?;
+
+        let neighbor_of = |link: &WordLinkRecord| {
+            if link.word_a_id == word_id {
+                link.word_b_id
+            } else {
+                link.word_a_id
+            }
+        };
+        let mut neighbor_ids: Vec<i64> = links.iter().map(neighbor_of).collect();
+        neighbor_ids.sort_unstable();
+        neighbor_ids.dedup();
+        let neighbors: std::collections::HashMap<i64, UserWordAggregate> = self
+            .word_repository
+            .find_user_words_by_word_ids(user_id, &neighbor_ids)
+            .await
+            .map_err(map_word_error)?
+            .into_iter()
+            .map(|aggregate| (aggregate.word.id, aggregate))
+            .collect();
+
+        Ok(links
+            .into_iter()
+            .filter_map(|link| {
+                let neighbor_id = neighbor_of(&link);
+                let Some(word) = neighbors.get(&neighbor_id) else {
+                    tracing::warn!(
+                        link_id = %link.link_id,
+                        neighbor_word_id = neighbor_id,
+                        "linked word missing from network"
+                    );
+                    return None;
+                };
+                Some(LinkedWord {
+                    word: word.clone(),
+                    link,
+                })
+            })
+            .collect())
+    }
+
+    /// 导出单个单词及其关联；单词不在用户网络中时返回 NotInNetwork
+    #[instrument(skip(self))]
+    pub async fn export_word(
+        &self,
+        user_id: i64,
+        user_word_id: i64,
+    ) -> Result<WordExport, AppError> {
+        let exported_at = chrono::Utc::now();
+        let word = self
+            .require_user_word(user_id, user_word_id, SenseOrder::default())
+            .await?;
+        let word_id = word.word.id;
+        let sense_ids: Vec<i64> = word
+            .user_word
+            .senses()
+            .iter()
+            .filter_map(UserSense::id)
+            .collect();
+
+        let ((word_links, sense_links), links_available) =
+            GraphReadResult::read(self.graph_repository.is_enabled(), "export links", async {
+                let word_links = self
+                    .graph_repository
+                    .list_word_links(WordLinkFilter {
+                        user_id,
+                        kind: None,
+                        word_id,
+                        limit: MAX_EXPORTED_LINKS,
+                        offset: 0,
+                    })
+                    .await?;
+                let sense_links = if sense_ids.is_empty() {
+                    Vec::new()
+                } else {
+                    self.graph_repository
+                        .list_links_for_senses(user_id, &sense_ids, MAX_EXPORTED_LINKS)
+                        .await?
+                };
+                Ok((word_links, sense_links))
+            })
+            .await
+            .into_parts();
+        let cap = MAX_EXPORTED_LINKS as usize;
+        let links_truncated = word_links.len() >= cap || sense_links.len() >= cap;
+
+        let mut related_ids: Vec<i64> = word_links
+            .iter()
+            .map(|link| {
+                if link.word_a_id == word_id {
+                    link.word_b_id
+                } else {
+                    link.word_a_id
+                }
+            })
+            .chain(sense_links.iter().map(|link| link.target_word_id))
+            .collect();
+        related_ids.sort_unstable();
+        related_ids.dedup();
+        let mut related_words = self
+            .word_repository
+            .find_words_by_ids(&related_ids)
+            .await
+            .map_err(map_word_error)?;
+        related_words.sort_by_key(|related| related.id);
+
+        Ok(WordExport {
+            exported_at,
+            word,
+            word_links,
+            sense_links,
+            related_words,
+            links_available,
+            links_truncated,
+        })
+    }
+
+    /// 导出用户的整个网络：全部单词（含全部义项）与图中的全部单词关联、义项关联。
+    /// 关联按单词分批读取，每批各自受 [`MAX_SYNC_LINKS_PER_PAGE`] 限制；图不可用时单词照常导出
+    #[instrument(skip(self))]
+    pub async fn export_network(&self, user_id: i64) -> Result<NetworkExport, AppError> {
+        let exported_at = chrono::Utc::now();
+        let words = self
+            .word_repository
+            .list_all_user_words(user_id)
+            .await
+            .map_err(map_word_error)?;
+
+        let cap = MAX_SYNC_LINKS_PER_PAGE as usize;
+        let ((word_links, sense_links, links_truncated), links_available) = GraphReadResult::read(
+            self.graph_repository.is_enabled(),
+            "network export",
+            async {
+                let mut word_links = Vec::new();
+                let mut sense_links = Vec::new();
+                let mut truncated = false;
+                for chunk in words.chunks(EXPORT_LINK_BATCH_WORDS) {
+                    let word_ids: Vec<i64> =
+                        chunk.iter().map(|aggregate| aggregate.word.id).collect();
+                    let sense_ids: Vec<i64> = chunk
+                        .iter()
+                        .flat_map(|aggregate| {
+                            aggregate
+                                .user_word
+                                .senses()
+                                .iter()
+                                .filter_map(UserSense::id)
+                        })
+                        .collect();
+                    let batch = self
+                        .graph_repository
+                        .list_word_links_from_words(user_id, &word_ids, MAX_SYNC_LINKS_PER_PAGE)
+                        .await?;
+                    truncated |= batch.len() >= cap;
+                    word_links.extend(batch);
+                    let batch = self
+                        .graph_repository
+                        .list_links_for_senses(user_id, &sense_ids, MAX_SYNC_LINKS_PER_PAGE)
+                        .await?;
+                    truncated |= batch.len() >= cap;
+                    sense_links.extend(batch);
+                }
+                Ok((word_links, sense_links, truncated))
+            },
+        )
+        .await
+        .into_parts();
+
+        Ok(NetworkExport {
+            exported_at,
+            words,
+            word_links,
+            sense_links,
+            links_available,
+            links_truncated,
+        })
+    }
+
+    /// 全量同步的一页。单词按 user_word_id 键集分页；单词关联归属于 id 较小的一端，
+    /// 义项关联归属于义项所在单词，因此逐页拉取到 `next_cursor` 为空时每条数据恰好出现一次。
+    /// `server_time` 在读取前取得，客户端可把它作为之后增量同步的起点
+    #[instrument(skip(self, cursor), fields(user_id = user_id))]
+    pub async fn sync_page(
+        &self,
+        user_id: i64,
+        cursor: Option<String>,
+        limit: Option<u32>,
+    ) -> Result<SyncPage, AppError> {
+        let after = cursor
+            .map(|value| {
+                SyncCursor::decode(&value)
+                    .ok_or_else(|| validation_error("cursor", "invalid cursor"))
+            })
+            .transpose()?;
+        let limit = limit
+            .unwrap_or(DEFAULT_SYNC_PAGE_SIZE)
+            .clamp(1, MAX_SYNC_PAGE_SIZE);
+        let server_time = chrono::Utc::now();
+
+        // 多取一条用于判断是否还有下一页
+        let mut words = self
+            .word_repository
+            .list_user_words_after(
+                user_id,
+                after.map(|cursor| cursor.user_word_id),
+                i64::from(limit) + 1,
+            )
+            .await
+            .map_err(map_word_error)?;
+        let has_more = words.len() > limit as usize;
+        words.truncate(limit as usize);
+        let next_cursor = words
+            .last()
+            .filter(|_| has_more)
+            .and_then(|last| last.user_word.id)
+            .map(|user_word_id| SyncCursor { user_word_id }.encode());
+
+        let word_ids: Vec<i64> = words.iter().map(|aggregate| aggregate.word.id).collect();
+        let sense_ids: Vec<i64> = words
+            .iter()
+            .flat_map(|aggregate| {
+                aggregate
+                    .user_word
+                    .senses()
+                    .iter()
+                    .filter_map(UserSense::id)
+            })
+            .collect();
+        let ((word_links, sense_links), links_available) =
+            GraphReadResult::read(self.graph_repository.is_enabled(), "sync links", async {
+                let word_links = self
+                    .graph_repository
+                    .list_word_links_from_words(user_id, &word_ids, MAX_SYNC_LINKS_PER_PAGE)
+                    .await?;
+                let sense_links = self
+                    .graph_repository
+                    .list_links_for_senses(user_id, &sense_ids, MAX_SYNC_LINKS_PER_PAGE)
+                    .await?;
+                Ok((word_links, sense_links))
+            })
+            .await
+            .into_parts();
+        let cap = MAX_SYNC_LINKS_PER_PAGE as usize;
+        let links_truncated = word_links.len() >= cap || sense_links.len() >= cap;
+
+        Ok(SyncPage {
+            server_time,
+            words,
+            word_links,
+            sense_links,
+            links_available,
+            links_truncated,
+            next_cursor,
+        })
+    }
+
+    /// `since`（RFC 3339）之后新增、修改或移除的单词。返回的 `server_time` 在读取前取得，
+    /// 作为下一次增量同步的 `since`
+    #[instrument(skip(self), fields(user_id = user_id))]
+    pub async fn sync_delta(&self, user_id: i64, since: &str) -> Result<SyncDelta, AppError> {
+        let since = parse_timestamp("since", since)?;
+        let server_time = chrono::Utc::now();
+        let changes = self
+            .word_repository
+            .list_changes_since(user_id, since, MAX_DELTA_CHANGES)
+            .await
+            .map_err(map_word_error)?;
+        let cap = MAX_DELTA_CHANGES as usize;
+        let truncated = changes.updated.len() >= cap || changes.deleted.len() >= cap;
+        Ok(SyncDelta {
+            server_time,
+            changes,
+            truncated,
+        })
+    }
+
+    /// 按加入网络的时间（RFC 3339，含两端）分页列出单词；任一端缺省表示不限。
+    /// `has_links` 按图中是否有关联过滤，见 [`Self::filter_by_links`]
+    #[instrument(skip(self), fields(user_id = user_id))]
+    pub async fn list_words_in_range(
+        &self,
+        user_id: i64,
+        created_from: Option<&str>,
+        created_to: Option<&str>,
+        has_links: Option<bool>,
+        page: u32,
+        page_size: u32,
+    ) -> Result<WordPage, AppError> {
+        let from = created_from
+            .map(|value| parse_timestamp("created_from", value))
+            .transpose()?;
+        let to = created_to
+            .map(|value| parse_timestamp("created_to", value))
+            .transpose()?;
+        if let (Some(from), Some(to)) = (from, to)
+            && from > to
         {
-            unimplemented!()
+            return Err(validation_error(
+                "created_from",
+                "must not be later than created_to",
+            ));
+        }
+        let page = page.max(1);
+        let page_size = page_size.clamp(1, 100);
+        let offset = (i64::from(page - 1) * i64::from(page_size)).min(10_000);
+
+        if let Some(has_links) = has_links {
+            let word_ids = self
+                .word_repository
+                .find_word_ids_in_range(user_id, from, to)
+                .await
+                .map_err(map_word_error)?;
+            let matching = self.filter_by_links(user_id, word_ids, has_links).await?;
+            let page_ids: Vec<i64> = matching
+                .iter()
+                .skip(offset as usize)
+                .take(page_size as usize)
+                .copied()
+                .collect();
+            let mut items = self
+                .word_repository
+                .find_user_words_by_word_ids(user_id, &page_ids)
+                .await
+                .map_err(map_word_error)?;
+            items.sort_by_key(|a| page_ids.iter().position(|id| *id == a.word.id));
+            return Ok(WordPage {
+                total: matching.len() as u64,
+                items,
+                page,
+                page_size,
+                next_cursor: None,
+                matches: HashMap::new(),
+            });
+        }
+
+        let total = self
+            .word_repository
+            .count_words_in_range(user_id, from, to)
+            .await
+            .map_err(map_word_error)?;
+        let items = self
+            .word_repository
+            .find_words_in_range(user_id, from, to, i64::from(page_size), offset)
+            .await
+            .map_err(map_word_error)?;
+
+        Ok(WordPage {
+            items,
+            page,
+            page_size,
+            total: total.max(0) as u64,
+            next_cursor: None,
+            matches: HashMap::new(),
+        })
+    }
+
+    /// 是否有关联只能从图中得知：对全部候选分批查询，保持候选原有顺序
+    async fn filter_by_links(
+        &self,
+        user_id: i64,
+        word_ids: Vec<i64>,
+        has_links: bool,
+    ) -> Result<Vec<i64>, AppError> {
+        if !self.graph_repository.is_enabled() {
+            return Err(AppError::from(BusinessError::Link(
+                LinkError::GraphDisabled,
+            )));
+        }
+        let mut linked = HashSet::new();
+        for chunk in word_ids.chunks(EXPORT_LINK_BATCH_WORDS) {
+            linked.extend(
+                self.graph_repository
+                    .linked_word_ids(user_id, chunk)
+                    .await
+                    .map_err(map_graph_error)?,
+            );
+        }
+        Ok(word_ids
+            .into_iter()
+            .filter(|id| linked.contains(id) == has_links)
+            .collect())
+    }
+
+    /// 按 canonical_key 相似度列出可能需要合并的单词对；阈值与数量超出范围时钳制而不是报错
+    #[instrument(skip(self), fields(user_id = user_id))]
+    pub async fn suggest_duplicate_words(
+        &self,
+        user_id: i64,
+        threshold: Option<f32>,
+        limit: Option<u32>,
+    ) -> Result<Vec<NearDuplicatePair>, AppError> {
+        let threshold = threshold
+            .filter(|value| value.is_finite())
+            .unwrap_or(DEFAULT_DUPLICATE_THRESHOLD)
+            .clamp(MIN_DUPLICATE_THRESHOLD, 1.0);
+        let limit = limit
+            .unwrap_or(DEFAULT_DUPLICATE_PAIRS)
+            .clamp(1, MAX_DUPLICATE_PAIRS);
+        self.word_repository
+            .find_near_duplicate_words(user_id, threshold, i64::from(limit))
+            .await
+            .map_err(map_word_error)
+    }
+
+    /// 先规范化再批量查询哪些文本已在用户网络中，结果按输入顺序返回
+    #[instrument(skip(self, texts), fields(user_id = user_id, count = texts.len()))]
+    pub async fn check_existing_words(
+        &self,
+        user_id: i64,
+        texts: Vec<String>,
+    ) -> Result<Vec<WordExistence>, AppError> {
+        if texts.is_empty() {
+            return Err(validation_error("texts", "不能为空"));
+        }
+        if texts.len() > MAX_EXISTS_BATCH_TEXTS {
+            return Err(validation_error(
+                "texts",
+                format!(
+                    "单次最多 {MAX_EXISTS_BATCH_TEXTS} 个文本，当前 {}",
+                    texts.len()
+                ),
+            ));
+        }
+
+        let mut errors = FieldErrors::default();
+        let mut parsed = Vec::with_capacity(texts.len());
+        for (index, text) in texts.into_iter().enumerate() {
+            match CanonicalKey::new_with(&text, self.canonical_mode) {
+                Ok(key) => parsed.push((text, key)),
+                Err(err) => errors.push(&format!("texts[{index}]"), canonical_error_message(&err)),
+            }
+        }
+        if !errors.is_empty() {
+            return Err(errors.into_error());
+        }
+
+        let mut keys: Vec<CanonicalKey> = parsed.iter().map(|(_, key)| key.clone()).collect();
+        keys.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        keys.dedup();
+        let existing: std::collections::HashSet<CanonicalKey> = self
+            .word_repository
+            .existing_canonical_keys(user_id, &keys)
+            .await
+            .map_err(map_word_error)?
+            .into_iter()
+            .collect();
+
+        Ok(parsed
+            .into_iter()
+            .map(|(text, canonical_key)| WordExistence {
+                exists: existing.contains(&canonical_key),
+                text,
+                canonical_key,
+            })
+            .collect())
+    }
+}
+
+impl SenseInput {
+    fn into_new_sense(self, user_word_id: i64) -> NewUserSense {
+        NewUserSense {
+            user_word_id,
+            text: self.text,
+            is_primary: self.is_primary,
+            sort_order: self.sort_order,
+            note: self.note,
+            source_url: self.source_url,
+        }
+    }
+}
+
+/// 字段级校验错误收集器，最终合并为单个 `BusinessError::Validation`
+#[derive(Debug, Default)]
+pub(crate) struct FieldErrors(Vec<ValidationField>);
+
+impl FieldErrors {
+    pub(crate) fn push(&mut self, field: &str, message: impl Into<String>) {
+        self.0.push(ValidationField {
+            field: field.into(),
+            message: message.into(),
+        });
+    }
+
+    pub(crate) fn check<T>(
+        &mut self,
+        field: &str,
+        result: Result<T, ValidationError>,
+    ) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(err) => {
+                self.push(field, validation_message(&err));
+                None
+            }
+        }
+    }
+
+    /// 并入校验结果中的字段错误；不是参数校验错误时原样返回
+    pub(crate) fn merge(&mut self, result: Result<(), AppError>) -> Result<(), AppError> {
+        match result {
+            Err(AppError::BusinessError(BusinessError::Validation(fields))) => {
+                self.0.extend(fields);
+                Ok(())
+            }
+            other => other,
+        }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn into_error(self) -> AppError {
+        AppError::from(BusinessError::Validation(self.0))
+    }
+}
+
+/// 校验义项输入，错误以 `{prefix}.text` / `{prefix}.note` 记录到 `errors`
+pub(crate) fn validate_sense_input(
+    prefix: &str,
+    sense: SenseInput,
+    limits: &TextLimits,
+    errors: &mut FieldErrors,
+) -> Option<SenseInput> {
+    let SenseInput {
+        text,
+        is_primary,
+        sort_order,
+        note,
+        source_url,
+    } = sense;
+    let text = errors.check(
+        &format!("{prefix}.text"),
+        validate_non_empty_text(text, limits.sense_text, limits.text_bytes),
+    );
+    let note = errors.check(
+        &format!("{prefix}.note"),
+        validate_note(note, limits.sense_note, limits.note_bytes),
+    );
+    let source_url = errors.check(
+        &format!("{prefix}.source_url"),
+        validate_source_url(source_url),
+    );
+
+    Some(SenseInput {
+        text: text?,
+        is_primary,
+        sort_order,
+        note: note?,
+        source_url: source_url?,
+    })
+}
+
+pub(crate) fn build_new_sense_payload(
+    user_word_id: i64,
+    sense: SenseInput,
+    limits: &TextLimits,
+) -> Result<NewUserSense, AppError> {
+    let mut errors = FieldErrors::default();
+    match validate_sense_input("first_sense", sense, limits, &mut errors) {
+        Some(sense) => Ok(sense.into_new_sense(user_word_id)),
+        None => Err(errors.into_error()),
+    }
+}
+
+fn parse_timestamp(field: &str, value: &str) -> Result<chrono::DateTime<chrono::Utc>, AppError> {
+    chrono::DateTime::parse_from_rfc3339(value)
+        .map(|at| at.with_timezone(&chrono::Utc))
+        .map_err(|_| validation_error(field, "must be an RFC 3339 timestamp"))
+}
+
+pub(crate) fn validation_error(field: &str, message: impl Into<String>) -> AppError {
+    let mut errors = FieldErrors::default();
+    errors.push(field, message);
+    errors.into_error()
+}
+
+fn validation_message(error: &ValidationError) -> String {
+    match error {
+        ValidationError::Blank => "不能为空".to_string(),
+        ValidationError::TextTooLong(len, max) => {
+            format!("文本长度不能超过 {max} 字符，当前 {len}")
+        }
+        ValidationError::NoteTooLong(len, max) => {
+            format!("备注长度不能超过 {max} 字符，当前 {len}")
+        }
+        ValidationError::TextTooManyBytes(len, max) => {
+            format!("文本大小不能超过 {max} 字节，当前 {len}")
+        }
+        ValidationError::NoteTooManyBytes(len, max) => {
+            format!("备注大小不能超过 {max} 字节，当前 {len}")
+        }
+        ValidationError::InvalidTag(tag) => format!("无效标签: {tag}"),
+        ValidationError::TagLimitExceeded(count) => {
+            format!("标签数量不能超过 {MAX_TAGS}，当前 {count}")
+        }
+        ValidationError::ControlCharacter => "不能包含控制字符".to_string(),
+        ValidationError::InvalidUrl(_) => "必须是有效的 http 或 https 链接".to_string(),
+    }
+}
+
+pub(crate) fn map_validation_error(field: &str, error: ValidationError) -> AppError {
+    validation_error(field, validation_message(&error))
+}
+
+fn canonical_error_message(err: &CanonicalKeyError) -> &'static str {
+    match err {
+        CanonicalKeyError::Empty => "文本不能为空",
+        CanonicalKeyError::Validation(_) => "文本不合法",
+    }
+}
+
+pub(crate) fn map_canonical_error(err: CanonicalKeyError) -> AppError {
+    validation_error("text", canonical_error_message(&err))
+}
+
+pub(crate) fn map_user_word_error(err: UserWordError) -> AppError {
+    match err {
+        UserWordError::TagLimitExceeded(count) => {
+            validation_error("tags", format!("标签数量不能超过 {MAX_TAGS}，当前 {count}"))
+        }
+        UserWordError::InvalidTag(tag) => validation_error("tags", format!("无效标签: {tag}")),
+        UserWordError::InvalidNote => validation_error("note", "备注不能为空"),
+        UserWordError::NoteTooLong(len, max) => {
+            validation_error("note", format!("备注长度不能超过 {max} 字符，当前 {len}"))
+        }
+        UserWordError::NoteTooManyBytes(len, max) => {
+            validation_error("note", format!("备注大小不能超过 {max} 字节，当前 {len}"))
+        }
+        UserWordError::NoteControlCharacter => validation_error("note", "备注不能包含控制字符"),
+        UserWordError::DuplicateSenseText(_) => {
+            AppError::from(BusinessError::Word(WordError::SenseDuplicate))
+        }
+        UserWordError::SenseNotFound(_) => {
+            AppError::from(BusinessError::Word(WordError::NotInNetwork))
+        }
+        UserWordError::SenseIndexOutOfBounds(_) => {
+            AppError::from(BusinessError::Word(WordError::PrimaryConflict))
+        }
+        UserWordError::SenseLimitExceeded(max) => {
+            validation_error("senses", format!("义项数量不能超过 {max}"))
+        }
+        UserWordError::Sense(err) => map_user_sense_error(err),
+    }
+}
+
+pub(crate) fn map_user_sense_error(err: UserSenseError) -> AppError {
+    match err {
+        UserSenseError::EmptyText => validation_error("sense.text", "义项文本不能为空"),
+        UserSenseError::TextTooLong(len, max) => validation_error(
+            "sense.text",
+            format!("义项文本长度不能超过 {max} 字符，当前 {len}"),
+        ),
+        UserSenseError::TextTooManyBytes(len, max) => validation_error(
+            "sense.text",
+            format!("义项文本大小不能超过 {max} 字节，当前 {len}"),
+        ),
+        UserSenseError::InvalidNote => validation_error("sense.note", "义项备注不能为空"),
+        UserSenseError::NoteTooLong(len, max) => validation_error(
+            "sense.note",
+            format!("义项备注长度不能超过 {max} 字符，当前 {len}"),
+        ),
+        UserSenseError::NoteTooManyBytes(len, max) => validation_error(
+            "sense.note",
+            format!("义项备注大小不能超过 {max} 字节，当前 {len}"),
+        ),
+        UserSenseError::ControlCharacter => validation_error("sense", "义项不能包含控制字符"),
+        UserSenseError::InvalidSourceUrl => validation_error(
+            "sense.source_url",
+            "义项来源链接必须是有效的 http 或 https 链接",
+        ),
+    }
+}
+
+/// 单词或义项不在用户网络中时的错误，所有按 id 的归属检查都经过这里。
+/// 默认不区分资源不存在与属于其他用户，一律返回 NotInNetwork，避免他人的 id 被枚举；
+/// `reveal` 开启时（仅限调试）再查一次属主，属于其他用户时返回 NotOwned
+pub(crate) async fn ownership_error<W>(
+    repository: &W,
+    reveal: bool,
+    user_id: i64,
+    resource: OwnedResource,
+) -> AppError
+where
+    W: WordRepository + Send + Sync,
+{
+    let not_in_network = AppError::from(BusinessError::Word(WordError::NotInNetwork));
+    if !reveal {
+        return not_in_network;
+    }
+    match repository.find_owner(resource).await {
+        Ok(Some(owner)) if owner != user_id => {
+            AppError::from(BusinessError::Word(WordError::NotOwned))
+        }
+        Ok(_) => not_in_network,
+        Err(err) => {
+            tracing::warn!(error = %err, ?resource, "ownership lookup failed");
+            not_in_network
+        }
+    }
+}
+
+pub(crate) fn map_word_error(err: WordRepositoryError) -> AppError {
+    match err {
+        WordRepositoryError::UserWord(inner) => map_user_word_error(inner),
+        WordRepositoryError::UserSense(inner) => map_user_sense_error(inner),
+        WordRepositoryError::Canonical(inner) => map_canonical_error(inner),
+        WordRepositoryError::Database(err) if is_statement_timeout(&err) => {
+            tracing::error!(error = %err, "word query cancelled by statement timeout");
+            AppError::from(InternalError::Unknown)
+        }
+        WordRepositoryError::Database(_) => {
+            AppError::from(BusinessError::Word(WordError::AlreadyExists))
+        }
+        WordRepositoryError::RemovedConcurrently(user_word_id) => {
+            tracing::warn!(user_word_id, "user word removed concurrently after write");
+            AppError::from(BusinessError::Word(WordError::NotInNetwork))
+        }
+    }
+}
+
+pub(crate) fn map_preferences_error(err: PreferencesRepositoryError) -> AppError {
+    tracing::error!(error = %err, "preferences repository failure");
+    AppError::from(InternalError::Unknown)
+}
+
+pub(crate) fn map_graph_error(err: GraphRepositoryError) -> AppError {
+    match err {
+        GraphRepositoryError::Business(BusinessError::Link(link_err)) => {
+            AppError::from(BusinessError::Link(link_err))
+        }
+        GraphRepositoryError::Business(other) => AppError::from(other),
+        // 图库故障不是“关联不存在”，按内部错误返回，避免客户端误以为数据缺失
+        GraphRepositoryError::Timeout => {
+            tracing::warn!("graph query timed out");
+            AppError::from(ExternalError::Timeout)
+        }
+        GraphRepositoryError::Database(err) => {
+            tracing::error!(error = %err, "graph repository failure");
+            AppError::from(InternalError::Unknown)
+        }
+        GraphRepositoryError::InvalidData(detail) => {
+            tracing::error!(%detail, "graph returned malformed data");
+            AppError::from(InternalError::Unknown)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::word::UserSense;
+    use crate::repository::graph::{SenseWordLinkKind, WordLinkKind};
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+
+    struct StubWordRepository;
+
+    #[async_trait]
+    impl WordRepository for StubWordRepository {
+        async fn upsert_word(
+            &self,
+            _canonical: &CanonicalKey,
+            _text: &str,
+        ) -> Result<crate::repository::word::WordRecord, WordRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn upsert_user_word(
+            &self,
+            _payload: UpsertUserWord,
+        ) -> Result<crate::repository::word::UpsertedUserWord, WordRepositoryError> {
+            Err(WordRepositoryError::UserWord(UserWordError::InvalidNote))
+        }
+
+        async fn import_user_words(
+            &self,
+            _items: Vec<crate::repository::word::ImportUserWord>,
+        ) -> Result<
+            Vec<Result<crate::repository::word::ImportedUserWord, WordRepositoryError>>,
+            WordRepositoryError,
+        > {
+            Ok(Vec::new())
+        }
+
+        async fn find_user_word(
+            &self,
+            _user_id: i64,
+            _user_word_id: i64,
+            _sense_order: SenseOrder,
+        ) -> Result<Option<UserWordAggregate>, WordRepositoryError> {
+            Ok(None)
+        }
+
+        async fn find_user_word_by_canonical(
+            &self,
+            _user_id: i64,
+            _canonical: &CanonicalKey,
+        ) -> Result<Option<UserWordAggregate>, WordRepositoryError> {
+            Ok(None)
+        }
+
+        async fn remove_user_word(
+            &self,
+            _user_id: i64,
+            _user_word_id: i64,
+        ) -> Result<(), WordRepositoryError> {
+            Ok(())
+        }
+
+        async fn add_user_sense(
+            &self,
+            _sense: NewUserSense,
+        ) -> Result<UserSense, WordRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn update_user_sense(
+            &self,
+            _user_id: i64,
+            _sense_id: i64,
+            _update: crate::repository::word::SenseUpdate,
+        ) -> Result<UserSense, WordRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn remove_user_sense(
+            &self,
+            _user_id: i64,
+            _sense_id: i64,
+        ) -> Result<UserSense, WordRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn set_primary_sense(
+            &self,
+            _user_id: i64,
+            _sense_id: i64,
+        ) -> Result<UserSense, WordRepositoryError> {
+            unimplemented!()
+        }
+
+        async fn search(
+            &self,
+            _params: SearchParams,
+        ) -> Result<
+            Vec<(
+                UserWordAggregate,
+                Option<crate::repository::word::SearchMatch>,
+            )>,
+            WordRepositoryError,
+        > {
+            Ok(vec![])
+        }
+
+        async fn count_search(&self, _params: &SearchParams) -> Result<i64, WordRepositoryError> {
+            Ok(0)
+        }
+
+        async fn search_word_ids(
+            &self,
+            _params: &SearchParams,
+        ) -> Result<Vec<i64>, WordRepositoryError> {
+            Ok(vec![])
+        }
+
+        async fn find_sense_word_id(
+            &self,
+            _user_id: i64,
+            _sense_id: i64,
+        ) -> Result<Option<i64>, WordRepositoryError> {
+            Ok(None)
+        }
+
+        async fn find_user_word_by_sense(
+            &self,
+            _user_id: i64,
+            _sense_id: i64,
+            _sense_order: SenseOrder,
+        ) -> Result<Option<UserWordAggregate>, WordRepositoryError> {
+            Ok(None)
+        }
+
+        async fn find_owner(
+            &self,
+            _resource: crate::repository::word::OwnedResource,
+        ) -> Result<Option<i64>, WordRepositoryError> {
+            Ok(None)
+        }
+
+        async fn count_owned_user_words(
+            &self,
+            _user_id: i64,
+            _user_word_ids: &[i64],
+        ) -> Result<i64, WordRepositoryError> {
+            Ok(0)
+        }
+
+        async fn existing_canonical_keys(
+            &self,
+            _user_id: i64,
+            _keys: &[CanonicalKey],
+        ) -> Result<Vec<CanonicalKey>, WordRepositoryError> {
+            Ok(Vec::new())
+        }
+
+        async fn find_near_duplicate_words(
+            &self,
+            _user_id: i64,
+            _threshold: f32,
+            _limit: i64,
+        ) -> Result<Vec<crate::repository::word::NearDuplicatePair>, WordRepositoryError> {
+            Ok(Vec::new())
+        }
+
+        async fn find_words_by_ids(
+            &self,
+            _word_ids: &[i64],
+        ) -> Result<Vec<crate::repository::word::WordRecord>, WordRepositoryError> {
+            Ok(Vec::new())
+        }
+
+        async fn find_user_words_by_word_ids(
+            &self,
+            _user_id: i64,
+            _word_ids: &[i64],
+        ) -> Result<Vec<UserWordAggregate>, WordRepositoryError> {
+            Ok(Vec::new())
+        }
+
+        async fn list_user_words_after(
+            &self,
+            _user_id: i64,
+            _after: Option<i64>,
+            _limit: i64,
+        ) -> Result<Vec<UserWordAggregate>, WordRepositoryError> {
+            Ok(Vec::new())
+        }
+
+        async fn find_words_in_range(
+            &self,
+            _user_id: i64,
+            _from: Option<chrono::DateTime<chrono::Utc>>,
+            _to: Option<chrono::DateTime<chrono::Utc>>,
+            _limit: i64,
+            _offset: i64,
+        ) -> Result<Vec<UserWordAggregate>, WordRepositoryError> {
+            Ok(Vec::new())
+        }
+
+        async fn find_word_ids_in_range(
+            &self,
+            _user_id: i64,
+            _from: Option<chrono::DateTime<chrono::Utc>>,
+            _to: Option<chrono::DateTime<chrono::Utc>>,
+        ) -> Result<Vec<i64>, WordRepositoryError> {
+            Ok(vec![])
+        }
+
+        async fn count_words_in_range(
+            &self,
+            _user_id: i64,
+            _from: Option<chrono::DateTime<chrono::Utc>>,
+            _to: Option<chrono::DateTime<chrono::Utc>>,
+        ) -> Result<i64, WordRepositoryError> {
+            Ok(0)
+        }
+
+        async fn list_changes_since(
+            &self,
+            _user_id: i64,
+            _since: chrono::DateTime<chrono::Utc>,
+            _limit: i64,
+        ) -> Result<crate::repository::word::WordChanges, WordRepositoryError> {
+            Ok(Default::default())
+        }
+
+        async fn update_tag(
+            &self,
+            _user_id: i64,
+            _user_word_ids: &[i64],
+            _tag: &str,
+            _action: crate::repository::word::TagAction,
+        ) -> Result<u64, WordRepositoryError> {
+            Ok(0)
+        }
+
+        async fn replace_tags(
+            &self,
+            _user_id: i64,
+            _user_word_id: i64,
+            _tags: &[String],
+        ) -> Result<bool, WordRepositoryError> {
+            Ok(false)
+        }
+
+        async fn update_user_word_metadata(
+            &self,
+            _user_id: i64,
+            _user_word_id: i64,
+            _tags: &[String],
+            _note: Option<&str>,
+        ) -> Result<bool, WordRepositoryError> {
+            Ok(false)
+        }
+
+        async fn repoint_user_word(
+            &self,
+            _user_id: i64,
+            _user_word_id: i64,
+            _word_id: i64,
+        ) -> Result<crate::repository::word::WordRepoint, WordRepositoryError> {
+            Ok(crate::repository::word::WordRepoint::WordNotFound)
+        }
+
+        async fn pending_word_repoint(
+            &self,
+            _user_id: i64,
+            _user_word_id: i64,
+        ) -> Result<Option<i64>, WordRepositoryError> {
+            Ok(None)
+        }
+
+        async fn complete_word_repoint(
+            &self,
+            _user_id: i64,
+            _user_word_id: i64,
+            _previous_word_id: i64,
+        ) -> Result<(), WordRepositoryError> {
+            Ok(())
+        }
+
+        async fn reorder_senses(
+            &self,
+            _user_id: i64,
+            _user_word_id: i64,
+            _sense_ids: &[i64],
+        ) -> Result<crate::repository::word::SenseReorder, WordRepositoryError> {
+            Ok(crate::repository::word::SenseReorder::WordNotFound)
+        }
+
+        async fn repair_primaries(&self, _user_id: i64) -> Result<u64, WordRepositoryError> {
+            Ok(0)
+        }
+    }
+
+    struct StubGraphRepository;
+
+    #[async_trait]
+    impl GraphRepository for StubGraphRepository {
+        async fn create_word_link(
+            &self,
+            _user_id: i64,
+            _word_a_id: i64,
+            _word_b_id: i64,
+            _kind: crate::repository::graph::WordLinkKind,
+            _note: Option<String>,
+        ) -> crate::repository::graph::GraphResult<crate::repository::graph::WordLinkRecord>
+        {
+            unimplemented!()
+        }
+
+        async fn delete_word_link(
+            &self,
+            _user_id: i64,
+            _word_a_id: i64,
+            _word_b_id: i64,
+            _kind: crate::repository::graph::WordLinkKind,
+        ) -> crate::repository::graph::GraphResult<u64> {
+            Ok(0)
+        }
+
+        async fn update_word_link(
+            &self,
+            _user_id: i64,
+            _word_a_id: i64,
+            _word_b_id: i64,
+            _old_kind: WordLinkKind,
+            _new_kind: Option<WordLinkKind>,
+            _note: Option<Option<String>>,
+        ) -> crate::repository::graph::GraphResult<crate::repository::graph::WordLinkRecord>
+        {
+            unimplemented!()
+        }
+
+        async fn list_word_links(
+            &self,
+            _filter: WordLinkFilter,
+        ) -> crate::repository::graph::GraphResult<Vec<crate::repository::graph::WordLinkRecord>>
+        {
+            Ok(vec![])
+        }
+
+        async fn get_word_links_between(
+            &self,
+            _user_id: i64,
+            _word_a_id: i64,
+            _word_b_id: i64,
+        ) -> crate::repository::graph::GraphResult<Vec<crate::repository::graph::WordLinkRecord>>
+        {
+            Ok(vec![])
+        }
+
+        async fn list_word_links_from_words(
+            &self,
+            _user_id: i64,
+            _word_ids: &[i64],
+            _limit: i64,
+        ) -> crate::repository::graph::GraphResult<Vec<crate::repository::graph::WordLinkRecord>>
+        {
+            Ok(vec![])
+        }
+
+        async fn create_sense_word_link(
+            &self,
+            _user_id: i64,
+            _sense_id: i64,
+            _source_word_id: i64,
+            _target_word_id: i64,
+            _kind: crate::repository::graph::SenseWordLinkKind,
+            _note: Option<String>,
+        ) -> crate::repository::graph::GraphResult<crate::repository::graph::SenseWordLinkRecord>
+        {
+            unimplemented!()
+        }
+
+        async fn delete_sense_word_link(
+            &self,
+            _user_id: i64,
+            _sense_id: i64,
+            _target_word_id: i64,
+            _kind: crate::repository::graph::SenseWordLinkKind,
+        ) -> crate::repository::graph::GraphResult<()> {
+            Ok(())
+        }
+
+        async fn list_sense_word_links(
+            &self,
+            _filter: crate::repository::graph::SenseLinkFilter,
+        ) -> crate::repository::graph::GraphResult<Vec<crate::repository::graph::SenseWordLinkRecord>>
+        {
+            Ok(vec![])
+        }
+
+        async fn list_links_for_senses(
+            &self,
+            _user_id: i64,
+            _sense_ids: &[i64],
+            _limit: i64,
+        ) -> crate::repository::graph::GraphResult<Vec<crate::repository::graph::SenseWordLinkRecord>>
+        {
+            Ok(vec![])
+        }
+
+        async fn remove_links_for_sense(
+            &self,
+            _sense_id: i64,
+        ) -> crate::repository::graph::GraphResult<()> {
+            Ok(())
+        }
+
+        async fn neighbors(
+            &self,
+            _query: crate::repository::graph::NeighborQuery,
+        ) -> crate::repository::graph::GraphResult<crate::repository::graph::Neighborhood> {
+            Ok(Default::default())
+        }
+
+        async fn word_degrees(
+            &self,
+            _user_id: i64,
+            _word_ids: &[i64],
+        ) -> crate::repository::graph::GraphResult<std::collections::HashMap<i64, i64>> {
+            Ok(Default::default())
+        }
+
+        async fn linked_word_ids(
+            &self,
+            _user_id: i64,
+            _word_ids: &[i64],
+        ) -> crate::repository::graph::GraphResult<std::collections::HashSet<i64>> {
+            Ok(Default::default())
+        }
+
+        async fn total_link_count(
+            &self,
+            _user_id: i64,
+        ) -> crate::repository::graph::GraphResult<crate::repository::graph::LinkCounts> {
+            Ok(Default::default())
+        }
+
+        async fn link_stats_by_kind(
+            &self,
+            _user_id: i64,
+        ) -> crate::repository::graph::GraphResult<crate::repository::graph::LinkKindStats>
+        {
+            Ok(Default::default())
+        }
+
+        async fn repoint_word_links(
+            &self,
+            _user_id: i64,
+            _from_word_id: i64,
+            _to_word_id: i64,
+        ) -> crate::repository::graph::GraphResult<()> {
+            Ok(())
+        }
+
+        async fn upsert_node_word(
+            &self,
+            _word_id: i64,
+        ) -> crate::repository::graph::GraphResult<()> {
+            Ok(())
+        }
+
+        async fn upsert_node_sense(
+            &self,
+            _sense_id: i64,
+            _user_id: i64,
+        ) -> crate::repository::graph::GraphResult<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn graph_failures_are_not_reported_as_missing_links() {
+        assert!(matches!(
+            map_graph_error(GraphRepositoryError::Timeout),
+            AppError::ExternalError(ExternalError::Timeout)
+        ));
+        assert!(matches!(
+            map_graph_error(GraphRepositoryError::InvalidData("bad row".into())),
+            AppError::InternalError(InternalError::Unknown)
+        ));
+        assert!(matches!(
+            map_graph_error(GraphRepositoryError::Business(BusinessError::Link(
+                LinkError::SelfForbidden
+            ))),
+            AppError::BusinessError(BusinessError::Link(LinkError::SelfForbidden))
+        ));
+    }
+
+    #[tokio::test]
+    async fn add_to_my_network_maps_validation_errors() {
+        let service = WordService::new(StubWordRepository, StubGraphRepository);
+        let result = service
+            .add_to_my_network(
+                1,
+                AddWordInput {
+                    text: "hello".into(),
+                    tags: vec![],
+                    note: None,
+                    first_sense: None,
+                },
+            )
+            .await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn word_removed_after_write_maps_to_not_in_network() {
+        // 不能沿用 Database 分支，否则会被误报为“单词已存在”
+        let err = map_word_error(WordRepositoryError::RemovedConcurrently(3));
+        assert!(matches!(
+            err,
+            AppError::BusinessError(BusinessError::Word(WordError::NotInNetwork))
+        ));
+    }
+
+    #[tokio::test]
+    async fn add_to_my_network_reports_all_field_errors() {
+        let service = WordService::new(StubWordRepository, StubGraphRepository);
+        let result = service
+            .add_to_my_network(
+                1,
+                AddWordInput {
+                    text: "hello".into(),
+                    tags: vec!["bad tag!".into()],
+                    note: Some("n".repeat(crate::util::MAX_NOTE_LENGTH + 1)),
+                    first_sense: Some(SenseInput {
+                        text: "   ".into(),
+                        is_primary: true,
+                        sort_order: 0,
+                        note: None,
+                        source_url: None,
+                    }),
+                },
+            )
+            .await;
+
+        let Err(AppError::BusinessError(BusinessError::Validation(fields))) = result else {
+            panic!("expected validation error");
+        };
+        let names: Vec<&str> = fields.iter().map(|f| f.field.as_str()).collect();
+        assert_eq!(names, vec!["tags", "note", "first_sense.text"]);
+    }
+
+    #[tokio::test]
+    async fn add_to_my_network_respects_the_sense_cap_when_merging() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let service = WordService::new(
+            InMemoryWordRepository::default().with_max_senses(1),
+            InMemoryGraphRepository::default(),
+        );
+        let add = |sense: &str| {
+            service.add_to_my_network(
+                1,
+                AddWordInput {
+                    text: "bank".into(),
+                    tags: vec![],
+                    note: None,
+                    first_sense: Some(SenseInput {
+                        text: sense.into(),
+                        is_primary: false,
+                        sort_order: 0,
+                        note: None,
+                        source_url: None,
+                    }),
+                },
+            )
+        };
+
+        add("river side").await.unwrap();
+        let Err(AppError::BusinessError(BusinessError::Validation(fields))) =
+            add("money house").await
+        else {
+            panic!("expected validation error");
+        };
+        assert_eq!(fields[0].field, "senses");
+    }
+
+    #[tokio::test]
+    async fn add_to_my_network_applies_each_configured_limit() {
+        let service = WordService::new(StubWordRepository, StubGraphRepository).with_text_limits(
+            TextLimits {
+                sense_text: 4,
+                sense_note: 5,
+                word_note: 6,
+                ..TextLimits::default()
+            },
+        );
+        let result = service
+            .add_to_my_network(
+                1,
+                AddWordInput {
+                    text: "hello".into(),
+                    tags: vec![],
+                    note: Some("n".repeat(7)),
+                    first_sense: Some(SenseInput {
+                        text: "t".repeat(5),
+                        is_primary: true,
+                        sort_order: 0,
+                        note: Some("s".repeat(6)),
+                        source_url: None,
+                    }),
+                },
+            )
+            .await;
+
+        let Err(AppError::BusinessError(BusinessError::Validation(fields))) = result else {
+            panic!("expected validation error");
+        };
+        let reported: Vec<(&str, &str)> = fields
+            .iter()
+            .map(|f| (f.field.as_str(), f.message.as_str()))
+            .collect();
+        assert_eq!(
+            reported,
+            vec![
+                ("note", "备注长度不能超过 6 字符，当前 7"),
+                ("first_sense.text", "文本长度不能超过 4 字符，当前 5"),
+                ("first_sense.note", "备注长度不能超过 5 字符，当前 6"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn check_existing_words_canonicalizes_and_keeps_input_order() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+        for text in ["apple", "ice cream"] {
+            service
+                .add_to_my_network(
+                    1,
+                    AddWordInput {
+                        text: text.into(),
+                        tags: vec![],
+                        note: None,
+                        first_sense: None,
+                    },
+                )
+                .await
+                .unwrap();
+        }
+
+        let results = service
+            .check_existing_words(
+                1,
+                vec!["Durian".into(), "  APPLE ".into(), "Ice-Cream".into()],
+            )
+            .await
+            .unwrap();
+        let summary: Vec<(&str, &str, bool)> = results
+            .iter()
+            .map(|r| (r.text.as_str(), r.canonical_key.as_str(), r.exists))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Durian", "durian", false),
+                ("  APPLE ", "apple", true),
+                ("Ice-Cream", "ice-cream", true),
+            ]
+        );
+
+        // 其他用户看不到
+        let results = service
+            .check_existing_words(2, vec!["apple".into()])
+            .await
+            .unwrap();
+        assert!(!results[0].exists);
+
+        let err = service
+            .check_existing_words(1, vec!["ok".into(), "   ".into()])
+            .await
+            .unwrap_err();
+        let AppError::BusinessError(BusinessError::Validation(fields)) = err else {
+            panic!("expected validation error");
+        };
+        assert_eq!(fields[0].field, "texts[1]");
+    }
+
+    async fn add_word_with_sense<W, G>(service: &WordService<W, G>, text: &str) -> UserWordAggregate
+    where
+        W: WordRepository + Send + Sync + 'static,
+        G: GraphRepository + Send + Sync + 'static,
+    {
+        service
+            .add_to_my_network(
+                1,
+                AddWordInput {
+                    text: text.into(),
+                    tags: vec![],
+                    note: None,
+                    first_sense: Some(SenseInput {
+                        text: format!("meaning of {text}"),
+                        is_primary: true,
+                        sort_order: 0,
+                        note: None,
+                        source_url: None,
+                    }),
+                },
+            )
+            .await
+            .unwrap()
+            .aggregate
+    }
+
+    #[tokio::test]
+    async fn export_word_bundles_senses_links_and_related_words() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let graph = InMemoryGraphRepository::default();
+        let service = WordService::new(InMemoryWordRepository::default(), graph.clone());
+        let bank = add_word_with_sense(&service, "bank").await;
+        let shore = add_word_with_sense(&service, "shore").await;
+        let banker = add_word_with_sense(&service, "banker").await;
+        let sense_id = bank.user_word.senses()[0].id().unwrap();
+        graph
+            .create_word_link(
+                1,
+                banker.word.id,
+                bank.word.id,
+                WordLinkKind::RootAffix,
+                None,
+            )
+            .await
+            .unwrap();
+        graph
+            .create_sense_word_link(
+                1,
+                sense_id,
+                bank.word.id,
+                shore.word.id,
+                SenseWordLinkKind::Related,
+                Some("river bank".into()),
+            )
+            .await
+            .unwrap();
+        // 其他用户的关联不会出现在导出中
+        graph
+            .create_word_link(
+                2,
+                bank.word.id,
+                shore.word.id,
+                WordLinkKind::SimilarForm,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let bank_id = bank.user_word.id.unwrap();
+        let export = service.export_word(1, bank_id).await.unwrap();
+
+        assert_eq!(export.word.word.text, "bank");
+        assert_eq!(export.word.user_word.senses().len(), 1);
+        assert!(export.links_available);
+        assert!(!export.links_truncated);
+        assert_eq!(export.word_links.len(), 1);
+        assert_eq!(export.word_links[0].kind, WordLinkKind::RootAffix);
+        assert_eq!(export.sense_links.len(), 1);
+        assert_eq!(export.sense_links[0].sense_id, sense_id);
+        assert_eq!(export.sense_links[0].note.as_deref(), Some("river bank"));
+        let related: Vec<&str> = export
+            .related_words
+            .iter()
+            .map(|word| word.text.as_str())
+            .collect();
+        assert_eq!(related, vec!["shore", "banker"]);
+
+        let err = service.export_word(2, bank_id).await.unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::BusinessError(BusinessError::Word(WordError::NotInNetwork))
+        ));
+    }
+
+    #[tokio::test]
+    async fn senses_with_links_inlines_target_words() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let graph = InMemoryGraphRepository::default();
+        let service = WordService::new(InMemoryWordRepository::default(), graph.clone());
+        let glad = add_word_with_sense(&service, "glad").await;
+        let happy = add_word_with_sense(&service, "happy").await;
+        let sense_id = glad.user_word.senses()[0].id().unwrap();
+        graph
+            .create_sense_word_link(
+                1,
+                sense_id,
+                glad.word.id,
+                happy.word.id,
+                SenseWordLinkKind::Synonym,
+                None,
+            )
+            .await
+            .unwrap();
+        // 目标单词不存在于 Postgres 的关联被跳过
+        graph
+            .create_sense_word_link(
+                1,
+                sense_id,
+                glad.word.id,
+                9999,
+                SenseWordLinkKind::Related,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let result = service
+            .get_senses_with_links(1, glad.user_word.id.unwrap(), SenseOrder::default())
+            .await
+            .unwrap();
+        assert!(result.links_available);
+        assert_eq!(result.word.text, "glad");
+        assert_eq!(result.senses.len(), 1);
+        let links = &result.senses[0].links;
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].target.text, "happy");
+        assert_eq!(links[0].link.kind, SenseWordLinkKind::Synonym);
+
+        let missing = service
+            .get_senses_with_links(2, glad.user_word.id.unwrap(), SenseOrder::default())
+            .await;
+        assert!(matches!(
+            missing,
+            Err(AppError::BusinessError(BusinessError::Word(
+                WordError::NotInNetwork
+            )))
+        ));
+    }
+
+    #[tokio::test]
+    async fn linked_words_attach_link_and_skip_deleted_neighbors() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let graph = InMemoryGraphRepository::default();
+        let service = WordService::new(InMemoryWordRepository::default(), graph.clone());
+        let root = add_word_with_sense(&service, "inject").await;
+        let eject = add_word_with_sense(&service, "eject").await;
+        let reject = add_word_with_sense(&service, "reject").await;
+        for (neighbor, note) in [(&eject, "共享 -ject"), (&reject, "re- 前缀")] {
+            graph
+                .create_word_link(
+                    1,
+                    root.word.id,
+                    neighbor.word.id,
+                    WordLinkKind::RootAffix,
+                    Some(note.into()),
+                )
+                .await
+                .unwrap();
+        }
+        service
+            .remove_from_my_network(1, reject.user_word.id.unwrap())
+            .await
+            .unwrap();
+        // 模拟图与 Postgres 不一致：单词已删除但关联仍在
+        graph
+            .create_word_link(
+                1,
+                root.word.id,
+                reject.word.id,
+                WordLinkKind::RootAffix,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let linked = service
+            .linked_words(1, root.word.id, None, 20, 0)
+            .await
+            .unwrap();
+
+        assert_eq!(linked.len(), 1);
+        assert_eq!(linked[0].word.word.text, "eject");
+        assert_eq!(linked[0].word.user_word.senses().len(), 1);
+        assert_eq!(linked[0].link.kind, WordLinkKind::RootAffix);
+        assert_eq!(linked[0].link.note.as_deref(), Some("共享 -ject"));
+    }
+
+    #[tokio::test]
+    async fn sync_pages_cover_every_word_and_link_exactly_once() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let graph = InMemoryGraphRepository::default();
+        let service = WordService::new(InMemoryWordRepository::default(), graph.clone());
+        let mut words = Vec::new();
+        for text in ["alpha", "beta", "gamma", "delta", "epsilon"] {
+            words.push(add_word_with_sense(&service, text).await);
+        }
+        // 每个单词与下一个单词相连，最后一个连回第一个，跨越页边界
+        for (index, word) in words.iter().enumerate() {
+            let next = &words[(index + 1) % words.len()];
+            graph
+                .create_word_link(
+                    1,
+                    word.word.id,
+                    next.word.id,
+                    WordLinkKind::SimilarForm,
+                    None,
+                )
+                .await
+                .unwrap();
+            graph
+                .create_sense_word_link(
+                    1,
+                    word.user_word.senses()[0].id().unwrap(),
+                    word.word.id,
+                    next.word.id,
+                    SenseWordLinkKind::Related,
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+        service
+            .add_to_my_network(
+                2,
+                AddWordInput {
+                    text: "zeta".into(),
+                    tags: vec![],
+                    note: None,
+                    first_sense: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let mut cursor = None;
+        let mut pages = 0;
+        let (mut word_ids, mut word_links, mut sense_links) = (Vec::new(), Vec::new(), Vec::new());
+        loop {
+            let page = service.sync_page(1, cursor, Some(2)).await.unwrap();
+            pages += 1;
+            assert!(page.links_available);
+            assert!(!page.links_truncated);
+            assert!(page.words.len() <= 2);
+            assert!(
+                page.words
+                    .iter()
+                    .all(|word| word.user_word.senses().len() == 1)
+            );
+            word_ids.extend(page.words.iter().map(|word| word.word.id));
+            word_links.extend(page.word_links.into_iter().map(|link| link.link_id));
+            sense_links.extend(page.sense_links.into_iter().map(|link| link.link_id));
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        assert_eq!(pages, 3);
+        let mut expected: Vec<i64> = words.iter().map(|word| word.word.id).collect();
+        expected.sort_unstable();
+        assert_eq!(word_ids, expected);
+        for ids in [&mut word_links, &mut sense_links] {
+            assert_eq!(ids.len(), 5);
+            ids.sort();
+            ids.dedup();
+            assert_eq!(ids.len(), 5);
+        }
+
+        let invalid = service.sync_page(1, Some("zz".into()), None).await;
+        assert!(matches!(
+            invalid,
+            Err(AppError::BusinessError(BusinessError::Validation(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn set_tags_replaces_the_whole_tag_set() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+        let word = add_word_with_sense(&service, "anchor").await;
+        let id = word.user_word.id.unwrap();
+        service
+            .set_tags(1, id, vec!["noun".into(), "nautical".into()])
+            .await
+            .unwrap();
+
+        let updated = service
+            .set_tags(1, id, vec![" verb ".into(), "noun".into(), "NOUN".into()])
+            .await
+            .unwrap();
+        assert_eq!(updated.user_word.tags(), ["verb", "noun"]);
+        let stored = service
+            .word_repository
+            .find_user_word(1, id, SenseOrder::default())
+            .await
+            .unwrap();
+        assert_eq!(stored.unwrap().user_word.tags(), ["verb", "noun"]);
+
+        let cleared = service.set_tags(1, id, vec![]).await.unwrap();
+        assert!(cleared.user_word.tags().is_empty());
+
+        let invalid = service.set_tags(1, id, vec!["not a tag!".into()]).await;
+        assert!(matches!(
+            invalid,
+            Err(AppError::BusinessError(BusinessError::Validation(_)))
+        ));
+        let foreign = service.set_tags(2, id, vec!["noun".into()]).await;
+        assert!(matches!(
+            foreign,
+            Err(AppError::BusinessError(BusinessError::Word(
+                WordError::NotInNetwork
+            )))
+        ));
+    }
+
+    #[tokio::test]
+    async fn sync_delta_returns_only_words_changed_since() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+        add_word_with_sense(&service, "untouched").await;
+        let edited = add_word_with_sense(&service, "edited").await;
+        let removed = add_word_with_sense(&service, "removed").await;
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        let since = chrono::Utc::now().to_rfc3339();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+
+        service
+            .update_tag_for_words(
+                1,
+                vec![edited.user_word.id.unwrap()],
+                "fresh".into(),
+                TagAction::Add,
+            )
+            .await
+            .unwrap();
+        service
+            .remove_from_my_network(1, removed.user_word.id.unwrap())
+            .await
+            .unwrap();
+
+        let delta = service.sync_delta(1, &since).await.unwrap();
+        assert!(!delta.truncated);
+        assert!(delta.server_time > chrono::DateTime::parse_from_rfc3339(&since).unwrap());
+        let texts: Vec<&str> = delta
+            .changes
+            .updated
+            .iter()
+            .map(|word| word.word.text.as_str())
+            .collect();
+        assert_eq!(texts, ["edited"]);
+        assert_eq!(delta.changes.updated[0].user_word.tags(), ["fresh"]);
+        assert_eq!(delta.changes.deleted.len(), 1);
+        assert_eq!(
+            delta.changes.deleted[0].user_word_id,
+            removed.user_word.id.unwrap()
+        );
+
+        let later = service
+            .sync_delta(1, &delta.server_time.to_rfc3339())
+            .await
+            .unwrap();
+        assert!(later.changes.updated.is_empty() && later.changes.deleted.is_empty());
+        assert!(matches!(
+            service.sync_delta(1, "yesterday").await,
+            Err(AppError::BusinessError(BusinessError::Validation(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn update_word_metadata_changes_only_the_given_fields() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let repository = InMemoryWordRepository::default();
+        let service = WordService::new(repository.clone(), InMemoryGraphRepository::default());
+        let id = add_word_with_sense(&service, "river")
+            .await
+            .user_word
+            .id
+            .unwrap();
+        let stored = || async {
+            let user_word = repository
+                .find_user_word(1, id, SenseOrder::default())
+                .await
+                .unwrap()
+                .unwrap()
+                .user_word;
+            (
+                user_word.tags().to_vec(),
+                user_word.note().map(str::to_string),
+            )
+        };
+        let update = |tags: Option<Vec<&str>>, note: Option<Option<&str>>| WordMetadataUpdate {
+            tags: tags.map(|tags| tags.into_iter().map(str::to_string).collect()),
+            note: note.map(|note| note.map(str::to_string)),
+        };
+
+        let updated = service
+            .update_word_metadata(1, id, update(None, Some(Some("original"))))
+            .await
+            .unwrap();
+        assert_eq!(updated.user_word.senses().len(), 1);
+        let updated = service
+            .update_word_metadata(1, id, update(Some(vec!["Geography", "geography"]), None))
+            .await
+            .unwrap();
+        assert_eq!(updated.user_word.tags(), ["Geography"]);
+        assert_eq!(updated.user_word.note(), Some("original"));
+        assert_eq!(
+            stored().await,
+            (vec!["Geography".to_string()], Some("original".to_string()))
+        );
+
+        // 显式 null 清空备注，标签不变
+        service
+            .update_word_metadata(1, id, update(None, Some(None)))
+            .await
+            .unwrap();
+        assert_eq!(stored().await, (vec!["Geography".to_string()], None));
+
+        let err = service
+            .update_word_metadata(1, id, update(Some(vec!["bad tag!"]), Some(Some("   "))))
+            .await
+            .unwrap_err();
+        let AppError::BusinessError(BusinessError::Validation(fields)) = err else {
+            panic!("expected validation error");
+        };
+        let names: Vec<&str> = fields.iter().map(|f| f.field.as_str()).collect();
+        assert_eq!(names, vec!["tags", "note"]);
+        // 校验失败时不写入任何字段
+        assert_eq!(stored().await, (vec!["Geography".to_string()], None));
+
+        let missing = service
+            .update_word_metadata(2, id, WordMetadataUpdate::default())
+            .await
+            .unwrap_err();
+        assert_eq!(missing.code(), 4202);
+    }
+
+    #[tokio::test]
+    async fn ownership_is_revealed_only_when_configured() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let repository = InMemoryWordRepository::default();
+        let safe = WordService::new(repository.clone(), InMemoryGraphRepository::default());
+        let debug = WordService::new(repository, InMemoryGraphRepository::default())
+            .with_reveal_ownership(true);
+        let owned = add_word_with_sense(&safe, "lantern").await;
+        let id = owned.user_word.id.unwrap();
+
+        // 4202 NotInNetwork，4206 NotOwned
+        for (service, expected) in [(&safe, 4202), (&debug, 4206)] {
+            let tags = service.set_tags(2, id, vec![]).await.unwrap_err();
+            assert_eq!(tags.code(), expected, "set_tags");
+            let detail = service
+                .get_senses_with_links(2, id, SenseOrder::default())
+                .await
+                .unwrap_err();
+            assert_eq!(detail.code(), expected, "get_senses_with_links");
+            let removed = service.remove_from_my_network(2, id).await.unwrap_err();
+            assert_eq!(removed.code(), expected, "remove_from_my_network");
+        }
+        // 确实不存在的单词在调试模式下仍是 NotInNetwork
+        let missing = debug.export_word(2, id + 100).await.unwrap_err();
+        assert_eq!(missing.code(), 4202);
+        assert!(debug.export_word(1, id).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn add_many_to_network_reports_each_item_separately() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let graph = InMemoryGraphRepository::default();
+        let service = WordService::new(InMemoryWordRepository::default(), graph.clone());
+        let existing = add_word_with_sense(&service, "river").await;
+        let word = |text: &str, tags: Vec<String>, sense: Option<&str>| {
+            BatchWordInput::from(AddWordInput {
+                text: text.into(),
+                tags,
+                note: None,
+                first_sense: sense.map(|text| SenseInput {
+                    text: text.into(),
+                    is_primary: true,
+                    sort_order: 0,
+                    note: None,
+                    source_url: None,
+                }),
+            })
+        };
+
+        let items = service
+            .add_many_to_network(
+                1,
+                vec![
+                    word("lake", vec!["nature".into()], Some("a body of water")),
+                    word("   ", vec![], None),
+                    // 已有的义项文本被跳过，不重复写入
+                    word("River", vec![], Some("meaning of river")),
+                    word("pond", vec!["bad tag!".into()], None),
+                    // 同一批里重复的义项文本同样只写入一次
+                    word("Lake", vec![], Some("a body of water")),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let indexes: Vec<usize> = items.iter().map(|item| item.index).collect();
+        assert_eq!(indexes, vec![0, 1, 2, 3, 4]);
+        let lake = items[0].result.as_ref().unwrap();
+        assert!(lake.created);
+        assert_eq!(lake.aggregate.user_word.senses().len(), 1);
+        assert_eq!(items[0].deduplicated_senses, 0);
+        assert_eq!(items[1].deduplicated_senses, 0);
+        assert!(matches!(
+            &items[1].result,
+            Err(AppError::BusinessError(BusinessError::Validation(fields))) if fields[0].field == "text"
+        ));
+        let river = items[2].result.as_ref().unwrap();
+        assert!(!river.created);
+        assert_eq!(river.aggregate.user_word.id, existing.user_word.id);
+        assert_eq!(river.aggregate.user_word.senses().len(), 1);
+        assert_eq!(items[2].deduplicated_senses, 1);
+        assert_eq!(
+            river.canonical_match.as_ref().unwrap().existing_text,
+            "river"
+        );
+        assert!(matches!(
+            &items[3].result,
+            Err(AppError::BusinessError(BusinessError::Validation(fields))) if fields[0].field == "tags"
+        ));
+        // 同一批里归并到前面的项
+        let merged = items[4].result.as_ref().unwrap();
+        assert_eq!(merged.aggregate.user_word.id, lake.aggregate.user_word.id);
+        assert_eq!(merged.aggregate.user_word.senses().len(), 1);
+        assert_eq!(items[4].deduplicated_senses, 1);
+        assert_eq!(
+            merged.canonical_match.as_ref().unwrap().existing_text,
+            "lake"
+        );
+
+        let words = service
+            .search_in_my_network(1, SearchOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(words.len(), 2);
+        // 预置单词 2 次；三个成功项各建单词节点与义项节点
+        assert_eq!(graph.calls(), 2 + 3 + 3);
+    }
+
+    #[tokio::test]
+    async fn add_many_to_network_writes_every_listed_sense() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+        let sense = |text: &str, is_primary: bool, sort_order: i32| SenseInput {
+            text: text.into(),
+            is_primary,
+            sort_order,
+            note: None,
+            source_url: None,
+        };
+        let word = |text: &str, senses: Vec<SenseInput>| BatchWordInput {
+            word: AddWordInput {
+                text: text.into(),
+                tags: vec![],
+                note: None,
+                first_sense: Some(sense("to put in", true, 0)),
+            },
+            senses,
+        };
+
+        let items = service
+            .add_many_to_network(
+                1,
+                vec![
+                    // 导出文档的 senses 含主义项本身，按文本跳过
+                    word(
+                        "inject",
+                        vec![sense("to put in", true, 0), sense("to introduce", false, 1)],
+                    ),
+                    word("eject", vec![sense("   ", false, 1)]),
+                ],
+            )
+            .await
+            .unwrap();
+
+        let inject = items[0].result.as_ref().unwrap();
+        let texts: Vec<&str> = inject
+            .aggregate
+            .user_word
+            .senses()
+            .iter()
+            .map(|sense| sense.text())
+            .collect();
+        assert_eq!(texts, vec!["to put in", "to introduce"]);
+        assert_eq!(items[0].deduplicated_senses, 1);
+        assert!(matches!(
+            &items[1].result,
+            Err(AppError::BusinessError(BusinessError::Validation(fields)))
+                if fields[0].field == "senses[0].text"
+        ));
+    }
+
+    #[tokio::test]
+    async fn add_many_to_network_rejects_empty_and_oversized_batches() {
+        let service = WordService::new(StubWordRepository, StubGraphRepository);
+        let input = || {
+            BatchWordInput::from(AddWordInput {
+                text: "word".into(),
+                tags: vec![],
+                note: None,
+                first_sense: None,
+            })
+        };
+        for inputs in [
+            Vec::new(),
+            (0..=MAX_BATCH_ADD_WORDS).map(|_| input()).collect(),
+        ] {
+            let err = service.add_many_to_network(1, inputs).await.unwrap_err();
+            assert!(matches!(
+                err,
+                AppError::BusinessError(BusinessError::Validation(_))
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn creation_beyond_rate_limit_is_rejected_with_retry_hint() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+        use crate::repository::rate_limit::InMemoryRateLimitStore;
+
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        )
+        .with_creation_rate_limit(
+            Arc::new(InMemoryRateLimitStore::default()),
+            2,
+            Duration::from_secs(60),
+        );
+        add_word_with_sense(&service, "one").await;
+        add_word_with_sense(&service, "two").await;
+
+        let err = service
+            .add_to_my_network(
+                1,
+                AddWordInput {
+                    text: "three".into(),
+                    tags: vec![],
+                    note: None,
+                    first_sense: None,
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::BusinessError(BusinessError::Word(WordError::CreationRateLimited(
+                secs
+            ))) if (1..=60).contains(&secs)
+        ));
+
+        // 读操作与其他用户不受影响
+        let words = service
+            .search_in_my_network(1, SearchOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(words.len(), 2);
+        service
+            .add_to_my_network(
+                2,
+                AddWordInput {
+                    text: "three".into(),
+                    tags: vec![],
+                    note: None,
+                    first_sense: None,
+                },
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn senses_with_links_survives_graph_failure() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let words = InMemoryWordRepository::default();
+        let seeded = add_word_with_sense(
+            &WordService::new(words.clone(), InMemoryGraphRepository::default()),
+            "glad",
+        )
+        .await;
+        let service = WordService::new(words, InMemoryGraphRepository::failing());
+
+        let result = service
+            .get_senses_with_links(1, seeded.user_word.id.unwrap(), SenseOrder::default())
+            .await
+            .unwrap();
+        assert!(!result.links_available);
+        assert_eq!(result.senses.len(), 1);
+        assert!(result.senses[0].links.is_empty());
+    }
+
+    #[tokio::test]
+    async fn word_detail_and_export_degrade_when_graph_errors() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let words = InMemoryWordRepository::default();
+        let seeded = add_word_with_sense(
+            &WordService::new(words.clone(), InMemoryGraphRepository::default()),
+            "river",
+        )
+        .await;
+        let user_word_id = seeded.user_word.id.unwrap();
+        let graph = InMemoryGraphRepository::failing();
+        let service = WordService::new(words, graph.clone());
+
+        let detail = service
+            .get_senses_with_links(1, user_word_id, SenseOrder::CreatedAtDesc)
+            .await
+            .unwrap();
+        assert_eq!(detail.word.text, "river");
+        assert_eq!(detail.user_word_id, user_word_id);
+        assert_eq!(detail.senses[0].sense.text(), "meaning of river");
+        assert!(!detail.links_available);
+
+        let export = service.export_word(1, user_word_id).await.unwrap();
+        assert_eq!(export.word.word.text, "river");
+        assert!(!export.links_available);
+        assert!(export.word_links.is_empty() && export.sense_links.is_empty());
+        // 两次读取各尝试过图库
+        assert!(graph.calls() >= 2);
+    }
+
+    #[tokio::test]
+    async fn suggest_duplicate_words_clamps_threshold() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+        for text in ["email", "e-mail", "banana"] {
+            add_word_with_sense(&service, text).await;
+        }
+
+        let pairs = service
+            .suggest_duplicate_words(1, None, None)
+            .await
+            .unwrap();
+        assert_eq!(pairs.len(), 1);
+        assert_eq!(pairs[0].first.text, "e-mail");
+        assert_eq!(pairs[0].second.text, "email");
+
+        // 阈值 0 会被钳制到下限，不会把所有单词两两配对
+        let clamped = service
+            .suggest_duplicate_words(1, Some(0.0), Some(1000))
+            .await
+            .unwrap();
+        assert_eq!(clamped.len(), 1);
+        let fallback = service
+            .suggest_duplicate_words(1, Some(f32::NAN), None)
+            .await
+            .unwrap();
+        assert_eq!(fallback.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn list_words_by_tag_returns_empty_page_for_unused_tag() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+        service
+            .add_to_my_network(
+                1,
+                AddWordInput {
+                    text: "apple".into(),
+                    tags: vec!["fruit".into()],
+                    note: None,
+                    first_sense: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        let page = service
+            .list_words_by_tag(1, " fruit ".into(), 1, 20, SearchSort::Alphabetical, None)
+            .await
+            .unwrap();
+        assert_eq!(page.total, 1);
+        assert_eq!(page.items[0].word.text, "apple");
+
+        let empty = service
+            .list_words_by_tag(1, "unused".into(), 1, 20, SearchSort::Recent, None)
+            .await
+            .unwrap();
+        assert!(empty.items.is_empty());
+        assert_eq!(empty.total, 0);
+
+        let invalid = service
+            .list_words_by_tag(1, "not a tag".into(), 1, 20, SearchSort::Alphabetical, None)
+            .await;
+        assert!(invalid.is_err());
+    }
+
+    #[tokio::test]
+    async fn list_words_by_tag_pages_with_cursor() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+        for text in ["apple", "banana", "cherry"] {
+            service
+                .add_to_my_network(
+                    1,
+                    AddWordInput {
+                        text: text.into(),
+                        tags: vec!["fruit".into()],
+                        note: None,
+                        first_sense: None,
+                    },
+                )
+                .await
+                .unwrap();
+        }
+
+        let first = service
+            .list_words_by_tag(1, "fruit".into(), 1, 2, SearchSort::Alphabetical, None)
+            .await
+            .unwrap();
+        assert_eq!(first.items.len(), 2);
+        let second = service
+            .list_words_by_tag(
+                1,
+                "fruit".into(),
+                1,
+                2,
+                SearchSort::Alphabetical,
+                first.next_cursor,
+            )
+            .await
+            .unwrap();
+        let texts: Vec<&str> = second.items.iter().map(|a| a.word.text.as_str()).collect();
+        assert_eq!(texts, vec!["cherry"]);
+        assert_eq!(second.total, 3);
+        assert!(second.next_cursor.is_none());
+
+        let invalid = service
+            .list_words_by_tag(
+                1,
+                "fruit".into(),
+                1,
+                2,
+                SearchSort::Recent,
+                Some("00".into()),
+            )
+            .await;
+        assert!(matches!(
+            invalid,
+            Err(AppError::BusinessError(BusinessError::Validation(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn add_and_remove_skip_graph_when_disabled() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let graph = InMemoryGraphRepository::disabled();
+        let service = WordService::new(InMemoryWordRepository::default(), graph.clone());
+
+        let added = service
+            .add_to_my_network(
+                1,
+                AddWordInput {
+                    text: "offline".into(),
+                    tags: vec![],
+                    note: None,
+                    first_sense: Some(SenseInput {
+                        text: "not connected".into(),
+                        is_primary: true,
+                        sort_order: 0,
+                        note: None,
+                        source_url: None,
+                    }),
+                },
+            )
+            .await
+            .unwrap();
+        assert!(added.created);
+        assert_eq!(added.aggregate.user_word.senses().len(), 1);
+
+        service
+            .remove_from_my_network(1, added.aggregate.user_word.id.unwrap())
+            .await
+            .unwrap();
+        assert_eq!(graph.calls(), 0);
+    }
+
+    #[tokio::test]
+    async fn delete_word_link_reports_removed_count() {
+        use crate::repository::memory::InMemoryGraphRepository;
+
+        let graph = InMemoryGraphRepository::default();
+        graph
+            .create_word_link(1, 10, 20, WordLinkKind::SimilarForm, None)
+            .await
+            .unwrap();
+
+        let removed = graph
+            .delete_word_link(1, 20, 10, WordLinkKind::SimilarForm)
+            .await
+            .unwrap();
+        let missing = graph
+            .delete_word_link(1, 20, 10, WordLinkKind::SimilarForm)
+            .await
+            .unwrap();
+
+        assert_eq!(removed, 1);
+        assert_eq!(missing, 0);
+    }
+
+    #[tokio::test]
+    async fn remove_from_my_network_fails_when_deletes_remove_nothing() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let graph = InMemoryGraphRepository::ignoring_deletes();
+        let service = WordService::new(InMemoryWordRepository::default(), graph.clone());
+        let added = service
+            .add_to_my_network(
+                1,
+                AddWordInput {
+                    text: "apple".into(),
+                    tags: vec![],
+                    note: None,
+                    first_sense: None,
+                },
+            )
+            .await
+            .unwrap();
+        let word_id = added.aggregate.word.id;
+        for target in 1..=3 {
+            graph
+                .create_word_link(
+                    1,
+                    word_id,
+                    word_id + target,
+                    WordLinkKind::SimilarForm,
+                    None,
+                )
+                .await
+                .unwrap();
         }
 
-        async fn delete_sense_word_link(
-            &self,
-            _user_id: i64,
-            _sense_id: i64,
-            _target_word_id: i64,
-            _kind: crate::repository::graph::SenseWordLinkKind,
-        ) -> crate::repository::graph::GraphResult<()> {
-            Ok(())
+        let user_word_id = added.aggregate.user_word.id.unwrap();
+        let err = service
+            .remove_from_my_network(1, user_word_id)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, AppError::InternalError(_)));
+        assert_eq!(graph.word_links().len(), 3);
+        // 图清理失败时保留 Postgres 中的单词，便于重试
+        assert!(
+            service
+                .word_repository
+                .find_user_word(1, user_word_id, SenseOrder::default())
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn search_orders_by_link_degree() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let graph = InMemoryGraphRepository::default();
+        let service = WordService::new(InMemoryWordRepository::default(), graph.clone());
+        let mut ids = HashMap::new();
+        for text in ["apple", "banana", "cherry", "date"] {
+            let added = service
+                .add_to_my_network(
+                    1,
+                    AddWordInput {
+                        text: text.into(),
+                        tags: vec![],
+                        note: None,
+                        first_sense: None,
+                    },
+                )
+                .await
+                .unwrap();
+            ids.insert(text, added.aggregate.word.id);
+        }
+        // cherry: 3，banana: 2，date: 2（含自己的义项发出的关联），apple: 1
+        for (a, b) in [
+            ("cherry", "banana"),
+            ("cherry", "apple"),
+            ("cherry", "date"),
+        ] {
+            graph
+                .create_word_link(1, ids[a], ids[b], WordLinkKind::SimilarForm, None)
+                .await
+                .unwrap();
         }
+        graph
+            .create_sense_word_link(
+                1,
+                99,
+                ids["date"],
+                ids["banana"],
+                SenseWordLinkKind::Related,
+                None,
+            )
+            .await
+            .unwrap();
 
-        async fn list_sense_word_links(
-            &self,
-            _filter: crate::repository::graph::SenseLinkFilter,
-        ) -> crate::repository::graph::GraphResult<Vec<crate::repository::graph::SenseWordLinkRecord>>
-        {
-            Ok(vec![])
+        let found = service
+            .search_in_my_network(
+                1,
+                SearchOptions {
+                    sort: Some(SearchSort::Degree),
+                    ..SearchOptions::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let texts: Vec<&str> = found.iter().map(|a| a.word.text.as_str()).collect();
+        assert_eq!(texts, vec!["cherry", "banana", "date", "apple"]);
+    }
+
+    #[tokio::test]
+    async fn degree_sort_ranks_every_candidate_beyond_the_first_pages() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let graph = InMemoryGraphRepository::default();
+        let service = WordService::new(InMemoryWordRepository::default(), graph.clone());
+        let mut word_ids = Vec::new();
+        for index in 0..=520 {
+            // 字母序最靠后的 zzz 关联最多
+            let text = if index == 520 {
+                "zzz".to_string()
+            } else {
+                format!("w{index:04}")
+            };
+            let added = service
+                .add_to_my_network(
+                    1,
+                    AddWordInput {
+                        text,
+                        tags: vec!["bulk".into()],
+                        note: None,
+                        first_sense: None,
+                    },
+                )
+                .await
+                .unwrap();
+            word_ids.push(added.aggregate.word.id);
+        }
+        let zzz = *word_ids.last().unwrap();
+        for target in &word_ids[..2] {
+            graph
+                .create_word_link(1, zzz, *target, WordLinkKind::SimilarForm, None)
+                .await
+                .unwrap();
         }
 
-        async fn remove_links_for_sense(
-            &self,
-            _sense_id: i64,
-        ) -> crate::repository::graph::GraphResult<()> {
-            Ok(())
+        let first = service
+            .search_page(
+                1,
+                SearchOptions {
+                    sort: Some(SearchSort::Degree),
+                    limit: 3,
+                    ..SearchOptions::default()
+                },
+            )
+            .await
+            .unwrap();
+        let texts: Vec<&str> = first.items.iter().map(|a| a.word.text.as_str()).collect();
+        assert_eq!(texts, ["zzz", "w0000", "w0001"]);
+        assert_eq!(first.total, 521);
+
+        let last = service
+            .list_words_by_tag(1, "bulk".into(), 6, 100, SearchSort::Degree, None)
+            .await
+            .unwrap();
+        assert_eq!(last.total, 521);
+        assert_eq!(last.items.len(), 21);
+        assert_eq!(last.items.last().unwrap().word.text, "w0519");
+    }
+
+    #[tokio::test]
+    async fn degree_sort_requires_graph() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::disabled(),
+        );
+
+        let err = service
+            .search_in_my_network(
+                1,
+                SearchOptions {
+                    sort: Some(SearchSort::Degree),
+                    ..SearchOptions::default()
+                },
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            AppError::BusinessError(BusinessError::Link(LinkError::GraphDisabled))
+        ));
+    }
+
+    #[tokio::test]
+    async fn list_words_filters_by_whether_words_have_links() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let graph = InMemoryGraphRepository::default();
+        let service = WordService::new(InMemoryWordRepository::default(), graph.clone());
+        let mut ids = HashMap::new();
+        for text in ["apple", "banana", "cherry", "date"] {
+            let added = service
+                .add_to_my_network(
+                    1,
+                    AddWordInput {
+                        text: text.into(),
+                        tags: vec![],
+                        note: None,
+                        first_sense: None,
+                    },
+                )
+                .await
+                .unwrap();
+            ids.insert(text, added.aggregate.word.id);
         }
+        // apple 与 banana 互相关联，date 只被义项指向，cherry 没有关联
+        graph
+            .create_word_link(
+                1,
+                ids["apple"],
+                ids["banana"],
+                WordLinkKind::SimilarForm,
+                None,
+            )
+            .await
+            .unwrap();
+        graph
+            .create_sense_word_link(
+                1,
+                99,
+                ids["apple"],
+                ids["date"],
+                SenseWordLinkKind::Related,
+                None,
+            )
+            .await
+            .unwrap();
 
-        async fn upsert_node_word(
-            &self,
-            _word_id: i64,
-        ) -> crate::repository::graph::GraphResult<()> {
-            Ok(())
+        let list = |has_links: Option<bool>, page_size: u32| {
+            service.list_words_in_range(1, None, None, has_links, 1, page_size)
+        };
+        let texts = |page: &WordPage| -> Vec<String> {
+            page.items.iter().map(|a| a.word.text.clone()).collect()
+        };
+
+        let linked = list(Some(true), 20).await.unwrap();
+        assert_eq!(texts(&linked), ["apple", "banana", "date"]);
+        assert_eq!(linked.total, 3);
+        let isolated = list(Some(false), 20).await.unwrap();
+        assert_eq!(texts(&isolated), ["cherry"]);
+        assert_eq!(isolated.total, 1);
+        // 过滤后再分页，total 为过滤后的数量
+        let first_page = list(Some(true), 2).await.unwrap();
+        assert_eq!(texts(&first_page), ["apple", "banana"]);
+        assert_eq!(first_page.total, 3);
+        assert_eq!(list(None, 20).await.unwrap().total, 4);
+
+        let disabled = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::disabled(),
+        );
+        let err = disabled
+            .list_words_in_range(1, None, None, Some(false), 1, 20)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::BusinessError(BusinessError::Link(LinkError::GraphDisabled))
+        ));
+    }
+
+    #[tokio::test]
+    async fn list_words_counts_outgoing_sense_links_as_linked() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let graph = InMemoryGraphRepository::default();
+        let service = WordService::new(InMemoryWordRepository::default(), graph.clone());
+        let mut ids = HashMap::new();
+        for text in ["bank", "shore"] {
+            let added = service
+                .add_to_my_network(
+                    1,
+                    AddWordInput {
+                        text: text.into(),
+                        tags: vec![],
+                        note: None,
+                        first_sense: None,
+                    },
+                )
+                .await
+                .unwrap();
+            ids.insert(text, added.aggregate.word.id);
         }
+        // bank 的义项指向网络外的单词，bank 自身不是任何关联的目标
+        graph
+            .create_sense_word_link(1, 99, ids["bank"], 9_999, SenseWordLinkKind::Related, None)
+            .await
+            .unwrap();
 
-        async fn upsert_node_sense(
-            &self,
-            _sense_id: i64,
-            _user_id: i64,
-        ) -> crate::repository::graph::GraphResult<()> {
-            Ok(())
+        let linked = service
+            .list_words_in_range(1, None, None, Some(true), 1, 20)
+            .await
+            .unwrap();
+        assert_eq!(linked.items[0].word.text, "bank");
+        assert_eq!(linked.total, 1);
+        let isolated = service
+            .list_words_in_range(1, None, None, Some(false), 1, 20)
+            .await
+            .unwrap();
+        assert_eq!(isolated.items[0].word.text, "shore");
+        assert_eq!(isolated.total, 1);
+    }
+
+    #[tokio::test]
+    async fn list_words_filters_links_beyond_one_graph_batch() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let graph = InMemoryGraphRepository::default();
+        let service = WordService::new(InMemoryWordRepository::default(), graph.clone());
+        let mut word_ids = Vec::new();
+        for index in 0..=EXPORT_LINK_BATCH_WORDS {
+            let added = service
+                .add_to_my_network(
+                    1,
+                    AddWordInput {
+                        text: format!("word{index:04}"),
+                        tags: vec![],
+                        note: None,
+                        first_sense: None,
+                    },
+                )
+                .await
+                .unwrap();
+            word_ids.push(added.aggregate.word.id);
         }
+        // 首尾两个单词分属不同批次
+        graph
+            .create_word_link(
+                1,
+                word_ids[0],
+                word_ids[EXPORT_LINK_BATCH_WORDS],
+                WordLinkKind::SimilarForm,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let linked = service
+            .list_words_in_range(1, None, None, Some(true), 1, 20)
+            .await
+            .unwrap();
+        let texts: Vec<&str> = linked.items.iter().map(|a| a.word.text.as_str()).collect();
+        assert_eq!(texts, ["word0000", "word0500"]);
+        let isolated = service
+            .list_words_in_range(1, None, None, Some(false), 1, 20)
+            .await
+            .unwrap();
+        assert_eq!(isolated.total, EXPORT_LINK_BATCH_WORDS as u64 - 1);
     }
 
     #[tokio::test]
-    async fn add_to_my_network_maps_validation_errors() {
+    async fn list_words_filters_links_across_the_whole_range() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let graph = InMemoryGraphRepository::default();
+        let service = WordService::new(InMemoryWordRepository::default(), graph.clone());
+        let mut word_ids = Vec::new();
+        for index in 0..520 {
+            let added = service
+                .add_to_my_network(
+                    1,
+                    AddWordInput {
+                        text: format!("w{index:04}"),
+                        tags: vec![],
+                        note: None,
+                        first_sense: None,
+                    },
+                )
+                .await
+                .unwrap();
+            word_ids.push(added.aggregate.word.id);
+        }
+        graph
+            .create_word_link(1, word_ids[0], word_ids[1], WordLinkKind::SimilarForm, None)
+            .await
+            .unwrap();
+
+        let last = service
+            .list_words_in_range(1, None, None, Some(false), 6, 100)
+            .await
+            .unwrap();
+        assert_eq!(last.total, 518);
+        assert_eq!(last.items.len(), 18);
+        assert_eq!(last.items.last().unwrap().word.text, "w0519");
+    }
+
+    #[tokio::test]
+    async fn bulk_tagging_rejects_words_outside_network() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+        let input = |text: &str| AddWordInput {
+            text: text.into(),
+            tags: vec![],
+            note: None,
+            first_sense: None,
+        };
+        let mine = service.add_to_my_network(1, input("apple")).await.unwrap();
+        let theirs = service.add_to_my_network(2, input("pear")).await.unwrap();
+        let mine_id = mine.aggregate.user_word.id.unwrap();
+        let theirs_id = theirs.aggregate.user_word.id.unwrap();
+
+        let err = service
+            .add_tag_to_words(1, vec![mine_id, theirs_id], "fruit".into())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::BusinessError(BusinessError::Word(WordError::NotInNetwork))
+        ));
+
+        let changed = service
+            .add_tag_to_words(1, vec![mine_id, mine_id], "fruit".into())
+            .await
+            .unwrap();
+        assert_eq!(changed, 1);
+        let removed = service
+            .remove_tag_from_words(1, vec![mine_id], "fruit".into())
+            .await
+            .unwrap();
+        assert_eq!(removed, 1);
+    }
+
+    #[tokio::test]
+    async fn bulk_tagging_reports_tag_and_ids_errors_together() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+
+        let err = service
+            .add_tag_to_words(1, vec![], "bad tag!".into())
+            .await
+            .unwrap_err();
+
+        let AppError::BusinessError(BusinessError::Validation(fields)) = err else {
+            panic!("expected validation error");
+        };
+        let names: Vec<&str> = fields.iter().map(|f| f.field.as_str()).collect();
+        assert_eq!(names, vec!["tag", "user_word_ids"]);
+    }
+
+    #[tokio::test]
+    async fn bulk_tagging_rejects_oversized_id_list_before_lookup() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+
+        let err = service
+            .add_tag_to_words(1, vec![1; 1_000_000], "fruit".into())
+            .await
+            .unwrap_err();
+
+        let AppError::BusinessError(BusinessError::Validation(fields)) = err else {
+            panic!("expected validation error");
+        };
+        assert_eq!(fields.len(), 1);
+        assert_eq!(fields[0].field, "user_word_ids");
+        assert!(
+            fields[0].message.contains("1000000"),
+            "{}",
+            fields[0].message
+        );
+    }
+
+    #[tokio::test]
+    async fn add_to_my_network_treats_nfc_and_nfd_as_one_word() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+        let input = |text: &str| AddWordInput {
+            text: text.into(),
+            tags: vec![],
+            note: None,
+            first_sense: None,
+        };
+
+        let composed = service
+            .add_to_my_network(1, input("caf\u{e9}"))
+            .await
+            .unwrap();
+        let decomposed = service
+            .add_to_my_network(1, input("cafe\u{301}"))
+            .await
+            .unwrap();
+
+        assert!(composed.created);
+        assert!(!decomposed.created);
+        assert_eq!(
+            composed.aggregate.user_word.id,
+            decomposed.aggregate.user_word.id
+        );
+        assert_eq!(decomposed.aggregate.word.text, "caf\u{e9}");
+    }
+
+    #[tokio::test]
+    async fn list_words_in_range_rejects_inverted_and_malformed_bounds() {
         let service = WordService::new(StubWordRepository, StubGraphRepository);
-        let result = service
+
+        let cases = [
+            (
+                Some("2025-02-01T00:00:00Z"),
+                Some("2025-01-01T00:00:00Z"),
+                "created_from",
+            ),
+            (Some("yesterday"), None, "created_from"),
+            (None, Some("2025-13-01"), "created_to"),
+        ];
+        for (from, to, field) in cases {
+            match service.list_words_in_range(1, from, to, None, 1, 20).await {
+                Err(AppError::BusinessError(BusinessError::Validation(fields))) => {
+                    assert_eq!(fields[0].field, field);
+                }
+                other => panic!("unexpected result: {other:?}"),
+            }
+        }
+
+        let same_instant = service
+            .list_words_in_range(
+                1,
+                Some("2025-01-01T08:00:00+08:00"),
+                Some("2025-01-01T00:00:00Z"),
+                None,
+                1,
+                20,
+            )
+            .await
+            .unwrap();
+        assert_eq!(same_instant.total, 0);
+    }
+
+    #[tokio::test]
+    async fn re_adding_canonically_equal_word_reports_match() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+        let input = |text: &str| AddWordInput {
+            text: text.into(),
+            tags: vec![],
+            note: None,
+            first_sense: None,
+        };
+
+        let first = service
+            .add_to_my_network(1, input("graph database"))
+            .await
+            .unwrap();
+        assert!(first.canonical_match.is_none());
+
+        let again = service
+            .add_to_my_network(1, input("Graph  Database"))
+            .await
+            .unwrap();
+        assert!(!again.created);
+        assert_eq!(again.aggregate.user_word.id, first.aggregate.user_word.id);
+        assert_eq!(
+            again.canonical_match,
+            Some(CanonicalMatch {
+                existing_text: "graph database".into(),
+            })
+        );
+
+        // 其他用户首次添加同一单词不算命中自己网络中的已有条目
+        let other = service
+            .add_to_my_network(2, input("Graph Database"))
+            .await
+            .unwrap();
+        assert!(other.created);
+        assert!(other.canonical_match.is_none());
+    }
+
+    #[tokio::test]
+    async fn search_falls_back_to_stored_scope_preference() {
+        use crate::repository::memory::{
+            InMemoryGraphRepository, InMemoryPreferencesRepository, InMemoryWordRepository,
+        };
+
+        let preferences = InMemoryPreferencesRepository::default();
+        preferences
+            .set(
+                1,
+                UserPreferences {
+                    default_scope: Some(SearchScope::Sense),
+                    default_sort: None,
+                },
+            )
+            .await
+            .unwrap();
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        )
+        .with_preferences(Arc::new(preferences));
+        service
             .add_to_my_network(
                 1,
                 AddWordInput {
-                    text: "hello".into(),
+                    text: "apple".into(),
                     tags: vec![],
                     note: None,
-                    first_sense: None,
+                    first_sense: Some(SenseInput {
+                        text: "a round fruit".into(),
+                        is_primary: true,
+                        sort_order: 0,
+                        note: None,
+                        source_url: None,
+                    }),
                 },
             )
-            .await;
-        assert!(result.is_err());
+            .await
+            .unwrap();
+
+        let stored = service
+            .search_in_my_network(
+                1,
+                SearchOptions {
+                    query: "fruit".into(),
+                    ..SearchOptions::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 1);
+
+        let explicit = service
+            .search_in_my_network(
+                1,
+                SearchOptions {
+                    query: "fruit".into(),
+                    scope: Some(SearchScope::Word),
+                    ..SearchOptions::default()
+                },
+            )
+            .await
+            .unwrap();
+        assert!(explicit.is_empty());
+    }
+
+    #[tokio::test]
+    async fn oversized_search_limit_is_clamped_unless_strict() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let oversized = SearchOptions {
+            query: "meaning".into(),
+            limit: i64::MAX,
+            ..SearchOptions::default()
+        };
+
+        let lenient = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+        add_word_with_sense(&lenient, "apple").await;
+        let found = lenient
+            .search_in_my_network(1, oversized.clone())
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+
+        let strict = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        )
+        .with_strict_limits(true);
+        add_word_with_sense(&strict, "apple").await;
+        let err = strict.search_in_my_network(1, oversized).await.unwrap_err();
+        match err {
+            AppError::BusinessError(BusinessError::Validation(fields)) => {
+                assert_eq!(fields[0].field, "limit");
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+        assert_eq!(
+            strict
+                .search_in_my_network(
+                    1,
+                    SearchOptions {
+                        query: "meaning".into(),
+                        limit: MAX_SEARCH_LIMIT,
+                        ..SearchOptions::default()
+                    },
+                )
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_query_behavior_controls_blank_searches() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        for (behavior, expected) in [
+            (EmptyQueryBehavior::ListAll, 2),
+            (EmptyQueryBehavior::Empty, 0),
+        ] {
+            let service = WordService::new(
+                InMemoryWordRepository::default(),
+                InMemoryGraphRepository::default(),
+            )
+            .with_empty_query_behavior(behavior);
+            add_word_with_sense(&service, "apple").await;
+            add_word_with_sense(&service, "pear").await;
+
+            for query in ["", "   "] {
+                let options = SearchOptions {
+                    query: query.into(),
+                    ..SearchOptions::default()
+                };
+                let found = service
+                    .search_in_my_network(1, options.clone())
+                    .await
+                    .unwrap();
+                assert_eq!(found.len(), expected, "{behavior:?} {query:?}");
+                let page = service.search_page(1, options).await.unwrap();
+                assert_eq!(page.total, expected as u64, "{behavior:?} {query:?}");
+            }
+
+            // 非空关键字不受影响
+            let found = service
+                .search_in_my_network(
+                    1,
+                    SearchOptions {
+                        query: "apple".into(),
+                        ..SearchOptions::default()
+                    },
+                )
+                .await
+                .unwrap();
+            assert_eq!(found.len(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn rewrite_word_text_moves_senses_and_links_to_the_corrected_word() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let repository = InMemoryWordRepository::default();
+        let graph = InMemoryGraphRepository::default();
+        let service = WordService::new(repository.clone(), graph.clone());
+        let typo = add_word_with_sense(&service, "recieve").await;
+        let give = add_word_with_sense(&service, "give").await;
+        let user_word_id = typo.user_word.id.unwrap();
+        graph
+            .create_word_link(
+                1,
+                typo.word.id,
+                give.word.id,
+                WordLinkKind::SimilarForm,
+                None,
+            )
+            .await
+            .unwrap();
+
+        let rewritten = service
+            .rewrite_word_text(1, user_word_id, "receive".into())
+            .await
+            .unwrap();
+        assert_eq!(rewritten.user_word.id, Some(user_word_id));
+        assert_eq!(rewritten.word.text, "receive");
+        assert_ne!(rewritten.word.id, typo.word.id);
+        assert_eq!(rewritten.user_word.senses()[0].text(), "meaning of recieve");
+
+        let links = graph
+            .list_word_links(WordLinkFilter {
+                user_id: 1,
+                kind: None,
+                word_id: rewritten.word.id,
+                limit: 10,
+                offset: 0,
+            })
+            .await
+            .unwrap();
+        assert_eq!(links.len(), 1);
+        // 拼错的单词不再被任何人引用，随之删除
+        assert!(
+            repository
+                .find_words_by_ids(&[typo.word.id])
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        // 键不变时原样返回
+        let same = service
+            .rewrite_word_text(1, user_word_id, "Receive".into())
+            .await
+            .unwrap();
+        assert_eq!(same.word.id, rewritten.word.id);
+
+        let err = service
+            .rewrite_word_text(1, user_word_id, "   ".into())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), 4001);
+        let err = service
+            .rewrite_word_text(2, user_word_id, "accept".into())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), 4202);
+    }
+
+    #[tokio::test]
+    async fn rewrite_word_text_retry_repairs_links_after_a_graph_failure() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let repository = InMemoryWordRepository::default();
+        let graph = InMemoryGraphRepository::default();
+        let service = WordService::new(repository.clone(), graph.clone());
+        let typo = add_word_with_sense(&service, "recieve").await;
+        let give = add_word_with_sense(&service, "give").await;
+        let user_word_id = typo.user_word.id.unwrap();
+        graph
+            .create_word_link(
+                1,
+                typo.word.id,
+                give.word.id,
+                WordLinkKind::SimilarForm,
+                None,
+            )
+            .await
+            .unwrap();
+
+        graph.set_failing(true);
+        let err = service
+            .rewrite_word_text(1, user_word_id, "receive".into())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), 5000);
+        // Postgres 已改挂，原单词在图库跟进前保留
+        let moved = service.get_word(1, user_word_id).await.unwrap();
+        assert_eq!(moved.word.text, "receive");
+        assert_eq!(
+            repository
+                .find_words_by_ids(&[typo.word.id])
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // 键已一致，重试仍补做图库改挂
+        graph.set_failing(false);
+        let retried = service
+            .rewrite_word_text(1, user_word_id, "receive".into())
+            .await
+            .unwrap();
+        assert_eq!(retried.word.id, moved.word.id);
+        let links = graph.word_links();
+        assert_eq!(links.len(), 1);
+        assert!(
+            [links[0].word_a_id, links[0].word_b_id].contains(&moved.word.id)
+                && !([links[0].word_a_id, links[0].word_b_id].contains(&typo.word.id))
+        );
+        assert!(
+            repository
+                .find_words_by_ids(&[typo.word.id])
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            repository
+                .pending_word_repoint(1, user_word_id)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn rewrite_word_text_reports_a_collision_with_an_owned_word() {
+        use crate::repository::memory::{InMemoryGraphRepository, InMemoryWordRepository};
+
+        let service = WordService::new(
+            InMemoryWordRepository::default(),
+            InMemoryGraphRepository::default(),
+        );
+        let typo = add_word_with_sense(&service, "recieve").await;
+        let owned = add_word_with_sense(&service, "receive").await;
+
+        let err = service
+            .rewrite_word_text(1, typo.user_word.id.unwrap(), "receive".into())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), 4207);
+        assert!(matches!(
+            err,
+            AppError::BusinessError(BusinessError::Word(WordError::RewriteCollision(id)))
+                if Some(id) == owned.user_word.id
+        ));
+        // 冲突时不做任何修改
+        let unchanged = service
+            .get_word(1, typo.user_word.id.unwrap())
+            .await
+            .unwrap();
+        assert_eq!(unchanged.word.text, "recieve");
     }
 }
@@ -1,6 +1,8 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::Deserialize;
 use thiserror::Error;
+use unicode_normalization::UnicodeNormalization;
 
 static MULTI_WHITESPACE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"\s+").expect("canonical key whitespace regex must compile"));
@@ -12,9 +14,27 @@ pub enum CanonicalError {
     Empty,
 }
 
-/// Convert arbitrary text into a canonical key format.
+/// Apply Unicode NFC so precomposed and decomposed forms (e.g. `é` vs `e\u{301}`) compare equal.
+pub fn normalize_nfc(input: &str) -> String {
+    input.nfc().collect()
+}
+
+/// How hyphens and spaces relate in a canonical key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CanonicalMode {
+    /// Spaces become hyphens, so `co op` and `co-op` share a key.
+    #[default]
+    Collapse,
+    /// Hyphens stay significant and spaces are kept as single spaces, so
+    /// `co op` and `co-op` are distinct words.
+    PreserveHyphens,
+}
+
+/// Convert arbitrary text into a canonical key format using [`CanonicalMode::Collapse`].
 ///
 /// Normalization steps:
+/// - apply Unicode NFC
 /// - trim leading/trailing whitespace
 /// - collapse consecutive whitespace into a single space
 /// - trim leading/trailing ASCII punctuation
@@ -22,7 +42,18 @@ pub enum CanonicalError {
 /// - replace internal spaces with single hyphen (`-`)
 /// - remove remaining ASCII punctuation, collapsing repeated hyphens
 pub fn canonicalize(input: impl AsRef<str>) -> Result<String, CanonicalError> {
-    let trimmed = input.as_ref().trim();
+    canonicalize_with(input, CanonicalMode::Collapse)
+}
+
+/// Like [`canonicalize`], but with [`CanonicalMode::PreserveHyphens`] internal
+/// spaces are kept as a single space instead of becoming hyphens; spaces next
+/// to a hyphen are dropped (`co - op` → `co-op`).
+pub fn canonicalize_with(
+    input: impl AsRef<str>,
+    mode: CanonicalMode,
+) -> Result<String, CanonicalError> {
+    let composed = normalize_nfc(input.as_ref());
+    let trimmed = composed.trim();
     if trimmed.is_empty() {
         return Err(CanonicalError::Empty);
     }
@@ -36,24 +67,32 @@ pub fn canonicalize(input: impl AsRef<str>) -> Result<String, CanonicalError> {
     }
 
     let lowercase = stripped.to_lowercase();
-    let replaced = lowercase.replace(' ', "-");
+    let replaced = match mode {
+        CanonicalMode::Collapse => lowercase.replace(' ', "-"),
+        CanonicalMode::PreserveHyphens => lowercase,
+    };
     let mut cleaned = String::with_capacity(replaced.len());
-    let mut last_dash = false;
     for ch in replaced.chars() {
-        if ch == '-' {
-            if !last_dash {
-                cleaned.push('-');
-                last_dash = true;
+        match ch {
+            '-' => {
+                if cleaned.ends_with(' ') {
+                    cleaned.pop();
+                }
+                if !cleaned.ends_with('-') {
+                    cleaned.push('-');
+                }
+            }
+            ' ' => {
+                if !cleaned.ends_with(['-', ' ']) {
+                    cleaned.push(' ');
+                }
             }
-        } else if ch.is_ascii_punctuation() {
-            continue;
-        } else {
-            cleaned.push(ch);
-            last_dash = false;
+            ch if ch.is_ascii_punctuation() => continue,
+            ch => cleaned.push(ch),
         }
     }
 
-    let normalized = cleaned.trim_matches('-').to_string();
+    let normalized = cleaned.trim_matches(['-', ' ']).to_string();
     if normalized.is_empty() {
         Err(CanonicalError::Empty)
     } else {
@@ -71,6 +110,14 @@ mod tests {
         assert_eq!(key, "graph-database");
     }
 
+    #[test]
+    fn canonicalize_collapses_nfc_and_nfd_forms() {
+        let composed = canonicalize("Caf\u{e9}").unwrap();
+        let decomposed = canonicalize("Cafe\u{301}").unwrap();
+        assert_eq!(composed, decomposed);
+        assert_eq!(composed, "caf\u{e9}");
+    }
+
     #[test]
     fn canonicalize_rejects_empty() {
         let result = canonicalize("   ");
@@ -82,4 +129,22 @@ mod tests {
         let key = canonicalize("**Hello, World!!").unwrap();
         assert_eq!(key, "hello-world");
     }
+
+    #[test]
+    fn preserve_hyphens_keeps_hyphen_and_space_distinct() {
+        assert_eq!(
+            canonicalize("co op").unwrap(),
+            canonicalize("co-op").unwrap()
+        );
+
+        let spaced = canonicalize_with("Co  Op", CanonicalMode::PreserveHyphens).unwrap();
+        let hyphenated = canonicalize_with("co-op", CanonicalMode::PreserveHyphens).unwrap();
+        assert_eq!(spaced, "co op");
+        assert_eq!(hyphenated, "co-op");
+        assert_ne!(spaced, hyphenated);
+        assert_eq!(
+            canonicalize_with("co - op!", CanonicalMode::PreserveHyphens).unwrap(),
+            "co-op"
+        );
+    }
 }
@@ -66,6 +66,23 @@ pub enum AuthFlowError {
     TokenInvalid,
     #[error("Refresh token disabled")]
     RefreshDisabled,
+    #[error("Insufficient scope")]
+    Forbidden,
+    #[error("Authentication is disabled")]
+    AuthDisabled,
+}
+
+impl AuthFlowError {
+    fn code(&self) -> i32 {
+        match self {
+            AuthFlowError::InvalidCredentials => 4011,
+            AuthFlowError::TokenExpired => 4012,
+            AuthFlowError::TokenInvalid => 4013,
+            AuthFlowError::RefreshDisabled => 4014,
+            AuthFlowError::AuthDisabled => 4015,
+            AuthFlowError::Forbidden => 4031,
+        }
+    }
 }
 
 #[allow(dead_code)]
@@ -79,6 +96,14 @@ pub enum WordError {
     SenseDuplicate,
     #[error("Primary sense conflict")]
     PrimaryConflict,
+    #[error("Too many words created, retry after {0} seconds")]
+    CreationRateLimited(u64),
+    /// 仅在 `words.reveal_ownership` 开启时使用，见 `service::word::ownership_error`
+    #[error("Word belongs to another user")]
+    NotOwned,
+    /// 改写单词文本时目标单词已在网络中，附带其 user_word id，客户端应改为合并
+    #[error("Word already exists in network, merge into it instead")]
+    RewriteCollision(i64),
 }
 
 impl WordError {
@@ -88,6 +113,9 @@ impl WordError {
             WordError::NotInNetwork => 4202,
             WordError::SenseDuplicate => 4203,
             WordError::PrimaryConflict => 4204,
+            WordError::CreationRateLimited(_) => 4205,
+            WordError::NotOwned => 4206,
+            WordError::RewriteCollision(_) => 4207,
         }
     }
 }
@@ -105,6 +133,8 @@ pub enum LinkError {
     TypeInvalid,
     #[error("Link limit exceeded")]
     LimitExceeded,
+    #[error("Graph feature is disabled")]
+    GraphDisabled,
 }
 
 impl LinkError {
@@ -115,6 +145,7 @@ impl LinkError {
             LinkError::TargetNotFound => 4303,
             LinkError::TypeInvalid => 4304,
             LinkError::LimitExceeded => 4305,
+            LinkError::GraphDisabled => 4306,
         }
     }
 }
@@ -157,16 +188,68 @@ pub enum InternalError {
     Unknown,
 }
 
+/// Postgres 取消语句（statement_timeout 到期）时的 SQLSTATE
+const QUERY_CANCELED: &str = "57014";
+
+/// 语句因超过 `database.statement_timeout_seconds` 被服务端取消
+pub fn is_statement_timeout(err: &sqlx::Error) -> bool {
+    err.as_database_error()
+        .and_then(|db_err| db_err.code())
+        .is_some_and(|code| code == QUERY_CANCELED)
+}
+
 #[derive(Debug, Serialize, Clone)]
 pub struct ValidationField {
     pub field: String,
     pub message: String,
 }
 
-#[derive(Serialize)]
+/// 批量接口中单项失败时的错误体，业务码与单独调用时一致
+#[derive(Debug, Serialize)]
 pub struct ErrorResponse {
     pub code: i32,
     pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<ValidationField>>,
+}
+
+impl AppError {
+    /// 与 `error_response` 返回的业务码一致
+    pub fn code(&self) -> i32 {
+        match self {
+            AppError::BusinessError(be) => match be {
+                BusinessError::Validation(_) => 4001,
+                BusinessError::Auth(auth_error) => auth_error.code(),
+                BusinessError::Word(word_error) => word_error.code(),
+                BusinessError::Link(link_error) => link_error.code(),
+                _ => 4000,
+            },
+            AppError::AuthError(_) => 4010,
+            AppError::DbError(_)
+            | AppError::ExternalError(_)
+            | AppError::InternalError(_)
+            | AppError::IoError(_) => 5000,
+        }
+    }
+}
+
+impl From<&AppError> for ErrorResponse {
+    fn from(error: &AppError) -> Self {
+        let code = error.code();
+        let (message, fields) = match error {
+            AppError::BusinessError(BusinessError::Validation(fields)) => {
+                ("参数校验失败".to_string(), Some(fields.clone()))
+            }
+            // 内部错误的细节只写日志，不返回给客户端
+            _ if code == 5000 => ("内部服务错误".to_string(), None),
+            _ => (error.to_string(), None),
+        };
+        Self {
+            code,
+            message,
+            fields,
+        }
+    }
 }
 
 impl ResponseError for AppError {
@@ -175,63 +258,85 @@ impl ResponseError for AppError {
         match self {
             AppError::BusinessError(be) => match be {
                 BusinessError::Validation(fields) => {
-                    let trace_id = crate::util::response::ResponseBuilder::current_trace_id();
+                    let trace_id = ResponseBuilder::current_trace_id();
                     let message = "参数校验失败".to_string();
                     let mut body: ApiResponse<Vec<ValidationField>> =
                         ApiResponse::error_with_trace(4001, message, trace_id);
                     body.data = Some(fields.clone());
-                    HttpResponse::Ok().json(body)
+                    ResponseBuilder::json(HttpResponse::Ok(), &body)
                 }
-                BusinessError::Auth(auth_error) => {
-                    let code = match auth_error {
-                        AuthFlowError::InvalidCredentials => 4011,
-                        AuthFlowError::TokenExpired => 4012,
-                        AuthFlowError::TokenInvalid => 4013,
-                        AuthFlowError::RefreshDisabled => 4014,
-                    };
-                    HttpResponse::Ok().json(ApiResponse::<serde_json::Value>::error_with_trace(
-                        code,
+                BusinessError::Auth(auth_error) => ResponseBuilder::json(
+                    HttpResponse::Ok(),
+                    &ApiResponse::<serde_json::Value>::error_with_trace(
+                        auth_error.code(),
                         auth_error.to_string(),
                         ResponseBuilder::current_trace_id(),
-                    ))
+                    ),
+                ),
+                BusinessError::Word(WordError::CreationRateLimited(retry_after)) => {
+                    let mut body = ApiResponse::<serde_json::Value>::error_with_trace(
+                        4205,
+                        be.to_string(),
+                        ResponseBuilder::current_trace_id(),
+                    );
+                    body.data = Some(serde_json::json!({ "retry_after_secs": retry_after }));
+                    let mut builder = HttpResponse::Ok();
+                    builder.insert_header((actix_web::http::header::RETRY_AFTER, *retry_after));
+                    ResponseBuilder::json(builder, &body)
                 }
-                BusinessError::Word(word_error) => {
-                    HttpResponse::Ok().json(ApiResponse::<serde_json::Value>::error_with_trace(
+                BusinessError::Word(WordError::RewriteCollision(existing)) => {
+                    let mut body = ApiResponse::<serde_json::Value>::error_with_trace(
+                        4207,
+                        be.to_string(),
+                        ResponseBuilder::current_trace_id(),
+                    );
+                    body.data = Some(serde_json::json!({ "existing_user_word_id": existing }));
+                    ResponseBuilder::json(HttpResponse::Ok(), &body)
+                }
+                BusinessError::Word(word_error) => ResponseBuilder::json(
+                    HttpResponse::Ok(),
+                    &ApiResponse::<serde_json::Value>::error_with_trace(
                         word_error.code(),
                         word_error.to_string(),
                         ResponseBuilder::current_trace_id(),
-                    ))
-                }
-                BusinessError::Link(link_error) => {
-                    HttpResponse::Ok().json(ApiResponse::<serde_json::Value>::error_with_trace(
+                    ),
+                ),
+                BusinessError::Link(link_error) => ResponseBuilder::json(
+                    HttpResponse::Ok(),
+                    &ApiResponse::<serde_json::Value>::error_with_trace(
                         link_error.code(),
                         link_error.to_string(),
                         ResponseBuilder::current_trace_id(),
-                    ))
-                }
-                _ => HttpResponse::Ok().json(ApiResponse::<serde_json::Value>::error_with_trace(
-                    4000,
-                    be.to_string(),
-                    ResponseBuilder::current_trace_id(),
-                )),
+                    ),
+                ),
+                _ => ResponseBuilder::json(
+                    HttpResponse::Ok(),
+                    &ApiResponse::<serde_json::Value>::error_with_trace(
+                        4000,
+                        be.to_string(),
+                        ResponseBuilder::current_trace_id(),
+                    ),
+                ),
             },
-            AppError::AuthError(ae) => {
-                HttpResponse::Ok().json(ApiResponse::<serde_json::Value>::error_with_trace(
+            AppError::AuthError(ae) => ResponseBuilder::json(
+                HttpResponse::Ok(),
+                &ApiResponse::<serde_json::Value>::error_with_trace(
                     4010,
                     ae.to_string(),
                     ResponseBuilder::current_trace_id(),
-                ))
-            }
+                ),
+            ),
             AppError::DbError(_)
             | AppError::ExternalError(_)
             | AppError::InternalError(_)
-            | AppError::IoError(_) => {
-                HttpResponse::Ok().json(ApiResponse::<serde_json::Value>::error_with_trace(
+            | AppError::IoError(_) => ResponseBuilder::json(
+                HttpResponse::Ok(),
+                &ApiResponse::<serde_json::Value>::error_with_trace(
                     5000,
                     "内部服务错误",
                     ResponseBuilder::current_trace_id(),
-                ))
-            }
+                ),
+            ),
         }
     }
 }
@@ -275,6 +380,30 @@ mod tests {
         assert!(json["timestamp"].is_number());
     }
 
+    #[actix_rt::test]
+    async fn error_response_matches_http_error_code() {
+        let errors = [
+            AppError::from(BusinessError::from(AuthFlowError::TokenExpired)),
+            AppError::from(BusinessError::from(WordError::AlreadyExists)),
+            AppError::from(BusinessError::Validation(vec![ValidationField {
+                field: "text".into(),
+                message: "不能为空".into(),
+            }])),
+            AppError::from(ExternalError::Timeout),
+        ];
+        for error in errors {
+            let item = ErrorResponse::from(&error);
+            let body = to_bytes(error.error_response().into_body()).await.unwrap();
+            let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+            assert_eq!(json["code"], item.code);
+            assert_eq!(json["message"], item.message);
+        }
+
+        let item = ErrorResponse::from(&AppError::from(ExternalError::Timeout));
+        assert_eq!(item.message, "内部服务错误");
+        assert!(item.fields.is_none());
+    }
+
     #[actix_rt::test]
     async fn word_error_returns_expected_payload() {
         let error = AppError::from(BusinessError::from(WordError::AlreadyExists));
@@ -290,6 +419,27 @@ mod tests {
         assert!(json["timestamp"].is_number());
     }
 
+    #[actix_rt::test]
+    async fn creation_rate_limit_sets_retry_after() {
+        let error = AppError::from(BusinessError::from(WordError::CreationRateLimited(12)));
+        let response = error.error_response();
+        assert_eq!(response.headers().get("retry-after").unwrap(), "12");
+
+        let body = to_bytes(response.into_body()).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], 4205);
+        assert_eq!(json["data"]["retry_after_secs"], 12);
+    }
+
+    #[actix_rt::test]
+    async fn rewrite_collision_returns_the_existing_word() {
+        let error = AppError::from(BusinessError::from(WordError::RewriteCollision(42)));
+        let body = to_bytes(error.error_response().into_body()).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["code"], 4207);
+        assert_eq!(json["data"]["existing_user_word_id"], 42);
+    }
+
     #[actix_rt::test]
     async fn link_error_returns_expected_payload() {
         let error = AppError::from(BusinessError::from(LinkError::SelfForbidden));
@@ -304,4 +454,20 @@ mod tests {
         assert!(json["traceId"].is_string());
         assert!(json["timestamp"].is_number());
     }
+
+    #[sqlx::test(migrations = false)]
+    #[ignore = "requires DATABASE_URL"]
+    async fn cancelled_statement_is_recognised_as_timeout(pool: sqlx::PgPool) {
+        let mut conn = pool.acquire().await.unwrap();
+        sqlx::query("SET statement_timeout = '20ms'")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        let err = sqlx::query("SELECT pg_sleep(1)")
+            .execute(&mut *conn)
+            .await
+            .unwrap_err();
+        assert!(is_statement_timeout(&err));
+        assert!(!is_statement_timeout(&sqlx::Error::RowNotFound));
+    }
 }
@@ -0,0 +1,68 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
+
+// 请求作用域的图库并发许可，由 `GraphConcurrencyLimit` 中间件按 `graph.max_concurrent_queries` 创建；
+// 不在请求内执行时不限流
+tokio::task_local! {
+    pub static GRAPH_PERMITS: Arc<Semaphore>;
+}
+
+/// 取得当前请求的一个图库许可后再执行 `future`，执行完毕即归还。
+/// 只应包在单次图库调用（查询或事务）外层：许可从不嵌套获取，
+/// 因此同一请求 `join!` 多个图库 future 时只会排队，不会互相等待而死锁
+pub async fn limited<F: Future>(future: F) -> F::Output {
+    let semaphore = GRAPH_PERMITS.try_with(Arc::clone).ok();
+    let _permit = match &semaphore {
+        // 信号量由中间件持有且从不 close，acquire 不会失败
+        Some(semaphore) => semaphore.acquire().await.ok(),
+        None => None,
+    };
+    future.await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// 记录同时进行中的调用数及其峰值
+    #[derive(Default)]
+    struct CountingGraph {
+        in_flight: AtomicUsize,
+        peak: AtomicUsize,
+    }
+
+    impl CountingGraph {
+        async fn query(&self) {
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    async fn fan_out(graph: &CountingGraph) {
+        tokio::join!(
+            limited(graph.query()),
+            limited(graph.query()),
+            limited(graph.query()),
+            limited(graph.query()),
+            limited(graph.query()),
+        );
+    }
+
+    #[tokio::test]
+    async fn single_permit_serializes_without_deadlock() {
+        let graph = CountingGraph::default();
+        tokio::time::timeout(
+            Duration::from_secs(1),
+            GRAPH_PERMITS.scope(Arc::new(Semaphore::new(1)), fan_out(&graph)),
+        )
+        .await
+        .expect("joined graph calls must not deadlock");
+        assert_eq!(graph.peak.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use once_cell::sync::Lazy;
+
+use crate::util::timing::Dependency;
+
+/// 依赖耗时直方图的桶上界（秒）
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+pub const HTTP_REQUESTS_TOTAL: &str = "wordmesh_http_requests_total";
+pub const DEPENDENCY_QUERY_DURATION: &str = "wordmesh_dependency_query_duration_seconds";
+pub const AUTH_ATTEMPTS_TOTAL: &str = "wordmesh_auth_attempts_total";
+
+static METRICS: Lazy<Metrics> = Lazy::new(Metrics::default);
+
+/// 进程级的指标注册表，`/metrics` 以 Prometheus 文本格式输出
+pub fn metrics() -> &'static Metrics {
+    &METRICS
+}
+
+#[derive(Debug, Default)]
+pub struct Metrics {
+    inner: Mutex<Registry>,
+}
+
+#[derive(Debug, Default)]
+struct Registry {
+    /// (method, 路由模板, status) → 次数；按模板而不是实际路径统计，避免 id 撑爆标签基数
+    requests: BTreeMap<(String, String, u16), u64>,
+    dependencies: BTreeMap<&'static str, Histogram>,
+    /// (流程, 结果) → 次数
+    auth: BTreeMap<(&'static str, &'static str), u64>,
+}
+
+#[derive(Debug, Default)]
+struct Histogram {
+    /// 各桶的累计计数，与 Prometheus 的 `le` 语义一致
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if seconds <= bound {
+                *bucket += 1;
+            }
+        }
+        self.count += 1;
+        self.sum += seconds;
+    }
+}
+
+impl Dependency {
+    fn label(self) -> &'static str {
+        match self {
+            Dependency::Postgres => "postgres",
+            Dependency::Neo4j => "neo4j",
+        }
+    }
+}
+
+impl Metrics {
+    pub fn record_request(&self, method: &str, path: &str, status: u16) {
+        let mut registry = self.inner.lock().unwrap();
+        *registry
+            .requests
+            .entry((method.to_string(), path.to_string(), status))
+            .or_insert(0) += 1;
+    }
+
+    pub fn observe_dependency(&self, dependency: Dependency, elapsed: Duration) {
+        let mut registry = self.inner.lock().unwrap();
+        registry
+            .dependencies
+            .entry(dependency.label())
+            .or_default()
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// `flow` 为 `login` / `refresh`
+    pub fn record_auth(&self, flow: &'static str, success: bool) {
+        let outcome = if success { "success" } else { "failure" };
+        let mut registry = self.inner.lock().unwrap();
+        *registry.auth.entry((flow, outcome)).or_insert(0) += 1;
+    }
+
+    /// Prometheus 文本格式（0.0.4）
+    pub fn render(&self) -> String {
+        let registry = self.inner.lock().unwrap();
+        let mut out = String::new();
+
+        let _ = writeln!(
+            out,
+            "# HELP {HTTP_REQUESTS_TOTAL} HTTP requests by method, route and status."
+        );
+        let _ = writeln!(out, "# TYPE {HTTP_REQUESTS_TOTAL} counter");
+        for ((method, path, status), count) in &registry.requests {
+            let _ = writeln!(
+                out,
+                "{HTTP_REQUESTS_TOTAL}{{method=\"{}\",path=\"{}\",status=\"{status}\"}} {count}",
+                escape(method),
+                escape(path),
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP {DEPENDENCY_QUERY_DURATION} Postgres and Neo4j query latency."
+        );
+        let _ = writeln!(out, "# TYPE {DEPENDENCY_QUERY_DURATION} histogram");
+        for (dependency, histogram) in &registry.dependencies {
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets) {
+                let _ = writeln!(
+                    out,
+                    "{DEPENDENCY_QUERY_DURATION}_bucket{{dependency=\"{dependency}\",le=\"{bound}\"}} {count}"
+                );
+            }
+            let _ = writeln!(
+                out,
+                "{DEPENDENCY_QUERY_DURATION}_bucket{{dependency=\"{dependency}\",le=\"+Inf\"}} {}",
+                histogram.count
+            );
+            let _ = writeln!(
+                out,
+                "{DEPENDENCY_QUERY_DURATION}_sum{{dependency=\"{dependency}\"}} {}",
+                histogram.sum
+            );
+            let _ = writeln!(
+                out,
+                "{DEPENDENCY_QUERY_DURATION}_count{{dependency=\"{dependency}\"}} {}",
+                histogram.count
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP {AUTH_ATTEMPTS_TOTAL} Login and refresh attempts by outcome."
+        );
+        let _ = writeln!(out, "# TYPE {AUTH_ATTEMPTS_TOTAL} counter");
+        for ((flow, outcome), count) in &registry.auth {
+            let _ = writeln!(
+                out,
+                "{AUTH_ATTEMPTS_TOTAL}{{flow=\"{flow}\",outcome=\"{outcome}\"}} {count}"
+            );
+        }
+        out
+    }
+}
+
+/// 标签值中的 `\`、`"` 与换行需要转义
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_counters_and_cumulative_buckets() {
+        let metrics = Metrics::default();
+        metrics.record_request("GET", "/api/v1/words/{id}", 200);
+        metrics.record_request("GET", "/api/v1/words/{id}", 200);
+        metrics.record_request("GET", "say \"hi\"", 404);
+        metrics.observe_dependency(Dependency::Postgres, Duration::from_millis(3));
+        metrics.observe_dependency(Dependency::Postgres, Duration::from_millis(200));
+        metrics.record_auth("login", false);
+
+        let text = metrics.render();
+        assert!(text.contains(
+            "wordmesh_http_requests_total{method=\"GET\",path=\"/api/v1/words/{id}\",status=\"200\"} 2"
+        ));
+        assert!(text.contains("path=\"say \\\"hi\\\"\""), "{text}");
+        assert!(text.contains(
+            "wordmesh_dependency_query_duration_seconds_bucket{dependency=\"postgres\",le=\"0.005\"} 1"
+        ));
+        assert!(text.contains(
+            "wordmesh_dependency_query_duration_seconds_bucket{dependency=\"postgres\",le=\"0.25\"} 2"
+        ));
+        assert!(text.contains(
+            "wordmesh_dependency_query_duration_seconds_count{dependency=\"postgres\"} 2"
+        ));
+        assert!(
+            text.contains("wordmesh_auth_attempts_total{flow=\"login\",outcome=\"failure\"} 1")
+        );
+    }
+}
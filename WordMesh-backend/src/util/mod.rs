@@ -1,14 +1,19 @@
 pub mod canonical;
 pub mod error;
+pub mod graph_limit;
+pub mod metrics;
 pub mod password;
 pub mod response;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod timing;
 pub mod token;
 pub mod validation;
 
-pub use canonical::{CanonicalError, canonicalize};
+pub use canonical::{CanonicalError, canonicalize, normalize_nfc};
 pub use error::AppError;
 pub use response::ResponseBuilder;
 pub use validation::{
-    MAX_NOTE_LENGTH, MAX_SENSE_NOTE_LENGTH, MAX_SENSE_TEXT_LENGTH, MAX_TAGS, ValidationError,
-    normalize_tags, validate_non_empty_text, validate_note,
+    MAX_NOTE_LENGTH, MAX_SENSE_NOTE_LENGTH, MAX_SENSE_TEXT_LENGTH, MAX_SENSES, MAX_TAGS,
+    TextLimits, ValidationError, normalize_tags, validate_non_empty_text, validate_note,
 };
@@ -1,6 +1,8 @@
 //! Password hashing and verification utilities.
 
-use bcrypt::{DEFAULT_COST, hash, verify};
+use std::str::FromStr;
+
+use bcrypt::{DEFAULT_COST, HashParts, hash, verify};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -13,6 +15,145 @@ pub enum PasswordError {
     Empty,
 }
 
+/// Why a candidate password was refused by the configured policy.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PasswordPolicyError {
+    #[error("password shorter than {0} characters")]
+    TooShort(usize),
+    #[error("password must mix letters and digits")]
+    NotComplex,
+    #[error("password too predictable: about {estimated} bits of entropy (min {required})")]
+    TooWeak { estimated: u32, required: u32 },
+}
+
+/// Check a new password against `auth.password` (length and, optionally, a
+/// letter + digit mix).
+pub fn check_password_policy(
+    raw: &str,
+    min_length: usize,
+    require_complexity: bool,
+) -> Result<(), PasswordPolicyError> {
+    if raw.chars().count() < min_length {
+        return Err(PasswordPolicyError::TooShort(min_length));
+    }
+    if require_complexity
+        && !(raw.chars().any(char::is_alphabetic) && raw.chars().any(|c| c.is_ascii_digit()))
+    {
+        return Err(PasswordPolicyError::NotComplex);
+    }
+    Ok(())
+}
+
+/// Common password stems, most common first. A match is charged only for its
+/// position in this list, however long the word is.
+const COMMON_WORDS: &[&str] = &[
+    "password", "qwerty", "letmein", "welcome", "admin", "iloveyou", "monkey", "dragon", "master",
+    "login", "abc", "football", "baseball", "sunshine", "princess", "shadow", "superman", "secret",
+    "hello", "freedom", "whatever", "trustno", "starwars", "computer",
+];
+
+/// Rough entropy estimate in bits, a much simplified take on zxcvbn.
+///
+/// Common words (ignoring case and leet substitutions such as `P@ssw0rd`) cost
+/// their rank in `COMMON_WORDS`; a character equal or adjacent to the previous
+/// one (`aaa`, `123`, `cba`) costs one bit; anything else costs log2 of the
+/// combined size of the character classes present.
+pub fn estimate_entropy_bits(raw: &str) -> f64 {
+    let chars: Vec<char> = raw.chars().collect();
+    let folded: Vec<char> = chars.iter().map(|&c| unleet(c)).collect();
+    let pool_bits = (char_pool(&chars) as f64).log2();
+
+    let mut bits = 0.0;
+    let mut index = 0;
+    while index < chars.len() {
+        if let Some((rank, word)) = common_word_at(&folded, index) {
+            let segment = &chars[index..index + word.chars().count()];
+            bits += ((rank + 2) as f64).log2();
+            if segment.iter().any(|c| c.is_uppercase()) {
+                bits += 1.0;
+            }
+            if segment.iter().any(|&c| unleet(c) != c.to_ascii_lowercase()) {
+                bits += 1.0;
+            }
+            index += segment.len();
+            continue;
+        }
+        let predictable = index > 0 && {
+            let prev = chars[index - 1].to_ascii_lowercase() as i64;
+            let current = chars[index].to_ascii_lowercase() as i64;
+            (current - prev).abs() <= 1 && chars[index].is_alphanumeric()
+        };
+        bits += if predictable { 1.0 } else { pool_bits };
+        index += 1;
+    }
+    bits
+}
+
+/// Reject passwords whose estimate falls below `auth.password.min_entropy_bits`,
+/// when configured.
+pub fn check_password_entropy(
+    raw: &str,
+    min_entropy_bits: Option<u32>,
+) -> Result<(), PasswordPolicyError> {
+    let Some(required) = min_entropy_bits else {
+        return Ok(());
+    };
+    let estimated = estimate_entropy_bits(raw).floor() as u32;
+    if estimated < required {
+        return Err(PasswordPolicyError::TooWeak {
+            estimated,
+            required,
+        });
+    }
+    Ok(())
+}
+
+fn unleet(c: char) -> char {
+    match c {
+        '0' => 'o',
+        '1' | '!' => 'i',
+        '3' => 'e',
+        '4' | '@' => 'a',
+        '5' | '$' => 's',
+        '7' => 't',
+        other => other.to_ascii_lowercase(),
+    }
+}
+
+fn common_word_at(folded: &[char], index: usize) -> Option<(usize, &'static str)> {
+    COMMON_WORDS
+        .iter()
+        .enumerate()
+        .filter(|(_, word)| {
+            let len = word.chars().count();
+            folded.len() >= index + len
+                && folded[index..index + len].iter().copied().eq(word.chars())
+        })
+        .max_by_key(|(_, word)| word.len())
+        .map(|(rank, word)| (rank, *word))
+}
+
+fn char_pool(chars: &[char]) -> u32 {
+    let has = |matches: fn(&char) -> bool| chars.iter().any(matches);
+    let mut pool = 0;
+    if has(char::is_ascii_lowercase) {
+        pool += 26;
+    }
+    if has(char::is_ascii_uppercase) {
+        pool += 26;
+    }
+    if has(char::is_ascii_digit) {
+        pool += 10;
+    }
+    if has(|c| c.is_ascii() && !c.is_ascii_alphanumeric()) {
+        pool += 33;
+    }
+    if has(|c| !c.is_ascii()) {
+        pool += 100;
+    }
+    pool.max(2)
+}
+
 pub fn hash_password(raw: &str, cost: u32) -> Result<String, PasswordError> {
     if raw.trim().is_empty() {
         return Err(PasswordError::Empty);
@@ -28,6 +169,21 @@ pub fn verify_password(raw: &str, hashed: &str) -> Result<bool, PasswordError> {
     verify(raw, hashed).map_err(PasswordError::from)
 }
 
+/// Extract the bcrypt cost factor encoded in a stored hash.
+pub fn hash_cost(hashed: &str) -> Option<u32> {
+    HashParts::from_str(hashed)
+        .ok()
+        .map(|parts| parts.get_cost())
+}
+
+/// Whether a stored hash was produced with a weaker cost than the configured one.
+///
+/// Hashes that cannot be parsed are reported as not needing a rehash, so that a
+/// foreign or corrupted value never triggers a write on login.
+pub fn needs_rehash(hashed: &str, cost: u32) -> bool {
+    hash_cost(hashed).is_some_and(|current| current < cost)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -39,12 +195,53 @@ mod tests {
         assert!(!verify_password("wrong", &hashed).unwrap());
     }
 
+    #[test]
+    fn password_policy_checks_length_and_complexity() {
+        assert_eq!(
+            check_password_policy("short1", 8, false),
+            Err(PasswordPolicyError::TooShort(8))
+        );
+        assert!(check_password_policy("longenough", 8, false).is_ok());
+        assert_eq!(
+            check_password_policy("longenough", 8, true),
+            Err(PasswordPolicyError::NotComplex)
+        );
+        assert!(check_password_policy("longenough1", 8, true).is_ok());
+    }
+
+    #[test]
+    fn entropy_rejects_complex_but_common_password() {
+        assert!(check_password_policy("Password1", 8, true).is_ok());
+        assert!(matches!(
+            check_password_entropy("Password1", Some(40)),
+            Err(PasswordPolicyError::TooWeak { required: 40, estimated }) if estimated < 15
+        ));
+        assert!(check_password_entropy("P@ssw0rd123", Some(40)).is_err());
+        assert!(check_password_entropy("aaaaaaaaaaaa", Some(40)).is_err());
+        assert!(check_password_entropy("abcdef123456", Some(40)).is_err());
+    }
+
+    #[test]
+    fn entropy_accepts_long_passphrase() {
+        assert!(check_password_entropy("correct horse battery staple", Some(60)).is_ok());
+        assert!(check_password_entropy("Password1", None).is_ok());
+    }
+
     #[test]
     fn hash_password_empty() {
         let result = hash_password("", 10);
         assert!(matches!(result, Err(PasswordError::Empty)));
     }
 
+    #[test]
+    fn needs_rehash_compares_cost() {
+        let hashed = hash_password("secret", 4).expect("hash");
+        assert_eq!(hash_cost(&hashed), Some(4));
+        assert!(needs_rehash(&hashed, 5));
+        assert!(!needs_rehash(&hashed, 4));
+        assert!(!needs_rehash("not-a-bcrypt-hash", 12));
+    }
+
     #[test]
     fn verify_password_empty_inputs() {
         let hashed = hash_password("secret", 10).expect("hash");
@@ -1,4 +1,5 @@
-use actix_web::HttpResponse;
+use actix_web::http::header::ContentType;
+use actix_web::{HttpResponse, HttpResponseBuilder};
 use chrono::Utc;
 use serde::Serialize;
 use uuid::Uuid;
@@ -51,7 +52,7 @@ impl ResponseBuilder {
     {
         let trace_id = Self::current_trace_id();
         let body = ApiResponse::success_with_trace(data, trace_id);
-        Ok(HttpResponse::Ok().json(body))
+        Ok(Self::json(HttpResponse::Ok(), &body))
     }
 
     /// 构建失败响应（HTTP 200），使用业务 code 与消息，data 为空
@@ -62,13 +63,27 @@ impl ResponseBuilder {
     ) -> Result<HttpResponse, crate::util::AppError> {
         let trace_id = Self::current_trace_id();
         let body = ApiResponse::<serde_json::Value>::error_with_trace(code, message, trace_id);
-        Ok(HttpResponse::Ok().json(body))
+        Ok(Self::json(HttpResponse::Ok(), &body))
+    }
+
+    /// 写出 JSON 响应体；请求作用域开启了 `PRETTY_JSON` 时缩进输出
+    pub(crate) fn json<T>(mut builder: HttpResponseBuilder, body: &T) -> HttpResponse
+    where
+        T: Serialize,
+    {
+        if !PRETTY_JSON.try_with(|pretty| *pretty).unwrap_or(false) {
+            return builder.json(body);
+        }
+        match serde_json::to_string_pretty(body) {
+            Ok(text) => builder.content_type(ContentType::json()).body(text),
+            Err(_) => builder.json(body),
+        }
     }
 
     /// 获取当前请求的 traceId：优先从 task-local 获取，否则生成 UUID
     pub(crate) fn current_trace_id() -> String {
         // 优先使用请求作用域中的 Request-Id
-        if let Some(id) = REQUEST_ID.try_with(|id| id.clone()).ok() {
+        if let Ok(id) = REQUEST_ID.try_with(|id| id.clone()) {
             return id;
         }
         Uuid::new_v4().to_string()
@@ -80,6 +95,8 @@ pub struct Pagination {
     pub page: u32,
     pub page_size: u32,
     pub total: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -101,3 +118,8 @@ pub struct ValidationErrorData {
 tokio::task_local! {
     pub static REQUEST_ID: String;
 }
+
+// 请求作用域的 JSON 输出格式，由 `JsonFormat` 中间件按配置设置
+tokio::task_local! {
+    pub static PRETTY_JSON: bool;
+}
@@ -0,0 +1,63 @@
+//! Helpers shared by tests: log capture and a token config for controller tests.
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use tracing::subscriber::DefaultGuard;
+
+use crate::util::token::TokenConfig;
+
+#[derive(Clone, Default)]
+struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+impl Write for Buffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// 当前线程的日志输出，存活期间替换默认 subscriber
+pub(crate) struct CapturedLogs {
+    buffer: Buffer,
+    _guard: DefaultGuard,
+}
+
+impl CapturedLogs {
+    /// 目前为止写入的全部日志，不含 ANSI 颜色
+    pub(crate) fn output(&self) -> String {
+        String::from_utf8(self.buffer.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+pub(crate) fn capture_logs() -> CapturedLogs {
+    let buffer = Buffer::default();
+    let writer = buffer.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(move || writer.clone())
+        .with_ansi(false)
+        .finish();
+    CapturedLogs {
+        buffer,
+        _guard: tracing::subscriber::set_default(subscriber),
+    }
+}
+
+/// 控制器测试共用的 HS256 配置：不签发 refresh token，也不启用吊销
+pub(crate) fn token_config() -> Arc<TokenConfig> {
+    let secret = b"controller-test-secret";
+    Arc::new(TokenConfig {
+        algorithm: jsonwebtoken::Algorithm::HS256,
+        access_ttl_secs: 60,
+        refresh_ttl_secs: None,
+        encoding_key: jsonwebtoken::EncodingKey::from_secret(secret),
+        decoding_key: jsonwebtoken::DecodingKey::from_secret(secret),
+        issuer: None,
+        auth_enabled: true,
+        revocations: None,
+    })
+}
@@ -0,0 +1,77 @@
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::util::metrics::metrics;
+
+/// 请求期间各外部依赖的累计耗时，由 `DependencyTiming` 中间件创建并在请求结束时输出
+#[derive(Debug, Default)]
+pub struct DependencyTimings {
+    postgres_micros: AtomicU64,
+    neo4j_micros: AtomicU64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dependency {
+    Postgres,
+    Neo4j,
+}
+
+impl DependencyTimings {
+    pub fn add(&self, dependency: Dependency, elapsed: Duration) {
+        let micros = u64::try_from(elapsed.as_micros()).unwrap_or(u64::MAX);
+        self.counter(dependency)
+            .fetch_add(micros, Ordering::Relaxed);
+    }
+
+    pub fn total(&self, dependency: Dependency) -> Duration {
+        Duration::from_micros(self.counter(dependency).load(Ordering::Relaxed))
+    }
+
+    fn counter(&self, dependency: Dependency) -> &AtomicU64 {
+        match dependency {
+            Dependency::Postgres => &self.postgres_micros,
+            Dependency::Neo4j => &self.neo4j_micros,
+        }
+    }
+}
+
+// 请求作用域的依赖耗时累加器；不在请求内（后台任务、测试）执行时不记录
+tokio::task_local! {
+    pub static DEPENDENCY_TIMINGS: Arc<DependencyTimings>;
+}
+
+/// 执行 `future` 并把耗时计入当前请求对应依赖的累计值，同时记入进程级的耗时直方图
+pub async fn timed<F: Future>(dependency: Dependency, future: F) -> F::Output {
+    let started = Instant::now();
+    let output = future.await;
+    let elapsed = started.elapsed();
+    let _ = DEPENDENCY_TIMINGS.try_with(|timings| timings.add(dependency, elapsed));
+    metrics().observe_dependency(dependency, elapsed);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn records_only_inside_scope() {
+        // 作用域外调用不会 panic
+        timed(Dependency::Postgres, async {}).await;
+
+        let timings = Arc::new(DependencyTimings::default());
+        DEPENDENCY_TIMINGS
+            .scope(timings.clone(), async {
+                timed(
+                    Dependency::Neo4j,
+                    tokio::time::sleep(Duration::from_millis(5)),
+                )
+                .await;
+            })
+            .await;
+        assert!(timings.total(Dependency::Neo4j) >= Duration::from_millis(5));
+        assert_eq!(timings.total(Dependency::Postgres), Duration::ZERO);
+    }
+}
@@ -1,40 +1,200 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::Deserialize;
 use thiserror::Error;
 
+use crate::util::canonical::normalize_nfc;
+
 pub const MAX_TAGS: usize = 20;
+/// 每个单词默认最多保存的义项数，可通过 `words.max_senses` 调整
+pub const MAX_SENSES: usize = 50;
+/// 去重前允许的原始标签条数，超过时不再逐条校验直接拒绝
+pub const MAX_TAG_INPUT: usize = MAX_TAGS * 4;
+/// 单个标签的默认最大字符数，可通过 `words.max_tag_length` 调整
+pub const MAX_TAG_LENGTH: usize = 24;
+/// 以下三个长度只是默认值，实际上限来自 `words` 配置，经 [`TextLimits`] 传入
 pub const MAX_NOTE_LENGTH: usize = 512;
 pub const MAX_SENSE_TEXT_LENGTH: usize = 512;
 pub const MAX_SENSE_NOTE_LENGTH: usize = 512;
+/// 字节上限默认按每字符 4 字节取满，即默认情况下只有字符数上限生效
+pub const MAX_TEXT_BYTES: usize = MAX_SENSE_TEXT_LENGTH * 4;
+pub const MAX_NOTE_BYTES: usize = MAX_NOTE_LENGTH * 4;
+/// 义项来源链接的最大字符数
+pub const MAX_SOURCE_URL_LENGTH: usize = 2048;
+
+/// 文本字段的长度上限：字符数按字段互相独立，字节数另行限制 UTF-8 编码后的大小
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextLimits {
+    /// 义项释义文本
+    pub sense_text: usize,
+    /// 义项备注
+    pub sense_note: usize,
+    /// 单词（用户词条）备注
+    pub word_note: usize,
+    /// 义项释义文本的字节数
+    pub text_bytes: usize,
+    /// 备注（单词与义项）的字节数
+    pub note_bytes: usize,
+}
+
+impl TextLimits {
+    /// 从存储中还原数据时使用：写入时已校验过，上限事后调低也不应导致旧数据无法读取
+    pub const fn unbounded() -> Self {
+        Self {
+            sense_text: usize::MAX,
+            sense_note: usize::MAX,
+            word_note: usize::MAX,
+            text_bytes: usize::MAX,
+            note_bytes: usize::MAX,
+        }
+    }
+}
+
+impl Default for TextLimits {
+    fn default() -> Self {
+        Self {
+            sense_text: MAX_SENSE_TEXT_LENGTH,
+            sense_note: MAX_SENSE_NOTE_LENGTH,
+            word_note: MAX_NOTE_LENGTH,
+            text_bytes: MAX_TEXT_BYTES,
+            note_bytes: MAX_NOTE_BYTES,
+        }
+    }
+}
 
-static TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[A-Za-z0-9_-]{1,24}$").unwrap());
+static STRICT_TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[A-Za-z0-9_-]+$").unwrap());
+static UNICODE_TAG_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[\p{L}\p{M}\p{N}_-]+$").unwrap());
+/// 只接受带主机名的 http/https 绝对链接，整体不含空白
+static SOURCE_URL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^https?://[^\s/?#]*[\p{L}\p{N}][^\s/?#]*(?:[/?#]\S*)?$").unwrap()
+});
+static ANY_TAG_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?s)^.+$").unwrap());
+
+/// 标签允许的字符集
+#[derive(Debug, Clone, Copy, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TagCharset {
+    /// ASCII 字母、数字、`_` 与 `-`
+    #[default]
+    Strict,
+    /// 任意语言的字母与数字（含组合附加符号），以及 `_` 与 `-`
+    Unicode,
+}
+
+/// 标签的字符规则与长度上限，来自 `words` 配置
+#[derive(Debug, Clone)]
+pub struct TagRules {
+    pattern: Regex,
+    max_length: usize,
+}
+
+impl TagRules {
+    /// `pattern` 为完整匹配的正则（自动加首尾锚点），提供时取代 `charset`
+    pub fn new(
+        charset: TagCharset,
+        pattern: Option<&str>,
+        max_length: usize,
+    ) -> Result<Self, regex::Error> {
+        let pattern = match pattern {
+            Some(pattern) => Regex::new(&format!("^(?:{pattern})$"))?,
+            None => match charset {
+                TagCharset::Strict => STRICT_TAG_REGEX.clone(),
+                TagCharset::Unicode => UNICODE_TAG_REGEX.clone(),
+            },
+        };
+        Ok(Self {
+            pattern,
+            max_length,
+        })
+    }
+
+    /// 从存储中还原数据时使用：规则事后收紧也不应导致旧数据无法读取
+    pub fn permissive() -> Self {
+        Self {
+            pattern: ANY_TAG_REGEX.clone(),
+            max_length: usize::MAX,
+        }
+    }
+
+    fn accepts(&self, tag: &str) -> bool {
+        tag.chars().count() <= self.max_length && self.pattern.is_match(tag)
+    }
+}
+
+impl Default for TagRules {
+    fn default() -> Self {
+        Self {
+            pattern: STRICT_TAG_REGEX.clone(),
+            max_length: MAX_TAG_LENGTH,
+        }
+    }
+}
 
 #[derive(Debug, Error, PartialEq, Eq)]
 pub enum ValidationError {
     #[error("value cannot be blank")]
     Blank,
-    #[error("text too long: {0} characters (max {MAX_SENSE_TEXT_LENGTH})")]
-    TextTooLong(usize),
-    #[error("note too long: {0} characters (max {MAX_NOTE_LENGTH})")]
-    NoteTooLong(usize),
+    /// (实际长度, 上限)
+    #[error("text too long: {0} characters (max {1})")]
+    TextTooLong(usize, usize),
+    /// (实际长度, 上限)
+    #[error("note too long: {0} characters (max {1})")]
+    NoteTooLong(usize, usize),
+    /// (实际字节数, 上限)
+    #[error("text too large: {0} bytes (max {1})")]
+    TextTooManyBytes(usize, usize),
+    /// (实际字节数, 上限)
+    #[error("note too large: {0} bytes (max {1})")]
+    NoteTooManyBytes(usize, usize),
     #[error("invalid tag: {0}")]
     InvalidTag(String),
     #[error("tag limit exceeded: {0} tags provided (max {MAX_TAGS})")]
     TagLimitExceeded(usize),
+    #[error("value contains disallowed control characters")]
+    ControlCharacter,
+    #[error("invalid url: {0}")]
+    InvalidUrl(String),
 }
 
-pub fn validate_non_empty_text(text: impl AsRef<str>) -> Result<String, ValidationError> {
-    let value = text.as_ref().trim();
+/// 除换行、回车、制表符外的控制字符（如 NUL）会污染日志并导致部分客户端解析失败
+fn ensure_no_control_chars(value: &str) -> Result<(), ValidationError> {
+    if value
+        .chars()
+        .any(|c| c.is_control() && !matches!(c, '\n' | '\r' | '\t'))
+    {
+        return Err(ValidationError::ControlCharacter);
+    }
+    Ok(())
+}
+
+/// 校验并返回去除首尾空白、NFC 规范化后的文本
+pub fn validate_non_empty_text(
+    text: impl AsRef<str>,
+    max_length: usize,
+    max_bytes: usize,
+) -> Result<String, ValidationError> {
+    let composed = normalize_nfc(text.as_ref());
+    let value = composed.trim();
     if value.is_empty() {
         return Err(ValidationError::Blank);
     }
-    if value.chars().count() > MAX_SENSE_TEXT_LENGTH {
-        return Err(ValidationError::TextTooLong(value.chars().count()));
+    let length = value.chars().count();
+    if length > max_length {
+        return Err(ValidationError::TextTooLong(length, max_length));
+    }
+    if value.len() > max_bytes {
+        return Err(ValidationError::TextTooManyBytes(value.len(), max_bytes));
     }
+    ensure_no_control_chars(value)?;
     Ok(value.to_string())
 }
 
-pub fn validate_note(note: Option<String>) -> Result<Option<String>, ValidationError> {
+pub fn validate_note(
+    note: Option<String>,
+    max_length: usize,
+    max_bytes: usize,
+) -> Result<Option<String>, ValidationError> {
     match note {
         Some(value) => {
             let trimmed = value.trim();
@@ -42,16 +202,43 @@ pub fn validate_note(note: Option<String>) -> Result<Option<String>, ValidationE
                 return Err(ValidationError::Blank);
             }
             let length = trimmed.chars().count();
-            if length > MAX_NOTE_LENGTH {
-                return Err(ValidationError::NoteTooLong(length));
+            if length > max_length {
+                return Err(ValidationError::NoteTooLong(length, max_length));
             }
+            if trimmed.len() > max_bytes {
+                return Err(ValidationError::NoteTooManyBytes(trimmed.len(), max_bytes));
+            }
+            ensure_no_control_chars(trimmed)?;
             Ok(Some(trimmed.to_string()))
         }
         None => Ok(None),
     }
 }
 
-pub fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>, ValidationError> {
+/// 校验来源链接并去除首尾空白；`None` 表示不设置
+pub fn validate_source_url(url: Option<String>) -> Result<Option<String>, ValidationError> {
+    let Some(value) = url else {
+        return Ok(None);
+    };
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return Err(ValidationError::Blank);
+    }
+    let length = trimmed.chars().count();
+    if length > MAX_SOURCE_URL_LENGTH {
+        return Err(ValidationError::TextTooLong(length, MAX_SOURCE_URL_LENGTH));
+    }
+    ensure_no_control_chars(trimmed)?;
+    if !SOURCE_URL_REGEX.is_match(trimmed) {
+        return Err(ValidationError::InvalidUrl(trimmed.to_string()));
+    }
+    Ok(Some(trimmed.to_string()))
+}
+
+pub fn normalize_tags(tags: Vec<String>, rules: &TagRules) -> Result<Vec<String>, ValidationError> {
+    if tags.len() > MAX_TAG_INPUT {
+        return Err(ValidationError::TagLimitExceeded(tags.len()));
+    }
     let mut seen = std::collections::HashSet::new();
     let mut normalized = Vec::new();
     for raw in tags {
@@ -59,10 +246,10 @@ pub fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>, ValidationError>
         if trimmed.is_empty() {
             return Err(ValidationError::InvalidTag(raw));
         }
-        if !TAG_REGEX.is_match(trimmed) {
+        if !rules.accepts(trimmed) {
             return Err(ValidationError::InvalidTag(trimmed.to_string()));
         }
-        let key = trimmed.to_ascii_lowercase();
+        let key = trimmed.to_lowercase();
         if seen.insert(key) {
             normalized.push(trimmed.to_string());
         }
@@ -79,27 +266,160 @@ mod tests {
 
     #[test]
     fn validate_non_empty_text_ok() {
-        assert_eq!(validate_non_empty_text(" hello ").unwrap(), "hello");
+        assert_eq!(
+            validate_non_empty_text(" hello ", MAX_SENSE_TEXT_LENGTH, MAX_TEXT_BYTES).unwrap(),
+            "hello"
+        );
     }
 
     #[test]
     fn validate_non_empty_text_blank() {
-        let err = validate_non_empty_text("   ").unwrap_err();
+        let err =
+            validate_non_empty_text("   ", MAX_SENSE_TEXT_LENGTH, MAX_TEXT_BYTES).unwrap_err();
         assert_eq!(err, ValidationError::Blank);
     }
 
     #[test]
     fn validate_note_checks_length() {
-        let err = validate_note(Some(" ".into())).unwrap_err();
+        let err = validate_note(Some(" ".into()), MAX_NOTE_LENGTH, MAX_NOTE_BYTES).unwrap_err();
         assert_eq!(err, ValidationError::Blank);
     }
 
+    #[test]
+    fn validate_note_rejects_control_characters() {
+        let err =
+            validate_note(Some("bad\0note".into()), MAX_NOTE_LENGTH, MAX_NOTE_BYTES).unwrap_err();
+        assert_eq!(err, ValidationError::ControlCharacter);
+        let err = validate_non_empty_text("bell\u{7}", MAX_SENSE_TEXT_LENGTH, MAX_TEXT_BYTES)
+            .unwrap_err();
+        assert_eq!(err, ValidationError::ControlCharacter);
+    }
+
+    #[test]
+    fn validate_note_allows_common_whitespace() {
+        let note = validate_note(
+            Some("line one\nline\ttwo".into()),
+            MAX_NOTE_LENGTH,
+            MAX_NOTE_BYTES,
+        )
+        .unwrap();
+        assert_eq!(note.as_deref(), Some("line one\nline\ttwo"));
+    }
+
+    #[test]
+    fn validate_source_url_accepts_http_links() {
+        assert_eq!(validate_source_url(None), Ok(None));
+        assert_eq!(
+            validate_source_url(Some(" https://example.com/dict?q=graph#sense-2 ".into())),
+            Ok(Some("https://example.com/dict?q=graph#sense-2".into()))
+        );
+        assert!(validate_source_url(Some("HTTP://localhost:8080".into())).is_ok());
+    }
+
+    #[test]
+    fn validate_source_url_rejects_malformed_links() {
+        for url in [
+            "example.com/page",
+            "ftp://example.com/file",
+            "https://",
+            "https:///path",
+            "https://exa mple.com",
+            "javascript:alert(1)",
+        ] {
+            assert!(
+                matches!(
+                    validate_source_url(Some(url.into())),
+                    Err(ValidationError::InvalidUrl(_))
+                ),
+                "{url} should be rejected"
+            );
+        }
+        assert_eq!(
+            validate_source_url(Some("  ".into())),
+            Err(ValidationError::Blank)
+        );
+        let long = format!("https://example.com/{}", "a".repeat(MAX_SOURCE_URL_LENGTH));
+        assert!(matches!(
+            validate_source_url(Some(long)),
+            Err(ValidationError::TextTooLong(_, MAX_SOURCE_URL_LENGTH))
+        ));
+    }
+
+    #[test]
+    fn length_checks_use_the_given_limit() {
+        assert_eq!(
+            validate_non_empty_text("abcdef", 5, 64),
+            Err(ValidationError::TextTooLong(6, 5))
+        );
+        assert_eq!(validate_non_empty_text("abcde", 5, 64).unwrap(), "abcde");
+        assert_eq!(
+            validate_note(Some("abcd".into()), 3, 64),
+            Err(ValidationError::NoteTooLong(4, 3))
+        );
+    }
+
+    #[test]
+    fn byte_limit_is_checked_independently_of_characters() {
+        // 4 个字符，但每个都是 4 字节
+        let emoji = "\u{1F600}".repeat(4);
+        assert_eq!(
+            validate_non_empty_text(&emoji, 10, 12),
+            Err(ValidationError::TextTooManyBytes(16, 12))
+        );
+        assert_eq!(
+            validate_note(Some(emoji.clone()), 10, 12),
+            Err(ValidationError::NoteTooManyBytes(16, 12))
+        );
+        assert!(validate_non_empty_text(&emoji, 10, 16).is_ok());
+        assert!(validate_note(Some("abcd".into()), 10, 4).is_ok());
+    }
+
+    #[test]
+    fn normalize_tags_rejects_oversized_input_before_iterating() {
+        // 每一项都不合法：若逐条校验会先得到 InvalidTag
+        let tags = vec!["not a tag!".to_string(); 1_000_000];
+        assert_eq!(
+            normalize_tags(tags, &TagRules::default()),
+            Err(ValidationError::TagLimitExceeded(1_000_000))
+        );
+    }
+
     #[test]
     fn normalize_tags_enforces_rules() {
         let tags = vec!["tag-one".into(), "Tag-One".into(), "tag_two".into()];
-        let normalized = normalize_tags(tags).unwrap();
+        let normalized = normalize_tags(tags, &TagRules::default()).unwrap();
         assert_eq!(normalized.len(), 2);
         assert_eq!(normalized[0], "tag-one");
         assert_eq!(normalized[1], "tag_two");
     }
+
+    #[test]
+    fn unicode_tags_need_unicode_mode() {
+        let tags = || vec!["中文".to_string(), "naïve".to_string()];
+        assert!(matches!(
+            normalize_tags(tags(), &TagRules::default()),
+            Err(ValidationError::InvalidTag(_))
+        ));
+
+        let unicode = TagRules::new(TagCharset::Unicode, None, MAX_TAG_LENGTH).unwrap();
+        assert_eq!(normalize_tags(tags(), &unicode).unwrap(), tags());
+        // 仍然拒绝空白与标点
+        assert!(normalize_tags(vec!["two words".into()], &unicode).is_err());
+        assert!(normalize_tags(vec!["标签!".into()], &unicode).is_err());
+    }
+
+    #[test]
+    fn tag_length_and_pattern_are_configurable() {
+        let short = TagRules::new(TagCharset::Strict, None, 3).unwrap();
+        assert!(normalize_tags(vec!["abc".into()], &short).is_ok());
+        assert!(normalize_tags(vec!["abcd".into()], &short).is_err());
+        // 按字符而非字节计数
+        let unicode = TagRules::new(TagCharset::Unicode, None, 2).unwrap();
+        assert!(normalize_tags(vec!["中文".into()], &unicode).is_ok());
+
+        let custom = TagRules::new(TagCharset::Strict, Some("[a-z]+(:[a-z]+)?"), 24).unwrap();
+        assert!(normalize_tags(vec!["lang:en".into()], &custom).is_ok());
+        assert!(normalize_tags(vec!["Lang".into()], &custom).is_err());
+        assert!(TagRules::new(TagCharset::Strict, Some("[unclosed"), 24).is_err());
+    }
 }